@@ -0,0 +1,1141 @@
+//!
+//! 7.3 Door Lock Cluster
+//!
+//! Attributes and commands for controlling a door lock and managing its users, PIN/RFID codes
+//! and schedules. Codes are copied into a [`Code`], which is zeroized when dropped.
+use byte::{BytesExt, TryRead, TryWrite};
+
+use crate::data_types::{CharacterString, OctetString};
+use crate::impl_byte;
+use crate::status::ZclStatus;
+
+/// Door Lock cluster identifier
+pub const CLUSTER_ID: u16 = 0x0101;
+
+/// Maximum length of a PIN code
+pub const PIN_CODE_MAX_LEN: usize = 16;
+
+/// Maximum length of an RFID code
+pub const RFID_CODE_MAX_LEN: usize = 32;
+
+impl_byte! {
+    /// 7.3.2.2 Door Lock Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        // Basic Information
+        LockState = 0x0000,
+        LockType = 0x0001,
+        ActuatorEnabled = 0x0002,
+        DoorState = 0x0003,
+        DoorOpenEvents = 0x0004,
+        DoorClosedEvents = 0x0005,
+        OpenPeriod = 0x0006,
+        // User, PIN, Schedule, Log Information
+        NumberOfLogRecordsSupported = 0x0010,
+        NumberOfTotalUsersSupported = 0x0011,
+        NumberOfPinUsersSupported = 0x0012,
+        NumberOfRfidUsersSupported = 0x0013,
+        NumberOfWeekDaySchedulesSupportedPerUser = 0x0014,
+        NumberOfYearDaySchedulesSupportedPerUser = 0x0015,
+        NumberOfHolidaySchedulesSupported = 0x0016,
+        MaxPinCodeLength = 0x0017,
+        MinPinCodeLength = 0x0018,
+        MaxRfidCodeLength = 0x0019,
+        MinRfidCodeLength = 0x001a,
+        // Operational Settings
+        EnableLogging = 0x0020,
+        Language = 0x0021,
+        LedSettings = 0x0022,
+        AutoRelockTime = 0x0023,
+        SoundVolume = 0x0024,
+        OperatingMode = 0x0025,
+        SupportedOperatingModes = 0x0026,
+        DefaultConfigurationRegister = 0x0027,
+        EnableLocalProgramming = 0x0028,
+        EnableOneTouchLocking = 0x0029,
+        EnableInsideStatusLed = 0x002a,
+        EnablePrivacyModeButton = 0x002b,
+        // Security Settings
+        WrongCodeEntryLimit = 0x0030,
+        UserCodeTemporaryDisableTime = 0x0031,
+        SendPinOverTheAir = 0x0032,
+        RequirePinForRfOperation = 0x0033,
+        SecurityLevel = 0x0034,
+        // Alarm and Event Masks
+        AlarmMask = 0x0040,
+        KeypadOperationEventMask = 0x0041,
+        RfOperationEventMask = 0x0042,
+        ManualOperationEventMask = 0x0043,
+        RfidOperationEventMask = 0x0044,
+        KeypadProgrammingEventMask = 0x0045,
+        RfProgrammingEventMask = 0x0046,
+        RfidProgrammingEventMask = 0x0047,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.16 Commands Received
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ReceivedCommandIdentifier {
+        LockDoor = 0x00,
+        UnlockDoor = 0x01,
+        Toggle = 0x02,
+        UnlockWithTimeout = 0x03,
+        GetLogRecord = 0x04,
+        SetPinCode = 0x05,
+        GetPinCode = 0x06,
+        ClearPinCode = 0x07,
+        ClearAllPinCodes = 0x08,
+        SetUserStatus = 0x09,
+        GetUserStatus = 0x0a,
+        SetWeekdaySchedule = 0x0b,
+        GetWeekdaySchedule = 0x0c,
+        ClearWeekdaySchedule = 0x0d,
+        SetYearDaySchedule = 0x0e,
+        GetYearDaySchedule = 0x0f,
+        ClearYearDaySchedule = 0x10,
+        SetHolidaySchedule = 0x11,
+        GetHolidaySchedule = 0x12,
+        ClearHolidaySchedule = 0x13,
+        SetUserType = 0x14,
+        GetUserType = 0x15,
+        SetRfidCode = 0x16,
+        GetRfidCode = 0x17,
+        ClearRfidCode = 0x18,
+        ClearAllRfidCodes = 0x19,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.17 Commands Generated
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum GeneratedCommandIdentifier {
+        LockDoorResponse = 0x00,
+        UnlockDoorResponse = 0x01,
+        ToggleResponse = 0x02,
+        UnlockWithTimeoutResponse = 0x03,
+        GetLogRecordResponse = 0x04,
+        SetPinCodeResponse = 0x05,
+        GetPinCodeResponse = 0x06,
+        ClearPinCodeResponse = 0x07,
+        ClearAllPinCodesResponse = 0x08,
+        SetUserStatusResponse = 0x09,
+        GetUserStatusResponse = 0x0a,
+        SetWeekdayScheduleResponse = 0x0b,
+        GetWeekdayScheduleResponse = 0x0c,
+        ClearWeekdayScheduleResponse = 0x0d,
+        SetYearDayScheduleResponse = 0x0e,
+        GetYearDayScheduleResponse = 0x0f,
+        ClearYearDayScheduleResponse = 0x10,
+        SetHolidayScheduleResponse = 0x11,
+        GetHolidayScheduleResponse = 0x12,
+        ClearHolidayScheduleResponse = 0x13,
+        SetUserTypeResponse = 0x14,
+        GetUserTypeResponse = 0x15,
+        SetRfidCodeResponse = 0x16,
+        GetRfidCodeResponse = 0x17,
+        ClearRfidCodeResponse = 0x18,
+        ClearAllRfidCodesResponse = 0x19,
+        OperationEventNotification = 0x20,
+        ProgrammingEventNotification = 0x21,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.2.1 `LockState` Attribute
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum LockState {
+        NotFullyLocked = 0x00,
+        Locked = 0x01,
+        Unlocked = 0x02,
+        Undefined = 0xff,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.2.2 `LockType` Attribute
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum LockType {
+        DeadBolt = 0x00,
+        Magnetic = 0x01,
+        Other = 0x02,
+        Mortise = 0x03,
+        Rim = 0x04,
+        LatchBolt = 0x05,
+        CylindricalLock = 0x06,
+        TubularLock = 0x07,
+        InterconnectedLock = 0x08,
+        DeadLatch = 0x09,
+        DoorFurniture = 0x0a,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.2.4 `DoorState` Attribute
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum DoorState {
+        Open = 0x00,
+        Closed = 0x01,
+        ErrorJammed = 0x02,
+        ErrorForcedOpen = 0x03,
+        ErrorUnspecified = 0x04,
+        Undefined = 0xff,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.8.9 `OperatingMode` Attribute
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum OperatingMode {
+        /// The lock operates normally
+        Normal = 0x00,
+        /// Only RF interaction is allowed, the keypad is disabled
+        Vacation = 0x01,
+        /// All external interaction is disabled
+        Privacy = 0x02,
+        /// RF interaction is disabled
+        NoRfLockOrUnlock = 0x03,
+        /// The lock is open and can be operated without a code
+        Passage = 0x04,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.8.10 `SupportedOperatingModes` Attribute
+    ///
+    /// A cleared bit means the operating mode is supported.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct SupportedOperatingModes(pub u16);
+}
+
+impl Default for SupportedOperatingModes {
+    fn default() -> Self {
+        Self(0xffff)
+    }
+}
+
+impl SupportedOperatingModes {
+    /// Returns `true` if the operating mode is supported.
+    pub fn supports(self, mode: OperatingMode) -> bool {
+        self.0 & (1 << mode as u16) == 0
+    }
+}
+
+impl_byte! {
+    /// User status of the user management commands
+    ///
+    /// See Section 7.3.2.16.6
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum UserStatus {
+        Available = 0x00,
+        OccupiedEnabled = 0x01,
+        OccupiedDisabled = 0x03,
+        NotSupported = 0xff,
+    }
+}
+
+impl_byte! {
+    /// User type of the user management commands
+    ///
+    /// See Section 7.3.2.16.6
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum UserType {
+        UnrestrictedUser = 0x00,
+        YearDayScheduleUser = 0x01,
+        WeekDayScheduleUser = 0x02,
+        MasterUser = 0x03,
+        NonAccessUser = 0x04,
+        NotSupported = 0xff,
+    }
+}
+
+impl_byte! {
+    /// Status of the Set PIN Code and Set RFID Code responses
+    ///
+    /// See Section 7.3.2.17.6
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SetCodeStatus {
+        Success = 0x00,
+        GeneralFailure = 0x01,
+        MemoryFull = 0x02,
+        DuplicateCode = 0x03,
+    }
+}
+
+impl_byte! {
+    /// Event type of a log record
+    ///
+    /// See Section 7.3.2.17.5
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum EventType {
+        Operation = 0x00,
+        Programming = 0x01,
+        Alarm = 0x02,
+    }
+}
+
+impl_byte! {
+    /// Source of an operation or programming event
+    ///
+    /// See Section 7.3.2.17.27
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum EventSource {
+        Keypad = 0x00,
+        Rf = 0x01,
+        Manual = 0x02,
+        Rfid = 0x03,
+        Indeterminate = 0xff,
+    }
+}
+
+impl_byte! {
+    /// Operation event code of the Operation Event Notification
+    ///
+    /// See Section 7.3.2.17.27 (Values can be found in Table 7-62)
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum OperationEventCode {
+        UnknownOrManufacturerSpecific = 0x00,
+        Lock = 0x01,
+        Unlock = 0x02,
+        LockFailureInvalidPinOrId = 0x03,
+        LockFailureInvalidSchedule = 0x04,
+        UnlockFailureInvalidPinOrId = 0x05,
+        UnlockFailureInvalidSchedule = 0x06,
+        OneTouchLock = 0x07,
+        KeyLock = 0x08,
+        KeyUnlock = 0x09,
+        AutoLock = 0x0a,
+        ScheduleLock = 0x0b,
+        ScheduleUnlock = 0x0c,
+        ManualLock = 0x0d,
+        ManualUnlock = 0x0e,
+        NonAccessUserOperationalEvent = 0x0f,
+    }
+}
+
+impl_byte! {
+    /// Programming event code of the Programming Event Notification
+    ///
+    /// See Section 7.3.2.17.28 (Values can be found in Table 7-64)
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ProgrammingEventCode {
+        UnknownOrManufacturerSpecific = 0x00,
+        MasterCodeChanged = 0x01,
+        PinCodeAdded = 0x02,
+        PinCodeDeleted = 0x03,
+        PinCodeChanged = 0x04,
+        RfidCodeAdded = 0x05,
+        RfidCodeDeleted = 0x06,
+    }
+}
+
+impl_byte! {
+    /// Days of the week day schedule commands
+    ///
+    /// See Section 7.3.2.16.12
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct DaysMask(pub u8);
+}
+
+impl DaysMask {
+    /// Sunday
+    pub const SUNDAY: u8 = 1 << 0;
+    /// Monday
+    pub const MONDAY: u8 = 1 << 1;
+    /// Tuesday
+    pub const TUESDAY: u8 = 1 << 2;
+    /// Wednesday
+    pub const WEDNESDAY: u8 = 1 << 3;
+    /// Thursday
+    pub const THURSDAY: u8 = 1 << 4;
+    /// Friday
+    pub const FRIDAY: u8 = 1 << 5;
+    /// Saturday
+    pub const SATURDAY: u8 = 1 << 6;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u8) -> bool {
+        self.0 & flags == flags
+    }
+}
+
+/// PIN or RFID code with a capacity of `N` octets
+///
+/// Encoded as an octet string. The code is copied out of the received frame and zeroized when
+/// dropped, comparisons run in constant time and the code is never printed by `Debug`.
+#[derive(Clone)]
+pub struct Code<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+/// PIN code
+pub type PinCode = Code<PIN_CODE_MAX_LEN>;
+
+/// RFID code
+pub type RfidCode = Code<RFID_CODE_MAX_LEN>;
+
+/// PIN or RFID code of the lock/unlock commands and the event notifications
+pub type PinOrRfidCode = Code<RFID_CODE_MAX_LEN>;
+
+impl<const N: usize> Code<N> {
+    /// Creates a code, returns `None` if `code` is longer than `N`.
+    pub fn new(code: &[u8]) -> Option<Self> {
+        if code.len() > N || code.len() > OctetString::MAX_LEN {
+            return None;
+        }
+        let mut bytes = [0; N];
+        bytes[..code.len()].copy_from_slice(code);
+        Some(Self {
+            bytes,
+            len: code.len(),
+        })
+    }
+
+    /// Returns the octets of the code.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    /// Returns the length of the code in octets.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no code is given.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Compares the code with `code` in constant time (for codes of the same length).
+    pub fn matches(&self, code: &[u8]) -> bool {
+        if code.len() != self.len {
+            return false;
+        }
+        self.as_bytes()
+            .iter()
+            .zip(code)
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+    }
+
+    /// Overwrites the code with zeros and empties it.
+    pub fn clear(&mut self) {
+        for byte in &mut self.bytes {
+            // SAFETY: `byte` is a valid, aligned reference into `self.bytes`. The volatile write
+            // keeps the compiler from eliding the zeroization of memory that is about to be freed.
+            unsafe { core::ptr::write_volatile(byte, 0) };
+        }
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+        self.len = 0;
+    }
+}
+
+impl<const N: usize> Default for Code<N> {
+    fn default() -> Self {
+        Self {
+            bytes: [0; N],
+            len: 0,
+        }
+    }
+}
+
+impl<const N: usize> Drop for Code<N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<const N: usize> PartialEq for Code<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.matches(other.as_bytes())
+    }
+}
+
+impl<const N: usize> Eq for Code<N> {}
+
+impl<const N: usize> core::fmt::Debug for Code<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Code {{ len: {}, .. }}", self.len)
+    }
+}
+
+impl<C: Default, const N: usize> TryRead<'_, C> for Code<N> {
+    fn try_read(bytes: &[u8], _: C) -> byte::Result<(Self, usize)> {
+        let (code, len) = OctetString::try_read(bytes, ())?;
+        let code = Self::new(code.as_bytes()).ok_or(byte::Error::BadInput {
+            err: "Code too long",
+        })?;
+
+        Ok((code, len))
+    }
+}
+
+impl<C: Default, const N: usize> TryWrite<C> for Code<N> {
+    fn try_write(self, bytes: &mut [u8], _: C) -> byte::Result<usize> {
+        let code = OctetString::new(self.as_bytes()).ok_or(byte::Error::BadInput {
+            err: "Code too long",
+        })?;
+        code.try_write(bytes, ())
+    }
+}
+
+/// 7.3.2.16.1 Lock Door Command
+///
+/// Also used for the Unlock Door and Toggle commands. The code is only sent if required by the
+/// lock and read as empty if missing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LockDoor {
+    /// PIN or RFID code
+    pub pin_or_rfid_code: PinOrRfidCode,
+}
+
+/// 7.3.2.16.2 Unlock Door Command
+pub type UnlockDoor = LockDoor;
+
+/// 7.3.2.16.3 Toggle Command
+pub type Toggle = LockDoor;
+
+/// Reads an optional trailing code.
+fn read_optional_code(bytes: &[u8], offset: &mut usize) -> byte::Result<PinOrRfidCode> {
+    if *offset >= bytes.len() {
+        Ok(PinOrRfidCode::default())
+    } else {
+        bytes.read_with(offset, ())
+    }
+}
+
+impl TryRead<'_, ()> for LockDoor {
+    fn try_read(bytes: &[u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let pin_or_rfid_code = read_optional_code(bytes, offset)?;
+
+        Ok((Self { pin_or_rfid_code }, *offset))
+    }
+}
+
+impl TryWrite for LockDoor {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        if !self.pin_or_rfid_code.is_empty() {
+            bytes.write_with(offset, self.pin_or_rfid_code, ())?;
+        }
+
+        Ok(*offset)
+    }
+}
+
+/// 7.3.2.16.4 Unlock With Timeout Command
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnlockWithTimeout {
+    /// Seconds until the lock relocks
+    pub timeout: u16,
+    /// PIN or RFID code
+    pub pin_or_rfid_code: PinOrRfidCode,
+}
+
+impl TryRead<'_, ()> for UnlockWithTimeout {
+    fn try_read(bytes: &[u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let timeout = bytes.read_with(offset, byte::LE)?;
+        let pin_or_rfid_code = read_optional_code(bytes, offset)?;
+
+        Ok((
+            Self {
+                timeout,
+                pin_or_rfid_code,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl TryWrite for UnlockWithTimeout {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, self.timeout, byte::LE)?;
+        if !self.pin_or_rfid_code.is_empty() {
+            bytes.write_with(offset, self.pin_or_rfid_code, ())?;
+        }
+
+        Ok(*offset)
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.16.5 Get Log Record Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GetLogRecord {
+        /// Index of the log record, `0` is the most recent
+        pub log_index: u16,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.16.6 Set PIN Code Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct SetPinCode {
+        pub user_id: u16,
+        pub user_status: UserStatus,
+        pub user_type: UserType,
+        pub pin: PinCode,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.16.7 Get PIN Code Command
+    ///
+    /// Also used for all commands addressing a single user.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GetPinCode {
+        /// User of the command
+        pub user_id: u16,
+    }
+}
+
+/// 7.3.2.16.8 Clear PIN Code Command
+pub type ClearPinCode = GetPinCode;
+
+/// 7.3.2.16.11 Get User Status Command
+pub type GetUserStatus = GetPinCode;
+
+/// 7.3.2.16.22 Get User Type Command
+pub type GetUserType = GetPinCode;
+
+/// 7.3.2.16.24 Get RFID Code Command
+pub type GetRfidCode = GetPinCode;
+
+/// 7.3.2.16.25 Clear RFID Code Command
+pub type ClearRfidCode = GetPinCode;
+
+impl_byte! {
+    /// 7.3.2.16.10 Set User Status Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct SetUserStatus {
+        pub user_id: u16,
+        pub user_status: UserStatus,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.16.12 Set Weekday Schedule Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct SetWeekdaySchedule {
+        pub schedule_id: u8,
+        pub user_id: u16,
+        pub days_mask: DaysMask,
+        pub start_hour: u8,
+        pub start_minute: u8,
+        pub end_hour: u8,
+        pub end_minute: u8,
+    }
+}
+
+impl SetWeekdaySchedule {
+    /// Returns `true` if the times are valid and the schedule ends after it starts.
+    pub fn is_valid(&self) -> bool {
+        let valid_time = |hour: u8, minute: u8| hour < 24 && minute < 60;
+        valid_time(self.start_hour, self.start_minute)
+            && valid_time(self.end_hour, self.end_minute)
+            && (self.start_hour, self.start_minute) < (self.end_hour, self.end_minute)
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.16.13 Get Weekday Schedule Command
+    ///
+    /// Also used for the Clear Weekday Schedule and the Get/Clear Year Day Schedule commands.
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GetWeekdaySchedule {
+        pub schedule_id: u8,
+        pub user_id: u16,
+    }
+}
+
+/// 7.3.2.16.14 Clear Weekday Schedule Command
+pub type ClearWeekdaySchedule = GetWeekdaySchedule;
+
+/// 7.3.2.16.16 Get Year Day Schedule Command
+pub type GetYearDaySchedule = GetWeekdaySchedule;
+
+/// 7.3.2.16.17 Clear Year Day Schedule Command
+pub type ClearYearDaySchedule = GetWeekdaySchedule;
+
+impl_byte! {
+    /// 7.3.2.16.15 Set Year Day Schedule Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct SetYearDaySchedule {
+        pub schedule_id: u8,
+        pub user_id: u16,
+        /// Local time in seconds since 2000-01-01 00:00:00
+        pub local_start_time: u32,
+        /// Local time in seconds since 2000-01-01 00:00:00
+        pub local_end_time: u32,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.16.18 Set Holiday Schedule Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct SetHolidaySchedule {
+        pub holiday_schedule_id: u8,
+        /// Local time in seconds since 2000-01-01 00:00:00
+        pub local_start_time: u32,
+        /// Local time in seconds since 2000-01-01 00:00:00
+        pub local_end_time: u32,
+        pub operating_mode_during_holiday: OperatingMode,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.16.19 Get Holiday Schedule Command
+    ///
+    /// Also used for the Clear Holiday Schedule command.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GetHolidaySchedule {
+        /// Holiday schedule of the command
+        pub holiday_schedule_id: u8,
+    }
+}
+
+/// 7.3.2.16.20 Clear Holiday Schedule Command
+pub type ClearHolidaySchedule = GetHolidaySchedule;
+
+impl_byte! {
+    /// 7.3.2.16.21 Set User Type Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct SetUserType {
+        pub user_id: u16,
+        pub user_type: UserType,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.16.23 Set RFID Code Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct SetRfidCode {
+        pub user_id: u16,
+        pub user_status: UserStatus,
+        pub user_type: UserType,
+        pub rfid_code: RfidCode,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.17.1 Lock Door Response Command
+    ///
+    /// Also used for all responses that only carry a status.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct StatusResponse {
+        /// Status of the command
+        pub status: ZclStatus,
+    }
+}
+
+/// 7.3.2.17.1 Lock Door Response Command
+pub type LockDoorResponse = StatusResponse;
+
+/// 7.3.2.17.2 Unlock Door Response Command
+pub type UnlockDoorResponse = StatusResponse;
+
+/// 7.3.2.17.3 Toggle Response Command
+pub type ToggleResponse = StatusResponse;
+
+/// 7.3.2.17.4 Unlock With Timeout Response Command
+pub type UnlockWithTimeoutResponse = StatusResponse;
+
+/// 7.3.2.17.8 Clear PIN Code Response Command
+pub type ClearPinCodeResponse = StatusResponse;
+
+/// 7.3.2.17.9 Clear All PIN Codes Response Command
+pub type ClearAllPinCodesResponse = StatusResponse;
+
+/// 7.3.2.17.10 Set User Status Response Command
+pub type SetUserStatusResponse = StatusResponse;
+
+/// 7.3.2.17.12 Set Weekday Schedule Response Command
+pub type SetWeekdayScheduleResponse = StatusResponse;
+
+/// 7.3.2.17.14 Clear Weekday Schedule Response Command
+pub type ClearWeekdayScheduleResponse = StatusResponse;
+
+/// 7.3.2.17.15 Set Year Day Schedule Response Command
+pub type SetYearDayScheduleResponse = StatusResponse;
+
+/// 7.3.2.17.17 Clear Year Day Schedule Response Command
+pub type ClearYearDayScheduleResponse = StatusResponse;
+
+/// 7.3.2.17.18 Set Holiday Schedule Response Command
+pub type SetHolidayScheduleResponse = StatusResponse;
+
+/// 7.3.2.17.20 Clear Holiday Schedule Response Command
+pub type ClearHolidayScheduleResponse = StatusResponse;
+
+/// 7.3.2.17.21 Set User Type Response Command
+pub type SetUserTypeResponse = StatusResponse;
+
+/// 7.3.2.17.25 Clear RFID Code Response Command
+pub type ClearRfidCodeResponse = StatusResponse;
+
+/// 7.3.2.17.26 Clear All RFID Codes Response Command
+pub type ClearAllRfidCodesResponse = StatusResponse;
+
+impl_byte! {
+    /// 7.3.2.17.6 Set PIN Code Response Command
+    ///
+    /// Also used for the Set RFID Code Response command.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct SetPinCodeResponse {
+        /// Status of the command
+        pub status: SetCodeStatus,
+    }
+}
+
+/// 7.3.2.17.23 Set RFID Code Response Command
+pub type SetRfidCodeResponse = SetPinCodeResponse;
+
+impl_byte! {
+    /// 7.3.2.17.5 Get Log Record Response Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct GetLogRecordResponse {
+        pub log_entry_id: u16,
+        /// Local time in seconds since 2000-01-01 00:00:00
+        pub timestamp: u32,
+        pub event_type: EventType,
+        pub source: EventSource,
+        /// Operation or programming event code, or alarm code
+        pub event_id_or_alarm_code: u8,
+        pub user_id: u16,
+        pub pin: PinOrRfidCode,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.17.7 Get PIN Code Response Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct GetPinCodeResponse {
+        pub user_id: u16,
+        pub user_status: UserStatus,
+        pub user_type: UserType,
+        pub code: PinCode,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.17.11 Get User Status Response Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GetUserStatusResponse {
+        pub user_id: u16,
+        pub user_status: UserStatus,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.17.13 Get Weekday Schedule Response Command
+    ///
+    /// The schedule is only present on success.
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GetWeekdayScheduleResponse {
+        pub schedule_id: u8,
+        pub user_id: u16,
+        pub status: ZclStatus,
+        #[parse_if = status.is_success()]
+        pub days_mask: Option<DaysMask>,
+        #[parse_if = status.is_success()]
+        pub start_hour: Option<u8>,
+        #[parse_if = status.is_success()]
+        pub start_minute: Option<u8>,
+        #[parse_if = status.is_success()]
+        pub end_hour: Option<u8>,
+        #[parse_if = status.is_success()]
+        pub end_minute: Option<u8>,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.17.16 Get Year Day Schedule Response Command
+    ///
+    /// The schedule is only present on success.
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GetYearDayScheduleResponse {
+        pub schedule_id: u8,
+        pub user_id: u16,
+        pub status: ZclStatus,
+        #[parse_if = status.is_success()]
+        pub local_start_time: Option<u32>,
+        #[parse_if = status.is_success()]
+        pub local_end_time: Option<u32>,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.17.19 Get Holiday Schedule Response Command
+    ///
+    /// The schedule is only present on success.
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GetHolidayScheduleResponse {
+        pub holiday_schedule_id: u8,
+        pub status: ZclStatus,
+        #[parse_if = status.is_success()]
+        pub local_start_time: Option<u32>,
+        #[parse_if = status.is_success()]
+        pub local_end_time: Option<u32>,
+        #[parse_if = status.is_success()]
+        pub operating_mode_during_holiday: Option<OperatingMode>,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.17.22 Get User Type Response Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GetUserTypeResponse {
+        pub user_id: u16,
+        pub user_type: UserType,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.17.24 Get RFID Code Response Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct GetRfidCodeResponse {
+        pub user_id: u16,
+        pub user_status: UserStatus,
+        pub user_type: UserType,
+        pub rfid_code: RfidCode,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.17.27 Operation Event Notification Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct OperationEventNotification<'a> {
+        pub operation_event_source: EventSource,
+        pub operation_event_code: OperationEventCode,
+        pub user_id: u16,
+        pub pin: PinOrRfidCode,
+        /// Local time in seconds since 2000-01-01 00:00:00
+        pub local_time: u32,
+        /// Manufacturer specific data
+        pub data: CharacterString<'a>,
+    }
+}
+
+impl_byte! {
+    /// 7.3.2.17.28 Programming Event Notification Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct ProgrammingEventNotification<'a> {
+        pub program_event_source: EventSource,
+        pub program_event_code: ProgrammingEventCode,
+        pub user_id: u16,
+        pub pin: PinOrRfidCode,
+        pub user_type: UserType,
+        pub user_status: UserStatus,
+        /// Local time in seconds since 2000-01-01 00:00:00
+        pub local_time: u32,
+        /// Manufacturer specific data
+        pub data: CharacterString<'a>,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::{TryRead, TryWrite};
+
+    use super::*;
+
+    #[test]
+    fn unpack_set_pin_code() {
+        // given
+        let input = [
+            0x03, 0x00, // user id
+            0x01, // occupied/enabled
+            0x00, // unrestricted
+            0x04, b'1', b'2', b'3', b'4', // pin
+        ];
+
+        // when
+        let (command, len) =
+            SetPinCode::try_read(&input, ()).expect("Could not read SetPinCode in test");
+
+        // then
+        assert_eq!(len, input.len());
+        assert_eq!(command.user_id, 3);
+        assert_eq!(command.user_status, UserStatus::OccupiedEnabled);
+        assert!(command.pin.matches(b"1234"));
+        assert!(!command.pin.matches(b"1235"));
+        assert!(!command.pin.matches(b"123"));
+    }
+
+    #[test]
+    fn lock_door_without_code() {
+        // given
+        let input = [];
+
+        // when
+        let (command, len) =
+            LockDoor::try_read(&input, ()).expect("Could not read LockDoor in test");
+
+        // then
+        assert_eq!(len, 0);
+        assert!(command.pin_or_rfid_code.is_empty());
+        let (command, _) = UnlockWithTimeout::try_read(&[0x0a, 0x00, 0x02, 0x31, 0x32], ())
+            .expect("Could not read UnlockWithTimeout in test");
+        assert_eq!(command.timeout, 10);
+        assert_eq!(command.pin_or_rfid_code.as_bytes(), b"12");
+    }
+
+    #[test]
+    fn code_is_zeroized() {
+        // given
+        let mut code = PinCode::new(b"987654").expect("Invalid PinCode in test");
+
+        // when
+        code.clear();
+
+        // then
+        assert!(code.is_empty());
+        assert!(code.bytes.iter().all(|b| *b == 0));
+        assert!(PinCode::new(&[0; PIN_CODE_MAX_LEN + 1]).is_none());
+        assert!(PinCode::try_read(&[0x11; 18], ()).is_err());
+    }
+
+    #[test]
+    fn code_is_zeroized_on_drop() {
+        // given
+        let mut code =
+            core::mem::ManuallyDrop::new(PinCode::new(b"987654").expect("Invalid PinCode in test"));
+
+        // when
+        // SAFETY: `code` is dropped exactly once and only its plain integer fields are read
+        // afterwards, their storage is still owned by the `ManuallyDrop`.
+        unsafe { core::ptr::drop_in_place(core::ptr::addr_of_mut!(*code)) };
+
+        // then
+        assert_eq!(code.len, 0);
+        assert!(code.bytes.iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn pack_get_weekday_schedule_response() {
+        // given
+        let response = GetWeekdayScheduleResponse {
+            schedule_id: 1,
+            user_id: 2,
+            status: ZclStatus::NotFound,
+            days_mask: None,
+            start_hour: None,
+            start_minute: None,
+            end_hour: None,
+            end_minute: None,
+        };
+
+        // when
+        let mut buf = [0u8; 16];
+        let len = response
+            .try_write(&mut buf, ())
+            .expect("Could not write GetWeekdayScheduleResponse in test");
+
+        // then
+        assert_eq!(&buf[..len], &[0x01, 0x02, 0x00, 0x8b]);
+        let (read, _) = GetWeekdayScheduleResponse::try_read(&buf[..len], ())
+            .expect("Could not read GetWeekdayScheduleResponse in test");
+        assert_eq!(read, response);
+        assert!(SetWeekdaySchedule {
+            schedule_id: 0,
+            user_id: 1,
+            days_mask: DaysMask(DaysMask::MONDAY),
+            start_hour: 8,
+            start_minute: 30,
+            end_hour: 17,
+            end_minute: 0,
+        }
+        .is_valid());
+    }
+
+    #[test]
+    fn unpack_operation_event_notification() {
+        // given
+        let input = [
+            0x00, 0x02, // keypad unlock
+            0x05, 0x00, // user id
+            0x00, // no pin
+            0x10, 0x00, 0x00, 0x00, // local time
+            0x00, // no data
+        ];
+
+        // when
+        let (notification, _) = OperationEventNotification::try_read(&input, ())
+            .expect("Could not read OperationEventNotification in test");
+
+        // then
+        assert_eq!(notification.operation_event_source, EventSource::Keypad);
+        assert_eq!(
+            notification.operation_event_code,
+            OperationEventCode::Unlock
+        );
+        assert!(notification.pin.is_empty());
+        assert_eq!(notification.local_time, 16);
+    }
+
+    #[test]
+    fn supported_operating_modes() {
+        // given
+        let default = SupportedOperatingModes::default();
+        let (modes, _) = SupportedOperatingModes::try_read(&[0xfa, 0xff], ())
+            .expect("Could not read SupportedOperatingModes in test");
+
+        // then
+        assert_eq!(default, SupportedOperatingModes(0xffff));
+        assert!(!default.supports(OperatingMode::Normal));
+        assert!(!default.supports(OperatingMode::Passage));
+        assert!(modes.supports(OperatingMode::Normal));
+        assert!(!modes.supports(OperatingMode::Vacation));
+        assert!(modes.supports(OperatingMode::Privacy));
+        assert!(!modes.supports(OperatingMode::NoRfLockOrUnlock));
+    }
+}
@@ -0,0 +1,8 @@
+//!
+//! Closures Clusters
+
+/// Provides attributes and commands for controlling a door lock and managing its users.
+pub mod door_lock;
+
+/// Provides attributes and commands for controlling the lift and tilt of a window covering.
+pub mod window_covering;
@@ -0,0 +1,489 @@
+//!
+//! 7.4 Window Covering Cluster
+//!
+//! Attributes and commands for controlling the lift and tilt of a window covering (e.g. blinds,
+//! shades or awnings). [`WindowCoveringServer`] tracks the commanded targets and maintains the
+//! position percentages from the installed open and closed limits.
+use crate::impl_byte;
+use crate::status::ZclStatus;
+
+/// Window Covering cluster identifier
+pub const CLUSTER_ID: u16 = 0x0102;
+
+impl_byte! {
+    /// 7.4.2.1 Window Covering Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        // Window Covering Information
+        WindowCoveringType = 0x0000,
+        PhysicalClosedLimitLift = 0x0001,
+        PhysicalClosedLimitTilt = 0x0002,
+        CurrentPositionLift = 0x0003,
+        CurrentPositionTilt = 0x0004,
+        NumberOfActuationsLift = 0x0005,
+        NumberOfActuationsTilt = 0x0006,
+        ConfigStatus = 0x0007,
+        CurrentPositionLiftPercentage = 0x0008,
+        CurrentPositionTiltPercentage = 0x0009,
+        // Window Covering Settings
+        InstalledOpenLimitLift = 0x0010,
+        InstalledClosedLimitLift = 0x0011,
+        InstalledOpenLimitTilt = 0x0012,
+        InstalledClosedLimitTilt = 0x0013,
+        VelocityLift = 0x0014,
+        AccelerationTimeLift = 0x0015,
+        DecelerationTimeLift = 0x0016,
+        Mode = 0x0017,
+        IntermediateSetpointsLift = 0x0018,
+        IntermediateSetpointsTilt = 0x0019,
+    }
+}
+
+impl_byte! {
+    /// 7.4.2.2 Commands Received
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ReceivedCommandIdentifier {
+        UpOpen = 0x00,
+        DownClose = 0x01,
+        Stop = 0x02,
+        GoToLiftValue = 0x04,
+        GoToLiftPercentage = 0x05,
+        GoToTiltValue = 0x07,
+        GoToTiltPercentage = 0x08,
+    }
+}
+
+impl_byte! {
+    /// 7.4.2.1.1.1 `WindowCoveringType` Attribute
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum WindowCoveringType {
+        Rollershade = 0x00,
+        Rollershade2Motor = 0x01,
+        RollershadeExterior = 0x02,
+        RollershadeExterior2Motor = 0x03,
+        Drapery = 0x04,
+        Awning = 0x05,
+        Shutter = 0x06,
+        TiltBlindTiltOnly = 0x07,
+        TiltBlindLiftAndTilt = 0x08,
+        ProjectorScreen = 0x09,
+    }
+}
+
+impl WindowCoveringType {
+    /// Returns `true` if the covering can be lifted.
+    pub fn supports_lift(self) -> bool {
+        self != Self::TiltBlindTiltOnly
+    }
+
+    /// Returns `true` if the covering can be tilted.
+    pub fn supports_tilt(self) -> bool {
+        matches!(
+            self,
+            Self::Shutter | Self::TiltBlindTiltOnly | Self::TiltBlindLiftAndTilt
+        )
+    }
+}
+
+impl_byte! {
+    /// 7.4.2.1.1.8 `ConfigStatus` Attribute
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct ConfigStatus(pub u8);
+}
+
+impl ConfigStatus {
+    /// The covering is operational
+    pub const OPERATIONAL: u8 = 1 << 0;
+    /// The covering is online
+    pub const ONLINE: u8 = 1 << 1;
+    /// The up and down commands are reversed
+    pub const COMMANDS_REVERSED: u8 = 1 << 2;
+    /// Lift control is closed loop
+    pub const LIFT_CLOSED_LOOP: u8 = 1 << 3;
+    /// Tilt control is closed loop
+    pub const TILT_CLOSED_LOOP: u8 = 1 << 4;
+    /// Lift is encoder controlled
+    pub const LIFT_ENCODER_CONTROLLED: u8 = 1 << 5;
+    /// Tilt is encoder controlled
+    pub const TILT_ENCODER_CONTROLLED: u8 = 1 << 6;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u8) -> bool {
+        self.0 & flags == flags
+    }
+}
+
+impl_byte! {
+    /// 7.4.2.1.2.8 `Mode` Attribute
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct Mode(pub u8);
+}
+
+impl Mode {
+    /// The motor direction is reversed
+    pub const MOTOR_DIRECTION_REVERSED: u8 = 1 << 0;
+    /// The covering is in calibration mode
+    pub const CALIBRATION: u8 = 1 << 1;
+    /// The covering is in maintenance mode
+    pub const MAINTENANCE: u8 = 1 << 2;
+    /// LEDs display feedback
+    pub const LED_FEEDBACK: u8 = 1 << 3;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u8) -> bool {
+        self.0 & flags == flags
+    }
+}
+
+impl_byte! {
+    /// 7.4.2.2.4 Go To Lift Value Command
+    ///
+    /// Also used for the Go To Tilt Value command.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GoToLiftValue {
+        /// Target position
+        pub value: u16,
+    }
+}
+
+/// 7.4.2.2.6 Go To Tilt Value Command
+pub type GoToTiltValue = GoToLiftValue;
+
+impl_byte! {
+    /// 7.4.2.2.5 Go To Lift Percentage Command
+    ///
+    /// Also used for the Go To Tilt Percentage command.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GoToLiftPercentage {
+        /// Target position in percent, `0` is fully open and `100` fully closed
+        pub percentage: u8,
+    }
+}
+
+/// 7.4.2.2.7 Go To Tilt Percentage Command
+pub type GoToTiltPercentage = GoToLiftPercentage;
+
+/// Position of the lift or tilt of a window covering
+///
+/// Positions are given in the unit of the device, between the installed open and closed limit.
+/// `0%` is the open limit and `100%` the closed limit, the open limit may be larger than the
+/// closed limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Axis {
+    installed_open_limit: u16,
+    installed_closed_limit: u16,
+    current_position: u16,
+    target_position: u16,
+}
+
+impl Default for Axis {
+    fn default() -> Self {
+        Self::new(0x0000, 0xffff)
+    }
+}
+
+impl Axis {
+    /// Creates a fully open position with the installed limits.
+    pub fn new(installed_open_limit: u16, installed_closed_limit: u16) -> Self {
+        Self {
+            installed_open_limit,
+            installed_closed_limit,
+            current_position: installed_open_limit,
+            target_position: installed_open_limit,
+        }
+    }
+
+    /// Returns the installed open limit.
+    pub fn installed_open_limit(&self) -> u16 {
+        self.installed_open_limit
+    }
+
+    /// Returns the installed closed limit.
+    pub fn installed_closed_limit(&self) -> u16 {
+        self.installed_closed_limit
+    }
+
+    /// Returns the current position.
+    pub fn current_position(&self) -> u16 {
+        self.current_position
+    }
+
+    /// Sets the current position as reported by the motor, limited to the installed limits.
+    pub fn set_current_position(&mut self, position: u16) {
+        self.current_position = self.clamp(position);
+    }
+
+    /// Returns the commanded target position.
+    pub fn target_position(&self) -> u16 {
+        self.target_position
+    }
+
+    /// Returns `true` if the current position differs from the target position.
+    pub fn is_moving(&self) -> bool {
+        self.current_position != self.target_position
+    }
+
+    /// Returns the current position in percent.
+    pub fn current_percentage(&self) -> u8 {
+        self.percentage(self.current_position)
+    }
+
+    /// Returns the target position in percent.
+    pub fn target_percentage(&self) -> u8 {
+        self.percentage(self.target_position)
+    }
+
+    fn clamp(self, position: u16) -> u16 {
+        let min = self.installed_open_limit.min(self.installed_closed_limit);
+        let max = self.installed_open_limit.max(self.installed_closed_limit);
+        position.clamp(min, max)
+    }
+
+    /// Converts a position to percent, rounded to the nearest percent.
+    pub fn percentage(&self, position: u16) -> u8 {
+        let range = i32::from(self.installed_closed_limit) - i32::from(self.installed_open_limit);
+        if range == 0 {
+            return 0;
+        }
+        let travelled = i32::from(self.clamp(position)) - i32::from(self.installed_open_limit);
+        let percentage = (travelled * 200 + range) / (range * 2);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let percentage = percentage.clamp(0, 100) as u8;
+        percentage
+    }
+
+    /// Converts a percentage to a position, returns `None` if `percentage` is larger than 100.
+    pub fn position(&self, percentage: u8) -> Option<u16> {
+        if percentage > 100 {
+            return None;
+        }
+        let open = i32::from(self.installed_open_limit);
+        let range = i32::from(self.installed_closed_limit) - open;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let position = (open + range * i32::from(percentage) / 100) as u16;
+        Some(position)
+    }
+
+    fn go_to(&mut self, position: u16) {
+        self.target_position = self.clamp(position);
+    }
+
+    fn go_to_percentage(&mut self, percentage: u8) -> ZclStatus {
+        self.position(percentage)
+            .map_or(ZclStatus::InvalidValue, |position| {
+                self.go_to(position);
+                ZclStatus::Success
+            })
+    }
+}
+
+/// Window Covering server
+///
+/// The commands set the target positions of lift and tilt. The application drives the motors
+/// towards [`Axis::target_position`] and reports the actual position with
+/// [`Axis::set_current_position`], from which the percentage attributes are derived.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowCoveringServer {
+    window_covering_type: WindowCoveringType,
+    /// `ConfigStatus` attribute
+    pub config_status: ConfigStatus,
+    /// `Mode` attribute
+    pub mode: Mode,
+    /// Lift position
+    pub lift: Axis,
+    /// Tilt position
+    pub tilt: Axis,
+}
+
+impl WindowCoveringServer {
+    /// Creates a server for a covering of the given type.
+    pub fn new(window_covering_type: WindowCoveringType, lift: Axis, tilt: Axis) -> Self {
+        Self {
+            window_covering_type,
+            config_status: ConfigStatus(ConfigStatus::OPERATIONAL | ConfigStatus::ONLINE),
+            mode: Mode::default(),
+            lift,
+            tilt,
+        }
+    }
+
+    /// Returns the `WindowCoveringType` attribute.
+    pub fn window_covering_type(&self) -> WindowCoveringType {
+        self.window_covering_type
+    }
+
+    /// Returns the `CurrentPositionLiftPercentage` attribute.
+    pub fn current_position_lift_percentage(&self) -> u8 {
+        self.lift.current_percentage()
+    }
+
+    /// Returns the `CurrentPositionTiltPercentage` attribute.
+    pub fn current_position_tilt_percentage(&self) -> u8 {
+        self.tilt.current_percentage()
+    }
+
+    /// Returns the status for a command, commands are not executed in maintenance mode.
+    fn check(&self, supported: bool) -> ZclStatus {
+        if !supported {
+            ZclStatus::UnsupClusterCommand
+        } else if self.mode.contains(Mode::MAINTENANCE) {
+            ZclStatus::Failure
+        } else {
+            ZclStatus::Success
+        }
+    }
+
+    /// Handles the Up/Open command.
+    ///
+    /// See Section 7.4.2.2.1
+    pub fn up_open(&mut self) -> ZclStatus {
+        let status = self.check(true);
+        if status.is_success() {
+            let (lift, tilt) = (
+                self.lift.installed_open_limit,
+                self.tilt.installed_open_limit,
+            );
+            self.lift.go_to(lift);
+            self.tilt.go_to(tilt);
+        }
+        status
+    }
+
+    /// Handles the Down/Close command.
+    ///
+    /// See Section 7.4.2.2.2
+    pub fn down_close(&mut self) -> ZclStatus {
+        let status = self.check(true);
+        if status.is_success() {
+            let (lift, tilt) = (
+                self.lift.installed_closed_limit,
+                self.tilt.installed_closed_limit,
+            );
+            self.lift.go_to(lift);
+            self.tilt.go_to(tilt);
+        }
+        status
+    }
+
+    /// Handles the Stop command, the current positions become the targets.
+    ///
+    /// See Section 7.4.2.2.3
+    pub fn stop(&mut self) -> ZclStatus {
+        let status = self.check(true);
+        if status.is_success() {
+            self.lift.target_position = self.lift.current_position;
+            self.tilt.target_position = self.tilt.current_position;
+        }
+        status
+    }
+
+    /// Handles the Go To Lift Value command.
+    ///
+    /// See Section 7.4.2.2.4
+    pub fn go_to_lift_value(&mut self, command: GoToLiftValue) -> ZclStatus {
+        let status = self.check(self.window_covering_type.supports_lift());
+        if status.is_success() {
+            self.lift.go_to(command.value);
+        }
+        status
+    }
+
+    /// Handles the Go To Lift Percentage command.
+    ///
+    /// See Section 7.4.2.2.5
+    pub fn go_to_lift_percentage(&mut self, command: GoToLiftPercentage) -> ZclStatus {
+        let status = self.check(self.window_covering_type.supports_lift());
+        if status.is_success() {
+            self.lift.go_to_percentage(command.percentage)
+        } else {
+            status
+        }
+    }
+
+    /// Handles the Go To Tilt Value command.
+    ///
+    /// See Section 7.4.2.2.6
+    pub fn go_to_tilt_value(&mut self, command: GoToTiltValue) -> ZclStatus {
+        let status = self.check(self.window_covering_type.supports_tilt());
+        if status.is_success() {
+            self.tilt.go_to(command.value);
+        }
+        status
+    }
+
+    /// Handles the Go To Tilt Percentage command.
+    ///
+    /// See Section 7.4.2.2.7
+    pub fn go_to_tilt_percentage(&mut self, command: GoToTiltPercentage) -> ZclStatus {
+        let status = self.check(self.window_covering_type.supports_tilt());
+        if status.is_success() {
+            self.tilt.go_to_percentage(command.percentage)
+        } else {
+            status
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lift_percentage() {
+        // given
+        let mut server = WindowCoveringServer::new(
+            WindowCoveringType::Rollershade,
+            Axis::new(100, 300),
+            Axis::default(),
+        );
+
+        // when
+        let status = server.go_to_lift_percentage(GoToLiftPercentage { percentage: 25 });
+
+        // then
+        assert_eq!(status, ZclStatus::Success);
+        assert_eq!(server.lift.target_position(), 150);
+        assert!(server.lift.is_moving());
+        server.lift.set_current_position(149);
+        assert_eq!(server.current_position_lift_percentage(), 25);
+        server.lift.set_current_position(1000);
+        assert_eq!(server.current_position_lift_percentage(), 100);
+        assert_eq!(
+            server.go_to_lift_percentage(GoToLiftPercentage { percentage: 101 }),
+            ZclStatus::InvalidValue
+        );
+        assert_eq!(
+            server.go_to_tilt_value(GoToTiltValue { value: 10 }),
+            ZclStatus::UnsupClusterCommand
+        );
+    }
+
+    #[test]
+    fn reversed_limits() {
+        // given
+        let mut server = WindowCoveringServer::new(
+            WindowCoveringType::TiltBlindLiftAndTilt,
+            Axis::new(1000, 0),
+            Axis::new(0, 90),
+        );
+
+        // when
+        server.down_close();
+
+        // then
+        assert_eq!(server.lift.target_position(), 0);
+        assert_eq!(server.tilt.target_position(), 90);
+        server.lift.set_current_position(250);
+        assert_eq!(server.current_position_lift_percentage(), 75);
+        server.stop();
+        assert!(!server.lift.is_moving());
+        server.mode = Mode(Mode::MAINTENANCE);
+        assert_eq!(server.up_open(), ZclStatus::Failure);
+    }
+}
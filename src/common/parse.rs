@@ -1,4 +1,4 @@
-/// Implements `byte` for a struct.
+/// Implements `byte` for a struct or a fieldless enum.
 #[macro_export]
 macro_rules! impl_byte {
     (
@@ -21,6 +21,57 @@ macro_rules! impl_byte {
             }
         }
     };
+    (
+        $(#[doc = $doc:literal])*
+        #[repr($ty:ident)]
+        $(#[$m:meta])*
+        $v:vis enum $name:ident {
+            $(
+                $(#[doc = $variant_doc:literal])*
+                $variant:ident = $value:literal
+            ),+
+            $(,)?
+        }
+    ) => {
+        $(#[doc = $doc])*
+        #[repr($ty)]
+        $(#[$m])*
+        $v enum $name {
+            $(
+                $(#[doc = $variant_doc])*
+                $variant = $value
+            ),+
+        }
+
+        impl ::core::convert::TryFrom<$ty> for $name {
+            type Error = $ty;
+
+            fn try_from(value: $ty) -> ::core::result::Result<Self, Self::Error> {
+                match value {
+                    $($value => Ok(Self::$variant),)+
+                    _ => Err(value),
+                }
+            }
+        }
+
+        impl<C: ::core::default::Default> ::byte::TryRead<'_, C> for $name {
+            fn try_read(bytes: &'_ [u8], _: C) -> ::byte::Result<(Self, usize)> {
+                let (v, sz) = <$ty as ::byte::TryRead<'_, ::byte::ctx::Endian>>::try_read(bytes, ::byte::LE)?;
+                let v = <Self as ::core::convert::TryFrom<$ty>>::try_from(v).map_err(|_| {
+                    ::byte::Error::BadInput {
+                        err: concat!("Invalid value for ", stringify!($name)),
+                    }
+                })?;
+                Ok((v, sz))
+            }
+        }
+
+        impl<C: ::core::default::Default> ::byte::TryWrite<C> for $name {
+            fn try_write(self, bytes: &mut [u8], _: C) -> ::byte::Result<usize> {
+                ::byte::TryWrite::try_write(self as $ty, bytes, ::byte::LE)
+            }
+        }
+    };
     (
         $(#[$m:meta])*
         $v:vis struct $name:ident $(<$lifetime:lifetime>)? {
@@ -129,6 +180,15 @@ mod tests {
         }
     }
 
+    impl_byte! {
+        #[repr(u16)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        enum Mode {
+            Off = 0x0000,
+            On = 0x0102,
+        }
+    }
+
     #[test]
     fn parse() {
         let bytes = &[0x01, 0x11, 0x22, 0x4, 0xaa, 0xaa, 0xaa, 0xaa];
@@ -148,4 +208,21 @@ mod tests {
             .expect("Could not write DataFrame in test");
         assert_eq!(&buf, bytes);
     }
+
+    #[test]
+    fn parse_enum() {
+        let bytes = &[0x02, 0x01];
+
+        let (mode, len) = Mode::try_read(bytes, ()).expect("Could not read Mode in test");
+
+        assert_eq!(len, 2);
+        assert_eq!(mode, Mode::On);
+        assert!(Mode::try_read(&[0x01, 0x00], ()).is_err());
+
+        let mut buf = [0u8; 2];
+        Mode::Off
+            .try_write(&mut buf, ())
+            .expect("Could not write Mode in test");
+        assert_eq!(&buf, &[0x00, 0x00]);
+    }
 }
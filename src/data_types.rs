@@ -0,0 +1,544 @@
+//! ZCL Data Types
+//!
+//! Data type identifiers and types without a native Rust equivalent.
+//!
+//! See Section 2.6.2
+use core::convert::TryFrom;
+
+use byte::{check_len, ctx, BytesExt, TryRead, TryWrite};
+
+use crate::impl_byte;
+
+impl_byte! {
+    /// Data type identifier used in attribute records.
+    ///
+    /// See Section 2.6.2 (Values can be found in Table 2-10)
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum DataType {
+        NoData = 0x00,
+        Data8 = 0x08,
+        Data16 = 0x09,
+        Data24 = 0x0a,
+        Data32 = 0x0b,
+        Data40 = 0x0c,
+        Data48 = 0x0d,
+        Data56 = 0x0e,
+        Data64 = 0x0f,
+        Bool = 0x10,
+        Bitmap8 = 0x18,
+        Bitmap16 = 0x19,
+        Bitmap24 = 0x1a,
+        Bitmap32 = 0x1b,
+        Bitmap40 = 0x1c,
+        Bitmap48 = 0x1d,
+        Bitmap56 = 0x1e,
+        Bitmap64 = 0x1f,
+        Uint8 = 0x20,
+        Uint16 = 0x21,
+        Uint24 = 0x22,
+        Uint32 = 0x23,
+        Uint40 = 0x24,
+        Uint48 = 0x25,
+        Uint56 = 0x26,
+        Uint64 = 0x27,
+        Int8 = 0x28,
+        Int16 = 0x29,
+        Int24 = 0x2a,
+        Int32 = 0x2b,
+        Int40 = 0x2c,
+        Int48 = 0x2d,
+        Int56 = 0x2e,
+        Int64 = 0x2f,
+        Enum8 = 0x30,
+        Enum16 = 0x31,
+        SemiPrecision = 0x38,
+        SinglePrecision = 0x39,
+        DoublePrecision = 0x3a,
+        OctetString = 0x41,
+        CharacterString = 0x42,
+        LongOctetString = 0x43,
+        LongCharacterString = 0x44,
+        Array = 0x48,
+        Structure = 0x4c,
+        Set = 0x50,
+        Bag = 0x51,
+        TimeOfDay = 0xe0,
+        Date = 0xe1,
+        UtcTime = 0xe2,
+        ClusterId = 0xe8,
+        AttributeId = 0xe9,
+        BacnetOid = 0xea,
+        IeeeAddress = 0xf0,
+        SecurityKey = 0xf1,
+        Unknown = 0xff,
+    }
+}
+
+/// Unsigned 24-bit integer (`uint24`)
+///
+/// See Section 2.6.2.5
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Uint24(u32);
+
+/// Signed 24-bit integer (`int24`)
+///
+/// See Section 2.6.2.6
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Int24(i32);
+
+/// Unsigned 48-bit integer (`uint48`)
+///
+/// See Section 2.6.2.5
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Uint48(u64);
+
+impl Uint24 {
+    /// Largest valid value
+    pub const MAX: Self = Self(0x00ff_fffe);
+    /// Value indicating an invalid or unknown value
+    pub const NON_VALUE: Self = Self(0x00ff_ffff);
+
+    /// Creates a new value, returns `None` if `value` does not fit into 24 bits.
+    pub fn new(value: u32) -> Option<Self> {
+        if value > Self::NON_VALUE.0 {
+            None
+        } else {
+            Some(Self(value))
+        }
+    }
+
+    /// Returns the value, or `None` if this is the non-value.
+    pub fn value(self) -> Option<u32> {
+        if self == Self::NON_VALUE {
+            None
+        } else {
+            Some(self.0)
+        }
+    }
+
+    /// Returns the raw value.
+    pub fn get(self) -> u32 {
+        self.0
+    }
+}
+
+impl Int24 {
+    /// Smallest valid value
+    pub const MIN: Self = Self(-0x007f_ffff);
+    /// Largest valid value
+    pub const MAX: Self = Self(0x007f_ffff);
+    /// Value indicating an invalid or unknown value
+    pub const NON_VALUE: Self = Self(-0x0080_0000);
+
+    /// Creates a new value, returns `None` if `value` does not fit into 24 bits.
+    pub fn new(value: i32) -> Option<Self> {
+        if (Self::NON_VALUE.0..=Self::MAX.0).contains(&value) {
+            Some(Self(value))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value, or `None` if this is the non-value.
+    pub fn value(self) -> Option<i32> {
+        if self == Self::NON_VALUE {
+            None
+        } else {
+            Some(self.0)
+        }
+    }
+
+    /// Returns the raw value.
+    pub fn get(self) -> i32 {
+        self.0
+    }
+}
+
+impl Uint48 {
+    /// Largest valid value
+    pub const MAX: Self = Self(0xffff_ffff_fffe);
+    /// Value indicating an invalid or unknown value
+    pub const NON_VALUE: Self = Self(0xffff_ffff_ffff);
+
+    /// Creates a new value, returns `None` if `value` does not fit into 48 bits.
+    pub fn new(value: u64) -> Option<Self> {
+        if value > Self::NON_VALUE.0 {
+            None
+        } else {
+            Some(Self(value))
+        }
+    }
+
+    /// Returns the value, or `None` if this is the non-value.
+    pub fn value(self) -> Option<u64> {
+        if self == Self::NON_VALUE {
+            None
+        } else {
+            Some(self.0)
+        }
+    }
+
+    /// Returns the raw value.
+    pub fn get(self) -> u64 {
+        self.0
+    }
+}
+
+/// Reads a little endian unsigned integer of `N` bytes.
+fn read_le<const N: usize>(bytes: &[u8]) -> byte::Result<u64> {
+    check_len(bytes, N)?;
+    Ok(bytes[..N]
+        .iter()
+        .rev()
+        .fold(0, |acc, b| (acc << 8) | u64::from(*b)))
+}
+
+/// Writes the lower `N` bytes of `value` in little endian.
+fn write_le<const N: usize>(value: u64, bytes: &mut [u8]) -> byte::Result<usize> {
+    check_len(bytes, N)?;
+    bytes[..N].copy_from_slice(&value.to_le_bytes()[..N]);
+    Ok(N)
+}
+
+impl<C: Default> TryRead<'_, C> for Uint24 {
+    #[allow(clippy::cast_possible_truncation)]
+    fn try_read(bytes: &[u8], _: C) -> byte::Result<(Self, usize)> {
+        Ok((Self(read_le::<3>(bytes)? as u32), 3))
+    }
+}
+
+impl<C: Default> TryWrite<C> for Uint24 {
+    fn try_write(self, bytes: &mut [u8], _: C) -> byte::Result<usize> {
+        write_le::<3>(u64::from(self.0), bytes)
+    }
+}
+
+impl<C: Default> TryRead<'_, C> for Int24 {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn try_read(bytes: &[u8], _: C) -> byte::Result<(Self, usize)> {
+        let raw = read_le::<3>(bytes)? as u32;
+        // sign extend from 24 to 32 bits
+        Ok((Self(((raw << 8) as i32) >> 8), 3))
+    }
+}
+
+impl<C: Default> TryWrite<C> for Int24 {
+    #[allow(clippy::cast_sign_loss)]
+    fn try_write(self, bytes: &mut [u8], _: C) -> byte::Result<usize> {
+        write_le::<3>(u64::from(self.0 as u32), bytes)
+    }
+}
+
+impl<C: Default> TryRead<'_, C> for Uint48 {
+    fn try_read(bytes: &[u8], _: C) -> byte::Result<(Self, usize)> {
+        Ok((Self(read_le::<6>(bytes)?), 6))
+    }
+}
+
+impl<C: Default> TryWrite<C> for Uint48 {
+    fn try_write(self, bytes: &mut [u8], _: C) -> byte::Result<usize> {
+        write_le::<6>(self.0, bytes)
+    }
+}
+
+/// Character string (`string`)
+///
+/// The first octet holds the length of the string. A length of `0xff` indicates an invalid
+/// string and is read as an empty string.
+///
+/// See Section 2.6.2.12
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CharacterString<'a>(&'a [u8]);
+
+impl<'a> CharacterString<'a> {
+    /// Maximum length of a character string
+    pub const MAX_LEN: usize = 0xfe;
+
+    /// Creates a new character string, returns `None` if `value` is too long.
+    pub fn new(value: &'a str) -> Option<Self> {
+        if value.len() > Self::MAX_LEN {
+            None
+        } else {
+            Some(Self(value.as_bytes()))
+        }
+    }
+
+    /// Returns the raw characters.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+
+    /// Returns the string or `None` if it is not valid UTF-8.
+    pub fn as_str(&self) -> Option<&'a str> {
+        core::str::from_utf8(self.0).ok()
+    }
+
+    /// Returns the length of the string in octets.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<'a, C: Default> TryRead<'a, C> for CharacterString<'a> {
+    fn try_read(bytes: &'a [u8], _: C) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let len: u8 = bytes.read_with(offset, byte::LE)?;
+        let len = if len == 0xff { 0 } else { usize::from(len) };
+        let value = bytes.read_with(offset, ctx::Bytes::Len(len))?;
+
+        Ok((Self(value), *offset))
+    }
+}
+
+impl<C: Default> TryWrite<C> for CharacterString<'_> {
+    fn try_write(self, bytes: &mut [u8], _: C) -> byte::Result<usize> {
+        let offset = &mut 0;
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.write_with(offset, self.0.len() as u8, byte::LE)?;
+        bytes.write(offset, self.0)?;
+
+        Ok(*offset)
+    }
+}
+
+/// Octet string (`octstr`)
+///
+/// The first octet holds the length of the string. A length of `0xff` indicates an invalid
+/// string and is read as an empty string.
+///
+/// See Section 2.6.2.11
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OctetString<'a>(&'a [u8]);
+
+impl<'a> OctetString<'a> {
+    /// Maximum length of an octet string
+    pub const MAX_LEN: usize = 0xfe;
+
+    /// Creates a new octet string, returns `None` if `value` is too long.
+    pub fn new(value: &'a [u8]) -> Option<Self> {
+        if value.len() > Self::MAX_LEN {
+            None
+        } else {
+            Some(Self(value))
+        }
+    }
+
+    /// Returns the octets.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+
+    /// Returns the length of the string in octets.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<'a, C: Default> TryRead<'a, C> for OctetString<'a> {
+    fn try_read(bytes: &'a [u8], _: C) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let len: u8 = bytes.read_with(offset, byte::LE)?;
+        let len = if len == 0xff { 0 } else { usize::from(len) };
+        let value = bytes.read_with(offset, ctx::Bytes::Len(len))?;
+
+        Ok((Self(value), *offset))
+    }
+}
+
+impl<C: Default> TryWrite<C> for OctetString<'_> {
+    fn try_write(self, bytes: &mut [u8], _: C) -> byte::Result<usize> {
+        let offset = &mut 0;
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.write_with(offset, self.0.len() as u8, byte::LE)?;
+        bytes.write(offset, self.0)?;
+
+        Ok(*offset)
+    }
+}
+
+/// UTC time (`UTC`)
+///
+/// Seconds since 2000-01-01 00:00:00 UTC. A value of `0xffffffff` indicates an invalid time.
+///
+/// See Section 2.6.2.19
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UtcTime(u32);
+
+impl Default for UtcTime {
+    fn default() -> Self {
+        Self::NON_VALUE
+    }
+}
+
+impl UtcTime {
+    /// Value indicating an invalid or unknown time
+    pub const NON_VALUE: Self = Self(0xffff_ffff);
+    /// Seconds between the Unix epoch (1970-01-01) and the `ZigBee` epoch (2000-01-01)
+    pub const UNIX_EPOCH_OFFSET: u64 = 946_684_800;
+
+    /// Creates a time from seconds since 2000-01-01, returns `None` for the non-value.
+    pub fn new(seconds: u32) -> Option<Self> {
+        if seconds == Self::NON_VALUE.0 {
+            None
+        } else {
+            Some(Self(seconds))
+        }
+    }
+
+    /// Creates a time from seconds since 1970-01-01, returns `None` if out of range.
+    pub fn from_unix(seconds: u64) -> Option<Self> {
+        seconds
+            .checked_sub(Self::UNIX_EPOCH_OFFSET)
+            .and_then(|seconds| u32::try_from(seconds).ok())
+            .and_then(Self::new)
+    }
+
+    /// Returns the seconds since 1970-01-01, or `None` if this is the non-value.
+    pub fn to_unix(self) -> Option<u64> {
+        self.value()
+            .map(|seconds| u64::from(seconds) + Self::UNIX_EPOCH_OFFSET)
+    }
+
+    /// Returns the seconds since 2000-01-01, or `None` if this is the non-value.
+    pub fn value(self) -> Option<u32> {
+        if self == Self::NON_VALUE {
+            None
+        } else {
+            Some(self.0)
+        }
+    }
+
+    /// Returns the raw value.
+    pub fn get(self) -> u32 {
+        self.0
+    }
+
+    /// Adds a signed offset in seconds, returns `None` on overflow or for the non-value.
+    pub fn checked_add_signed(self, seconds: i32) -> Option<Self> {
+        let value = i64::from(self.value()?) + i64::from(seconds);
+        u32::try_from(value).ok().and_then(Self::new)
+    }
+}
+
+impl<C: Default> TryRead<'_, C> for UtcTime {
+    fn try_read(bytes: &[u8], _: C) -> byte::Result<(Self, usize)> {
+        let (value, len) = u32::try_read(bytes, byte::LE)?;
+        Ok((Self(value), len))
+    }
+}
+
+impl<C: Default> TryWrite<C> for UtcTime {
+    fn try_write(self, bytes: &mut [u8], _: C) -> byte::Result<usize> {
+        self.0.try_write(bytes, byte::LE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::{TryRead, TryWrite};
+
+    use super::*;
+
+    #[test]
+    fn unpack_uint24() {
+        // given
+        let input = [0x01, 0x02, 0x03];
+
+        // when
+        let (value, len) = Uint24::try_read(&input, ()).expect("Could not read Uint24 in test");
+
+        // then
+        assert_eq!(len, 3);
+        assert_eq!(value.value(), Some(0x0003_0201));
+        assert_eq!(Uint24::new(0x0100_0000), None);
+    }
+
+    #[test]
+    fn unpack_int24() {
+        // given
+        let input = [0xfe, 0xff, 0xff];
+
+        // when
+        let (value, _) = Int24::try_read(&input, ()).expect("Could not read Int24 in test");
+
+        // then
+        assert_eq!(value.value(), Some(-2));
+
+        let mut buf = [0u8; 3];
+        value
+            .try_write(&mut buf, ())
+            .expect("Could not write Int24 in test");
+        assert_eq!(buf, input);
+        let (value, _) =
+            Int24::try_read(&[0x00, 0x00, 0x80], ()).expect("Could not read Int24 in test");
+        assert_eq!(value.value(), None);
+    }
+
+    #[test]
+    fn pack_uint48() {
+        // given
+        let value = Uint48::new(0x0605_0403_0201).expect("Invalid Uint48 in test");
+
+        // when
+        let mut buf = [0u8; 6];
+        let len = value
+            .try_write(&mut buf, ())
+            .expect("Could not write Uint48 in test");
+
+        // then
+        assert_eq!(len, 6);
+        assert_eq!(buf, [0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+        assert!(Uint48::try_read(&buf[..5], ()).is_err());
+    }
+
+    #[test]
+    fn unpack_character_string() {
+        // given
+        let input = [0x05, b'h', b'e', b'l', b'l', b'o', 0x00];
+
+        // when
+        let (value, len) =
+            CharacterString::try_read(&input, ()).expect("Could not read CharacterString in test");
+
+        // then
+        assert_eq!(len, 6);
+        assert_eq!(value.as_str(), Some("hello"));
+
+        let (value, len) =
+            CharacterString::try_read(&[0xff], ()).expect("Could not read CharacterString in test");
+        assert_eq!(len, 1);
+        assert!(value.is_empty());
+    }
+
+    #[test]
+    fn utc_time() {
+        // given
+        let input = [0x80, 0x51, 0x01, 0x00];
+
+        // when
+        let (time, len) = UtcTime::try_read(&input, ()).expect("Could not read UtcTime in test");
+
+        // then
+        assert_eq!(len, 4);
+        assert_eq!(time.value(), Some(86_400));
+        assert_eq!(time.to_unix(), Some(946_771_200));
+        assert_eq!(UtcTime::from_unix(946_771_200), Some(time));
+        assert_eq!(UtcTime::from_unix(0), None);
+        assert_eq!(time.checked_add_signed(-86_401), None);
+        assert_eq!(UtcTime::NON_VALUE.checked_add_signed(1), None);
+        assert_eq!(UtcTime::default(), UtcTime::NON_VALUE);
+        assert_eq!(UtcTime::default().value(), None);
+    }
+}
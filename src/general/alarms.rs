@@ -0,0 +1,288 @@
+//!
+//! 3.11 Alarms Cluster
+//!
+//! Commands for sending alarm notifications and configuring the alarm functionality. The
+//! [`AlarmTable`] logs the alarms raised by other clusters on the device, e.g. the Power
+//! Configuration or Device Temperature Configuration clusters.
+use core::convert::TryFrom;
+
+use crate::data_types::UtcTime;
+use crate::impl_byte;
+use crate::status::ZclStatus;
+
+/// Alarms cluster identifier
+pub const CLUSTER_ID: u16 = 0x0009;
+
+impl_byte! {
+    /// 3.11.2.2 Alarms Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        AlarmCount = 0x0000,
+    }
+}
+
+impl_byte! {
+    /// 3.11.2.4 Commands Received
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ReceivedCommandIdentifier {
+        ResetAlarm = 0x00,
+        ResetAllAlarms = 0x01,
+        GetAlarm = 0x02,
+        ResetAlarmLog = 0x03,
+    }
+}
+
+impl_byte! {
+    /// 3.11.2.5 Commands Generated
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum GeneratedCommandIdentifier {
+        Alarm = 0x00,
+        GetAlarmResponse = 0x01,
+    }
+}
+
+impl_byte! {
+    /// 3.11.2.4.1 Reset Alarm Command
+    ///
+    /// The application forwards the reset to the cluster identified by `cluster_id`, which raises
+    /// the alarm again if the alarm condition still exists.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ResetAlarm {
+        /// Alarm code of the cluster
+        pub alarm_code: u8,
+        /// Cluster which raised the alarm
+        pub cluster_id: u16,
+    }
+}
+
+impl_byte! {
+    /// 3.11.2.5.1 Alarm Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Alarm {
+        /// Alarm code of the cluster
+        pub alarm_code: u8,
+        /// Cluster which raised the alarm
+        pub cluster_id: u16,
+    }
+}
+
+impl_byte! {
+    /// 3.11.2.5.2 Get Alarm Response Command
+    ///
+    /// The alarm is only present on success.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GetAlarmResponse {
+        /// `Success` or `NotFound` if the alarm table is empty
+        pub status: ZclStatus,
+        /// Alarm code of the cluster
+        #[parse_if = status.is_success()]
+        pub alarm_code: Option<u8>,
+        /// Cluster which raised the alarm
+        #[parse_if = status.is_success()]
+        pub cluster_id: Option<u16>,
+        /// Time at which the alarm was raised
+        #[parse_if = status.is_success()]
+        pub timestamp: Option<UtcTime>,
+    }
+}
+
+/// 3.11.2.1 Alarm Table entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlarmEntry {
+    /// Alarm code of the cluster
+    pub alarm_code: u8,
+    /// Cluster which raised the alarm
+    pub cluster_id: u16,
+    /// Time at which the alarm was raised
+    pub timestamp: UtcTime,
+}
+
+/// Alarm table with room for `N` alarms
+///
+/// If the table is full, the alarm with the earliest timestamp is replaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlarmTable<const N: usize> {
+    entries: [Option<AlarmEntry>; N],
+    len: usize,
+}
+
+impl<const N: usize> Default for AlarmTable<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> AlarmTable<N> {
+    /// Creates an empty alarm table.
+    pub fn new() -> Self {
+        Self {
+            entries: [None; N],
+            len: 0,
+        }
+    }
+
+    /// Returns the `AlarmCount` attribute.
+    pub fn alarm_count(&self) -> u16 {
+        u16::try_from(self.len).unwrap_or(u16::MAX)
+    }
+
+    /// Returns the logged alarms in the order they were raised.
+    pub fn alarms(&self) -> impl Iterator<Item = &AlarmEntry> {
+        self.entries[..self.len].iter().flatten()
+    }
+
+    /// Logs an alarm raised by a cluster.
+    ///
+    /// Returns the Alarm command to send to the bound devices.
+    pub fn raise(&mut self, alarm_code: u8, cluster_id: u16, timestamp: UtcTime) -> Alarm {
+        if self.len == N {
+            self.remove_oldest();
+        }
+        if let Some(entry) = self.entries.get_mut(self.len) {
+            *entry = Some(AlarmEntry {
+                alarm_code,
+                cluster_id,
+                timestamp,
+            });
+            self.len += 1;
+        }
+
+        Alarm {
+            alarm_code,
+            cluster_id,
+        }
+    }
+
+    /// Handles the Get Alarm command, the alarm with the earliest timestamp is removed from the
+    /// table and returned.
+    ///
+    /// See Section 3.11.2.4.3
+    pub fn get_alarm(&mut self) -> GetAlarmResponse {
+        self.remove_oldest().map_or(
+            GetAlarmResponse {
+                status: ZclStatus::NotFound,
+                alarm_code: None,
+                cluster_id: None,
+                timestamp: None,
+            },
+            |entry| GetAlarmResponse {
+                status: ZclStatus::Success,
+                alarm_code: Some(entry.alarm_code),
+                cluster_id: Some(entry.cluster_id),
+                timestamp: Some(entry.timestamp),
+            },
+        )
+    }
+
+    /// Handles the Reset Alarm Log command.
+    ///
+    /// See Section 3.11.2.4.4
+    pub fn reset_alarm_log(&mut self) {
+        self.entries = [None; N];
+        self.len = 0;
+    }
+
+    fn remove_oldest(&mut self) -> Option<AlarmEntry> {
+        let (index, _) = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| entry.map(|entry| (i, entry.timestamp)))
+            .min_by_key(|(_, timestamp)| *timestamp)?;
+        let entry = self.entries[index].take();
+        self.entries[index..self.len].rotate_left(1);
+        self.len -= 1;
+        entry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::TryWrite;
+
+    use super::*;
+    use crate::general::power_configuration::{
+        self, BatteryAlarmMask, BatterySource, MainsAlarmMask,
+    };
+
+    fn time(seconds: u32) -> UtcTime {
+        UtcTime::new(seconds).expect("Invalid UtcTime in test")
+    }
+
+    #[test]
+    fn get_oldest_alarm() {
+        // given
+        let mut table = AlarmTable::<4>::new();
+        table.raise(0x10, 0x0001, time(20));
+        table.raise(0x00, 0x0002, time(10));
+        table.raise(0x11, 0x0001, time(30));
+
+        // when
+        let response = table.get_alarm();
+
+        // then
+        assert_eq!(response.alarm_code, Some(0x00));
+        assert_eq!(response.cluster_id, Some(0x0002));
+        assert_eq!(table.alarm_count(), 2);
+        assert!(table
+            .alarms()
+            .map(|alarm| alarm.alarm_code)
+            .eq([0x10, 0x11].iter().copied()));
+        let mut buf = [0u8; 8];
+        let len = response
+            .try_write(&mut buf, ())
+            .expect("Could not write GetAlarmResponse in test");
+        assert_eq!(
+            &buf[..len],
+            &[0x00, 0x00, 0x02, 0x00, 0x0a, 0x00, 0x00, 0x00]
+        );
+    }
+
+    #[test]
+    fn raise_battery_alarms() {
+        // given
+        let mut table = AlarmTable::<4>::new();
+        let alarms = BatteryAlarmMask(BatteryAlarmMask::BATTERY_TOO_LOW | BatteryAlarmMask::ALARM2);
+
+        // when
+        for alarm_code in alarms.alarm_codes(BatterySource::Two) {
+            table.raise(alarm_code, power_configuration::CLUSTER_ID, time(5));
+        }
+
+        // then
+        assert!(table
+            .alarms()
+            .map(|alarm| alarm.alarm_code)
+            .eq([0x20, 0x22].iter().copied()));
+        let mains =
+            MainsAlarmMask(MainsAlarmMask::VOLTAGE_TOO_HIGH | MainsAlarmMask::POWER_SUPPLY_LOST);
+        assert!(mains.alarm_codes().eq([0x01, 0x3a].iter().copied()));
+    }
+
+    #[test]
+    fn full_table_replaces_oldest() {
+        // given
+        let mut table = AlarmTable::<2>::new();
+        table.raise(0x01, 0x0001, time(1));
+        table.raise(0x02, 0x0001, time(2));
+
+        // when
+        let alarm = table.raise(0x03, 0x0001, time(3));
+
+        // then
+        assert_eq!(alarm.alarm_code, 0x03);
+        assert_eq!(table.alarm_count(), 2);
+        assert!(table
+            .alarms()
+            .map(|alarm| alarm.alarm_code)
+            .eq([0x02, 0x03].iter().copied()));
+        table.reset_alarm_log();
+        assert_eq!(table.get_alarm().status, ZclStatus::NotFound);
+    }
+}
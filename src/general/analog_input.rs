@@ -0,0 +1,28 @@
+//!
+//! 3.14.2 Analog Input Cluster
+//!
+//! Attributes for an analog measurement, based on the `BACnet` Analog Input object.
+//! `PresentValue` is a single precision float.
+use crate::impl_byte;
+
+/// Analog Input cluster identifier
+pub const CLUSTER_ID: u16 = 0x000c;
+
+impl_byte! {
+    /// 3.14.2.2 Analog Input Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        Description = 0x001c,
+        MaxPresentValue = 0x0041,
+        MinPresentValue = 0x0045,
+        OutOfService = 0x0051,
+        PresentValue = 0x0055,
+        Reliability = 0x0067,
+        Resolution = 0x006a,
+        StatusFlags = 0x006f,
+        EngineeringUnits = 0x0075,
+        ApplicationType = 0x0100,
+    }
+}
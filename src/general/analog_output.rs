@@ -0,0 +1,30 @@
+//!
+//! 3.14.3 Analog Output Cluster
+//!
+//! Attributes for an analog output, based on the `BACnet` Analog Output object. `PresentValue`
+//! is a single precision float commanded through the `PriorityArray`.
+use crate::impl_byte;
+
+/// Analog Output cluster identifier
+pub const CLUSTER_ID: u16 = 0x000d;
+
+impl_byte! {
+    /// 3.14.3.2 Analog Output Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        Description = 0x001c,
+        MaxPresentValue = 0x0041,
+        MinPresentValue = 0x0045,
+        OutOfService = 0x0051,
+        PresentValue = 0x0055,
+        PriorityArray = 0x0057,
+        Reliability = 0x0067,
+        RelinquishDefault = 0x0068,
+        Resolution = 0x006a,
+        StatusFlags = 0x006f,
+        EngineeringUnits = 0x0075,
+        ApplicationType = 0x0100,
+    }
+}
@@ -0,0 +1,27 @@
+//!
+//! 3.14.4 Analog Value Cluster
+//!
+//! Attributes for an analog setting, based on the `BACnet` Analog Value object. `PresentValue`
+//! is a single precision float commanded through the `PriorityArray`.
+use crate::impl_byte;
+
+/// Analog Value cluster identifier
+pub const CLUSTER_ID: u16 = 0x000e;
+
+impl_byte! {
+    /// 3.14.4.2 Analog Value Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        Description = 0x001c,
+        OutOfService = 0x0051,
+        PresentValue = 0x0055,
+        PriorityArray = 0x0057,
+        Reliability = 0x0067,
+        RelinquishDefault = 0x0068,
+        StatusFlags = 0x006f,
+        EngineeringUnits = 0x0075,
+        ApplicationType = 0x0100,
+    }
+}
@@ -0,0 +1,383 @@
+//!
+//! 3.2 Basic Cluster
+//!
+//! Attributes for determining basic information about a device and for setting user device
+//! information. The cluster is mandatory on every endpoint, [`Basic`] holds the server attributes
+//! and encodes them for read attributes responses.
+use byte::BytesExt;
+
+use crate::data_types::{CharacterString, DataType};
+use crate::impl_byte;
+
+/// Basic cluster identifier
+pub const CLUSTER_ID: u16 = 0x0000;
+
+/// `ZCLVersion` of the ZCL revision 6
+pub const ZCL_VERSION: u8 = 0x03;
+
+/// Maximum length of the `LocationDescription` attribute
+pub const LOCATION_DESCRIPTION_MAX_LEN: usize = 16;
+
+impl_byte! {
+    /// 3.2.2.2 Basic Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        ZclVersion = 0x0000,
+        ApplicationVersion = 0x0001,
+        StackVersion = 0x0002,
+        HwVersion = 0x0003,
+        ManufacturerName = 0x0004,
+        ModelIdentifier = 0x0005,
+        DateCode = 0x0006,
+        PowerSource = 0x0007,
+        LocationDescription = 0x0010,
+        PhysicalEnvironment = 0x0011,
+        DeviceEnabled = 0x0012,
+        AlarmMask = 0x0013,
+        DisableLocalConfig = 0x0014,
+        SwBuildId = 0x4000,
+    }
+}
+
+impl_byte! {
+    /// Primary power source of the device (lower 7 bits of `PowerSource`)
+    ///
+    /// See Section 3.2.2.2.8
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PrimaryPowerSource {
+        Unknown = 0x00,
+        MainsSinglePhase = 0x01,
+        MainsThreePhase = 0x02,
+        Battery = 0x03,
+        DcSource = 0x04,
+        EmergencyMainsConstantlyPowered = 0x05,
+        EmergencyMainsAndTransferSwitch = 0x06,
+    }
+}
+
+impl_byte! {
+    /// 3.2.2.2.8 `PowerSource` Attribute
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct PowerSource(pub u8);
+}
+
+impl PowerSource {
+    /// Creates a new power source.
+    pub fn new(primary: PrimaryPowerSource, secondary_battery: bool) -> Self {
+        let mut value = primary as u8;
+        if secondary_battery {
+            value |= mask::SECONDARY_BATTERY;
+        }
+        Self(value)
+    }
+
+    /// Returns the primary power source, `None` for reserved values.
+    pub fn primary(self) -> Option<PrimaryPowerSource> {
+        use core::convert::TryFrom;
+
+        PrimaryPowerSource::try_from(self.0 & mask::PRIMARY).ok()
+    }
+
+    /// Returns `true` if the device has a secondary power source in the form of a battery
+    /// backup.
+    pub fn has_secondary_battery(self) -> bool {
+        self.0 & mask::SECONDARY_BATTERY != 0
+    }
+}
+
+impl_byte! {
+    /// 3.2.2.2.10 `PhysicalEnvironment` Attribute
+    ///
+    /// Values `0x01` - `0x7f` are specified by the application profile.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct PhysicalEnvironment(pub u8);
+}
+
+impl PhysicalEnvironment {
+    /// Unspecified environment
+    pub const UNSPECIFIED: Self = Self(0x00);
+    /// Unknown environment
+    pub const UNKNOWN: Self = Self(0xff);
+}
+
+impl_byte! {
+    /// 3.2.2.2.12 `AlarmMask` Attribute
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct AlarmMask(pub u8);
+}
+
+impl AlarmMask {
+    /// General hardware fault
+    pub const GENERAL_HARDWARE_FAULT: u8 = 1 << 0;
+    /// General software fault
+    pub const GENERAL_SOFTWARE_FAULT: u8 = 1 << 1;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u8) -> bool {
+        self.0 & flags == flags
+    }
+}
+
+impl_byte! {
+    /// 3.2.2.2.13 `DisableLocalConfig` Attribute
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct DisableLocalConfig(pub u8);
+}
+
+impl DisableLocalConfig {
+    /// Disables the reset to factory defaults (e.g. by a button)
+    pub const RESET: u8 = 1 << 0;
+    /// Disables the local device configuration
+    pub const DEVICE_CONFIGURATION: u8 = 1 << 1;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u8) -> bool {
+        self.0 & flags == flags
+    }
+}
+
+mod mask {
+    pub(super) const PRIMARY: u8 = 0b0111_1111; // 7 bits
+    pub(super) const SECONDARY_BATTERY: u8 = 0b1000_0000; // 1 bit
+}
+
+impl_byte! {
+    /// 3.2.2.3 Commands Received
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ReceivedCommandIdentifier {
+        /// Resets all attribute values to their factory defaults.
+        ResetToFactoryDefaults = 0x00,
+    }
+}
+
+/// Basic Server Attributes
+///
+/// Read-only information is borrowed from the firmware, writable attributes are stored inline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Basic<'a> {
+    /// `ZCLVersion`
+    pub zcl_version: u8,
+    /// `ApplicationVersion`
+    pub application_version: u8,
+    /// `StackVersion`
+    pub stack_version: u8,
+    /// `HWVersion`
+    pub hw_version: u8,
+    /// `ManufacturerName` (up to 32 characters)
+    pub manufacturer_name: &'a str,
+    /// `ModelIdentifier` (up to 32 characters)
+    pub model_identifier: &'a str,
+    /// `DateCode` (up to 16 characters)
+    pub date_code: &'a str,
+    /// `PowerSource`
+    pub power_source: PowerSource,
+    /// `SWBuildID` (up to 16 characters)
+    pub sw_build_id: &'a str,
+    /// `PhysicalEnvironment`
+    pub physical_environment: PhysicalEnvironment,
+    /// `DeviceEnabled`
+    pub device_enabled: bool,
+    /// `AlarmMask`
+    pub alarm_mask: AlarmMask,
+    /// `DisableLocalConfig`
+    pub disable_local_config: DisableLocalConfig,
+    location_description: [u8; LOCATION_DESCRIPTION_MAX_LEN],
+    location_description_len: usize,
+}
+
+impl<'a> Basic<'a> {
+    /// Creates the attribute set with the factory defaults.
+    pub fn new(
+        manufacturer_name: &'a str,
+        model_identifier: &'a str,
+        power_source: PowerSource,
+    ) -> Self {
+        Self {
+            zcl_version: ZCL_VERSION,
+            application_version: 0,
+            stack_version: 0,
+            hw_version: 0,
+            manufacturer_name,
+            model_identifier,
+            date_code: "",
+            power_source,
+            sw_build_id: "",
+            physical_environment: PhysicalEnvironment::UNSPECIFIED,
+            device_enabled: true,
+            alarm_mask: AlarmMask::default(),
+            disable_local_config: DisableLocalConfig::default(),
+            location_description: [0; LOCATION_DESCRIPTION_MAX_LEN],
+            location_description_len: 0,
+        }
+    }
+
+    /// Returns the `LocationDescription` attribute.
+    pub fn location_description(&self) -> &str {
+        core::str::from_utf8(&self.location_description[..self.location_description_len])
+            .unwrap_or_default()
+    }
+
+    /// Sets the `LocationDescription` attribute.
+    ///
+    /// Returns `false` if `location` is longer than 16 octets.
+    pub fn set_location_description(&mut self, location: &str) -> bool {
+        let location = location.as_bytes();
+        if location.len() > LOCATION_DESCRIPTION_MAX_LEN {
+            return false;
+        }
+        self.location_description[..location.len()].copy_from_slice(location);
+        self.location_description_len = location.len();
+        true
+    }
+
+    /// Handles the Reset to Factory Defaults command and resets all writable attributes.
+    ///
+    /// The `DisableLocalConfig` attribute only disables local resets, e.g. by a button, and
+    /// does not affect this command.
+    ///
+    /// See Section 3.2.2.3.1
+    pub fn reset_to_factory_defaults(&mut self) {
+        self.location_description_len = 0;
+        self.physical_environment = PhysicalEnvironment::UNSPECIFIED;
+        self.device_enabled = true;
+        self.alarm_mask = AlarmMask::default();
+    }
+
+    /// Writes the value of an attribute into `bytes` and returns its data type and length, as
+    /// used in a read attributes response.
+    pub fn read_attribute(
+        &self,
+        id: AttributeIdentifier,
+        bytes: &mut [u8],
+    ) -> byte::Result<(DataType, usize)> {
+        let offset = &mut 0;
+        let data_type = match id {
+            AttributeIdentifier::ZclVersion => write_u8(bytes, offset, self.zcl_version)?,
+            AttributeIdentifier::ApplicationVersion => {
+                write_u8(bytes, offset, self.application_version)?
+            }
+            AttributeIdentifier::StackVersion => write_u8(bytes, offset, self.stack_version)?,
+            AttributeIdentifier::HwVersion => write_u8(bytes, offset, self.hw_version)?,
+            AttributeIdentifier::ManufacturerName => {
+                write_string(bytes, offset, self.manufacturer_name)?
+            }
+            AttributeIdentifier::ModelIdentifier => {
+                write_string(bytes, offset, self.model_identifier)?
+            }
+            AttributeIdentifier::DateCode => write_string(bytes, offset, self.date_code)?,
+            AttributeIdentifier::PowerSource => {
+                bytes.write_with(offset, self.power_source, ())?;
+                DataType::Enum8
+            }
+            AttributeIdentifier::LocationDescription => {
+                write_string(bytes, offset, self.location_description())?
+            }
+            AttributeIdentifier::PhysicalEnvironment => {
+                bytes.write_with(offset, self.physical_environment, ())?;
+                DataType::Enum8
+            }
+            AttributeIdentifier::DeviceEnabled => {
+                bytes.write_with(offset, u8::from(self.device_enabled), byte::LE)?;
+                DataType::Bool
+            }
+            AttributeIdentifier::AlarmMask => {
+                bytes.write_with(offset, self.alarm_mask, ())?;
+                DataType::Bitmap8
+            }
+            AttributeIdentifier::DisableLocalConfig => {
+                bytes.write_with(offset, self.disable_local_config, ())?;
+                DataType::Bitmap8
+            }
+            AttributeIdentifier::SwBuildId => write_string(bytes, offset, self.sw_build_id)?,
+        };
+
+        Ok((data_type, *offset))
+    }
+}
+
+fn write_u8(bytes: &mut [u8], offset: &mut usize, value: u8) -> byte::Result<DataType> {
+    bytes.write_with(offset, value, byte::LE)?;
+    Ok(DataType::Uint8)
+}
+
+fn write_string(bytes: &mut [u8], offset: &mut usize, value: &str) -> byte::Result<DataType> {
+    let value = CharacterString::new(value).ok_or(byte::Error::BadInput {
+        err: "String attribute too long",
+    })?;
+    bytes.write_with(offset, value, ())?;
+    Ok(DataType::CharacterString)
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::TryRead;
+
+    use super::*;
+
+    #[test]
+    fn unpack_power_source() {
+        // given
+        let input = [0x83];
+
+        // when
+        let (power_source, _) =
+            PowerSource::try_read(&input, ()).expect("Could not read PowerSource in test");
+
+        // then
+        assert_eq!(power_source.primary(), Some(PrimaryPowerSource::Battery));
+        assert!(power_source.has_secondary_battery());
+        assert_eq!(
+            power_source,
+            PowerSource::new(PrimaryPowerSource::Battery, true)
+        );
+        assert_eq!(PowerSource(0x7f).primary(), None);
+    }
+
+    #[test]
+    fn read_basic_attributes() {
+        // given
+        let basic = Basic::new(
+            "zigbee-rs",
+            "lamp",
+            PowerSource::new(PrimaryPowerSource::MainsSinglePhase, false),
+        );
+        let mut buf = [0u8; 32];
+
+        // when
+        let (data_type, len) = basic
+            .read_attribute(AttributeIdentifier::ManufacturerName, &mut buf)
+            .expect("Could not read ManufacturerName in test");
+
+        // then
+        assert_eq!(data_type, DataType::CharacterString);
+        assert_eq!(&buf[..len], b"\x09zigbee-rs");
+
+        let (data_type, len) = basic
+            .read_attribute(AttributeIdentifier::ZclVersion, &mut buf)
+            .expect("Could not read ZclVersion in test");
+        assert_eq!(data_type, DataType::Uint8);
+        assert_eq!(&buf[..len], &[ZCL_VERSION]);
+    }
+
+    #[test]
+    fn reset_to_factory_defaults() {
+        // given
+        let mut basic = Basic::new("zigbee-rs", "lamp", PowerSource::default());
+        assert!(basic.set_location_description("kitchen"));
+        assert!(!basic.set_location_description("a very long location"));
+        basic.device_enabled = false;
+        basic.disable_local_config = DisableLocalConfig(DisableLocalConfig::RESET);
+
+        // when
+        basic.reset_to_factory_defaults();
+
+        // then
+        assert_eq!(basic.location_description(), "");
+        assert!(basic.device_enabled);
+    }
+}
@@ -0,0 +1,27 @@
+//!
+//! 3.14.5 Binary Input Cluster
+//!
+//! Attributes for a binary measurement, based on the `BACnet` Binary Input object.
+//! `PresentValue` is a boolean, inverted by a reverse `Polarity`.
+use crate::impl_byte;
+
+/// Binary Input cluster identifier
+pub const CLUSTER_ID: u16 = 0x000f;
+
+impl_byte! {
+    /// 3.14.5.2 Binary Input Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        ActiveText = 0x0004,
+        Description = 0x001c,
+        InactiveText = 0x002e,
+        OutOfService = 0x0051,
+        Polarity = 0x0054,
+        PresentValue = 0x0055,
+        Reliability = 0x0067,
+        StatusFlags = 0x006f,
+        ApplicationType = 0x0100,
+    }
+}
@@ -0,0 +1,31 @@
+//!
+//! 3.14.6 Binary Output Cluster
+//!
+//! Attributes for a binary output, based on the `BACnet` Binary Output object. `PresentValue`
+//! is a boolean commanded through the `PriorityArray`.
+use crate::impl_byte;
+
+/// Binary Output cluster identifier
+pub const CLUSTER_ID: u16 = 0x0010;
+
+impl_byte! {
+    /// 3.14.6.2 Binary Output Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        ActiveText = 0x0004,
+        Description = 0x001c,
+        InactiveText = 0x002e,
+        MinimumOffTime = 0x0042,
+        MinimumOnTime = 0x0043,
+        OutOfService = 0x0051,
+        Polarity = 0x0054,
+        PresentValue = 0x0055,
+        PriorityArray = 0x0057,
+        Reliability = 0x0067,
+        RelinquishDefault = 0x0068,
+        StatusFlags = 0x006f,
+        ApplicationType = 0x0100,
+    }
+}
@@ -0,0 +1,30 @@
+//!
+//! 3.14.7 Binary Value Cluster
+//!
+//! Attributes for a binary setting, based on the `BACnet` Binary Value object. `PresentValue`
+//! is a boolean commanded through the `PriorityArray`.
+use crate::impl_byte;
+
+/// Binary Value cluster identifier
+pub const CLUSTER_ID: u16 = 0x0011;
+
+impl_byte! {
+    /// 3.14.7.2 Binary Value Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        ActiveText = 0x0004,
+        Description = 0x001c,
+        InactiveText = 0x002e,
+        MinimumOffTime = 0x0042,
+        MinimumOnTime = 0x0043,
+        OutOfService = 0x0051,
+        PresentValue = 0x0055,
+        PriorityArray = 0x0057,
+        Reliability = 0x0067,
+        RelinquishDefault = 0x0068,
+        StatusFlags = 0x006f,
+        ApplicationType = 0x0100,
+    }
+}
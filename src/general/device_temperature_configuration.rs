@@ -0,0 +1,301 @@
+//!
+//! 3.4 Device Temperature Configuration Cluster
+//!
+//! Attributes for determining information about a device's internal temperature, and for
+//! configuring under/over temperature alarms. Temperatures are in degrees Celsius.
+//! [`DeviceTemperature`] tracks the experienced temperatures and raises the alarms once the
+//! temperature stayed beyond a threshold for longer than its dwell trip point.
+use crate::data_types::Uint24;
+use crate::impl_byte;
+
+/// Device Temperature Configuration cluster identifier
+pub const CLUSTER_ID: u16 = 0x0002;
+
+/// Value of the temperature attributes if the temperature is unknown
+pub const INVALID_TEMPERATURE: i16 = -0x8000;
+
+/// Lowest valid temperature
+pub const MIN_TEMPERATURE: i16 = -200;
+
+/// Highest valid temperature
+pub const MAX_TEMPERATURE: i16 = 200;
+
+impl_byte! {
+    /// 3.4.2.2 Device Temperature Configuration Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        // Device Temperature Information
+        CurrentTemperature = 0x0000,
+        MinTempExperienced = 0x0001,
+        MaxTempExperienced = 0x0002,
+        OverTempTotalDwell = 0x0003,
+        // Device Temperature Settings
+        DeviceTempAlarmMask = 0x0010,
+        LowTempThreshold = 0x0011,
+        HighTempThreshold = 0x0012,
+        LowTempDwellTripPoint = 0x0013,
+        HighTempDwellTripPoint = 0x0014,
+    }
+}
+
+impl_byte! {
+    /// 3.4.2.2.2.1 `DeviceTempAlarmMask` Attribute
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct DeviceTempAlarmMask(pub u8);
+}
+
+impl DeviceTempAlarmMask {
+    /// Device temperature too low
+    pub const TOO_LOW: u8 = 1 << 0;
+    /// Device temperature too high
+    pub const TOO_HIGH: u8 = 1 << 1;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u8) -> bool {
+        self.0 & flags == flags
+    }
+
+    /// Returns `true` if no alarm is set.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the alarm codes of the set alarms for the Alarms cluster.
+    ///
+    /// See Section 3.4.2.2.2.1 (Values can be found in Table 3-29)
+    pub fn alarm_codes(self) -> impl Iterator<Item = u8> {
+        [(Self::TOO_LOW, 0x00), (Self::TOO_HIGH, 0x01)]
+            .iter()
+            .filter(move |(flag, _)| self.contains(*flag))
+            .map(|(_, code)| *code)
+    }
+}
+
+/// Device temperature server
+///
+/// The application updates the temperature with [`DeviceTemperature::set_current_temperature`]
+/// and calls [`DeviceTemperature::tick`] every second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceTemperature {
+    current_temperature: i16,
+    min_temp_experienced: i16,
+    max_temp_experienced: i16,
+    over_temp_total_dwell: u16,
+    /// `DeviceTempAlarmMask` attribute, the alarms which are raised
+    pub alarm_mask: DeviceTempAlarmMask,
+    /// `LowTempThreshold` attribute
+    pub low_temp_threshold: Option<i16>,
+    /// `HighTempThreshold` attribute
+    pub high_temp_threshold: Option<i16>,
+    /// `LowTempDwellTripPoint` attribute, in seconds
+    pub low_temp_dwell_trip_point: Uint24,
+    /// `HighTempDwellTripPoint` attribute, in seconds
+    pub high_temp_dwell_trip_point: Uint24,
+    over_temp_seconds: u16,
+    low_temp_dwell: u32,
+    high_temp_dwell: u32,
+    active_alarms: DeviceTempAlarmMask,
+}
+
+impl Default for DeviceTemperature {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeviceTemperature {
+    /// Creates a server with an unknown temperature and no alarms configured.
+    pub fn new() -> Self {
+        Self {
+            current_temperature: INVALID_TEMPERATURE,
+            min_temp_experienced: INVALID_TEMPERATURE,
+            max_temp_experienced: INVALID_TEMPERATURE,
+            over_temp_total_dwell: 0,
+            alarm_mask: DeviceTempAlarmMask::default(),
+            low_temp_threshold: None,
+            high_temp_threshold: None,
+            low_temp_dwell_trip_point: Uint24::NON_VALUE,
+            high_temp_dwell_trip_point: Uint24::NON_VALUE,
+            over_temp_seconds: 0,
+            low_temp_dwell: 0,
+            high_temp_dwell: 0,
+            active_alarms: DeviceTempAlarmMask::default(),
+        }
+    }
+
+    /// Returns the `CurrentTemperature` attribute.
+    pub fn current_temperature(&self) -> i16 {
+        self.current_temperature
+    }
+
+    /// Returns the `MinTempExperienced` attribute.
+    pub fn min_temp_experienced(&self) -> i16 {
+        self.min_temp_experienced
+    }
+
+    /// Returns the `MaxTempExperienced` attribute.
+    pub fn max_temp_experienced(&self) -> i16 {
+        self.max_temp_experienced
+    }
+
+    /// Returns the `OverTempTotalDwell` attribute, the hours spent above the
+    /// `HighTempThreshold`.
+    pub fn over_temp_total_dwell(&self) -> u16 {
+        self.over_temp_total_dwell
+    }
+
+    /// Returns the alarms which are currently active.
+    pub fn active_alarms(&self) -> DeviceTempAlarmMask {
+        self.active_alarms
+    }
+
+    /// Updates the `CurrentTemperature` attribute and the experienced temperatures.
+    ///
+    /// The temperature is clamped to the valid range of -200 to +200 degrees.
+    pub fn set_current_temperature(&mut self, temperature: i16) {
+        let temperature = temperature.clamp(MIN_TEMPERATURE, MAX_TEMPERATURE);
+        self.current_temperature = temperature;
+        if self.min_temp_experienced == INVALID_TEMPERATURE
+            || temperature < self.min_temp_experienced
+        {
+            self.min_temp_experienced = temperature;
+        }
+        if self.max_temp_experienced == INVALID_TEMPERATURE
+            || temperature > self.max_temp_experienced
+        {
+            self.max_temp_experienced = temperature;
+        }
+    }
+
+    /// Advances the dwell times by one second.
+    ///
+    /// Returns the alarms raised by this tick, which are enabled in the `alarm_mask`. An alarm is
+    /// raised again after the temperature returned within the threshold.
+    pub fn tick(&mut self) -> DeviceTempAlarmMask {
+        let known = self.current_temperature != INVALID_TEMPERATURE;
+        let too_low = known
+            && self
+                .low_temp_threshold
+                .map_or(false, |threshold| self.current_temperature < threshold);
+        let too_high = known
+            && self
+                .high_temp_threshold
+                .map_or(false, |threshold| self.current_temperature > threshold);
+
+        if too_high {
+            self.over_temp_seconds += 1;
+            if self.over_temp_seconds == 3600 {
+                self.over_temp_seconds = 0;
+                self.over_temp_total_dwell = self.over_temp_total_dwell.saturating_add(1);
+            }
+        }
+
+        let mut alarms = 0;
+        if Self::dwell(
+            &mut self.low_temp_dwell,
+            too_low,
+            self.low_temp_dwell_trip_point,
+        ) {
+            alarms |= DeviceTempAlarmMask::TOO_LOW;
+        }
+        if Self::dwell(
+            &mut self.high_temp_dwell,
+            too_high,
+            self.high_temp_dwell_trip_point,
+        ) {
+            alarms |= DeviceTempAlarmMask::TOO_HIGH;
+        }
+
+        let raised = alarms & !self.active_alarms.0 & self.alarm_mask.0;
+        self.active_alarms = DeviceTempAlarmMask(alarms);
+        DeviceTempAlarmMask(raised)
+    }
+
+    fn dwell(dwell: &mut u32, beyond_threshold: bool, trip_point: Uint24) -> bool {
+        *dwell = if beyond_threshold {
+            dwell.saturating_add(1)
+        } else {
+            0
+        };
+        trip_point
+            .value()
+            .map_or(false, |trip_point| *dwell > trip_point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_types::UtcTime;
+    use crate::general::alarms::AlarmTable;
+
+    #[test]
+    fn experienced_temperatures() {
+        // given
+        let mut device = DeviceTemperature::new();
+
+        // when
+        device.set_current_temperature(35);
+        device.set_current_temperature(-12);
+        device.set_current_temperature(300);
+
+        // then
+        assert_eq!(device.current_temperature(), MAX_TEMPERATURE);
+        assert_eq!(device.min_temp_experienced(), -12);
+        assert_eq!(device.max_temp_experienced(), MAX_TEMPERATURE);
+    }
+
+    #[test]
+    fn raise_alarm_after_dwell_trip_point() {
+        // given
+        let mut device = DeviceTemperature::new();
+        device.alarm_mask = DeviceTempAlarmMask(DeviceTempAlarmMask::TOO_HIGH);
+        device.high_temp_threshold = Some(70);
+        device.high_temp_dwell_trip_point = Uint24::new(2).expect("Invalid Uint24 in test");
+        device.set_current_temperature(75);
+        let mut table = AlarmTable::<2>::new();
+
+        // when
+        let raised = [device.tick(), device.tick(), device.tick(), device.tick()];
+
+        // then
+        assert!(raised[..2].iter().all(|alarms| alarms.is_empty()));
+        assert_eq!(
+            raised[2],
+            DeviceTempAlarmMask(DeviceTempAlarmMask::TOO_HIGH)
+        );
+        assert!(raised[3].is_empty());
+        for alarm_code in raised[2].alarm_codes() {
+            table.raise(
+                alarm_code,
+                CLUSTER_ID,
+                UtcTime::new(3).expect("Invalid UtcTime in test"),
+            );
+        }
+        let alarm = table.get_alarm();
+        assert_eq!(alarm.alarm_code, Some(0x01));
+        assert_eq!(alarm.cluster_id, Some(CLUSTER_ID));
+        device.set_current_temperature(20);
+        device.tick();
+        assert!(device.active_alarms().is_empty());
+    }
+
+    #[test]
+    fn over_temp_total_dwell() {
+        // given
+        let mut device = DeviceTemperature::new();
+        device.high_temp_threshold = Some(60);
+        device.set_current_temperature(61);
+
+        // when
+        for _ in 0..7200 {
+            device.tick();
+        }
+
+        // then
+        assert_eq!(device.over_temp_total_dwell(), 2);
+        assert!(device.active_alarms().is_empty());
+    }
+}
@@ -0,0 +1,204 @@
+//!
+//! Home Automation 9.3 Diagnostics Cluster
+//!
+//! Attributes for providing diagnostic information about the device and its network stack.
+//! [`DiagnosticsCounters`] holds all counters, so the application can take a consistent
+//! snapshot of them at once.
+use core::convert::TryFrom;
+
+use crate::impl_byte;
+
+/// Diagnostics cluster identifier
+pub const CLUSTER_ID: u16 = 0x0b05;
+
+impl_byte! {
+    /// 9.3.2.2 Diagnostics Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        // Hardware Information
+        NumberOfResets = 0x0000,
+        PersistentMemoryWrites = 0x0001,
+        // Stack/Network Information
+        MacRxBcast = 0x0100,
+        MacTxBcast = 0x0101,
+        MacRxUcast = 0x0102,
+        MacTxUcast = 0x0103,
+        MacTxUcastRetry = 0x0104,
+        MacTxUcastFail = 0x0105,
+        ApsRxBcast = 0x0106,
+        ApsTxBcast = 0x0107,
+        ApsRxUcast = 0x0108,
+        ApsTxUcastSuccess = 0x0109,
+        ApsTxUcastRetry = 0x010a,
+        ApsTxUcastFail = 0x010b,
+        RouteDiscInitiated = 0x010c,
+        NeighborAdded = 0x010d,
+        NeighborRemoved = 0x010e,
+        NeighborStale = 0x010f,
+        JoinIndication = 0x0110,
+        ChildMoved = 0x0111,
+        NwkFcFailure = 0x0112,
+        ApsFcFailure = 0x0113,
+        ApsUnauthorizedKey = 0x0114,
+        NwkDecryptFailures = 0x0115,
+        ApsDecryptFailures = 0x0116,
+        PacketBufferAllocateFailures = 0x0117,
+        RelayedUcast = 0x0118,
+        PhyToMacQueueLimitReached = 0x0119,
+        PacketValidateDropCount = 0x011a,
+        AverageMacRetryPerApsMessageSent = 0x011b,
+        LastMessageLqi = 0x011c,
+        LastMessageRssi = 0x011d,
+    }
+}
+
+/// Counters of the Diagnostics cluster
+///
+/// Each field holds the attribute of the same name. The counters saturate instead of wrapping.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub struct DiagnosticsCounters {
+    // Hardware Information
+    pub number_of_resets: u16,
+    pub persistent_memory_writes: u16,
+    // Stack/Network Information
+    pub mac_rx_bcast: u32,
+    pub mac_tx_bcast: u32,
+    pub mac_rx_ucast: u32,
+    pub mac_tx_ucast: u32,
+    pub mac_tx_ucast_retry: u16,
+    pub mac_tx_ucast_fail: u16,
+    pub aps_rx_bcast: u16,
+    pub aps_tx_bcast: u16,
+    pub aps_rx_ucast: u16,
+    pub aps_tx_ucast_success: u16,
+    pub aps_tx_ucast_retry: u16,
+    pub aps_tx_ucast_fail: u16,
+    pub route_disc_initiated: u16,
+    pub neighbor_added: u16,
+    pub neighbor_removed: u16,
+    pub neighbor_stale: u16,
+    pub join_indication: u16,
+    pub child_moved: u16,
+    pub nwk_fc_failure: u16,
+    pub aps_fc_failure: u16,
+    pub aps_unauthorized_key: u16,
+    pub nwk_decrypt_failures: u16,
+    pub aps_decrypt_failures: u16,
+    pub packet_buffer_allocate_failures: u16,
+    pub relayed_ucast: u16,
+    pub phy_to_mac_queue_limit_reached: u16,
+    pub packet_validate_drop_count: u16,
+    pub average_mac_retry_per_aps_message_sent: u16,
+    pub last_message_lqi: u8,
+    pub last_message_rssi: i8,
+}
+
+impl DiagnosticsCounters {
+    /// Identifiers of all attributes, e.g. to read them with a single Read Attributes command.
+    pub const ATTRIBUTES: [AttributeIdentifier; 32] = [
+        AttributeIdentifier::NumberOfResets,
+        AttributeIdentifier::PersistentMemoryWrites,
+        AttributeIdentifier::MacRxBcast,
+        AttributeIdentifier::MacTxBcast,
+        AttributeIdentifier::MacRxUcast,
+        AttributeIdentifier::MacTxUcast,
+        AttributeIdentifier::MacTxUcastRetry,
+        AttributeIdentifier::MacTxUcastFail,
+        AttributeIdentifier::ApsRxBcast,
+        AttributeIdentifier::ApsTxBcast,
+        AttributeIdentifier::ApsRxUcast,
+        AttributeIdentifier::ApsTxUcastSuccess,
+        AttributeIdentifier::ApsTxUcastRetry,
+        AttributeIdentifier::ApsTxUcastFail,
+        AttributeIdentifier::RouteDiscInitiated,
+        AttributeIdentifier::NeighborAdded,
+        AttributeIdentifier::NeighborRemoved,
+        AttributeIdentifier::NeighborStale,
+        AttributeIdentifier::JoinIndication,
+        AttributeIdentifier::ChildMoved,
+        AttributeIdentifier::NwkFcFailure,
+        AttributeIdentifier::ApsFcFailure,
+        AttributeIdentifier::ApsUnauthorizedKey,
+        AttributeIdentifier::NwkDecryptFailures,
+        AttributeIdentifier::ApsDecryptFailures,
+        AttributeIdentifier::PacketBufferAllocateFailures,
+        AttributeIdentifier::RelayedUcast,
+        AttributeIdentifier::PhyToMacQueueLimitReached,
+        AttributeIdentifier::PacketValidateDropCount,
+        AttributeIdentifier::AverageMacRetryPerApsMessageSent,
+        AttributeIdentifier::LastMessageLqi,
+        AttributeIdentifier::LastMessageRssi,
+    ];
+
+    /// Resets the network counters, the hardware information is kept.
+    pub fn reset(&mut self) {
+        *self = Self {
+            number_of_resets: self.number_of_resets,
+            persistent_memory_writes: self.persistent_memory_writes,
+            ..Self::default()
+        };
+    }
+
+    /// Records the link quality of the last received message.
+    pub fn record_last_message(&mut self, lqi: u8, rssi: i8) {
+        self.last_message_lqi = lqi;
+        self.last_message_rssi = rssi;
+    }
+
+    /// Records an APS unicast sent with `mac_retries` retries and updates the
+    /// `AverageMacRetryPerApsMessageSent` attribute.
+    pub fn record_aps_unicast_sent(&mut self, success: bool, mac_retries: u16) {
+        if success {
+            self.aps_tx_ucast_success = self.aps_tx_ucast_success.saturating_add(1);
+        } else {
+            self.aps_tx_ucast_fail = self.aps_tx_ucast_fail.saturating_add(1);
+        }
+        self.mac_tx_ucast_retry = self.mac_tx_ucast_retry.saturating_add(mac_retries);
+
+        let sent = u32::from(self.aps_tx_ucast_success) + u32::from(self.aps_tx_ucast_fail);
+        let average = u32::from(self.mac_tx_ucast_retry) / sent;
+        self.average_mac_retry_per_aps_message_sent = u16::try_from(average).unwrap_or(u16::MAX);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_counters() {
+        // given
+        let mut counters = DiagnosticsCounters {
+            number_of_resets: 3,
+            ..DiagnosticsCounters::default()
+        };
+        counters.record_aps_unicast_sent(true, 1);
+        counters.record_aps_unicast_sent(true, 0);
+        counters.record_aps_unicast_sent(false, 5);
+        counters.record_last_message(0xc8, -62);
+
+        // when
+        let snapshot = counters;
+        counters.reset();
+
+        // then
+        assert_eq!(snapshot.aps_tx_ucast_success, 2);
+        assert_eq!(snapshot.aps_tx_ucast_fail, 1);
+        assert_eq!(snapshot.average_mac_retry_per_aps_message_sent, 2);
+        assert_eq!(snapshot.last_message_rssi, -62);
+        assert_eq!(
+            counters,
+            DiagnosticsCounters {
+                number_of_resets: 3,
+                ..DiagnosticsCounters::default()
+            }
+        );
+        assert_eq!(
+            DiagnosticsCounters::ATTRIBUTES.last(),
+            Some(&AttributeIdentifier::LastMessageRssi)
+        );
+    }
+}
@@ -0,0 +1,613 @@
+//!
+//! 3.6 Groups Cluster
+//!
+//! Commands to manage the group table of an endpoint, which is used for multicast addressing.
+//! [`GroupTable`] is an allocation free group table with a fixed capacity which handles the
+//! received commands and creates the responses.
+use byte::{ctx, BytesExt, TryRead, TryWrite};
+
+use crate::data_types::CharacterString;
+use crate::impl_byte;
+use crate::status::ZclStatus;
+
+/// Groups cluster identifier
+pub const CLUSTER_ID: u16 = 0x0004;
+
+/// Maximum length of a group name
+pub const GROUP_NAME_MAX_LEN: usize = 16;
+
+impl_byte! {
+    /// 3.6.2.2 Groups Server Attributes
+    #[repr(u16)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        /// Indicates the ability to store a name for a group.
+        NameSupport = 0x0000,
+    }
+}
+
+impl_byte! {
+    /// 3.6.2.2.1 `NameSupport` Attribute
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct NameSupport(pub u8);
+}
+
+impl NameSupport {
+    /// Group names are supported
+    pub const SUPPORTED: Self = Self(0x80);
+
+    /// Returns `true` if group names are supported.
+    pub fn is_supported(self) -> bool {
+        self.0 & Self::SUPPORTED.0 != 0
+    }
+}
+
+impl_byte! {
+    /// 3.6.2.3 Commands Received
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ReceivedCommandIdentifier {
+        /// Adds a group to the group table.
+        AddGroup = 0x00,
+        /// Requests the name of a group.
+        ViewGroup = 0x01,
+        /// Inquires about the group membership.
+        GetGroupMembership = 0x02,
+        /// Removes a group from the group table.
+        RemoveGroup = 0x03,
+        /// Removes all groups from the group table.
+        RemoveAllGroups = 0x04,
+        /// Adds a group to the group table if the device is identifying itself.
+        AddGroupIfIdentifying = 0x05,
+    }
+}
+
+impl_byte! {
+    /// 3.6.2.4 Commands Generated
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum GeneratedCommandIdentifier {
+        /// Response to [`ReceivedCommandIdentifier::AddGroup`]
+        AddGroupResponse = 0x00,
+        /// Response to [`ReceivedCommandIdentifier::ViewGroup`]
+        ViewGroupResponse = 0x01,
+        /// Response to [`ReceivedCommandIdentifier::GetGroupMembership`]
+        GetGroupMembershipResponse = 0x02,
+        /// Response to [`ReceivedCommandIdentifier::RemoveGroup`]
+        RemoveGroupResponse = 0x03,
+    }
+}
+
+impl_byte! {
+    /// 3.6.2.3.2 Add Group Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct AddGroup<'a> {
+        /// Group to add
+        pub group_id: u16,
+        /// Name of the group
+        pub group_name: CharacterString<'a>,
+    }
+}
+
+/// 3.6.2.3.7 Add Group If Identifying Command
+pub type AddGroupIfIdentifying<'a> = AddGroup<'a>;
+
+impl_byte! {
+    /// 3.6.2.3.3 View Group Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ViewGroup {
+        /// Group to view
+        pub group_id: u16,
+    }
+}
+
+impl_byte! {
+    /// 3.6.2.3.5 Remove Group Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct RemoveGroup {
+        /// Group to remove
+        pub group_id: u16,
+    }
+}
+
+/// Iterates a raw list of group identifiers.
+fn group_ids(list: &[u8]) -> impl Iterator<Item = u16> + '_ {
+    list.chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+}
+
+/// 3.6.2.3.4 Get Group Membership Command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GetGroupMembership<'a> {
+    /// Number of groups in `group_list`
+    pub group_count: u8,
+    /// Raw list of group identifiers, see [`Self::groups`]
+    pub group_list: &'a [u8],
+}
+
+impl GetGroupMembership<'_> {
+    /// Iterates the requested group identifiers.
+    pub fn groups(&self) -> impl Iterator<Item = u16> + '_ {
+        group_ids(self.group_list)
+    }
+}
+
+impl<'a> TryRead<'a, ()> for GetGroupMembership<'a> {
+    fn try_read(bytes: &'a [u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let group_count: u8 = bytes.read_with(offset, byte::LE)?;
+        let group_list = bytes.read_with(offset, ctx::Bytes::Len(usize::from(group_count) * 2))?;
+
+        Ok((
+            Self {
+                group_count,
+                group_list,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl TryWrite for GetGroupMembership<'_> {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, self.group_count, byte::LE)?;
+        bytes.write(offset, self.group_list)?;
+
+        Ok(*offset)
+    }
+}
+
+impl_byte! {
+    /// 3.6.2.4.1 Add Group Response Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct AddGroupResponse {
+        /// Status of the add group command
+        pub status: ZclStatus,
+        /// Group that was added
+        pub group_id: u16,
+    }
+}
+
+impl_byte! {
+    /// 3.6.2.4.2 View Group Response Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ViewGroupResponse<'a> {
+        /// Status of the view group command
+        pub status: ZclStatus,
+        /// Group that was viewed
+        pub group_id: u16,
+        /// Name of the group
+        pub group_name: CharacterString<'a>,
+    }
+}
+
+impl_byte! {
+    /// 3.6.2.4.4 Remove Group Response Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct RemoveGroupResponse {
+        /// Status of the remove group command
+        pub status: ZclStatus,
+        /// Group that was removed
+        pub group_id: u16,
+    }
+}
+
+/// 3.6.2.4.3 Get Group Membership Response Command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GetGroupMembershipResponse<'a> {
+    /// Remaining capacity of the group table
+    ///
+    /// `0xfe` means at least one further group can be added, `0xff` means unknown.
+    pub capacity: u8,
+    /// Number of groups in `group_list`
+    pub group_count: u8,
+    /// Raw list of group identifiers, see [`Self::groups`]
+    pub group_list: &'a [u8],
+}
+
+impl GetGroupMembershipResponse<'_> {
+    /// Iterates the group identifiers.
+    pub fn groups(&self) -> impl Iterator<Item = u16> + '_ {
+        group_ids(self.group_list)
+    }
+}
+
+impl<'a> TryRead<'a, ()> for GetGroupMembershipResponse<'a> {
+    fn try_read(bytes: &'a [u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let capacity = bytes.read_with(offset, byte::LE)?;
+        let group_count: u8 = bytes.read_with(offset, byte::LE)?;
+        let group_list = bytes.read_with(offset, ctx::Bytes::Len(usize::from(group_count) * 2))?;
+
+        Ok((
+            Self {
+                capacity,
+                group_count,
+                group_list,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl TryWrite for GetGroupMembershipResponse<'_> {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, self.capacity, byte::LE)?;
+        bytes.write_with(offset, self.group_count, byte::LE)?;
+        bytes.write(offset, self.group_list)?;
+
+        Ok(*offset)
+    }
+}
+
+/// Returns `true` if the group identifier is in the valid range `0x0001` - `0xfff7`.
+pub fn is_valid_group_id(group_id: u16) -> bool {
+    (0x0001..=0xfff7).contains(&group_id)
+}
+
+/// Entry of a [`GroupTable`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct GroupEntry {
+    group_id: u16,
+    name: [u8; GROUP_NAME_MAX_LEN],
+    name_len: usize,
+}
+
+/// Group table of an endpoint with a capacity of `N` groups
+///
+/// The table implements the server side of the Groups cluster, the command handlers return the
+/// response to send.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupTable<const N: usize> {
+    entries: [Option<GroupEntry>; N],
+    name_support: bool,
+}
+
+impl<const N: usize> Default for GroupTable<N> {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+impl<const N: usize> GroupTable<N> {
+    /// Creates an empty group table.
+    ///
+    /// If `name_support` is set, group names are stored (up to 16 characters).
+    pub fn new(name_support: bool) -> Self {
+        Self {
+            entries: [None; N],
+            name_support,
+        }
+    }
+
+    /// Returns the `NameSupport` attribute.
+    pub fn name_support(&self) -> NameSupport {
+        if self.name_support {
+            NameSupport::SUPPORTED
+        } else {
+            NameSupport::default()
+        }
+    }
+
+    /// Returns the number of groups in the table.
+    pub fn len(&self) -> usize {
+        self.entries.iter().flatten().count()
+    }
+
+    /// Returns `true` if the table has no groups.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the endpoint is a member of the group.
+    pub fn contains(&self, group_id: u16) -> bool {
+        self.entry(group_id).is_some()
+    }
+
+    /// Iterates the group identifiers in the table.
+    pub fn groups(&self) -> impl Iterator<Item = u16> + '_ {
+        self.entries.iter().flatten().map(|e| e.group_id)
+    }
+
+    /// Returns the remaining capacity as reported in the Get Group Membership Response.
+    pub fn capacity(&self) -> u8 {
+        let remaining = N - self.len();
+        #[allow(clippy::cast_possible_truncation)]
+        if remaining >= 0xfe {
+            0xfe
+        } else {
+            remaining as u8
+        }
+    }
+
+    fn entry(&self, group_id: u16) -> Option<&GroupEntry> {
+        self.entries
+            .iter()
+            .flatten()
+            .find(|e| e.group_id == group_id)
+    }
+
+    /// Adds a group to the table.
+    pub fn add(&mut self, group_id: u16, name: &str) -> ZclStatus {
+        if !is_valid_group_id(group_id) {
+            return ZclStatus::InvalidValue;
+        }
+        if self.contains(group_id) {
+            return ZclStatus::DuplicateExists;
+        }
+        let name_support = self.name_support;
+        let slot = match self.entries.iter_mut().find(|e| e.is_none()) {
+            Some(slot) => slot,
+            None => return ZclStatus::InsufficientSpace,
+        };
+
+        let mut entry = GroupEntry {
+            group_id,
+            name: [0; GROUP_NAME_MAX_LEN],
+            name_len: 0,
+        };
+        if name_support {
+            let mut len = name.len().min(GROUP_NAME_MAX_LEN);
+            while !name.is_char_boundary(len) {
+                len -= 1;
+            }
+            entry.name[..len].copy_from_slice(&name.as_bytes()[..len]);
+            entry.name_len = len;
+        }
+        *slot = Some(entry);
+
+        ZclStatus::Success
+    }
+
+    /// Removes a group from the table.
+    pub fn remove(&mut self, group_id: u16) -> ZclStatus {
+        if !is_valid_group_id(group_id) {
+            return ZclStatus::InvalidValue;
+        }
+        let slot = self
+            .entries
+            .iter_mut()
+            .find(|e| e.map_or(false, |e| e.group_id == group_id));
+        slot.map_or(ZclStatus::NotFound, |slot| {
+            *slot = None;
+            ZclStatus::Success
+        })
+    }
+
+    /// Removes all groups from the table.
+    pub fn remove_all(&mut self) {
+        self.entries = [None; N];
+    }
+
+    /// Handles the Add Group command.
+    ///
+    /// See Section 3.6.2.3.2
+    pub fn add_group(&mut self, command: &AddGroup<'_>) -> AddGroupResponse {
+        let name = command.group_name.as_str().unwrap_or_default();
+        AddGroupResponse {
+            status: self.add(command.group_id, name),
+            group_id: command.group_id,
+        }
+    }
+
+    /// Handles the View Group command.
+    ///
+    /// See Section 3.6.2.3.3
+    pub fn view_group(&self, command: &ViewGroup) -> ViewGroupResponse<'_> {
+        let status = if !is_valid_group_id(command.group_id) {
+            ZclStatus::InvalidValue
+        } else if self.contains(command.group_id) {
+            ZclStatus::Success
+        } else {
+            ZclStatus::NotFound
+        };
+        let name = self
+            .entry(command.group_id)
+            .map_or(&[][..], |e| &e.name[..e.name_len]);
+
+        ViewGroupResponse {
+            status,
+            group_id: command.group_id,
+            group_name: CharacterString::new(core::str::from_utf8(name).unwrap_or_default())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Handles the Get Group Membership command and writes the group list into `buf`.
+    ///
+    /// If the group count of the command is zero all groups are returned, otherwise only the
+    /// requested groups the endpoint is a member of.
+    ///
+    /// See Section 3.6.2.3.4
+    pub fn get_group_membership<'b>(
+        &self,
+        command: &GetGroupMembership<'_>,
+        buf: &'b mut [u8],
+    ) -> byte::Result<GetGroupMembershipResponse<'b>> {
+        let offset = &mut 0;
+        let mut group_count: u8 = 0;
+        for group_id in self.groups() {
+            if group_count == u8::MAX {
+                break;
+            }
+            if command.group_count == 0 || command.groups().any(|g| g == group_id) {
+                buf.write_with(offset, group_id, byte::LE)?;
+                group_count += 1;
+            }
+        }
+
+        Ok(GetGroupMembershipResponse {
+            capacity: self.capacity(),
+            group_count,
+            group_list: &buf[..*offset],
+        })
+    }
+
+    /// Handles the Remove Group command.
+    ///
+    /// See Section 3.6.2.3.5
+    pub fn remove_group(&mut self, command: &RemoveGroup) -> RemoveGroupResponse {
+        RemoveGroupResponse {
+            status: self.remove(command.group_id),
+            group_id: command.group_id,
+        }
+    }
+
+    /// Handles the Remove All Groups command.
+    ///
+    /// See Section 3.6.2.3.6
+    pub fn remove_all_groups(&mut self) -> ZclStatus {
+        self.remove_all();
+        ZclStatus::Success
+    }
+
+    /// Handles the Add Group If Identifying command.
+    ///
+    /// The command has no response, the returned status is used for the default response.
+    ///
+    /// See Section 3.6.2.3.7
+    pub fn add_group_if_identifying(
+        &mut self,
+        command: &AddGroupIfIdentifying<'_>,
+        identifying: bool,
+    ) -> ZclStatus {
+        if identifying {
+            self.add_group(command).status
+        } else {
+            ZclStatus::Success
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::TryRead;
+
+    use super::*;
+
+    #[test]
+    fn unpack_add_group() {
+        // given
+        let input = [0x34, 0x12, 0x06, b'k', b'i', b't', b'c', b'h', b'n'];
+
+        // when
+        let (command, _) = AddGroup::try_read(&input, ()).expect("Could not read AddGroup in test");
+
+        // then
+        assert_eq!(command.group_id, 0x1234);
+        assert_eq!(command.group_name.as_str(), Some("kitchn"));
+    }
+
+    #[test]
+    fn group_table_status() {
+        // given
+        let mut table = GroupTable::<2>::new(true);
+        let add = |group_id| AddGroup {
+            group_id,
+            group_name: CharacterString::new("living room").unwrap_or_default(),
+        };
+
+        // then
+        assert_eq!(table.add_group(&add(0x0001)).status, ZclStatus::Success);
+        assert_eq!(
+            table.add_group(&add(0x0001)).status,
+            ZclStatus::DuplicateExists
+        );
+        assert_eq!(
+            table.add_group(&add(0x0000)).status,
+            ZclStatus::InvalidValue
+        );
+        assert_eq!(table.add_group(&add(0x0002)).status, ZclStatus::Success);
+        assert_eq!(
+            table.add_group(&add(0x0003)).status,
+            ZclStatus::InsufficientSpace
+        );
+
+        let response = table.view_group(&ViewGroup { group_id: 0x0002 });
+        assert_eq!(response.status, ZclStatus::Success);
+        assert_eq!(response.group_name.as_str(), Some("living room"));
+        assert_eq!(
+            table.view_group(&ViewGroup { group_id: 0x0003 }).status,
+            ZclStatus::NotFound
+        );
+
+        assert_eq!(
+            table.remove_group(&RemoveGroup { group_id: 0x0001 }).status,
+            ZclStatus::Success
+        );
+        assert_eq!(
+            table.remove_group(&RemoveGroup { group_id: 0x0001 }).status,
+            ZclStatus::NotFound
+        );
+        assert_eq!(table.len(), 1);
+        assert_eq!(
+            table.add_group_if_identifying(&add(0x0004), false),
+            ZclStatus::Success
+        );
+        assert!(!table.contains(0x0004));
+        assert_eq!(table.remove_all_groups(), ZclStatus::Success);
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn get_group_membership() {
+        // given
+        let mut table = GroupTable::<4>::default();
+        table.add(0x0001, "");
+        table.add(0x0002, "");
+        table.add(0x0003, "");
+        let input = [0x02, 0x02, 0x00, 0x05, 0x00];
+        let (command, _) = GetGroupMembership::try_read(&input, ())
+            .expect("Could not read GetGroupMembership in test");
+        let mut buf = [0u8; 8];
+
+        // when
+        let response = table
+            .get_group_membership(&command, &mut buf)
+            .expect("Could not get group membership in test");
+
+        // then
+        assert_eq!(response.capacity, 1);
+        assert_eq!(response.group_count, 1);
+        assert_eq!(response.groups().next(), Some(0x0002));
+        assert_eq!(table.name_support(), NameSupport::default());
+    }
+
+    #[test]
+    fn truncate_name_at_char_boundary() {
+        // given
+        let mut table = GroupTable::<1>::new(true);
+
+        // when
+        let status = table.add(0x0001, "Arbeitszimmer Büro");
+
+        // then
+        assert_eq!(status, ZclStatus::Success);
+        let response = table.view_group(&ViewGroup { group_id: 0x0001 });
+        assert_eq!(response.group_name.as_str(), Some("Arbeitszimmer B"));
+    }
+
+    #[test]
+    fn group_membership_count_saturates() {
+        // given
+        let mut table = GroupTable::<300>::default();
+        for group_id in 1..=300 {
+            table.add(group_id, "");
+        }
+        let command = GetGroupMembership::try_read(&[0x00], ())
+            .expect("Could not read GetGroupMembership in test")
+            .0;
+        let mut buf = [0u8; 600];
+
+        // when
+        let response = table
+            .get_group_membership(&command, &mut buf)
+            .expect("Could not get group membership in test");
+
+        // then
+        assert_eq!(response.group_count, u8::MAX);
+        assert_eq!(response.groups().count(), 255);
+    }
+}
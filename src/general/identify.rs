@@ -0,0 +1,285 @@
+//!
+//! 3.5 Identify Cluster
+//!
+//! Attributes and commands to put a device into an identification mode (e.g. flashing a light),
+//! that indicates to an observer which of several devices it is. [`IdentifyServer`] implements
+//! the `IdentifyTime` countdown and is ticked by the application once per second.
+use crate::impl_byte;
+
+/// Identify cluster identifier
+pub const CLUSTER_ID: u16 = 0x0003;
+
+impl_byte! {
+    /// 3.5.2.2 Identify Server Attributes
+    #[repr(u16)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        /// Remaining length of time, in seconds, that the device will continue to identify itself.
+        IdentifyTime = 0x0000,
+    }
+}
+
+impl_byte! {
+    /// 3.5.2.3 Commands Received
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ReceivedCommandIdentifier {
+        /// Starts or stops the receiving device identifying itself.
+        Identify = 0x00,
+        /// Requests the target to respond if it is currently identifying itself.
+        IdentifyQuery = 0x01,
+        /// Triggers an effect to identify the device.
+        TriggerEffect = 0x40,
+    }
+}
+
+impl_byte! {
+    /// 3.5.2.4 Commands Generated
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum GeneratedCommandIdentifier {
+        /// Response to [`ReceivedCommandIdentifier::IdentifyQuery`]
+        IdentifyQueryResponse = 0x00,
+    }
+}
+
+impl_byte! {
+    /// Effect identifier of the Trigger Effect command
+    ///
+    /// See Section 3.5.2.3.3.1
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum EffectIdentifier {
+        /// Light is turned on/off once.
+        Blink = 0x00,
+        /// Light is turned on/off over 1 second and repeated 15 times.
+        Breathe = 0x01,
+        /// Colored light turns green for 1 second, non-colored light flashes twice.
+        Okay = 0x02,
+        /// Colored light turns orange for 8 seconds, non-colored light switches to maximum
+        /// brightness for 0.5s and then minimum brightness for 7.5s.
+        ChannelChange = 0x0b,
+        /// Complete the current effect sequence before terminating.
+        FinishEffect = 0xfe,
+        /// Terminate the effect as soon as possible.
+        StopEffect = 0xff,
+    }
+}
+
+impl_byte! {
+    /// Effect variant of the Trigger Effect command
+    ///
+    /// See Section 3.5.2.3.3.2
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum EffectVariant {
+        /// Default variant of the effect
+        Default = 0x00,
+    }
+}
+
+impl_byte! {
+    /// 3.5.2.3.1 Identify Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Identify {
+        /// Time in seconds to identify, `0` stops identifying.
+        pub identify_time: u16,
+    }
+}
+
+impl_byte! {
+    /// 3.5.2.3.3 Trigger Effect Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct TriggerEffect {
+        /// Effect to trigger
+        pub effect_identifier: EffectIdentifier,
+        /// Raw effect variant, see [`TriggerEffect::variant`]
+        pub effect_variant: u8,
+    }
+}
+
+impl TriggerEffect {
+    /// Returns the effect variant.
+    ///
+    /// Unsupported variants fall back to the default variant.
+    pub fn variant(&self) -> EffectVariant {
+        use core::convert::TryFrom;
+
+        EffectVariant::try_from(self.effect_variant).unwrap_or(EffectVariant::Default)
+    }
+}
+
+impl_byte! {
+    /// 3.5.2.4.1 Identify Query Response Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct IdentifyQueryResponse {
+        /// Remaining time in seconds the device will identify itself.
+        pub timeout: u16,
+    }
+}
+
+/// Actions the application has to perform, returned by [`IdentifyServer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentifyEvent {
+    /// Start identifying (e.g. start blinking).
+    StartIdentifying,
+    /// Stop identifying.
+    StopIdentifying,
+    /// Start the given effect.
+    StartEffect(EffectIdentifier, EffectVariant),
+    /// Complete the current effect sequence and then terminate the effect.
+    FinishEffect,
+    /// Terminate the current effect as soon as possible.
+    StopEffect,
+}
+
+/// Identify server state machine
+///
+/// Holds the `IdentifyTime` attribute, which is decremented by [`IdentifyServer::tick`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IdentifyServer {
+    identify_time: u16,
+}
+
+impl IdentifyServer {
+    /// Creates a server that is not identifying.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the `IdentifyTime` attribute.
+    pub fn identify_time(&self) -> u16 {
+        self.identify_time
+    }
+
+    /// Returns `true` if the device is currently identifying.
+    pub fn is_identifying(&self) -> bool {
+        self.identify_time > 0
+    }
+
+    /// Sets the `IdentifyTime` attribute, either by the Identify command or a write attributes
+    /// command.
+    ///
+    /// Returns an event if identifying started or stopped.
+    pub fn set_identify_time(&mut self, identify_time: u16) -> Option<IdentifyEvent> {
+        let was_identifying = self.is_identifying();
+        self.identify_time = identify_time;
+        match (was_identifying, self.is_identifying()) {
+            (false, true) => Some(IdentifyEvent::StartIdentifying),
+            (true, false) => Some(IdentifyEvent::StopIdentifying),
+            _ => None,
+        }
+    }
+
+    /// Handles the Identify command.
+    ///
+    /// See Section 3.5.2.3.1
+    pub fn identify(&mut self, command: Identify) -> Option<IdentifyEvent> {
+        self.set_identify_time(command.identify_time)
+    }
+
+    /// Handles the Identify Query command.
+    ///
+    /// Returns the response, or `None` if the device is not identifying and no response shall be
+    /// sent.
+    ///
+    /// See Section 3.5.2.3.2
+    pub fn identify_query(&self) -> Option<IdentifyQueryResponse> {
+        if self.is_identifying() {
+            Some(IdentifyQueryResponse {
+                timeout: self.identify_time,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Handles the Trigger Effect command.
+    ///
+    /// See Section 3.5.2.3.3
+    pub fn trigger_effect(&mut self, command: TriggerEffect) -> IdentifyEvent {
+        match command.effect_identifier {
+            EffectIdentifier::FinishEffect => IdentifyEvent::FinishEffect,
+            EffectIdentifier::StopEffect => IdentifyEvent::StopEffect,
+            effect => IdentifyEvent::StartEffect(effect, command.variant()),
+        }
+    }
+
+    /// Advances the state machine by one second.
+    ///
+    /// Returns [`IdentifyEvent::StopIdentifying`] once `IdentifyTime` reaches zero.
+    pub fn tick(&mut self) -> Option<IdentifyEvent> {
+        if self.identify_time == 0 {
+            return None;
+        }
+        self.identify_time -= 1;
+        if self.identify_time == 0 {
+            Some(IdentifyEvent::StopIdentifying)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::TryRead;
+
+    use super::*;
+
+    #[test]
+    fn unpack_trigger_effect() {
+        // given
+        let input = [0x01, 0x05];
+
+        // when
+        let (command, _) =
+            TriggerEffect::try_read(&input, ()).expect("Could not read TriggerEffect in test");
+
+        // then
+        assert_eq!(command.effect_identifier, EffectIdentifier::Breathe);
+        assert_eq!(command.variant(), EffectVariant::Default);
+    }
+
+    #[test]
+    fn identify_time_counts_down() {
+        // given
+        let mut server = IdentifyServer::new();
+
+        // when
+        let event = server.identify(Identify { identify_time: 2 });
+
+        // then
+        assert_eq!(event, Some(IdentifyEvent::StartIdentifying));
+        assert_eq!(
+            server.identify_query(),
+            Some(IdentifyQueryResponse { timeout: 2 })
+        );
+        assert_eq!(server.tick(), None);
+        assert_eq!(server.identify_time(), 1);
+        assert_eq!(server.tick(), Some(IdentifyEvent::StopIdentifying));
+        assert_eq!(server.tick(), None);
+        assert_eq!(server.identify_query(), None);
+    }
+
+    #[test]
+    fn identify_with_zero_stops_identifying() {
+        // given
+        let mut server = IdentifyServer::new();
+        server.set_identify_time(60);
+
+        // when
+        let event = server.identify(Identify { identify_time: 0 });
+
+        // then
+        assert_eq!(event, Some(IdentifyEvent::StopIdentifying));
+        assert!(!server.is_identifying());
+        assert_eq!(
+            server.trigger_effect(TriggerEffect {
+                effect_identifier: EffectIdentifier::StopEffect,
+                effect_variant: 0,
+            }),
+            IdentifyEvent::StopEffect
+        );
+    }
+}
@@ -0,0 +1,537 @@
+//!
+//! 3.14 Input, Output and Value Clusters
+//!
+//! Types shared by the Analog, Binary and Multistate Input, Output and Value clusters, which
+//! are based on the corresponding BACnet objects. Outputs and values are commandable, their
+//! `PresentValue` is resolved from a [`PriorityArray`].
+use core::convert::TryFrom;
+
+use byte::{BytesExt, TryRead, TryWrite};
+
+use crate::data_types::{CharacterString, DataType};
+use crate::impl_byte;
+use crate::status::ZclStatus;
+
+impl_byte! {
+    /// 3.14.11.12 `StatusFlags` Attribute
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct StatusFlags(pub u8);
+}
+
+impl StatusFlags {
+    /// The value is in an alarm state
+    pub const IN_ALARM: u8 = 1 << 0;
+    /// The `Reliability` attribute indicates a fault
+    pub const FAULT: u8 = 1 << 1;
+    /// The value has been overridden locally
+    pub const OVERRIDDEN: u8 = 1 << 2;
+    /// The `OutOfService` attribute is set
+    pub const OUT_OF_SERVICE: u8 = 1 << 3;
+
+    /// Derives the flags from the state of the cluster.
+    pub fn new(
+        in_alarm: bool,
+        reliability: Reliability,
+        overridden: bool,
+        out_of_service: bool,
+    ) -> Self {
+        let flag = |set: bool, flag: u8| if set { flag } else { 0 };
+        Self(
+            flag(in_alarm, Self::IN_ALARM)
+                | flag(reliability != Reliability::NoFaultDetected, Self::FAULT)
+                | flag(overridden, Self::OVERRIDDEN)
+                | flag(out_of_service, Self::OUT_OF_SERVICE),
+        )
+    }
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u8) -> bool {
+        self.0 & flags == flags
+    }
+}
+
+impl_byte! {
+    /// 3.14.11.9 `Reliability` Attribute
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Reliability {
+        NoFaultDetected = 0x00,
+        NoSensor = 0x01,
+        OverRange = 0x02,
+        UnderRange = 0x03,
+        OpenLoop = 0x04,
+        ShortedLoop = 0x05,
+        NoOutput = 0x06,
+        UnreliableOther = 0x07,
+        ProcessError = 0x08,
+        MultiStateFault = 0x09,
+        ConfigurationError = 0x0a,
+    }
+}
+
+impl Default for Reliability {
+    fn default() -> Self {
+        Self::NoFaultDetected
+    }
+}
+
+impl_byte! {
+    /// 3.14.11.8 `Polarity` Attribute
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Polarity {
+        Normal = 0x00,
+        Reverse = 0x01,
+    }
+}
+
+impl Polarity {
+    /// Returns the `PresentValue` of a binary input for the physical state of the input.
+    pub fn present_value(self, physical_state: bool) -> bool {
+        physical_state ^ (self == Self::Reverse)
+    }
+}
+
+impl_byte! {
+    /// 3.14.11.13 `EngineeringUnits` Attribute
+    ///
+    /// `BACnet` engineering units, only a few common units are defined as constants.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct EngineeringUnits(pub u16);
+}
+
+impl EngineeringUnits {
+    /// Amperes
+    pub const AMPERES: Self = Self(3);
+    /// Volts
+    pub const VOLTS: Self = Self(5);
+    /// Kilowatt hours
+    pub const KILOWATT_HOURS: Self = Self(19);
+    /// Percent relative humidity
+    pub const PERCENT_RELATIVE_HUMIDITY: Self = Self(29);
+    /// Watts
+    pub const WATTS: Self = Self(47);
+    /// Pascals
+    pub const PASCALS: Self = Self(53);
+    /// Degrees Celsius
+    pub const DEGREES_CELSIUS: Self = Self(62);
+    /// Seconds
+    pub const SECONDS: Self = Self(73);
+    /// No units
+    pub const NO_UNITS: Self = Self(95);
+    /// Parts per million
+    pub const PARTS_PER_MILLION: Self = Self(96);
+    /// Percent
+    pub const PERCENT: Self = Self(98);
+}
+
+impl_byte! {
+    /// 3.14.11.19 `ApplicationType` Attribute
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct ApplicationType(pub u32);
+}
+
+impl ApplicationType {
+    /// Creates an application type from its fields.
+    pub fn new(group: u8, object_type: u8, index: u16) -> Self {
+        Self(u32::from(group) << 24 | u32::from(object_type) << 16 | u32::from(index))
+    }
+
+    /// Returns the application group.
+    pub fn group(self) -> u8 {
+        self.0.to_be_bytes()[0]
+    }
+
+    /// Returns the object type, e.g. `0x00` for analog input.
+    pub fn object_type(self) -> u8 {
+        self.0.to_be_bytes()[1]
+    }
+
+    /// Returns the application index.
+    pub fn index(self) -> u16 {
+        let [_, _, high, low] = self.0.to_be_bytes();
+        u16::from_be_bytes([high, low])
+    }
+}
+
+/// Number of priority levels of a [`PriorityArray`]
+pub const PRIORITY_LEVELS: usize = 16;
+
+/// 3.14.11.11 `PriorityArray` Attribute
+///
+/// Holds the values commanded at the 16 priority levels, `1` being the highest priority. The
+/// `PresentValue` is the value of the highest priority level in use or the `RelinquishDefault`.
+///
+/// Encoded as a ZCL array of 16 structures, each holding a boolean null flag, `TRUE` for a
+/// relinquished level, and the value, which is ignored for relinquished levels. The context of
+/// the codec is the context of the values, e.g. `byte::LE`.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PriorityArray<T> {
+    values: [Option<T>; PRIORITY_LEVELS],
+}
+
+impl<T: Copy> Default for PriorityArray<T> {
+    fn default() -> Self {
+        Self {
+            values: [None; PRIORITY_LEVELS],
+        }
+    }
+}
+
+impl<T: Copy> PriorityArray<T> {
+    /// Returns the value commanded at `priority` (1 - 16).
+    pub fn get(&self, priority: u8) -> Option<T> {
+        self.index(priority).and_then(|index| self.values[index])
+    }
+
+    /// Commands a value at `priority` (1 - 16), `None` relinquishes the priority level.
+    pub fn write(&mut self, priority: u8, value: Option<T>) -> ZclStatus {
+        self.index(priority)
+            .map_or(ZclStatus::InvalidValue, |index| {
+                self.values[index] = value;
+                ZclStatus::Success
+            })
+    }
+
+    /// Returns the value of the highest priority level in use, or `relinquish_default`.
+    pub fn present_value(&self, relinquish_default: T) -> T {
+        self.values
+            .iter()
+            .flatten()
+            .next()
+            .copied()
+            .unwrap_or(relinquish_default)
+    }
+
+    fn index(&self, priority: u8) -> Option<usize> {
+        let index = usize::from(priority).checked_sub(1)?;
+        (index < self.values.len()).then(|| index)
+    }
+}
+
+/// Value commanded through a [`PriorityArray`]
+pub trait PriorityValue: Copy + Default {
+    /// Data type of the value
+    const DATA_TYPE: DataType;
+}
+
+impl PriorityValue for bool {
+    const DATA_TYPE: DataType = DataType::Bool;
+}
+
+impl PriorityValue for u16 {
+    const DATA_TYPE: DataType = DataType::Uint16;
+}
+
+impl PriorityValue for f32 {
+    const DATA_TYPE: DataType = DataType::SinglePrecision;
+}
+
+/// Number of members of a priority level structure
+const PRIORITY_LEVEL_MEMBERS: u16 = 2;
+
+/// Null flag of a relinquished priority level
+const NULL_FLAG: u8 = 0x01;
+
+fn read_data_type(bytes: &[u8], offset: &mut usize, expected: DataType) -> byte::Result<()> {
+    let data_type: DataType = bytes.read_with(offset, ())?;
+    if data_type == expected {
+        Ok(())
+    } else {
+        Err(byte::Error::BadInput {
+            err: "Invalid element type for PriorityArray",
+        })
+    }
+}
+
+impl<'a, T, C> TryRead<'a, C> for PriorityArray<T>
+where
+    T: TryRead<'a, C> + PriorityValue,
+    C: Copy,
+{
+    fn try_read(bytes: &'a [u8], ctx: C) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        read_data_type(bytes, offset, DataType::Structure)?;
+        let number_of_elements: u16 = bytes.read_with(offset, byte::LE)?;
+        if usize::from(number_of_elements) != PRIORITY_LEVELS {
+            return Err(byte::Error::BadInput {
+                err: "Invalid number of priority levels",
+            });
+        }
+        let mut values = [None; PRIORITY_LEVELS];
+        for value in &mut values {
+            let members: u16 = bytes.read_with(offset, byte::LE)?;
+            if members != PRIORITY_LEVEL_MEMBERS {
+                return Err(byte::Error::BadInput {
+                    err: "Invalid number of priority level members",
+                });
+            }
+            read_data_type(bytes, offset, DataType::Bool)?;
+            let null: u8 = bytes.read_with(offset, byte::LE)?;
+            read_data_type(bytes, offset, T::DATA_TYPE)?;
+            let entry: T = bytes.read_with(offset, ctx)?;
+            if null != NULL_FLAG {
+                *value = Some(entry);
+            }
+        }
+
+        Ok((Self { values }, *offset))
+    }
+}
+
+impl<T, C> TryWrite<C> for PriorityArray<T>
+where
+    T: TryWrite<C> + PriorityValue,
+    C: Copy,
+{
+    fn try_write(self, bytes: &mut [u8], ctx: C) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, DataType::Structure, ())?;
+        #[allow(clippy::cast_possible_truncation)]
+        let number_of_elements = PRIORITY_LEVELS as u16;
+        bytes.write_with(offset, number_of_elements, byte::LE)?;
+        for value in &self.values {
+            let null = if value.is_some() { 0x00 } else { NULL_FLAG };
+            bytes.write_with(offset, PRIORITY_LEVEL_MEMBERS, byte::LE)?;
+            bytes.write_with(offset, DataType::Bool, ())?;
+            bytes.write_with(offset, null, byte::LE)?;
+            bytes.write_with(offset, T::DATA_TYPE, ())?;
+            bytes.write_with(offset, value.unwrap_or_default(), ctx)?;
+        }
+
+        Ok(*offset)
+    }
+}
+
+/// 3.14.11.14 `StateText` Attribute
+///
+/// Array of character strings naming the states of a multistate cluster, encoded as a ZCL
+/// array.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StateText<'a> {
+    number_of_elements: u16,
+    elements: &'a [u8],
+}
+
+impl<'a> StateText<'a> {
+    /// Returns the number of states.
+    pub fn len(&self) -> usize {
+        usize::from(self.number_of_elements)
+    }
+
+    /// Returns `true` if no state texts are given.
+    pub fn is_empty(&self) -> bool {
+        self.number_of_elements == 0
+    }
+
+    /// Returns the state texts.
+    pub fn texts(&self) -> StateTexts<'a> {
+        StateTexts {
+            remaining: self.number_of_elements,
+            elements: self.elements,
+            offset: 0,
+        }
+    }
+
+    /// Returns the text of a state (1 - `NumberOfStates`).
+    pub fn get(&self, state: u16) -> Option<CharacterString<'a>> {
+        self.texts().nth(usize::from(state.checked_sub(1)?))
+    }
+
+    /// Encodes the texts into `buf` and returns the attribute value borrowing it.
+    pub fn encode(texts: &[&str], buf: &'a mut [u8]) -> byte::Result<Self> {
+        let offset = &mut 0;
+        for text in texts {
+            let text = CharacterString::new(text).ok_or(byte::Error::BadInput {
+                err: "State text too long",
+            })?;
+            buf.write_with(offset, text, ())?;
+        }
+        let number_of_elements = u16::try_from(texts.len()).map_err(|_| byte::Error::BadInput {
+            err: "Too many state texts",
+        })?;
+
+        Ok(Self {
+            number_of_elements,
+            elements: &buf[..*offset],
+        })
+    }
+}
+
+impl<'a> TryRead<'a, ()> for StateText<'a> {
+    fn try_read(bytes: &'a [u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let element_type: DataType = bytes.read_with(offset, ())?;
+        if element_type != DataType::CharacterString {
+            return Err(byte::Error::BadInput {
+                err: "Invalid element type for StateText",
+            });
+        }
+        let number_of_elements: u16 = bytes.read_with(offset, byte::LE)?;
+        let start = *offset;
+        for _ in 0..number_of_elements {
+            let _: CharacterString<'_> = bytes.read_with(offset, ())?;
+        }
+        let elements = &bytes[start..*offset];
+
+        Ok((
+            Self {
+                number_of_elements,
+                elements,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl TryWrite for StateText<'_> {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, DataType::CharacterString, ())?;
+        bytes.write_with(offset, self.number_of_elements, byte::LE)?;
+        bytes.write_with(offset, self.elements, ())?;
+
+        Ok(*offset)
+    }
+}
+
+/// Iterator over the texts of a [`StateText`]
+#[derive(Debug, Clone)]
+pub struct StateTexts<'a> {
+    remaining: u16,
+    elements: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Iterator for StateTexts<'a> {
+    type Item = CharacterString<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.remaining = self.remaining.checked_sub(1)?;
+        self.elements.read_with(&mut self.offset, ()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::{TryRead, TryWrite};
+
+    use super::*;
+
+    #[test]
+    fn priority_array() {
+        // given
+        let mut array = PriorityArray::<f32>::default();
+
+        // when
+        array.write(8, Some(21.5));
+        array.write(16, Some(18.0));
+
+        // then
+        assert!((array.present_value(0.0) - 21.5).abs() < f32::EPSILON);
+        array.write(8, None);
+        assert!((array.present_value(0.0) - 18.0).abs() < f32::EPSILON);
+        assert_eq!(array.write(17, Some(1.0)), ZclStatus::InvalidValue);
+        assert_eq!(array.write(0, Some(1.0)), ZclStatus::InvalidValue);
+        assert_eq!(array.get(16), Some(18.0));
+    }
+
+    #[test]
+    fn pack_priority_array() {
+        // given
+        let mut array = PriorityArray::<u16>::default();
+        array.write(1, Some(0x0203));
+        array.write(16, Some(0x0001));
+
+        // when
+        let mut buf = [0u8; 128];
+        let len = array
+            .try_write(&mut buf, byte::LE)
+            .expect("Could not write PriorityArray in test");
+        let (read, read_len) = PriorityArray::<u16>::try_read(&buf[..len], byte::LE)
+            .expect("Could not read PriorityArray in test");
+
+        // then
+        assert_eq!(len, 115);
+        assert_eq!(
+            &buf[..17],
+            &[
+                0x4c, 0x10, 0x00, // array of 16 structures
+                0x02, 0x00, 0x10, 0x00, 0x21, 0x03, 0x02, // priority 1
+                0x02, 0x00, 0x10, 0x01, 0x21, 0x00, 0x00, // priority 2, relinquished
+            ]
+        );
+        assert_eq!(&buf[108..len], &[0x02, 0x00, 0x10, 0x00, 0x21, 0x01, 0x00]);
+        assert_eq!(read_len, len);
+        assert_eq!(read, array);
+        assert!(PriorityArray::<u16>::try_read(&buf[..len - 1], byte::LE).is_err());
+        assert!(PriorityArray::<f32>::try_read(&buf[..len], byte::LE).is_err());
+    }
+
+    #[test]
+    fn read_binary_priority_array() {
+        // given
+        let mut buf = [0u8; 3 + PRIORITY_LEVELS * 6];
+        buf[..3].copy_from_slice(&[0x4c, 0x10, 0x00]);
+        for level in buf[3..].chunks_mut(6) {
+            level.copy_from_slice(&[0x02, 0x00, 0x10, 0x01, 0x10, 0x00]);
+        }
+        buf[3 + 2 * 6..3 + 3 * 6].copy_from_slice(&[0x02, 0x00, 0x10, 0x00, 0x10, 0x01]);
+
+        // when
+        let (array, len) = PriorityArray::<bool>::try_read(&buf, ())
+            .expect("Could not read PriorityArray in test");
+
+        // then
+        assert_eq!(len, buf.len());
+        assert_eq!(array.get(3), Some(true));
+        assert_eq!(array.get(1), None);
+        assert!(array.present_value(false));
+    }
+
+    #[test]
+    fn state_text() {
+        // given
+        let buf = &mut [0u8; 16];
+        let text =
+            StateText::encode(&["Off", "Auto"], buf).expect("Could not encode StateText in test");
+
+        // when
+        let mut out = [0u8; 16];
+        let len = text
+            .try_write(&mut out, ())
+            .expect("Could not write StateText in test");
+        let (read, _) =
+            StateText::try_read(&out[..len], ()).expect("Could not read StateText in test");
+
+        // then
+        assert_eq!(
+            &out[..len],
+            &[0x42, 0x02, 0x00, 0x03, b'O', b'f', b'f', 0x04, b'A', b'u', b't', b'o']
+        );
+        assert_eq!(read.len(), 2);
+        assert_eq!(read.get(2).and_then(|text| text.as_str()), Some("Auto"));
+        assert_eq!(read.get(0), None);
+        assert_eq!(read.get(3), None);
+    }
+
+    #[test]
+    fn status_flags() {
+        // given
+        let reliability = Reliability::OverRange;
+
+        // when
+        let flags = StatusFlags::new(false, reliability, false, true);
+
+        // then
+        assert_eq!(
+            flags,
+            StatusFlags(StatusFlags::FAULT | StatusFlags::OUT_OF_SERVICE)
+        );
+        assert!(Polarity::Reverse.present_value(false));
+        assert_eq!(ApplicationType::new(0x01, 0x02, 0x0003).index(), 3);
+    }
+}
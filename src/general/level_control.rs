@@ -0,0 +1,847 @@
+//!
+//! 3.10 Level Control Cluster
+//!
+//! Attributes and commands for controlling a characteristic of a device that can be set to a
+//! level between fully 'On' and fully 'Off', e.g. the brightness of a light. [`LevelControlServer`]
+//! implements the transitions and is ticked by the application every 1/10th of a second.
+use byte::{BytesExt, TryRead, TryWrite};
+
+use crate::general::scenes::{LevelControlExtensionFieldSet, SceneExtension};
+use crate::impl_byte;
+use crate::status::ZclStatus;
+
+/// Level Control cluster identifier
+pub const CLUSTER_ID: u16 = 0x0008;
+
+/// Value of `StartUpCurrentLevel` to set `CurrentLevel` to the minimum level
+pub const START_UP_MINIMUM_LEVEL: u8 = 0x00;
+
+/// Value of `StartUpCurrentLevel` to restore the previous `CurrentLevel`
+pub const START_UP_PREVIOUS_LEVEL: u8 = 0xff;
+
+/// Transition time of the Move To Level command to use `OnOffTransitionTime`
+pub const USE_ON_OFF_TRANSITION_TIME: u16 = 0xffff;
+
+/// Rate of the Move command to use `DefaultMoveRate`
+pub const USE_DEFAULT_MOVE_RATE: u8 = 0xff;
+
+impl_byte! {
+    /// 3.10.2.3 Level Control Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        CurrentLevel = 0x0000,
+        RemainingTime = 0x0001,
+        MinLevel = 0x0002,
+        MaxLevel = 0x0003,
+        CurrentFrequency = 0x0004,
+        MinFrequency = 0x0005,
+        MaxFrequency = 0x0006,
+        Options = 0x000f,
+        OnOffTransitionTime = 0x0010,
+        OnLevel = 0x0011,
+        OnTransitionTime = 0x0012,
+        OffTransitionTime = 0x0013,
+        DefaultMoveRate = 0x0014,
+        StartUpCurrentLevel = 0x4000,
+    }
+}
+
+impl_byte! {
+    /// 3.10.2.4 Commands Received
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ReceivedCommandIdentifier {
+        MoveToLevel = 0x00,
+        Move = 0x01,
+        Step = 0x02,
+        Stop = 0x03,
+        MoveToLevelWithOnOff = 0x04,
+        MoveWithOnOff = 0x05,
+        StepWithOnOff = 0x06,
+        StopWithOnOff = 0x07,
+        MoveToClosestFrequency = 0x08,
+    }
+}
+
+impl_byte! {
+    /// 3.10.2.3.8 `Options` Attribute
+    ///
+    /// Also used for the options mask and options override fields of the commands.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct Options(pub u8);
+}
+
+impl Options {
+    /// Execute commands even if the device is off
+    pub const EXECUTE_IF_OFF: u8 = 1 << 0;
+    /// Couple changes of the level to the color temperature of the Color Control cluster
+    pub const COUPLE_COLOR_TEMP_TO_LEVEL: u8 = 1 << 1;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u8) -> bool {
+        self.0 & flags == flags
+    }
+
+    /// Returns the options to use for a command, i.e. the bits of `options_mask` are taken from
+    /// `options_override`, all other bits from `self`.
+    ///
+    /// See Section 3.10.2.4.1.3
+    #[must_use]
+    pub fn apply(self, options_mask: Self, options_override: Self) -> Self {
+        Self((self.0 & !options_mask.0) | (options_override.0 & options_mask.0))
+    }
+}
+
+impl_byte! {
+    /// Move mode of the Move command and step mode of the Step command
+    ///
+    /// See Section 3.10.2.4.2.1
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum MoveMode {
+        Up = 0x00,
+        Down = 0x01,
+    }
+}
+
+/// Step mode of the Step command
+///
+/// See Section 3.10.2.4.3.1
+pub type StepMode = MoveMode;
+
+/// Reads the optional options mask and override fields, which are not sent by older devices.
+pub(crate) fn read_options(bytes: &[u8], offset: &mut usize) -> byte::Result<(Options, Options)> {
+    if bytes.len() - *offset < 2 {
+        return Ok((Options::default(), Options::default()));
+    }
+    let options_mask = bytes.read_with(offset, ())?;
+    let options_override = bytes.read_with(offset, ())?;
+
+    Ok((options_mask, options_override))
+}
+
+/// 3.10.2.4.1 Move To Level Command
+///
+/// Also used for the Move To Level With On/Off command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveToLevel {
+    /// Target level
+    pub level: u8,
+    /// Transition time in 1/10th of a second
+    pub transition_time: u16,
+    /// Bits of `Options` to override
+    pub options_mask: Options,
+    /// Values of the overridden bits
+    pub options_override: Options,
+}
+
+impl TryRead<'_, ()> for MoveToLevel {
+    fn try_read(bytes: &[u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let level = bytes.read_with(offset, byte::LE)?;
+        let transition_time = bytes.read_with(offset, byte::LE)?;
+        let (options_mask, options_override) = read_options(bytes, offset)?;
+
+        Ok((
+            Self {
+                level,
+                transition_time,
+                options_mask,
+                options_override,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl TryWrite for MoveToLevel {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, self.level, byte::LE)?;
+        bytes.write_with(offset, self.transition_time, byte::LE)?;
+        bytes.write_with(offset, self.options_mask, ())?;
+        bytes.write_with(offset, self.options_override, ())?;
+
+        Ok(*offset)
+    }
+}
+
+/// 3.10.2.4.2 Move Command
+///
+/// Also used for the Move With On/Off command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    /// Direction of the move
+    pub move_mode: MoveMode,
+    /// Rate in units per second
+    pub rate: u8,
+    /// Bits of `Options` to override
+    pub options_mask: Options,
+    /// Values of the overridden bits
+    pub options_override: Options,
+}
+
+impl TryRead<'_, ()> for Move {
+    fn try_read(bytes: &[u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let move_mode = bytes.read_with(offset, ())?;
+        let rate = bytes.read_with(offset, byte::LE)?;
+        let (options_mask, options_override) = read_options(bytes, offset)?;
+
+        Ok((
+            Self {
+                move_mode,
+                rate,
+                options_mask,
+                options_override,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl TryWrite for Move {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, self.move_mode, ())?;
+        bytes.write_with(offset, self.rate, byte::LE)?;
+        bytes.write_with(offset, self.options_mask, ())?;
+        bytes.write_with(offset, self.options_override, ())?;
+
+        Ok(*offset)
+    }
+}
+
+/// 3.10.2.4.3 Step Command
+///
+/// Also used for the Step With On/Off command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    /// Direction of the step
+    pub step_mode: StepMode,
+    /// Change of the level
+    pub step_size: u8,
+    /// Transition time in 1/10th of a second
+    pub transition_time: u16,
+    /// Bits of `Options` to override
+    pub options_mask: Options,
+    /// Values of the overridden bits
+    pub options_override: Options,
+}
+
+impl TryRead<'_, ()> for Step {
+    fn try_read(bytes: &[u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let step_mode = bytes.read_with(offset, ())?;
+        let step_size = bytes.read_with(offset, byte::LE)?;
+        let transition_time = bytes.read_with(offset, byte::LE)?;
+        let (options_mask, options_override) = read_options(bytes, offset)?;
+
+        Ok((
+            Self {
+                step_mode,
+                step_size,
+                transition_time,
+                options_mask,
+                options_override,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl TryWrite for Step {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, self.step_mode, ())?;
+        bytes.write_with(offset, self.step_size, byte::LE)?;
+        bytes.write_with(offset, self.transition_time, byte::LE)?;
+        bytes.write_with(offset, self.options_mask, ())?;
+        bytes.write_with(offset, self.options_override, ())?;
+
+        Ok(*offset)
+    }
+}
+
+/// 3.10.2.4.4 Stop Command
+///
+/// Also used for the Stop With On/Off command.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stop {
+    /// Bits of `Options` to override
+    pub options_mask: Options,
+    /// Values of the overridden bits
+    pub options_override: Options,
+}
+
+impl TryRead<'_, ()> for Stop {
+    fn try_read(bytes: &[u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let (options_mask, options_override) = read_options(bytes, offset)?;
+
+        Ok((
+            Self {
+                options_mask,
+                options_override,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl TryWrite for Stop {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, self.options_mask, ())?;
+        bytes.write_with(offset, self.options_override, ())?;
+
+        Ok(*offset)
+    }
+}
+
+impl_byte! {
+    /// 3.10.2.4.6 Move To Closest Frequency Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct MoveToClosestFrequency {
+        /// Requested frequency in Hz
+        pub frequency: u16,
+    }
+}
+
+/// Changes the application has to apply, returned by [`LevelControlServer`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LevelUpdate {
+    /// New value of `CurrentLevel`, if it changed
+    pub level: Option<u8>,
+    /// Requested value of the `OnOff` attribute of the On/Off cluster, set by the commands with
+    /// On/Off
+    pub on_off: Option<bool>,
+}
+
+/// Running transition of a [`LevelControlServer`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Transition {
+    start: u8,
+    target: u8,
+    /// Duration in 1/10th of a second
+    duration: u16,
+    elapsed: u16,
+    with_on_off: bool,
+}
+
+impl Transition {
+    /// Level after `elapsed` ticks, linearly interpolated and rounded towards the start level.
+    fn level(self) -> u8 {
+        let start = i32::from(self.start);
+        let diff = i32::from(self.target) - start;
+        let level = start + diff * i32::from(self.elapsed) / i32::from(self.duration);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let level = level as u8;
+        level
+    }
+}
+
+/// Level Control server with a deterministic transition engine
+///
+/// Commands start a transition from `CurrentLevel` to a target level. Each
+/// [`LevelControlServer::tick`] advances the transition by 1/10th of a second, the level after `n`
+/// of `d` ticks is `start + (target - start) * n / d` (integer division), so the same commands
+/// always produce the same levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LevelControlServer {
+    current_level: u8,
+    min_level: u8,
+    max_level: u8,
+    current_frequency: u16,
+    min_frequency: u16,
+    max_frequency: u16,
+    options: Options,
+    on_off_transition_time: u16,
+    on_level: Option<u8>,
+    default_move_rate: Option<u8>,
+    start_up_current_level: Option<u8>,
+    transition: Option<Transition>,
+}
+
+impl Default for LevelControlServer {
+    fn default() -> Self {
+        Self::new(0x01, 0xfe)
+    }
+}
+
+impl LevelControlServer {
+    /// Creates a server with the given level range, `CurrentLevel` is set to `max_level`.
+    pub fn new(min_level: u8, max_level: u8) -> Self {
+        Self {
+            current_level: max_level,
+            min_level,
+            max_level,
+            current_frequency: 0,
+            min_frequency: 0,
+            max_frequency: 0,
+            options: Options::default(),
+            on_off_transition_time: 0,
+            on_level: None,
+            default_move_rate: None,
+            start_up_current_level: None,
+            transition: None,
+        }
+    }
+
+    /// Returns the `CurrentLevel` attribute.
+    pub fn current_level(&self) -> u8 {
+        self.current_level
+    }
+
+    /// Returns the `RemainingTime` attribute in 1/10th of a second.
+    pub fn remaining_time(&self) -> u16 {
+        self.transition.map_or(0, |t| t.duration - t.elapsed)
+    }
+
+    /// Returns the `MinLevel` attribute.
+    pub fn min_level(&self) -> u8 {
+        self.min_level
+    }
+
+    /// Returns the `MaxLevel` attribute.
+    pub fn max_level(&self) -> u8 {
+        self.max_level
+    }
+
+    /// Returns the `CurrentFrequency` attribute.
+    pub fn current_frequency(&self) -> u16 {
+        self.current_frequency
+    }
+
+    /// Sets the `MinFrequency` and `MaxFrequency` attributes, `CurrentFrequency` is moved into
+    /// the range.
+    pub fn set_frequency_range(&mut self, min_frequency: u16, max_frequency: u16) {
+        self.min_frequency = min_frequency;
+        self.max_frequency = max_frequency;
+        self.current_frequency = self.current_frequency.max(min_frequency).min(max_frequency);
+    }
+
+    /// Returns the `Options` attribute.
+    pub fn options(&self) -> Options {
+        self.options
+    }
+
+    /// Sets the `Options` attribute.
+    pub fn set_options(&mut self, options: Options) {
+        self.options = options;
+    }
+
+    /// Returns the `OnOffTransitionTime` attribute.
+    pub fn on_off_transition_time(&self) -> u16 {
+        self.on_off_transition_time
+    }
+
+    /// Sets the `OnOffTransitionTime` attribute.
+    pub fn set_on_off_transition_time(&mut self, on_off_transition_time: u16) {
+        self.on_off_transition_time = on_off_transition_time;
+    }
+
+    /// Returns the `OnLevel` attribute, `None` if undefined.
+    pub fn on_level(&self) -> Option<u8> {
+        self.on_level
+    }
+
+    /// Sets the `OnLevel` attribute.
+    pub fn set_on_level(&mut self, on_level: Option<u8>) {
+        self.on_level = on_level;
+    }
+
+    /// Returns the `DefaultMoveRate` attribute, `None` if undefined.
+    pub fn default_move_rate(&self) -> Option<u8> {
+        self.default_move_rate
+    }
+
+    /// Sets the `DefaultMoveRate` attribute.
+    pub fn set_default_move_rate(&mut self, default_move_rate: Option<u8>) {
+        self.default_move_rate = default_move_rate;
+    }
+
+    /// Returns the `StartUpCurrentLevel` attribute.
+    pub fn start_up_current_level(&self) -> Option<u8> {
+        self.start_up_current_level
+    }
+
+    /// Sets the `StartUpCurrentLevel` attribute.
+    pub fn set_start_up_current_level(&mut self, start_up_current_level: Option<u8>) {
+        self.start_up_current_level = start_up_current_level;
+    }
+
+    /// Sets `CurrentLevel` after a power cycle according to `StartUpCurrentLevel`.
+    ///
+    /// `previous` is the persisted value of `CurrentLevel` before the power cycle.
+    pub fn start_up(&mut self, previous: u8) {
+        let level = match self.start_up_current_level {
+            Some(START_UP_MINIMUM_LEVEL) => self.min_level,
+            Some(START_UP_PREVIOUS_LEVEL) | None => previous,
+            Some(level) => level,
+        };
+        self.current_level = self.clamp(level);
+        self.transition = None;
+    }
+
+    fn clamp(&self, level: u8) -> u8 {
+        level.max(self.min_level).min(self.max_level)
+    }
+
+    /// Returns `true` if a command without On/Off shall be executed.
+    fn execute(&self, on_off: bool, options_mask: Options, options_override: Options) -> bool {
+        on_off
+            || self
+                .options
+                .apply(options_mask, options_override)
+                .contains(Options::EXECUTE_IF_OFF)
+    }
+
+    fn start_transition(&mut self, target: u8, duration: u16, with_on_off: bool) -> LevelUpdate {
+        let target = self.clamp(target);
+        let mut update = LevelUpdate::default();
+        if with_on_off && target > self.min_level {
+            update.on_off = Some(true);
+        }
+
+        if duration == 0 || target == self.current_level {
+            self.transition = None;
+            if target != self.current_level {
+                self.current_level = target;
+                update.level = Some(target);
+            }
+            if with_on_off && target == self.min_level {
+                update.on_off = Some(false);
+            }
+        } else {
+            self.transition = Some(Transition {
+                start: self.current_level,
+                target,
+                duration,
+                elapsed: 0,
+                with_on_off,
+            });
+        }
+        update
+    }
+
+    fn move_to(&mut self, command: MoveToLevel, with_on_off: bool) -> LevelUpdate {
+        let duration = if command.transition_time == USE_ON_OFF_TRANSITION_TIME {
+            self.on_off_transition_time
+        } else {
+            command.transition_time
+        };
+        self.start_transition(command.level, duration, with_on_off)
+    }
+
+    fn move_at_rate(&mut self, command: Move, with_on_off: bool) -> LevelUpdate {
+        let rate = match command.rate {
+            0 => return LevelUpdate::default(),
+            USE_DEFAULT_MOVE_RATE => self.default_move_rate,
+            rate => Some(rate),
+        };
+        let target = match command.move_mode {
+            MoveMode::Up => self.max_level,
+            MoveMode::Down => self.min_level,
+        };
+        // as fast as possible without a rate
+        let duration = rate.map_or(0, |rate| {
+            let distance =
+                u16::from(self.current_level.max(target) - self.current_level.min(target));
+            let rate = u16::from(rate);
+            (distance * 10 + rate - 1) / rate
+        });
+        self.start_transition(target, duration, with_on_off)
+    }
+
+    fn step_by(&mut self, command: Step, with_on_off: bool) -> LevelUpdate {
+        let target = match command.step_mode {
+            StepMode::Up => self.current_level.saturating_add(command.step_size),
+            StepMode::Down => self.current_level.saturating_sub(command.step_size),
+        };
+        // 0xffff moves as fast as possible
+        let duration = if command.transition_time == 0xffff {
+            0
+        } else {
+            command.transition_time
+        };
+        self.start_transition(target, duration, with_on_off)
+    }
+
+    /// Handles the Move To Level command, `on_off` is the `OnOff` attribute of the On/Off
+    /// cluster.
+    ///
+    /// See Section 3.10.2.4.1
+    pub fn move_to_level(&mut self, command: MoveToLevel, on_off: bool) -> LevelUpdate {
+        if !self.execute(on_off, command.options_mask, command.options_override) {
+            return LevelUpdate::default();
+        }
+        self.move_to(command, false)
+    }
+
+    /// Handles the Move command, `on_off` is the `OnOff` attribute of the On/Off cluster.
+    ///
+    /// See Section 3.10.2.4.2
+    pub fn move_level(&mut self, command: Move, on_off: bool) -> LevelUpdate {
+        if !self.execute(on_off, command.options_mask, command.options_override) {
+            return LevelUpdate::default();
+        }
+        self.move_at_rate(command, false)
+    }
+
+    /// Handles the Step command, `on_off` is the `OnOff` attribute of the On/Off cluster.
+    ///
+    /// See Section 3.10.2.4.3
+    pub fn step_level(&mut self, command: Step, on_off: bool) -> LevelUpdate {
+        if !self.execute(on_off, command.options_mask, command.options_override) {
+            return LevelUpdate::default();
+        }
+        self.step_by(command, false)
+    }
+
+    /// Handles the Stop command, `on_off` is the `OnOff` attribute of the On/Off cluster.
+    ///
+    /// See Section 3.10.2.4.4
+    pub fn stop(&mut self, command: Stop, on_off: bool) {
+        if self.execute(on_off, command.options_mask, command.options_override) {
+            self.transition = None;
+        }
+    }
+
+    /// Handles the Move To Level With On/Off command.
+    ///
+    /// See Section 3.10.2.4.5
+    pub fn move_to_level_with_on_off(&mut self, command: MoveToLevel) -> LevelUpdate {
+        self.move_to(command, true)
+    }
+
+    /// Handles the Move With On/Off command.
+    ///
+    /// See Section 3.10.2.4.5
+    pub fn move_with_on_off(&mut self, command: Move) -> LevelUpdate {
+        self.move_at_rate(command, true)
+    }
+
+    /// Handles the Step With On/Off command.
+    ///
+    /// See Section 3.10.2.4.5
+    pub fn step_with_on_off(&mut self, command: Step) -> LevelUpdate {
+        self.step_by(command, true)
+    }
+
+    /// Handles the Stop With On/Off command.
+    ///
+    /// See Section 3.10.2.4.5
+    pub fn stop_with_on_off(&mut self) {
+        self.transition = None;
+    }
+
+    /// Handles the Move To Closest Frequency command.
+    ///
+    /// See Section 3.10.2.4.6
+    pub fn move_to_closest_frequency(&mut self, command: MoveToClosestFrequency) -> ZclStatus {
+        if self.max_frequency == 0 {
+            return ZclStatus::UnsupClusterCommand;
+        }
+        self.current_frequency = command
+            .frequency
+            .max(self.min_frequency)
+            .min(self.max_frequency);
+        ZclStatus::Success
+    }
+
+    /// Advances the running transition by 1/10th of a second.
+    pub fn tick(&mut self) -> LevelUpdate {
+        let mut update = LevelUpdate::default();
+        let mut transition = match self.transition {
+            Some(transition) => transition,
+            None => return update,
+        };
+
+        transition.elapsed += 1;
+        let level = transition.level();
+        if transition.elapsed >= transition.duration {
+            self.transition = None;
+            if transition.with_on_off && level == self.min_level {
+                update.on_off = Some(false);
+            }
+        } else {
+            self.transition = Some(transition);
+        }
+        if level != self.current_level {
+            self.current_level = level;
+            update.level = Some(level);
+        }
+        update
+    }
+}
+
+impl SceneExtension for LevelControlServer {
+    fn cluster_id(&self) -> u16 {
+        CLUSTER_ID
+    }
+
+    fn store_scene(&self, data: &mut [u8]) -> byte::Result<usize> {
+        let offset = &mut 0;
+        data.write_with(
+            offset,
+            LevelControlExtensionFieldSet {
+                current_level: self.current_level,
+            },
+            (),
+        )?;
+        Ok(*offset)
+    }
+
+    fn recall_scene(&mut self, data: &[u8], transition_time: u16) {
+        if let Ok((set, _)) = LevelControlExtensionFieldSet::try_read(data, ()) {
+            self.start_transition(set.current_level, transition_time, false);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::TryRead;
+
+    use super::*;
+
+    #[test]
+    fn unpack_move_to_level() {
+        // given
+        let without_options = [0x80, 0x0a, 0x00];
+        let with_options = [0x80, 0x0a, 0x00, 0x01, 0x01];
+
+        // when
+        let (short, _) = MoveToLevel::try_read(&without_options, ())
+            .expect("Could not read MoveToLevel in test");
+        let (long, len) =
+            MoveToLevel::try_read(&with_options, ()).expect("Could not read MoveToLevel in test");
+
+        // then
+        assert_eq!(short.level, 0x80);
+        assert_eq!(short.transition_time, 10);
+        assert_eq!(short.options_mask, Options::default());
+        assert_eq!(len, 5);
+        assert_eq!(long.options_override, Options(Options::EXECUTE_IF_OFF));
+    }
+
+    #[test]
+    fn options_override() {
+        // given
+        let options = Options(Options::COUPLE_COLOR_TEMP_TO_LEVEL);
+
+        // when
+        let applied = options.apply(
+            Options(Options::EXECUTE_IF_OFF),
+            Options(Options::EXECUTE_IF_OFF | Options::COUPLE_COLOR_TEMP_TO_LEVEL),
+        );
+
+        // then
+        assert_eq!(
+            applied,
+            Options(Options::EXECUTE_IF_OFF | Options::COUPLE_COLOR_TEMP_TO_LEVEL)
+        );
+        let mut server = LevelControlServer::new(1, 254);
+        let command = MoveToLevel {
+            level: 10,
+            transition_time: 0,
+            options_mask: Options::default(),
+            options_override: Options::default(),
+        };
+        assert_eq!(server.move_to_level(command, false), LevelUpdate::default());
+        let command = MoveToLevel {
+            options_mask: Options(Options::EXECUTE_IF_OFF),
+            options_override: Options(Options::EXECUTE_IF_OFF),
+            ..command
+        };
+        assert_eq!(server.move_to_level(command, false).level, Some(10));
+    }
+
+    #[test]
+    fn transition_curve() {
+        // given
+        let mut server = LevelControlServer::new(1, 254);
+        server.start_up(100);
+
+        // when
+        let update = server.move_to_level(
+            MoveToLevel {
+                level: 110,
+                transition_time: 4,
+                options_mask: Options::default(),
+                options_override: Options::default(),
+            },
+            true,
+        );
+
+        // then
+        assert_eq!(update, LevelUpdate::default());
+        assert_eq!(server.remaining_time(), 4);
+        let levels = [
+            server.tick().level,
+            server.tick().level,
+            server.tick().level,
+            server.tick().level,
+            server.tick().level,
+        ];
+        assert_eq!(levels, [Some(102), Some(105), Some(107), Some(110), None]);
+        assert_eq!(server.remaining_time(), 0);
+    }
+
+    #[test]
+    fn move_down_with_on_off() {
+        // given
+        let mut server = LevelControlServer::new(1, 254);
+        server.start_up(21);
+
+        // when
+        let update = server.move_with_on_off(Move {
+            move_mode: MoveMode::Down,
+            rate: 100,
+            options_mask: Options::default(),
+            options_override: Options::default(),
+        });
+
+        // then
+        assert_eq!(update, LevelUpdate::default());
+        assert_eq!(server.remaining_time(), 2);
+        assert_eq!(server.tick().level, Some(11));
+        assert_eq!(
+            server.tick(),
+            LevelUpdate {
+                level: Some(1),
+                on_off: Some(false),
+            }
+        );
+    }
+
+    #[test]
+    fn step_with_on_off_turns_on() {
+        // given
+        let mut server = LevelControlServer::new(1, 254);
+        server.start_up(1);
+
+        // when
+        let update = server.step_with_on_off(Step {
+            step_mode: StepMode::Up,
+            step_size: 0xff,
+            transition_time: 0,
+            options_mask: Options::default(),
+            options_override: Options::default(),
+        });
+
+        // then
+        assert_eq!(
+            update,
+            LevelUpdate {
+                level: Some(254),
+                on_off: Some(true),
+            }
+        );
+    }
+}
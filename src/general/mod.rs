@@ -0,0 +1,74 @@
+//!
+//! General Clusters
+
+/// Provides attributes for determining basic information about a device and the Reset to Factory
+/// Defaults command.
+pub mod basic;
+
+/// Provides attributes for determining detailed information about a device's power source(s),
+/// and for configuring under/over voltage alarms.
+pub mod power_configuration;
+
+/// Provides attributes for determining information about a device's internal temperature, and
+/// for configuring under/over temperature alarms.
+pub mod device_temperature_configuration;
+
+/// Provides attributes and commands to put a device into an identification mode.
+pub mod identify;
+
+/// Provides commands to manage the group table of an endpoint.
+pub mod groups;
+
+/// Provides commands to store and recall scenes, i.e. attribute values of other clusters on the
+/// same endpoint.
+pub mod scenes;
+
+/// Provides attributes and commands for switching devices between 'On' and 'Off' states.
+pub mod on_off;
+
+/// Provides attributes and commands for controlling the level of a device, e.g. the brightness
+/// of a light.
+pub mod level_control;
+
+/// Provides commands for sending alarm notifications and a table logging the raised alarms.
+pub mod alarms;
+
+/// Provides attributes for synchronizing the time of a device and deriving its local time.
+pub mod time;
+
+/// Provides the types shared by the Analog, Binary and Multistate Input, Output and Value
+/// clusters.
+pub mod input_output_value;
+
+/// Provides the attributes of the Analog Input cluster for an analog measurement.
+pub mod analog_input;
+
+/// Provides the attributes of the Analog Output cluster for an analog output.
+pub mod analog_output;
+
+/// Provides the attributes of the Analog Value cluster for an analog setting.
+pub mod analog_value;
+
+/// Provides the attributes of the Binary Input cluster for a binary measurement.
+pub mod binary_input;
+
+/// Provides the attributes of the Binary Output cluster for a binary output.
+pub mod binary_output;
+
+/// Provides the attributes of the Binary Value cluster for a binary setting.
+pub mod binary_value;
+
+/// Provides the attributes of the Multistate Input cluster for a multistate measurement.
+pub mod multistate_input;
+
+/// Provides the attributes of the Multistate Output cluster for a multistate output.
+pub mod multistate_output;
+
+/// Provides the attributes of the Multistate Value cluster for a multistate setting.
+pub mod multistate_value;
+
+/// Provides attributes and commands for managing the polling of sleepy end devices.
+pub mod poll_control;
+
+/// Provides attributes for diagnostic counters of the device and its network stack.
+pub mod diagnostics;
@@ -0,0 +1,26 @@
+//!
+//! 3.14.8 Multistate Input Cluster
+//!
+//! Attributes for a multistate measurement, based on the `BACnet` Multistate Input object.
+//! `PresentValue` is the number of the current state (1 - `NumberOfStates`).
+use crate::impl_byte;
+
+/// Multistate Input cluster identifier
+pub const CLUSTER_ID: u16 = 0x0012;
+
+impl_byte! {
+    /// 3.14.8.2 Multistate Input Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        StateText = 0x000e,
+        Description = 0x001c,
+        NumberOfStates = 0x004a,
+        OutOfService = 0x0051,
+        PresentValue = 0x0055,
+        Reliability = 0x0067,
+        StatusFlags = 0x006f,
+        ApplicationType = 0x0100,
+    }
+}
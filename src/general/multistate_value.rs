@@ -0,0 +1,28 @@
+//!
+//! 3.14.10 Multistate Value Cluster
+//!
+//! Attributes for a multistate setting, based on the `BACnet` Multistate Value object.
+//! `PresentValue` is the number of the current state commanded through the `PriorityArray`.
+use crate::impl_byte;
+
+/// Multistate Value cluster identifier
+pub const CLUSTER_ID: u16 = 0x0014;
+
+impl_byte! {
+    /// 3.14.10.2 Multistate Value Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        StateText = 0x000e,
+        Description = 0x001c,
+        NumberOfStates = 0x004a,
+        OutOfService = 0x0051,
+        PresentValue = 0x0055,
+        PriorityArray = 0x0057,
+        Reliability = 0x0067,
+        RelinquishDefault = 0x0068,
+        StatusFlags = 0x006f,
+        ApplicationType = 0x0100,
+    }
+}
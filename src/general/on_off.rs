@@ -0,0 +1,524 @@
+//!
+//! 3.8 On/Off Cluster
+//!
+//! Attributes and commands for switching devices between 'On' and 'Off' states.
+//! [`OnOffServer`] implements the timed on/off logic of the `OnTime` and `OffWaitTime`
+//! attributes and is ticked by the application every 1/10th of a second.
+use byte::{BytesExt, TryRead};
+
+use crate::general::scenes::{OnOffExtensionFieldSet, SceneExtension};
+use crate::impl_byte;
+
+/// On/Off cluster identifier
+pub const CLUSTER_ID: u16 = 0x0006;
+
+impl_byte! {
+    /// 3.8.2.2 On/Off Server Attributes
+    #[repr(u16)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        /// On/off state of the device.
+        OnOff = 0x0000,
+        /// Whether the global scene may be stored by the Off With Effect command.
+        GlobalSceneControl = 0x4000,
+        /// Remaining time in 1/10th of a second until the device turns off.
+        OnTime = 0x4001,
+        /// Time in 1/10th of a second the device ignores On With Timed Off commands after
+        /// turning off.
+        OffWaitTime = 0x4002,
+        /// Desired startup behavior of the device.
+        StartUpOnOff = 0x4003,
+    }
+}
+
+impl_byte! {
+    /// 3.8.2.3 Commands Received
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ReceivedCommandIdentifier {
+        Off = 0x00,
+        On = 0x01,
+        Toggle = 0x02,
+        OffWithEffect = 0x40,
+        OnWithRecallGlobalScene = 0x41,
+        OnWithTimedOff = 0x42,
+    }
+}
+
+impl_byte! {
+    /// 3.8.2.2.5 `StartUpOnOff` Attribute
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum StartUpOnOff {
+        /// Set `OnOff` to `false`.
+        Off = 0x00,
+        /// Set `OnOff` to `true`.
+        On = 0x01,
+        /// Toggle the previous value of `OnOff`.
+        Toggle = 0x02,
+        /// Restore the previous value of `OnOff`.
+        Previous = 0xff,
+    }
+}
+
+impl_byte! {
+    /// Effect identifier of the Off With Effect command
+    ///
+    /// See Section 3.8.2.3.4.1
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum EffectIdentifier {
+        /// Delayed all off, see [`OffEffect`]
+        DelayedAllOff = 0x00,
+        /// Dying light, see [`OffEffect`]
+        DyingLight = 0x01,
+    }
+}
+
+/// Effect of the Off With Effect command, i.e. the effect identifier with its variant
+///
+/// See Section 3.8.2.3.4.2
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OffEffect {
+    /// Fade to off in 0.8 seconds.
+    FadeToOff,
+    /// No fade.
+    NoFade,
+    /// 50% dim down in 0.8 seconds then fade to off in 12 seconds.
+    DimDownThenFadeToOff,
+    /// 20% dim up in 0.5s then fade to off in 1 second.
+    DimUpThenFadeToOff,
+}
+
+impl_byte! {
+    /// 3.8.2.3.4 Off With Effect Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct OffWithEffect {
+        /// Effect to use
+        pub effect_identifier: EffectIdentifier,
+        /// Raw effect variant, see [`OffWithEffect::effect`]
+        pub effect_variant: u8,
+    }
+}
+
+impl OffWithEffect {
+    /// Returns the effect.
+    ///
+    /// Unsupported variants fall back to the default variant of the effect.
+    pub fn effect(&self) -> OffEffect {
+        match (self.effect_identifier, self.effect_variant) {
+            (EffectIdentifier::DelayedAllOff, 0x01) => OffEffect::NoFade,
+            (EffectIdentifier::DelayedAllOff, 0x02) => OffEffect::DimDownThenFadeToOff,
+            (EffectIdentifier::DelayedAllOff, _) => OffEffect::FadeToOff,
+            (EffectIdentifier::DyingLight, _) => OffEffect::DimUpThenFadeToOff,
+        }
+    }
+}
+
+impl_byte! {
+    /// On/off control field of the On With Timed Off command
+    ///
+    /// See Section 3.8.2.3.6.1
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct OnOffControl(pub u8);
+}
+
+impl OnOffControl {
+    /// Only accept the command if the device is on
+    pub const ACCEPT_ONLY_WHEN_ON: u8 = 1 << 0;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u8) -> bool {
+        self.0 & flags == flags
+    }
+}
+
+impl_byte! {
+    /// 3.8.2.3.6 On With Timed Off Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct OnWithTimedOff {
+        /// Control field
+        pub on_off_control: OnOffControl,
+        /// Time in 1/10th of a second the device stays on
+        pub on_time: u16,
+        /// Time in 1/10th of a second the device ignores further commands after turning off
+        pub off_wait_time: u16,
+    }
+}
+
+/// Actions the application has to perform, returned by [`OnOffServer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnOffEvent {
+    /// Turn the device on.
+    On,
+    /// Turn the device off.
+    Off,
+    /// Turn the device off with the given effect.
+    OffWithEffect(OffEffect),
+    /// Recall the global scene, see
+    /// [`SceneTable::recall_global_scene`](crate::general::scenes::SceneTable::recall_global_scene).
+    RecallGlobalScene,
+}
+
+/// Value of `OnTime` and `OffWaitTime` that is not decremented
+const NO_TIMEOUT: u16 = 0xffff;
+
+/// On/Off server state machine
+///
+/// Holds the attributes of the cluster. While the device is on, `OnTime` is decremented by
+/// [`OnOffServer::tick`] and the device turns off once it reaches zero. While the device is off,
+/// `OffWaitTime` is decremented and On With Timed Off commands only shorten it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OnOffServer {
+    on_off: bool,
+    global_scene_control: bool,
+    on_time: u16,
+    off_wait_time: u16,
+    start_up_on_off: Option<StartUpOnOff>,
+}
+
+impl Default for OnOffServer {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+impl OnOffServer {
+    /// Creates a server in the given state.
+    pub fn new(on_off: bool) -> Self {
+        Self {
+            on_off,
+            global_scene_control: true,
+            on_time: 0,
+            off_wait_time: 0,
+            start_up_on_off: None,
+        }
+    }
+
+    /// Creates a server after a power cycle, applying the `StartUpOnOff` attribute.
+    ///
+    /// `previous` is the persisted value of `OnOff` before the power cycle.
+    pub fn start_up(start_up_on_off: Option<StartUpOnOff>, previous: bool) -> Self {
+        let on_off = match start_up_on_off {
+            Some(StartUpOnOff::Off) => false,
+            Some(StartUpOnOff::On) => true,
+            Some(StartUpOnOff::Toggle) => !previous,
+            Some(StartUpOnOff::Previous) | None => previous,
+        };
+        Self {
+            start_up_on_off,
+            ..Self::new(on_off)
+        }
+    }
+
+    /// Returns the `OnOff` attribute.
+    pub fn on_off(&self) -> bool {
+        self.on_off
+    }
+
+    /// Returns the `GlobalSceneControl` attribute.
+    ///
+    /// If set, the global scene has to be stored (see
+    /// [`SceneTable::store_global_scene`](crate::general::scenes::SceneTable::store_global_scene))
+    /// before handling the Off With Effect command.
+    pub fn global_scene_control(&self) -> bool {
+        self.global_scene_control
+    }
+
+    /// Returns the `OnTime` attribute.
+    pub fn on_time(&self) -> u16 {
+        self.on_time
+    }
+
+    /// Sets the `OnTime` attribute.
+    pub fn set_on_time(&mut self, on_time: u16) {
+        self.on_time = on_time;
+    }
+
+    /// Returns the `OffWaitTime` attribute.
+    pub fn off_wait_time(&self) -> u16 {
+        self.off_wait_time
+    }
+
+    /// Sets the `OffWaitTime` attribute.
+    pub fn set_off_wait_time(&mut self, off_wait_time: u16) {
+        self.off_wait_time = off_wait_time;
+    }
+
+    /// Returns the `StartUpOnOff` attribute.
+    pub fn start_up_on_off(&self) -> Option<StartUpOnOff> {
+        self.start_up_on_off
+    }
+
+    /// Sets the `StartUpOnOff` attribute.
+    pub fn set_start_up_on_off(&mut self, start_up_on_off: Option<StartUpOnOff>) {
+        self.start_up_on_off = start_up_on_off;
+    }
+
+    fn turn_on(&mut self) -> Option<OnOffEvent> {
+        if self.on_time == 0 {
+            self.off_wait_time = 0;
+        }
+        self.global_scene_control = true;
+        self.set(true)
+    }
+
+    fn turn_off(&mut self) -> Option<OnOffEvent> {
+        self.on_time = 0;
+        self.set(false)
+    }
+
+    fn set(&mut self, on_off: bool) -> Option<OnOffEvent> {
+        if self.on_off == on_off {
+            return None;
+        }
+        self.on_off = on_off;
+        if on_off {
+            Some(OnOffEvent::On)
+        } else {
+            Some(OnOffEvent::Off)
+        }
+    }
+
+    /// Handles the Off command.
+    ///
+    /// Returns an event if the state changed.
+    ///
+    /// See Section 3.8.2.3.1
+    pub fn off(&mut self) -> Option<OnOffEvent> {
+        self.turn_off()
+    }
+
+    /// Handles the On command.
+    ///
+    /// Returns an event if the state changed.
+    ///
+    /// See Section 3.8.2.3.2
+    pub fn on(&mut self) -> Option<OnOffEvent> {
+        self.turn_on()
+    }
+
+    /// Handles the Toggle command.
+    ///
+    /// See Section 3.8.2.3.3
+    pub fn toggle(&mut self) -> Option<OnOffEvent> {
+        if self.on_off {
+            self.turn_off()
+        } else {
+            self.turn_on()
+        }
+    }
+
+    /// Handles the Off With Effect command.
+    ///
+    /// If [`Self::global_scene_control`] is set, the global scene must be stored before calling
+    /// this method.
+    ///
+    /// See Section 3.8.2.3.4
+    pub fn off_with_effect(&mut self, command: OffWithEffect) -> Option<OnOffEvent> {
+        self.global_scene_control = false;
+        self.turn_off()
+            .map(|_| OnOffEvent::OffWithEffect(command.effect()))
+    }
+
+    /// Handles the On With Recall Global Scene command.
+    ///
+    /// Returns [`OnOffEvent::RecallGlobalScene`] if the global scene has to be recalled, the
+    /// command is discarded if `GlobalSceneControl` is set.
+    ///
+    /// See Section 3.8.2.3.5
+    pub fn on_with_recall_global_scene(&mut self) -> Option<OnOffEvent> {
+        if self.global_scene_control {
+            return None;
+        }
+        self.global_scene_control = true;
+        if self.on_time == 0 {
+            self.off_wait_time = 0;
+        }
+        Some(OnOffEvent::RecallGlobalScene)
+    }
+
+    /// Handles the On With Timed Off command.
+    ///
+    /// See Section 3.8.2.3.6
+    pub fn on_with_timed_off(&mut self, command: OnWithTimedOff) -> Option<OnOffEvent> {
+        if command
+            .on_off_control
+            .contains(OnOffControl::ACCEPT_ONLY_WHEN_ON)
+            && !self.on_off
+        {
+            return None;
+        }
+
+        if !self.on_off && self.off_wait_time > 0 {
+            // delayed off: only shorten the wait time
+            self.off_wait_time = self.off_wait_time.min(command.off_wait_time);
+            None
+        } else {
+            self.on_time = self.on_time.max(command.on_time);
+            self.off_wait_time = command.off_wait_time;
+            self.set(true)
+        }
+    }
+
+    /// Advances the timers by 1/10th of a second.
+    ///
+    /// Returns [`OnOffEvent::Off`] once `OnTime` reaches zero.
+    pub fn tick(&mut self) -> Option<OnOffEvent> {
+        if self.on_off {
+            if self.on_time == 0 || self.on_time == NO_TIMEOUT {
+                return None;
+            }
+            self.on_time -= 1;
+            if self.on_time == 0 {
+                self.off_wait_time = 0;
+                return self.set(false);
+            }
+        } else if self.off_wait_time > 0 && self.off_wait_time != NO_TIMEOUT {
+            self.off_wait_time -= 1;
+        }
+        None
+    }
+}
+
+impl SceneExtension for OnOffServer {
+    fn cluster_id(&self) -> u16 {
+        CLUSTER_ID
+    }
+
+    fn store_scene(&self, data: &mut [u8]) -> byte::Result<usize> {
+        let offset = &mut 0;
+        data.write_with(
+            offset,
+            OnOffExtensionFieldSet {
+                on_off: u8::from(self.on_off),
+            },
+            (),
+        )?;
+        Ok(*offset)
+    }
+
+    fn recall_scene(&mut self, data: &[u8], _transition_time: u16) {
+        if let Ok((set, _)) = OnOffExtensionFieldSet::try_read(data, ()) {
+            if set.on_off == 0 {
+                self.turn_off();
+            } else {
+                self.turn_on();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::TryRead;
+
+    use super::*;
+    use crate::general::scenes::SceneTable;
+
+    #[test]
+    fn unpack_on_with_timed_off() {
+        // given
+        let input = [0x01, 0x2c, 0x01, 0x0a, 0x00];
+
+        // when
+        let (command, len) =
+            OnWithTimedOff::try_read(&input, ()).expect("Could not read OnWithTimedOff in test");
+
+        // then
+        assert_eq!(len, 5);
+        assert!(command
+            .on_off_control
+            .contains(OnOffControl::ACCEPT_ONLY_WHEN_ON));
+        assert_eq!(command.on_time, 300);
+        assert_eq!(command.off_wait_time, 10);
+    }
+
+    #[test]
+    fn timed_off() {
+        // given
+        let mut server = OnOffServer::new(false);
+        let command = OnWithTimedOff {
+            on_off_control: OnOffControl::default(),
+            on_time: 2,
+            off_wait_time: 3,
+        };
+
+        // when
+        let event = server.on_with_timed_off(command);
+
+        // then
+        assert_eq!(event, Some(OnOffEvent::On));
+        assert_eq!(server.tick(), None);
+        assert_eq!(server.on_time(), 1);
+        assert_eq!(server.tick(), Some(OnOffEvent::Off));
+        assert!(!server.on_off());
+        assert_eq!(server.off_wait_time(), 0);
+    }
+
+    #[test]
+    fn delayed_off_shortens_off_wait_time() {
+        // given
+        let mut server = OnOffServer::new(false);
+        server.set_off_wait_time(5);
+        let command = OnWithTimedOff {
+            on_off_control: OnOffControl::default(),
+            on_time: 10,
+            off_wait_time: 2,
+        };
+
+        // when
+        let event = server.on_with_timed_off(command);
+
+        // then
+        assert_eq!(event, None);
+        assert!(!server.on_off());
+        assert_eq!(server.off_wait_time(), 2);
+        server.tick();
+        server.tick();
+        assert_eq!(server.on_with_timed_off(command), Some(OnOffEvent::On));
+        assert_eq!(
+            server.on_with_timed_off(OnWithTimedOff {
+                on_off_control: OnOffControl(OnOffControl::ACCEPT_ONLY_WHEN_ON),
+                ..command
+            }),
+            None
+        );
+        assert_eq!(server.on_time(), 10);
+    }
+
+    #[test]
+    fn off_with_effect_and_recall_global_scene() {
+        // given
+        let mut scenes = SceneTable::<1>::default();
+        let mut server = OnOffServer::new(true);
+        assert!(server.global_scene_control());
+
+        // when
+        scenes.store_global_scene(&[&server]);
+        let event = server.off_with_effect(OffWithEffect {
+            effect_identifier: EffectIdentifier::DelayedAllOff,
+            effect_variant: 0x05,
+        });
+
+        // then
+        assert_eq!(event, Some(OnOffEvent::OffWithEffect(OffEffect::FadeToOff)));
+        assert!(!server.global_scene_control());
+        assert_eq!(
+            server.on_with_recall_global_scene(),
+            Some(OnOffEvent::RecallGlobalScene)
+        );
+        scenes.recall_global_scene(&mut [&mut server]);
+        assert!(server.on_off());
+        assert_eq!(server.on_with_recall_global_scene(), None);
+        assert_eq!(scenes.scene_count(), 1);
+    }
+
+    #[test]
+    fn start_up_on_off() {
+        assert!(OnOffServer::start_up(Some(StartUpOnOff::Toggle), false).on_off());
+        assert!(!OnOffServer::start_up(Some(StartUpOnOff::Off), true).on_off());
+        assert!(OnOffServer::start_up(None, true).on_off());
+    }
+}
@@ -0,0 +1,424 @@
+//!
+//! 3.16 Poll Control Cluster
+//!
+//! Attributes and commands for managing the polling of sleepy end devices. The device checks in
+//! periodically, the client may then put it into fast poll mode to exchange data.
+//! [`PollIntervals`] enforces the constraints between the intervals, [`PollControlClient`]
+//! tracks the fast poll window of a device on the client side. All intervals are in quarter
+//! seconds.
+use crate::impl_byte;
+use crate::status::ZclStatus;
+
+/// Poll Control cluster identifier
+pub const CLUSTER_ID: u16 = 0x0020;
+
+/// Maximum `CheckInInterval` and `LongPollInterval` (7 days)
+pub const MAX_INTERVAL: u32 = 0x006e_0000;
+
+/// Minimum `LongPollInterval`
+pub const MIN_LONG_POLL_INTERVAL: u32 = 0x0000_0004;
+
+impl_byte! {
+    /// 3.16.4.1 Poll Control Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        CheckInInterval = 0x0000,
+        LongPollInterval = 0x0001,
+        ShortPollInterval = 0x0002,
+        FastPollTimeout = 0x0003,
+        CheckInIntervalMin = 0x0004,
+        LongPollIntervalMin = 0x0005,
+        FastPollTimeoutMax = 0x0006,
+    }
+}
+
+impl_byte! {
+    /// 3.16.5.1 Commands Received
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ReceivedCommandIdentifier {
+        CheckInResponse = 0x00,
+        FastPollStop = 0x01,
+        SetLongPollInterval = 0x02,
+        SetShortPollInterval = 0x03,
+    }
+}
+
+impl_byte! {
+    /// 3.16.5.2 Commands Generated
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum GeneratedCommandIdentifier {
+        CheckIn = 0x00,
+    }
+}
+
+impl_byte! {
+    /// 3.16.5.1.1 Check-in Response Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct CheckInResponse {
+        /// Whether the device should start fast polling
+        #[ctx = ()]
+        pub start_fast_polling: bool,
+        /// Fast poll duration, `0` uses the `FastPollTimeout` attribute
+        pub fast_poll_timeout: u16,
+    }
+}
+
+impl_byte! {
+    /// 3.16.5.1.3 Set Long Poll Interval Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct SetLongPollInterval {
+        /// New `LongPollInterval`
+        pub new_long_poll_interval: u32,
+    }
+}
+
+impl_byte! {
+    /// 3.16.5.1.4 Set Short Poll Interval Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct SetShortPollInterval {
+        /// New `ShortPollInterval`
+        pub new_short_poll_interval: u16,
+    }
+}
+
+/// Poll Control attributes
+///
+/// The setters reject values violating
+/// `CheckInInterval >= LongPollInterval >= ShortPollInterval` or the configured bounds with
+/// `InvalidValue`. A `CheckInInterval` of `0` disables the check-in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PollIntervals {
+    check_in_interval: u32,
+    long_poll_interval: u32,
+    short_poll_interval: u16,
+    fast_poll_timeout: u16,
+    check_in_interval_min: u32,
+    long_poll_interval_min: u32,
+    fast_poll_timeout_max: u16,
+}
+
+impl Default for PollIntervals {
+    /// Defaults of the specification: check-in every hour, long poll every 5 seconds, short
+    /// poll twice a second and fast poll for 10 seconds.
+    fn default() -> Self {
+        Self {
+            check_in_interval: 0x3840,
+            long_poll_interval: 0x14,
+            short_poll_interval: 0x02,
+            fast_poll_timeout: 0x28,
+            check_in_interval_min: 0,
+            long_poll_interval_min: 0,
+            fast_poll_timeout_max: 0,
+        }
+    }
+}
+
+impl PollIntervals {
+    /// Returns the `CheckInInterval` attribute.
+    pub fn check_in_interval(&self) -> u32 {
+        self.check_in_interval
+    }
+
+    /// Returns the `LongPollInterval` attribute.
+    pub fn long_poll_interval(&self) -> u32 {
+        self.long_poll_interval
+    }
+
+    /// Returns the `ShortPollInterval` attribute.
+    pub fn short_poll_interval(&self) -> u16 {
+        self.short_poll_interval
+    }
+
+    /// Returns the `FastPollTimeout` attribute.
+    pub fn fast_poll_timeout(&self) -> u16 {
+        self.fast_poll_timeout
+    }
+
+    /// Returns the `CheckInIntervalMin` attribute.
+    pub fn check_in_interval_min(&self) -> u32 {
+        self.check_in_interval_min
+    }
+
+    /// Returns the `LongPollIntervalMin` attribute.
+    pub fn long_poll_interval_min(&self) -> u32 {
+        self.long_poll_interval_min
+    }
+
+    /// Returns the `FastPollTimeoutMax` attribute.
+    pub fn fast_poll_timeout_max(&self) -> u16 {
+        self.fast_poll_timeout_max
+    }
+
+    /// Sets the `CheckInInterval` attribute.
+    pub fn set_check_in_interval(&mut self, value: u32) -> ZclStatus {
+        let valid = value == 0
+            || (value <= MAX_INTERVAL
+                && value >= self.check_in_interval_min
+                && value >= self.long_poll_interval);
+        self.update(valid, |intervals| intervals.check_in_interval = value)
+    }
+
+    /// Sets the `LongPollInterval` attribute, also used for the Set Long Poll Interval command.
+    ///
+    /// See Section 3.16.5.1.3
+    pub fn set_long_poll_interval(&mut self, value: u32) -> ZclStatus {
+        let valid = (MIN_LONG_POLL_INTERVAL..=MAX_INTERVAL).contains(&value)
+            && value >= self.long_poll_interval_min
+            && value >= u32::from(self.short_poll_interval)
+            && (self.check_in_interval == 0 || value <= self.check_in_interval);
+        self.update(valid, |intervals| intervals.long_poll_interval = value)
+    }
+
+    /// Sets the `ShortPollInterval` attribute, also used for the Set Short Poll Interval
+    /// command.
+    ///
+    /// See Section 3.16.5.1.4
+    pub fn set_short_poll_interval(&mut self, value: u16) -> ZclStatus {
+        let valid = value != 0 && u32::from(value) <= self.long_poll_interval;
+        self.update(valid, |intervals| intervals.short_poll_interval = value)
+    }
+
+    /// Sets the `FastPollTimeout` attribute.
+    pub fn set_fast_poll_timeout(&mut self, value: u16) -> ZclStatus {
+        let valid = value != 0 && self.fast_poll_timeout_allowed(value);
+        self.update(valid, |intervals| intervals.fast_poll_timeout = value)
+    }
+
+    /// Sets the `CheckInIntervalMin` attribute, the `CheckInInterval` is raised to the new
+    /// minimum if enabled.
+    pub fn set_check_in_interval_min(&mut self, value: u32) {
+        self.check_in_interval_min = value.min(MAX_INTERVAL);
+        if self.check_in_interval != 0 {
+            self.check_in_interval = self.check_in_interval.max(self.check_in_interval_min);
+        }
+    }
+
+    /// Sets the `LongPollIntervalMin` attribute, the `LongPollInterval` is raised to the new
+    /// minimum.
+    pub fn set_long_poll_interval_min(&mut self, value: u32) {
+        self.long_poll_interval_min = value.min(MAX_INTERVAL);
+        self.long_poll_interval = self.long_poll_interval.max(self.long_poll_interval_min);
+        if self.check_in_interval != 0 {
+            self.check_in_interval = self.check_in_interval.max(self.long_poll_interval);
+        }
+    }
+
+    /// Sets the `FastPollTimeoutMax` attribute, `0` disables the limit. The `FastPollTimeout`
+    /// is lowered to the new maximum.
+    pub fn set_fast_poll_timeout_max(&mut self, value: u16) {
+        self.fast_poll_timeout_max = value;
+        if value != 0 {
+            self.fast_poll_timeout = self.fast_poll_timeout.min(value);
+        }
+    }
+
+    /// Returns the fast poll duration requested by a Check-in Response, or `None` if it
+    /// exceeds the `FastPollTimeoutMax` attribute.
+    ///
+    /// See Section 3.16.5.1.1
+    pub fn fast_poll_duration(&self, response: CheckInResponse) -> Option<u16> {
+        match response.fast_poll_timeout {
+            0 => Some(self.fast_poll_timeout),
+            timeout if self.fast_poll_timeout_allowed(timeout) => Some(timeout),
+            _ => None,
+        }
+    }
+
+    fn fast_poll_timeout_allowed(&self, value: u16) -> bool {
+        self.fast_poll_timeout_max == 0 || value <= self.fast_poll_timeout_max
+    }
+
+    fn update(&mut self, valid: bool, set: impl FnOnce(&mut Self)) -> ZclStatus {
+        if valid {
+            set(self);
+            ZclStatus::Success
+        } else {
+            ZclStatus::InvalidValue
+        }
+    }
+}
+
+/// Poll Control client tracking a sleepy end device
+///
+/// The client answers the Check-in commands of the device and keeps track of the fast poll
+/// window, during which the device polls with its `ShortPollInterval` and commands can be
+/// delivered quickly. The application ticks the client every quarter second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PollControlClient {
+    /// Attributes of the device as last read or configured
+    pub intervals: PollIntervals,
+    fast_poll_remaining: u16,
+}
+
+impl PollControlClient {
+    /// Creates a client for a device with the given attributes.
+    pub fn new(intervals: PollIntervals) -> Self {
+        Self {
+            intervals,
+            fast_poll_remaining: 0,
+        }
+    }
+
+    /// Returns `true` while the device is fast polling.
+    pub fn is_fast_polling(&self) -> bool {
+        self.fast_poll_remaining > 0
+    }
+
+    /// Returns the remaining fast poll time.
+    pub fn fast_poll_remaining(&self) -> u16 {
+        self.fast_poll_remaining
+    }
+
+    /// Returns the interval at which the device currently polls its parent.
+    pub fn poll_interval(&self) -> u32 {
+        if self.is_fast_polling() {
+            u32::from(self.intervals.short_poll_interval)
+        } else {
+            self.intervals.long_poll_interval
+        }
+    }
+
+    /// Handles a Check-in command, the device fast polls for `fast_poll_timeout` if there is
+    /// pending work (`None` uses the `FastPollTimeout` attribute).
+    ///
+    /// Returns the Check-in Response to send. The timeout is limited to the
+    /// `FastPollTimeoutMax` attribute of the device.
+    ///
+    /// See Section 3.16.5.2.1
+    pub fn check_in(
+        &mut self,
+        pending_work: bool,
+        fast_poll_timeout: Option<u16>,
+    ) -> CheckInResponse {
+        let fast_poll_timeout =
+            fast_poll_timeout.map_or(0, |timeout| match self.intervals.fast_poll_timeout_max {
+                0 => timeout.max(1),
+                max => timeout.clamp(1, max),
+            });
+        let response = CheckInResponse {
+            start_fast_polling: pending_work,
+            fast_poll_timeout,
+        };
+        if pending_work {
+            self.fast_poll_remaining = self.intervals.fast_poll_duration(response).unwrap_or(0);
+        }
+
+        response
+    }
+
+    /// Ends the fast poll window early once the pending work is done.
+    ///
+    /// Returns `false` if the device is not fast polling and no Fast Poll Stop command needs to
+    /// be sent.
+    pub fn fast_poll_stop(&mut self) -> bool {
+        let fast_polling = self.is_fast_polling();
+        self.fast_poll_remaining = 0;
+        fast_polling
+    }
+
+    /// Returns the Set Long Poll Interval command to send, or `None` if the device would reject
+    /// the interval.
+    pub fn set_long_poll_interval(&mut self, value: u32) -> Option<SetLongPollInterval> {
+        self.intervals
+            .set_long_poll_interval(value)
+            .is_success()
+            .then(|| SetLongPollInterval {
+                new_long_poll_interval: value,
+            })
+    }
+
+    /// Returns the Set Short Poll Interval command to send, or `None` if the device would reject
+    /// the interval.
+    pub fn set_short_poll_interval(&mut self, value: u16) -> Option<SetShortPollInterval> {
+        self.intervals
+            .set_short_poll_interval(value)
+            .is_success()
+            .then(|| SetShortPollInterval {
+                new_short_poll_interval: value,
+            })
+    }
+
+    /// Advances the fast poll window by a quarter second.
+    pub fn tick(&mut self) {
+        self.fast_poll_remaining = self.fast_poll_remaining.saturating_sub(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::TryRead;
+
+    use super::*;
+
+    #[test]
+    fn unpack_check_in_response() {
+        // given
+        let input = [0x01, 0x28, 0x00];
+
+        // when
+        let (response, len) =
+            CheckInResponse::try_read(&input, ()).expect("Could not read CheckInResponse in test");
+
+        // then
+        assert_eq!(len, input.len());
+        assert!(response.start_fast_polling);
+        assert_eq!(response.fast_poll_timeout, 40);
+    }
+
+    #[test]
+    fn interval_constraints() {
+        // given
+        let mut intervals = PollIntervals::default();
+        intervals.set_long_poll_interval_min(0x28);
+
+        // when
+        let too_short = intervals.set_long_poll_interval(0x14);
+        let valid = intervals.set_long_poll_interval(0x50);
+
+        // then
+        assert_eq!(too_short, ZclStatus::InvalidValue);
+        assert_eq!(valid, ZclStatus::Success);
+        assert_eq!(
+            intervals.set_short_poll_interval(0x51),
+            ZclStatus::InvalidValue
+        );
+        assert_eq!(
+            intervals.set_check_in_interval(0x40),
+            ZclStatus::InvalidValue
+        );
+        assert_eq!(intervals.set_check_in_interval(0), ZclStatus::Success);
+        assert_eq!(
+            intervals.set_long_poll_interval(MAX_INTERVAL + 1),
+            ZclStatus::InvalidValue
+        );
+    }
+
+    #[test]
+    fn fast_poll_window() {
+        // given
+        let mut intervals = PollIntervals::default();
+        intervals.set_fast_poll_timeout_max(8);
+        let mut client = PollControlClient::new(intervals);
+
+        // when
+        let response = client.check_in(true, Some(100));
+
+        // then
+        assert_eq!(response.fast_poll_timeout, 8);
+        assert!(client.is_fast_polling());
+        assert_eq!(client.poll_interval(), 2);
+        for _ in 0..8 {
+            client.tick();
+        }
+        assert!(!client.is_fast_polling());
+        assert_eq!(client.poll_interval(), 20);
+        assert!(!client.check_in(false, None).start_fast_polling);
+        assert!(!client.fast_poll_stop());
+    }
+}
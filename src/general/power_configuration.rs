@@ -0,0 +1,462 @@
+//!
+//! 3.3 Power Configuration Cluster
+//!
+//! Attributes for determining detailed information about a device's power source(s) and for
+//! configuring under/over voltage alarms. Up to three battery sources are supported, each with its
+//! own information and settings attribute set.
+use crate::impl_byte;
+
+/// Power Configuration cluster identifier
+pub const CLUSTER_ID: u16 = 0x0001;
+
+impl_byte! {
+    /// 3.3.2.2 Power Configuration Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        // Mains Information
+        MainsVoltage = 0x0000,
+        MainsFrequency = 0x0001,
+        // Mains Settings
+        MainsAlarmMask = 0x0010,
+        MainsVoltageMinThreshold = 0x0011,
+        MainsVoltageMaxThreshold = 0x0012,
+        MainsVoltageDwellTripPoint = 0x0013,
+        // Battery Information
+        BatteryVoltage = 0x0020,
+        BatteryPercentageRemaining = 0x0021,
+        // Battery Settings
+        BatteryManufacturer = 0x0030,
+        BatterySize = 0x0031,
+        BatteryAHrRating = 0x0032,
+        BatteryQuantity = 0x0033,
+        BatteryRatedVoltage = 0x0034,
+        BatteryAlarmMask = 0x0035,
+        BatteryVoltageMinThreshold = 0x0036,
+        BatteryVoltageThreshold1 = 0x0037,
+        BatteryVoltageThreshold2 = 0x0038,
+        BatteryVoltageThreshold3 = 0x0039,
+        BatteryPercentageMinThreshold = 0x003A,
+        BatteryPercentageThreshold1 = 0x003B,
+        BatteryPercentageThreshold2 = 0x003C,
+        BatteryPercentageThreshold3 = 0x003D,
+        BatteryAlarmState = 0x003E,
+        // Battery Information 2
+        Battery2Voltage = 0x0040,
+        Battery2PercentageRemaining = 0x0041,
+        // Battery Settings 2
+        Battery2Manufacturer = 0x0050,
+        Battery2Size = 0x0051,
+        Battery2AHrRating = 0x0052,
+        Battery2Quantity = 0x0053,
+        Battery2RatedVoltage = 0x0054,
+        Battery2AlarmMask = 0x0055,
+        Battery2VoltageMinThreshold = 0x0056,
+        Battery2VoltageThreshold1 = 0x0057,
+        Battery2VoltageThreshold2 = 0x0058,
+        Battery2VoltageThreshold3 = 0x0059,
+        Battery2PercentageMinThreshold = 0x005A,
+        Battery2PercentageThreshold1 = 0x005B,
+        Battery2PercentageThreshold2 = 0x005C,
+        Battery2PercentageThreshold3 = 0x005D,
+        Battery2AlarmState = 0x005E,
+        // Battery Information 3
+        Battery3Voltage = 0x0060,
+        Battery3PercentageRemaining = 0x0061,
+        // Battery Settings 3
+        Battery3Manufacturer = 0x0070,
+        Battery3Size = 0x0071,
+        Battery3AHrRating = 0x0072,
+        Battery3Quantity = 0x0073,
+        Battery3RatedVoltage = 0x0074,
+        Battery3AlarmMask = 0x0075,
+        Battery3VoltageMinThreshold = 0x0076,
+        Battery3VoltageThreshold1 = 0x0077,
+        Battery3VoltageThreshold2 = 0x0078,
+        Battery3VoltageThreshold3 = 0x0079,
+        Battery3PercentageMinThreshold = 0x007A,
+        Battery3PercentageThreshold1 = 0x007B,
+        Battery3PercentageThreshold2 = 0x007C,
+        Battery3PercentageThreshold3 = 0x007D,
+        Battery3AlarmState = 0x007E,
+    }
+}
+
+/// Battery source, each source has its own information and settings attribute set.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatterySource {
+    One,
+    Two,
+    Three,
+}
+
+impl BatterySource {
+    /// Offset of the attribute identifiers of this source relative to the first source.
+    fn attribute_offset(self) -> u16 {
+        match self {
+            Self::One => 0x0000,
+            Self::Two => 0x0020,
+            Self::Three => 0x0040,
+        }
+    }
+
+    /// First alarm code of this source for the Alarms cluster.
+    fn alarm_code_offset(self) -> u8 {
+        match self {
+            Self::One => 0x10,
+            Self::Two => 0x20,
+            Self::Three => 0x30,
+        }
+    }
+
+    /// Offset of the alarm bits of this source within the `BatteryAlarmState` attribute.
+    fn alarm_state_offset(self) -> u32 {
+        match self {
+            Self::One => 0,
+            Self::Two => 10,
+            Self::Three => 20,
+        }
+    }
+}
+
+impl AttributeIdentifier {
+    /// Returns the identifier of the same battery attribute for another battery source.
+    ///
+    /// Returns `None` for mains attributes.
+    pub fn with_battery_source(self, source: BatterySource) -> Option<Self> {
+        use core::convert::TryFrom;
+
+        let id = self as u16;
+        if id < Self::BatteryVoltage as u16 {
+            return None;
+        }
+        let base = (id - Self::BatteryVoltage as u16) % 0x0020 + Self::BatteryVoltage as u16;
+        Self::try_from(base + source.attribute_offset()).ok()
+    }
+}
+
+impl_byte! {
+    /// 3.3.2.2.4.2 `BatterySize` Attribute
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BatterySize {
+        NoBattery = 0x00,
+        BuiltIn = 0x01,
+        Other = 0x02,
+        Aa = 0x03,
+        Aaa = 0x04,
+        C = 0x05,
+        D = 0x06,
+        Cr2 = 0x07,
+        Cr123A = 0x08,
+        Unknown = 0xff,
+    }
+}
+
+impl_byte! {
+    /// 3.3.2.2.2.1 `MainsAlarmMask` Attribute
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct MainsAlarmMask(pub u8);
+}
+
+impl MainsAlarmMask {
+    /// Mains voltage too low
+    pub const VOLTAGE_TOO_LOW: u8 = 1 << 0;
+    /// Mains voltage too high
+    pub const VOLTAGE_TOO_HIGH: u8 = 1 << 1;
+    /// Mains power supply lost/unavailable
+    pub const POWER_SUPPLY_LOST: u8 = 1 << 2;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u8) -> bool {
+        self.0 & flags == flags
+    }
+
+    /// Returns the alarm codes of the set alarms for the Alarms cluster.
+    ///
+    /// See Section 3.3.2.2.4.7 (Values can be found in Table 3-21)
+    pub fn alarm_codes(self) -> impl Iterator<Item = u8> {
+        [
+            (Self::VOLTAGE_TOO_LOW, 0x00),
+            (Self::VOLTAGE_TOO_HIGH, 0x01),
+            (Self::POWER_SUPPLY_LOST, 0x3a),
+        ]
+        .iter()
+        .filter(move |(flag, _)| self.contains(*flag))
+        .map(|(_, code)| *code)
+    }
+}
+
+impl_byte! {
+    /// 3.3.2.2.4.7 `BatteryAlarmMask` Attribute
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct BatteryAlarmMask(pub u8);
+}
+
+impl BatteryAlarmMask {
+    /// Battery voltage (or percentage) too low to continue operating the device's radio
+    pub const BATTERY_TOO_LOW: u8 = 1 << 0;
+    /// Alarm 1 (`BatteryVoltageThreshold1` or `BatteryPercentageThreshold1` reached)
+    pub const ALARM1: u8 = 1 << 1;
+    /// Alarm 2 (`BatteryVoltageThreshold2` or `BatteryPercentageThreshold2` reached)
+    pub const ALARM2: u8 = 1 << 2;
+    /// Alarm 3 (`BatteryVoltageThreshold3` or `BatteryPercentageThreshold3` reached)
+    pub const ALARM3: u8 = 1 << 3;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u8) -> bool {
+        self.0 & flags == flags
+    }
+
+    /// Returns the alarm codes of the set alarms of a battery source for the Alarms cluster.
+    ///
+    /// See Section 3.3.2.2.4.7 (Values can be found in Table 3-21)
+    pub fn alarm_codes(self, source: BatterySource) -> impl Iterator<Item = u8> {
+        (0..4)
+            .filter(move |bit| self.contains(1 << bit))
+            .map(move |bit| source.alarm_code_offset() + bit)
+    }
+}
+
+impl_byte! {
+    /// 3.3.2.2.4.15 `BatteryAlarmState` Attribute
+    ///
+    /// Holds the active alarms of all battery sources and the mains power supply.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct BatteryAlarmState(pub u32);
+}
+
+impl BatteryAlarmState {
+    /// Mains power supply lost/unavailable
+    pub const MAINS_POWER_SUPPLY_LOST: u32 = 1 << 30;
+
+    /// Returns the active alarms of a battery source, using the bits of [`BatteryAlarmMask`].
+    pub fn battery(self, source: BatterySource) -> BatteryAlarmMask {
+        #[allow(clippy::cast_possible_truncation)]
+        let bits = (self.0 >> source.alarm_state_offset()) as u8 & mask::BATTERY_ALARMS;
+        BatteryAlarmMask(bits)
+    }
+
+    /// Replaces the active alarms of a battery source.
+    pub fn set_battery(&mut self, source: BatterySource, alarms: BatteryAlarmMask) {
+        let offset = source.alarm_state_offset();
+        self.0 &= !(u32::from(mask::BATTERY_ALARMS) << offset);
+        self.0 |= u32::from(alarms.0 & mask::BATTERY_ALARMS) << offset;
+    }
+
+    /// Returns `true` if the mains power supply is lost or unavailable.
+    pub fn mains_power_supply_lost(self) -> bool {
+        self.0 & Self::MAINS_POWER_SUPPLY_LOST != 0
+    }
+}
+
+mod mask {
+    pub(super) const BATTERY_ALARMS: u8 = 0b0000_1111; // 4 bits
+}
+
+/// Value indicating an invalid or unknown battery voltage or percentage
+const INVALID: u8 = 0xff;
+
+/// Converts `MainsVoltage` (100 mV units) into volts.
+pub fn mains_voltage(raw: u16) -> Option<f32> {
+    if raw == 0xffff {
+        None
+    } else {
+        Some(f32::from(raw) / 10.0)
+    }
+}
+
+/// Converts `MainsFrequency` (0.5 Hz units) into Hz.
+///
+/// Returns `None` if the frequency is not measurable or too high to measure.
+pub fn mains_frequency(raw: u8) -> Option<f32> {
+    match raw {
+        0x00 | 0xff => None,
+        raw => Some(f32::from(raw) / 2.0),
+    }
+}
+
+/// Converts `BatteryVoltage` (100 mV units) into volts.
+pub fn battery_voltage(raw: u8) -> Option<f32> {
+    if raw == INVALID {
+        None
+    } else {
+        Some(f32::from(raw) / 10.0)
+    }
+}
+
+/// Converts `BatteryPercentageRemaining` (0.5 % units) into percent.
+pub fn battery_percentage(raw: u8) -> Option<f32> {
+    if raw == INVALID {
+        None
+    } else {
+        Some(f32::from(raw.min(200)) / 2.0)
+    }
+}
+
+/// 3.3.2.2.3 Battery Information Attribute Set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatteryInformation {
+    /// `BatteryVoltage` in units of 100 mV
+    pub voltage: u8,
+    /// `BatteryPercentageRemaining` in units of 0.5 %
+    pub percentage_remaining: u8,
+}
+
+impl Default for BatteryInformation {
+    fn default() -> Self {
+        Self {
+            voltage: INVALID,
+            percentage_remaining: 0,
+        }
+    }
+}
+
+impl BatteryInformation {
+    /// Battery voltage in volts
+    pub fn volts(&self) -> Option<f32> {
+        battery_voltage(self.voltage)
+    }
+
+    /// Remaining battery capacity in percent
+    pub fn percent(&self) -> Option<f32> {
+        battery_percentage(self.percentage_remaining)
+    }
+}
+
+/// 3.3.2.2.4 Battery Settings Attribute Set
+///
+/// Thresholds of `0` are treated as not configured.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatterySettings {
+    pub size: BatterySize,
+    /// `BatteryAHrRating` in units of 10 mAh
+    pub a_hr_rating: u16,
+    pub quantity: u8,
+    /// `BatteryRatedVoltage` in units of 100 mV
+    pub rated_voltage: u8,
+    pub alarm_mask: BatteryAlarmMask,
+    /// `BatteryVoltageMinThreshold` and `BatteryVoltageThreshold1-3` in units of 100 mV
+    pub voltage_thresholds: [u8; 4],
+    /// `BatteryPercentageMinThreshold` and `BatteryPercentageThreshold1-3` in units of 0.5 %
+    pub percentage_thresholds: [u8; 4],
+}
+
+impl Default for BatterySettings {
+    fn default() -> Self {
+        Self {
+            size: BatterySize::Unknown,
+            a_hr_rating: 0,
+            quantity: 0,
+            rated_voltage: 0,
+            alarm_mask: BatteryAlarmMask::default(),
+            voltage_thresholds: [0; 4],
+            percentage_thresholds: [0; 4],
+        }
+    }
+}
+
+impl BatterySettings {
+    /// Evaluates the configured thresholds against the battery information.
+    ///
+    /// An alarm is active if the voltage or percentage remaining is below its threshold and the
+    /// alarm is enabled in the `BatteryAlarmMask`.
+    pub fn alarms(&self, info: &BatteryInformation) -> BatteryAlarmMask {
+        let mut alarms = 0;
+        for i in 0..4 {
+            let voltage = self.voltage_thresholds[i];
+            let percentage = self.percentage_thresholds[i];
+            let voltage_low = info.voltage != INVALID && voltage != 0 && info.voltage < voltage;
+            let percentage_low = info.percentage_remaining != INVALID
+                && percentage != 0
+                && info.percentage_remaining < percentage;
+            if voltage_low || percentage_low {
+                alarms |= 1 << i;
+            }
+        }
+        BatteryAlarmMask(alarms & self.alarm_mask.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::TryRead;
+
+    use super::*;
+
+    #[test]
+    fn convert_battery_information() {
+        // given
+        let info = BatteryInformation {
+            voltage: 29,
+            percentage_remaining: 151,
+        };
+
+        // then
+        assert_eq!(info.volts(), Some(2.9));
+        assert_eq!(info.percent(), Some(75.5));
+        assert_eq!(battery_voltage(0xff), None);
+        assert_eq!(battery_percentage(0xff), None);
+        assert_eq!(mains_voltage(2301), Some(230.1));
+        assert_eq!(mains_frequency(100), Some(50.0));
+    }
+
+    #[test]
+    fn evaluate_battery_alarms() {
+        // given
+        let settings = BatterySettings {
+            size: BatterySize::Cr2,
+            alarm_mask: BatteryAlarmMask(
+                BatteryAlarmMask::BATTERY_TOO_LOW | BatteryAlarmMask::ALARM1,
+            ),
+            voltage_thresholds: [24, 27, 0, 0],
+            percentage_thresholds: [0, 0, 40, 0],
+            ..BatterySettings::default()
+        };
+        let info = BatteryInformation {
+            voltage: 26,
+            percentage_remaining: 30,
+        };
+
+        // when
+        let alarms = settings.alarms(&info);
+
+        // then
+        assert_eq!(alarms, BatteryAlarmMask(BatteryAlarmMask::ALARM1));
+
+        let mut state = BatteryAlarmState::default();
+        state.set_battery(BatterySource::Two, alarms);
+        assert_eq!(state, BatteryAlarmState(0b10 << 10));
+        assert_eq!(state.battery(BatterySource::Two), alarms);
+        assert_eq!(state.battery(BatterySource::One), BatteryAlarmMask(0));
+    }
+
+    #[test]
+    fn unpack_battery_size() {
+        // given
+        let input = [0x04];
+
+        // when
+        let (size, _) =
+            BatterySize::try_read(&input, ()).expect("Could not read BatterySize in test");
+
+        // then
+        assert_eq!(size, BatterySize::Aaa);
+        assert_eq!(
+            AttributeIdentifier::BatteryAlarmMask.with_battery_source(BatterySource::Three),
+            Some(AttributeIdentifier::Battery3AlarmMask)
+        );
+        assert_eq!(
+            AttributeIdentifier::Battery2Voltage.with_battery_source(BatterySource::One),
+            Some(AttributeIdentifier::BatteryVoltage)
+        );
+        assert_eq!(
+            AttributeIdentifier::MainsVoltage.with_battery_source(BatterySource::Two),
+            None
+        );
+    }
+}
@@ -0,0 +1,1316 @@
+//!
+//! 3.7 Scenes Cluster
+//!
+//! Commands to store and recall scenes, i.e. a set of attribute values of other clusters on the
+//! same endpoint (extension field sets). [`SceneTable`] is an allocation free scene table with a
+//! fixed capacity, the attribute values are captured and recalled through the [`SceneExtension`]
+//! trait which is implemented by the cluster servers of this crate.
+use byte::{ctx, BytesExt, TryRead, TryWrite};
+
+use crate::data_types::CharacterString;
+use crate::general::groups::GroupTable;
+use crate::impl_byte;
+use crate::status::ZclStatus;
+
+/// Scenes cluster identifier
+pub const CLUSTER_ID: u16 = 0x0005;
+
+/// Maximum length of a scene name
+pub const SCENE_NAME_MAX_LEN: usize = 16;
+
+/// Maximum length of all extension field sets of a single scene in a [`SceneTable`]
+pub const EXTENSION_FIELD_SETS_MAX_LEN: usize = 32;
+
+/// Group identifier of the global scene
+pub const GLOBAL_SCENE_GROUP_ID: u16 = 0x0000;
+
+/// Scene identifier of the global scene
+pub const GLOBAL_SCENE_ID: u8 = 0x00;
+
+/// Transition time of the Recall Scene command to use the transition time of the scene
+pub const USE_SCENE_TRANSITION_TIME: u16 = 0xffff;
+
+impl_byte! {
+    /// 3.7.2.2 Scenes Server Attributes
+    #[repr(u16)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        /// Number of scenes currently in the scene table.
+        SceneCount = 0x0000,
+        /// Scene identifier of the scene last invoked.
+        CurrentScene = 0x0001,
+        /// Group identifier of the scene last invoked.
+        CurrentGroup = 0x0002,
+        /// Indicates whether the state of the device corresponds to `CurrentScene` and
+        /// `CurrentGroup`.
+        SceneValid = 0x0003,
+        /// Indicates support for scene names.
+        NameSupport = 0x0004,
+        /// IEEE address of the device that last configured the scene table.
+        LastConfiguredBy = 0x0005,
+    }
+}
+
+impl_byte! {
+    /// 3.7.2.4 Commands Received
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ReceivedCommandIdentifier {
+        AddScene = 0x00,
+        ViewScene = 0x01,
+        RemoveScene = 0x02,
+        RemoveAllScenes = 0x03,
+        StoreScene = 0x04,
+        RecallScene = 0x05,
+        GetSceneMembership = 0x06,
+        EnhancedAddScene = 0x40,
+        EnhancedViewScene = 0x41,
+        CopyScene = 0x42,
+    }
+}
+
+impl_byte! {
+    /// 3.7.2.5 Commands Generated
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum GeneratedCommandIdentifier {
+        AddSceneResponse = 0x00,
+        ViewSceneResponse = 0x01,
+        RemoveSceneResponse = 0x02,
+        RemoveAllScenesResponse = 0x03,
+        StoreSceneResponse = 0x04,
+        GetSceneMembershipResponse = 0x06,
+        EnhancedAddSceneResponse = 0x40,
+        EnhancedViewSceneResponse = 0x41,
+        CopySceneResponse = 0x42,
+    }
+}
+
+impl_byte! {
+    /// Extension field set of a scene
+    ///
+    /// Holds the attribute values of another cluster, in the order given by the cluster
+    /// specification and without attribute identifiers.
+    ///
+    /// See Section 3.7.2.4.2
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ExtensionFieldSet<'a> {
+        /// Cluster the attribute values belong to
+        pub cluster_id: u16,
+        /// Length of `data`
+        pub length: u8,
+        /// Attribute values
+        #[ctx = ctx::Bytes::Len(usize::from(length))]
+        pub data: &'a [u8],
+    }
+}
+
+/// Iterates the extension field sets of a raw buffer.
+#[derive(Debug, Clone)]
+pub struct ExtensionFieldSets<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> ExtensionFieldSets<'a> {
+    /// Creates an iterator over the extension field sets in `bytes`.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+}
+
+impl<'a> Iterator for ExtensionFieldSets<'a> {
+    type Item = ExtensionFieldSet<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset >= self.bytes.len() {
+            return None;
+        }
+        self.bytes.read_with(&mut self.offset, ()).ok()
+    }
+}
+
+impl_byte! {
+    /// Extension field set of the On/Off cluster
+    ///
+    /// See Section 3.8.2.4
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct OnOffExtensionFieldSet {
+        /// `OnOff` attribute
+        pub on_off: u8,
+    }
+}
+
+impl_byte! {
+    /// Extension field set of the Level Control cluster
+    ///
+    /// See Section 3.10.2.4
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct LevelControlExtensionFieldSet {
+        /// `CurrentLevel` attribute
+        pub current_level: u8,
+    }
+}
+
+impl_byte! {
+    /// Extension field set of the Color Control cluster
+    ///
+    /// See Section 5.2.2.5
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct ColorControlExtensionFieldSet {
+        pub current_x: u16,
+        pub current_y: u16,
+        pub enhanced_current_hue: u16,
+        pub current_saturation: u8,
+        pub color_loop_active: u8,
+        pub color_loop_direction: u8,
+        pub color_loop_time: u16,
+        pub color_temperature_mireds: u16,
+    }
+}
+
+/// Captures and recalls the attribute values of a cluster server for the scene table.
+///
+/// Implemented by the cluster servers whose attributes are part of a scene (e.g. On/Off, Level
+/// Control and Color Control).
+pub trait SceneExtension {
+    /// Returns the identifier of the cluster.
+    fn cluster_id(&self) -> u16;
+
+    /// Writes the current attribute values as extension field set data into `data` and returns
+    /// the number of bytes written.
+    fn store_scene(&self, data: &mut [u8]) -> byte::Result<usize>;
+
+    /// Applies the attribute values of an extension field set within the given transition time
+    /// (1/10th of a second).
+    fn recall_scene(&mut self, data: &[u8], transition_time: u16);
+}
+
+impl_byte! {
+    /// 3.7.2.4.3 View Scene Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ViewScene {
+        /// Group of the scene
+        pub group_id: u16,
+        /// Scene to view
+        pub scene_id: u8,
+    }
+}
+
+/// 3.7.2.4.4 Remove Scene Command
+pub type RemoveScene = ViewScene;
+
+/// 3.7.2.4.6 Store Scene Command
+pub type StoreScene = ViewScene;
+
+/// 3.7.2.4.10 Enhanced View Scene Command
+pub type EnhancedViewScene = ViewScene;
+
+impl_byte! {
+    /// 3.7.2.4.5 Remove All Scenes Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct RemoveAllScenes {
+        /// Group of the scenes to remove
+        pub group_id: u16,
+    }
+}
+
+/// 3.7.2.4.8 Get Scene Membership Command
+pub type GetSceneMembership = RemoveAllScenes;
+
+/// 3.7.2.4.2 Add Scene Command
+///
+/// The same payload is used by the Enhanced Add Scene command, which specifies the transition
+/// time in 1/10th of a second instead of seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddScene<'a> {
+    /// Group of the scene
+    pub group_id: u16,
+    /// Scene to add
+    pub scene_id: u8,
+    /// Transition time
+    pub transition_time: u16,
+    /// Name of the scene
+    pub scene_name: CharacterString<'a>,
+    /// Raw extension field sets, see [`Self::extension_field_sets`]
+    pub extension_field_sets: &'a [u8],
+}
+
+/// 3.7.2.4.9 Enhanced Add Scene Command
+pub type EnhancedAddScene<'a> = AddScene<'a>;
+
+impl<'a> AddScene<'a> {
+    /// Iterates the extension field sets.
+    pub fn extension_field_sets(&self) -> ExtensionFieldSets<'a> {
+        ExtensionFieldSets::new(self.extension_field_sets)
+    }
+}
+
+impl<'a> TryRead<'a, ()> for AddScene<'a> {
+    fn try_read(bytes: &'a [u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let group_id = bytes.read_with(offset, byte::LE)?;
+        let scene_id = bytes.read_with(offset, byte::LE)?;
+        let transition_time = bytes.read_with(offset, byte::LE)?;
+        let scene_name = bytes.read_with(offset, ())?;
+        let extension_field_sets =
+            bytes.read_with(offset, ctx::Bytes::Len(bytes.len() - *offset))?;
+
+        Ok((
+            Self {
+                group_id,
+                scene_id,
+                transition_time,
+                scene_name,
+                extension_field_sets,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl TryWrite for AddScene<'_> {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, self.group_id, byte::LE)?;
+        bytes.write_with(offset, self.scene_id, byte::LE)?;
+        bytes.write_with(offset, self.transition_time, byte::LE)?;
+        bytes.write_with(offset, self.scene_name, ())?;
+        bytes.write(offset, self.extension_field_sets)?;
+
+        Ok(*offset)
+    }
+}
+
+/// 3.7.2.4.7 Recall Scene Command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecallScene {
+    /// Group of the scene
+    pub group_id: u16,
+    /// Scene to recall
+    pub scene_id: u8,
+    /// Optional transition time in 1/10th of a second, overrides the transition time of the
+    /// scene unless it is [`USE_SCENE_TRANSITION_TIME`]
+    pub transition_time: Option<u16>,
+}
+
+impl TryRead<'_, ()> for RecallScene {
+    fn try_read(bytes: &[u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let group_id = bytes.read_with(offset, byte::LE)?;
+        let scene_id = bytes.read_with(offset, byte::LE)?;
+        let transition_time = if bytes.len() - *offset >= 2 {
+            Some(bytes.read_with(offset, byte::LE)?)
+        } else {
+            None
+        };
+
+        Ok((
+            Self {
+                group_id,
+                scene_id,
+                transition_time,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl TryWrite for RecallScene {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, self.group_id, byte::LE)?;
+        bytes.write_with(offset, self.scene_id, byte::LE)?;
+        if let Some(transition_time) = self.transition_time {
+            bytes.write_with(offset, transition_time, byte::LE)?;
+        }
+
+        Ok(*offset)
+    }
+}
+
+impl_byte! {
+    /// 3.7.2.4.11 Copy Scene Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct CopyScene {
+        /// Copy mode, see [`CopyScene::COPY_ALL_SCENES`]
+        pub mode: u8,
+        /// Source group
+        pub group_id_from: u16,
+        /// Source scene, ignored when copying all scenes
+        pub scene_id_from: u8,
+        /// Destination group
+        pub group_id_to: u16,
+        /// Destination scene, ignored when copying all scenes
+        pub scene_id_to: u8,
+    }
+}
+
+impl CopyScene {
+    /// Copies all scenes of `group_id_from` to `group_id_to`
+    pub const COPY_ALL_SCENES: u8 = 0x01;
+
+    /// Returns `true` if all scenes of the group shall be copied.
+    pub fn copy_all_scenes(&self) -> bool {
+        self.mode & Self::COPY_ALL_SCENES != 0
+    }
+}
+
+impl_byte! {
+    /// 3.7.2.5.1 Add Scene Response Command
+    ///
+    /// Also used for the Remove Scene, Store Scene and Enhanced Add Scene responses.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct AddSceneResponse {
+        /// Status of the command
+        pub status: ZclStatus,
+        /// Group of the scene
+        pub group_id: u16,
+        /// Scene of the command
+        pub scene_id: u8,
+    }
+}
+
+/// 3.7.2.5.3 Remove Scene Response Command
+pub type RemoveSceneResponse = AddSceneResponse;
+
+/// 3.7.2.5.5 Store Scene Response Command
+pub type StoreSceneResponse = AddSceneResponse;
+
+/// 3.7.2.5.7 Enhanced Add Scene Response Command
+pub type EnhancedAddSceneResponse = AddSceneResponse;
+
+impl_byte! {
+    /// 3.7.2.5.4 Remove All Scenes Response Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct RemoveAllScenesResponse {
+        /// Status of the command
+        pub status: ZclStatus,
+        /// Group of the removed scenes
+        pub group_id: u16,
+    }
+}
+
+impl_byte! {
+    /// 3.7.2.5.9 Copy Scene Response Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct CopySceneResponse {
+        /// Status of the command
+        pub status: ZclStatus,
+        /// Source group
+        pub group_id_from: u16,
+        /// Source scene
+        pub scene_id_from: u8,
+    }
+}
+
+/// 3.7.2.5.2 View Scene Response Command
+///
+/// The same payload is used by the Enhanced View Scene Response command, which specifies the
+/// transition time in 1/10th of a second instead of seconds. The scene fields are only present
+/// on success.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ViewSceneResponse<'a> {
+    /// Status of the command
+    pub status: ZclStatus,
+    /// Group of the scene
+    pub group_id: u16,
+    /// Scene of the command
+    pub scene_id: u8,
+    /// Transition time
+    pub transition_time: u16,
+    /// Name of the scene
+    pub scene_name: CharacterString<'a>,
+    /// Raw extension field sets, see [`Self::extension_field_sets`]
+    pub extension_field_sets: &'a [u8],
+}
+
+/// 3.7.2.5.8 Enhanced View Scene Response Command
+pub type EnhancedViewSceneResponse<'a> = ViewSceneResponse<'a>;
+
+impl<'a> ViewSceneResponse<'a> {
+    fn failure(status: ZclStatus, group_id: u16, scene_id: u8) -> Self {
+        Self {
+            status,
+            group_id,
+            scene_id,
+            transition_time: 0,
+            scene_name: CharacterString::default(),
+            extension_field_sets: &[],
+        }
+    }
+
+    /// Iterates the extension field sets.
+    pub fn extension_field_sets(&self) -> ExtensionFieldSets<'a> {
+        ExtensionFieldSets::new(self.extension_field_sets)
+    }
+}
+
+impl<'a> TryRead<'a, ()> for ViewSceneResponse<'a> {
+    fn try_read(bytes: &'a [u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let status = bytes.read_with(offset, byte::LE)?;
+        let group_id = bytes.read_with(offset, byte::LE)?;
+        let scene_id = bytes.read_with(offset, byte::LE)?;
+        let mut response = Self::failure(status, group_id, scene_id);
+        if status == ZclStatus::Success {
+            response.transition_time = bytes.read_with(offset, byte::LE)?;
+            response.scene_name = bytes.read_with(offset, ())?;
+            response.extension_field_sets =
+                bytes.read_with(offset, ctx::Bytes::Len(bytes.len() - *offset))?;
+        }
+
+        Ok((response, *offset))
+    }
+}
+
+impl TryWrite for ViewSceneResponse<'_> {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, self.status, byte::LE)?;
+        bytes.write_with(offset, self.group_id, byte::LE)?;
+        bytes.write_with(offset, self.scene_id, byte::LE)?;
+        if self.status == ZclStatus::Success {
+            bytes.write_with(offset, self.transition_time, byte::LE)?;
+            bytes.write_with(offset, self.scene_name, ())?;
+            bytes.write(offset, self.extension_field_sets)?;
+        }
+
+        Ok(*offset)
+    }
+}
+
+/// 3.7.2.5.6 Get Scene Membership Response Command
+///
+/// The scene list is only present on success.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GetSceneMembershipResponse<'a> {
+    /// Status of the command
+    pub status: ZclStatus,
+    /// Remaining capacity of the scene table
+    ///
+    /// `0xfe` means at least one further scene can be added, `0xff` means unknown.
+    pub capacity: u8,
+    /// Group of the scenes
+    pub group_id: u16,
+    /// Scenes of the group
+    pub scene_list: &'a [u8],
+}
+
+impl<'a> TryRead<'a, ()> for GetSceneMembershipResponse<'a> {
+    fn try_read(bytes: &'a [u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let status = bytes.read_with(offset, byte::LE)?;
+        let capacity = bytes.read_with(offset, byte::LE)?;
+        let group_id = bytes.read_with(offset, byte::LE)?;
+        let scene_list: &[u8] = if status == ZclStatus::Success {
+            let scene_count: u8 = bytes.read_with(offset, byte::LE)?;
+            bytes.read_with(offset, ctx::Bytes::Len(usize::from(scene_count)))?
+        } else {
+            &[]
+        };
+
+        Ok((
+            Self {
+                status,
+                capacity,
+                group_id,
+                scene_list,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl TryWrite for GetSceneMembershipResponse<'_> {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, self.status, byte::LE)?;
+        bytes.write_with(offset, self.capacity, byte::LE)?;
+        bytes.write_with(offset, self.group_id, byte::LE)?;
+        if self.status == ZclStatus::Success {
+            #[allow(clippy::cast_possible_truncation)]
+            bytes.write_with(offset, self.scene_list.len() as u8, byte::LE)?;
+            bytes.write(offset, self.scene_list)?;
+        }
+
+        Ok(*offset)
+    }
+}
+
+/// Entry of a [`SceneTable`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SceneEntry {
+    group_id: u16,
+    scene_id: u8,
+    name: [u8; SCENE_NAME_MAX_LEN],
+    name_len: usize,
+    /// Transition time in 1/10th of a second
+    transition_time: u32,
+    extension_field_sets: [u8; EXTENSION_FIELD_SETS_MAX_LEN],
+    extension_field_sets_len: usize,
+}
+
+impl SceneEntry {
+    fn new(group_id: u16, scene_id: u8) -> Self {
+        Self {
+            group_id,
+            scene_id,
+            name: [0; SCENE_NAME_MAX_LEN],
+            name_len: 0,
+            transition_time: 0,
+            extension_field_sets: [0; EXTENSION_FIELD_SETS_MAX_LEN],
+            extension_field_sets_len: 0,
+        }
+    }
+
+    fn name(&self) -> CharacterString<'_> {
+        let name = core::str::from_utf8(&self.name[..self.name_len]).unwrap_or_default();
+        CharacterString::new(name).unwrap_or_default()
+    }
+
+    fn transition_time(&self, enhanced: bool) -> u16 {
+        let time = if enhanced {
+            self.transition_time
+        } else {
+            self.transition_time / 10
+        };
+        #[allow(clippy::cast_possible_truncation)]
+        let time = time.min(u32::from(u16::MAX)) as u16;
+        time
+    }
+}
+
+/// Returns `true` if the group is `0x0000` (no group) or part of the group table.
+fn is_known_group<const G: usize>(groups: &GroupTable<G>, group_id: u16) -> bool {
+    group_id == 0x0000 || groups.contains(group_id)
+}
+
+/// Scene table of an endpoint with a capacity of `N` scenes
+///
+/// Holds the scene attributes and implements the server side of the Scenes cluster, the command
+/// handlers return the response to send. Group identifiers are validated against the
+/// [`GroupTable`] of the endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SceneTable<const N: usize> {
+    entries: [Option<SceneEntry>; N],
+    name_support: bool,
+    current_scene: u8,
+    current_group: u16,
+    scene_valid: bool,
+}
+
+impl<const N: usize> Default for SceneTable<N> {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+impl<const N: usize> SceneTable<N> {
+    /// Creates an empty scene table.
+    ///
+    /// If `name_support` is set, scene names are stored (up to 16 characters).
+    pub fn new(name_support: bool) -> Self {
+        Self {
+            entries: [None; N],
+            name_support,
+            current_scene: 0,
+            current_group: 0,
+            scene_valid: false,
+        }
+    }
+
+    /// Returns the `SceneCount` attribute.
+    pub fn scene_count(&self) -> u8 {
+        #[allow(clippy::cast_possible_truncation)]
+        let count = self.entries.iter().flatten().count().min(0xff) as u8;
+        count
+    }
+
+    /// Returns the `CurrentScene` attribute.
+    pub fn current_scene(&self) -> u8 {
+        self.current_scene
+    }
+
+    /// Returns the `CurrentGroup` attribute.
+    pub fn current_group(&self) -> u16 {
+        self.current_group
+    }
+
+    /// Returns the `SceneValid` attribute.
+    pub fn scene_valid(&self) -> bool {
+        self.scene_valid
+    }
+
+    /// Clears the `SceneValid` attribute, must be called when an attribute of a scene changes.
+    pub fn invalidate(&mut self) {
+        self.scene_valid = false;
+    }
+
+    /// Returns the `NameSupport` attribute.
+    pub fn name_support(&self) -> u8 {
+        if self.name_support {
+            0x80
+        } else {
+            0x00
+        }
+    }
+
+    /// Returns the remaining capacity as reported in the Get Scene Membership Response.
+    pub fn capacity(&self) -> u8 {
+        let remaining = N - self.entries.iter().flatten().count();
+        #[allow(clippy::cast_possible_truncation)]
+        if remaining >= 0xfe {
+            0xfe
+        } else {
+            remaining as u8
+        }
+    }
+
+    fn position(&self, group_id: u16, scene_id: u8) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|e| e.map_or(false, |e| e.group_id == group_id && e.scene_id == scene_id))
+    }
+
+    fn entry(&self, group_id: u16, scene_id: u8) -> Option<&SceneEntry> {
+        self.position(group_id, scene_id)
+            .and_then(|i| self.entries[i].as_ref())
+    }
+
+    /// Inserts or replaces a scene, returns `false` if the table is full.
+    fn insert(&mut self, entry: SceneEntry) -> bool {
+        let slot = self
+            .position(entry.group_id, entry.scene_id)
+            .or_else(|| self.entries.iter().position(Option::is_none));
+        match slot {
+            Some(i) => {
+                self.entries[i] = Some(entry);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn add(&mut self, command: &AddScene<'_>, enhanced: bool) -> ZclStatus {
+        let mut entry = SceneEntry::new(command.group_id, command.scene_id);
+        entry.transition_time = if enhanced {
+            u32::from(command.transition_time)
+        } else {
+            u32::from(command.transition_time) * 10
+        };
+        if self.name_support {
+            let name = command.scene_name.as_str().unwrap_or_default();
+            let mut len = name.len().min(SCENE_NAME_MAX_LEN);
+            while !name.is_char_boundary(len) {
+                len -= 1;
+            }
+            entry.name[..len].copy_from_slice(&name.as_bytes()[..len]);
+            entry.name_len = len;
+        }
+
+        let mut len = 0;
+        let offset = &mut 0;
+        while *offset < command.extension_field_sets.len() {
+            let set: ExtensionFieldSet<'_> =
+                match command.extension_field_sets.read_with(offset, ()) {
+                    Ok(set) => set,
+                    Err(_) => return ZclStatus::MalformedCommand,
+                };
+            let set_len = 3 + set.data.len();
+            if len + set_len > EXTENSION_FIELD_SETS_MAX_LEN {
+                return ZclStatus::InsufficientSpace;
+            }
+            if entry.extension_field_sets[len..]
+                .write_with(&mut 0, set, ())
+                .is_err()
+            {
+                return ZclStatus::InsufficientSpace;
+            }
+            len += set_len;
+        }
+        entry.extension_field_sets_len = len;
+
+        if self.insert(entry) {
+            ZclStatus::Success
+        } else {
+            ZclStatus::InsufficientSpace
+        }
+    }
+
+    /// Handles the Add Scene or, if `enhanced` is set, the Enhanced Add Scene command.
+    ///
+    /// See Section 3.7.2.4.2
+    pub fn add_scene<const G: usize>(
+        &mut self,
+        command: &AddScene<'_>,
+        enhanced: bool,
+        groups: &GroupTable<G>,
+    ) -> AddSceneResponse {
+        let status = if is_known_group(groups, command.group_id) {
+            self.add(command, enhanced)
+        } else {
+            ZclStatus::InvalidField
+        };
+
+        AddSceneResponse {
+            status,
+            group_id: command.group_id,
+            scene_id: command.scene_id,
+        }
+    }
+
+    /// Handles the View Scene or, if `enhanced` is set, the Enhanced View Scene command.
+    ///
+    /// See Section 3.7.2.4.3
+    pub fn view_scene<const G: usize>(
+        &self,
+        command: &ViewScene,
+        enhanced: bool,
+        groups: &GroupTable<G>,
+    ) -> ViewSceneResponse<'_> {
+        if !is_known_group(groups, command.group_id) {
+            return ViewSceneResponse::failure(
+                ZclStatus::InvalidField,
+                command.group_id,
+                command.scene_id,
+            );
+        }
+
+        self.entry(command.group_id, command.scene_id).map_or_else(
+            || ViewSceneResponse::failure(ZclStatus::NotFound, command.group_id, command.scene_id),
+            |entry| ViewSceneResponse {
+                status: ZclStatus::Success,
+                group_id: command.group_id,
+                scene_id: command.scene_id,
+                transition_time: entry.transition_time(enhanced),
+                scene_name: entry.name(),
+                extension_field_sets: &entry.extension_field_sets[..entry.extension_field_sets_len],
+            },
+        )
+    }
+
+    /// Handles the Remove Scene command.
+    ///
+    /// See Section 3.7.2.4.4
+    pub fn remove_scene<const G: usize>(
+        &mut self,
+        command: &RemoveScene,
+        groups: &GroupTable<G>,
+    ) -> RemoveSceneResponse {
+        let status = if !is_known_group(groups, command.group_id) {
+            ZclStatus::InvalidField
+        } else if let Some(i) = self.position(command.group_id, command.scene_id) {
+            self.entries[i] = None;
+            if self.current_group == command.group_id && self.current_scene == command.scene_id {
+                self.scene_valid = false;
+            }
+            ZclStatus::Success
+        } else {
+            ZclStatus::NotFound
+        };
+
+        RemoveSceneResponse {
+            status,
+            group_id: command.group_id,
+            scene_id: command.scene_id,
+        }
+    }
+
+    /// Handles the Remove All Scenes command.
+    ///
+    /// See Section 3.7.2.4.5
+    pub fn remove_all_scenes<const G: usize>(
+        &mut self,
+        command: &RemoveAllScenes,
+        groups: &GroupTable<G>,
+    ) -> RemoveAllScenesResponse {
+        let status = if is_known_group(groups, command.group_id) {
+            for slot in &mut self.entries {
+                if slot.map_or(false, |e| e.group_id == command.group_id) {
+                    *slot = None;
+                }
+            }
+            if self.current_group == command.group_id {
+                self.scene_valid = false;
+            }
+            ZclStatus::Success
+        } else {
+            ZclStatus::InvalidField
+        };
+
+        RemoveAllScenesResponse {
+            status,
+            group_id: command.group_id,
+        }
+    }
+
+    /// Handles the Store Scene command and captures the attribute values of `clusters`.
+    ///
+    /// See Section 3.7.2.4.6
+    pub fn store_scene<const G: usize>(
+        &mut self,
+        command: &StoreScene,
+        groups: &GroupTable<G>,
+        clusters: &[&dyn SceneExtension],
+    ) -> StoreSceneResponse {
+        let status = if is_known_group(groups, command.group_id) {
+            self.store(command.group_id, command.scene_id, clusters)
+        } else {
+            ZclStatus::InvalidField
+        };
+
+        StoreSceneResponse {
+            status,
+            group_id: command.group_id,
+            scene_id: command.scene_id,
+        }
+    }
+
+    fn store(
+        &mut self,
+        group_id: u16,
+        scene_id: u8,
+        clusters: &[&dyn SceneExtension],
+    ) -> ZclStatus {
+        // keep the name and transition time of an existing scene
+        let mut entry = self
+            .entry(group_id, scene_id)
+            .copied()
+            .unwrap_or_else(|| SceneEntry::new(group_id, scene_id));
+
+        let buf = &mut entry.extension_field_sets;
+        let offset = &mut 0;
+        for cluster in clusters {
+            let header = *offset;
+            let data = match buf.get_mut(header + 3..) {
+                Some(data) => data,
+                None => return ZclStatus::InsufficientSpace,
+            };
+            let len = match cluster.store_scene(data) {
+                Ok(len) => len,
+                Err(_) => return ZclStatus::InsufficientSpace,
+            };
+            #[allow(clippy::cast_possible_truncation)]
+            let header_written = buf
+                .write_with(offset, cluster.cluster_id(), byte::LE)
+                .and_then(|_| buf.write_with(offset, len as u8, byte::LE));
+            if header_written.is_err() {
+                return ZclStatus::InsufficientSpace;
+            }
+            *offset += len;
+        }
+        entry.extension_field_sets_len = *offset;
+
+        if !self.insert(entry) {
+            return ZclStatus::InsufficientSpace;
+        }
+        self.current_group = group_id;
+        self.current_scene = scene_id;
+        self.scene_valid = true;
+        ZclStatus::Success
+    }
+
+    /// Handles the Recall Scene command and applies the extension field sets to `clusters`.
+    ///
+    /// The command has no response, the returned status is used for the default response.
+    ///
+    /// See Section 3.7.2.4.7
+    pub fn recall_scene<const G: usize>(
+        &mut self,
+        command: &RecallScene,
+        groups: &GroupTable<G>,
+        clusters: &mut [&mut dyn SceneExtension],
+    ) -> ZclStatus {
+        if !is_known_group(groups, command.group_id) {
+            return ZclStatus::InvalidField;
+        }
+        self.recall(
+            command.group_id,
+            command.scene_id,
+            command.transition_time,
+            clusters,
+        )
+    }
+
+    fn recall(
+        &mut self,
+        group_id: u16,
+        scene_id: u8,
+        transition_time: Option<u16>,
+        clusters: &mut [&mut dyn SceneExtension],
+    ) -> ZclStatus {
+        let entry = match self.entry(group_id, scene_id) {
+            Some(entry) => *entry,
+            None => return ZclStatus::NotFound,
+        };
+
+        let transition_time = match transition_time {
+            Some(time) if time != USE_SCENE_TRANSITION_TIME => time,
+            _ => entry.transition_time(true),
+        };
+        let sets =
+            ExtensionFieldSets::new(&entry.extension_field_sets[..entry.extension_field_sets_len]);
+        for set in sets {
+            if let Some(cluster) = clusters
+                .iter_mut()
+                .find(|c| c.cluster_id() == set.cluster_id)
+            {
+                cluster.recall_scene(set.data, transition_time);
+            }
+        }
+
+        self.current_group = group_id;
+        self.current_scene = scene_id;
+        self.scene_valid = true;
+        ZclStatus::Success
+    }
+
+    /// Captures the attribute values of `clusters` into the global scene.
+    ///
+    /// Used by the Off With Effect command of the On/Off cluster.
+    pub fn store_global_scene(&mut self, clusters: &[&dyn SceneExtension]) -> ZclStatus {
+        self.store(GLOBAL_SCENE_GROUP_ID, GLOBAL_SCENE_ID, clusters)
+    }
+
+    /// Applies the global scene to `clusters`.
+    ///
+    /// Used by the On With Recall Global Scene command of the On/Off cluster.
+    pub fn recall_global_scene(&mut self, clusters: &mut [&mut dyn SceneExtension]) -> ZclStatus {
+        self.recall(GLOBAL_SCENE_GROUP_ID, GLOBAL_SCENE_ID, None, clusters)
+    }
+
+    /// Handles the Get Scene Membership command and writes the scene list into `buf`.
+    ///
+    /// See Section 3.7.2.4.8
+    pub fn get_scene_membership<'b, const G: usize>(
+        &self,
+        command: &GetSceneMembership,
+        groups: &GroupTable<G>,
+        buf: &'b mut [u8],
+    ) -> byte::Result<GetSceneMembershipResponse<'b>> {
+        let mut status = ZclStatus::InvalidField;
+        let mut len = 0;
+        if is_known_group(groups, command.group_id) {
+            status = ZclStatus::Success;
+            let scenes = self
+                .entries
+                .iter()
+                .flatten()
+                .filter(|e| e.group_id == command.group_id);
+            for entry in scenes {
+                buf.write_with(&mut len, entry.scene_id, byte::LE)?;
+            }
+        }
+
+        Ok(GetSceneMembershipResponse {
+            status,
+            capacity: self.capacity(),
+            group_id: command.group_id,
+            scene_list: &buf[..len],
+        })
+    }
+
+    /// Handles the Copy Scene command.
+    ///
+    /// See Section 3.7.2.4.11
+    pub fn copy_scene<const G: usize>(
+        &mut self,
+        command: &CopyScene,
+        groups: &GroupTable<G>,
+    ) -> CopySceneResponse {
+        let status = if !is_known_group(groups, command.group_id_from)
+            || !is_known_group(groups, command.group_id_to)
+        {
+            ZclStatus::InvalidField
+        } else if command.copy_all_scenes() {
+            self.copy_all(command.group_id_from, command.group_id_to)
+        } else {
+            let entry = self
+                .entry(command.group_id_from, command.scene_id_from)
+                .copied();
+            entry.map_or(ZclStatus::NotFound, |mut entry| {
+                entry.group_id = command.group_id_to;
+                entry.scene_id = command.scene_id_to;
+                if self.insert(entry) {
+                    ZclStatus::Success
+                } else {
+                    ZclStatus::InsufficientSpace
+                }
+            })
+        };
+
+        CopySceneResponse {
+            status,
+            group_id_from: command.group_id_from,
+            scene_id_from: command.scene_id_from,
+        }
+    }
+
+    fn copy_all(&mut self, group_id_from: u16, group_id_to: u16) -> ZclStatus {
+        for i in 0..N {
+            let mut entry = match self.entries[i] {
+                Some(entry) if entry.group_id == group_id_from => entry,
+                _ => continue,
+            };
+            entry.group_id = group_id_to;
+            if !self.insert(entry) {
+                return ZclStatus::InsufficientSpace;
+            }
+        }
+        ZclStatus::Success
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::TryRead;
+
+    use super::*;
+
+    struct Light {
+        on_off: u8,
+        transition_time: u16,
+    }
+
+    impl SceneExtension for Light {
+        fn cluster_id(&self) -> u16 {
+            0x0006
+        }
+
+        fn store_scene(&self, data: &mut [u8]) -> byte::Result<usize> {
+            let offset = &mut 0;
+            data.write_with(
+                offset,
+                OnOffExtensionFieldSet {
+                    on_off: self.on_off,
+                },
+                (),
+            )?;
+            Ok(*offset)
+        }
+
+        fn recall_scene(&mut self, data: &[u8], transition_time: u16) {
+            if let Ok((set, _)) = OnOffExtensionFieldSet::try_read(data, ()) {
+                self.on_off = set.on_off;
+                self.transition_time = transition_time;
+            }
+        }
+    }
+
+    #[test]
+    fn unpack_add_scene() {
+        // given
+        let input = [
+            0x01, 0x00, // group id
+            0x05, // scene id
+            0x02, 0x00, // transition time
+            0x03, b'o', b'f', b'f', // scene name
+            0x06, 0x00, 0x01, 0x00, // on/off extension field set
+            0x08, 0x00, 0x01, 0xfe, // level control extension field set
+        ];
+
+        // when
+        let (command, len) =
+            AddScene::try_read(&input, ()).expect("Could not read AddScene in test");
+
+        // then
+        assert_eq!(len, input.len());
+        assert_eq!(command.scene_name.as_str(), Some("off"));
+        let mut sets = command.extension_field_sets();
+        let on_off = sets.next().expect("Expected OnOff extension field set");
+        assert_eq!(on_off.cluster_id, 0x0006);
+        assert_eq!(on_off.data, &[0x00]);
+        let level = sets.next().expect("Expected Level extension field set");
+        let (level, _) = LevelControlExtensionFieldSet::try_read(level.data, ())
+            .expect("Could not read LevelControlExtensionFieldSet in test");
+        assert_eq!(level.current_level, 0xfe);
+        assert_eq!(sets.next(), None);
+    }
+
+    #[test]
+    fn store_and_recall_scene() {
+        // given
+        let mut groups = GroupTable::<2>::default();
+        groups.add(0x0001, "");
+        let mut scenes = SceneTable::<2>::default();
+        let mut light = Light {
+            on_off: 1,
+            transition_time: 0,
+        };
+
+        // when
+        let response = scenes.store_scene(
+            &StoreScene {
+                group_id: 0x0001,
+                scene_id: 0x01,
+            },
+            &groups,
+            &[&light],
+        );
+        light.on_off = 0;
+        scenes.invalidate();
+        let status = scenes.recall_scene(
+            &RecallScene {
+                group_id: 0x0001,
+                scene_id: 0x01,
+                transition_time: Some(20),
+            },
+            &groups,
+            &mut [&mut light],
+        );
+
+        // then
+        assert_eq!(response.status, ZclStatus::Success);
+        assert_eq!(status, ZclStatus::Success);
+        assert_eq!(light.on_off, 1);
+        assert_eq!(light.transition_time, 20);
+        assert!(scenes.scene_valid());
+        assert_eq!(scenes.scene_count(), 1);
+    }
+
+    #[test]
+    fn scene_table_status() {
+        // given
+        let groups = GroupTable::<1>::default();
+        let mut scenes = SceneTable::<1>::new(true);
+        let add = AddScene {
+            group_id: 0x0000,
+            scene_id: 0x01,
+            transition_time: 3,
+            scene_name: CharacterString::new("evening").unwrap_or_default(),
+            extension_field_sets: &[0x06, 0x00, 0x01, 0x01],
+        };
+
+        // then
+        assert_eq!(
+            scenes.add_scene(&add, false, &groups).status,
+            ZclStatus::Success
+        );
+        let view = scenes.view_scene(
+            &ViewScene {
+                group_id: 0x0000,
+                scene_id: 0x01,
+            },
+            true,
+            &groups,
+        );
+        assert_eq!(view.transition_time, 30);
+        assert_eq!(view.scene_name.as_str(), Some("evening"));
+        assert_eq!(
+            scenes
+                .add_scene(
+                    &AddScene {
+                        group_id: 0x0002,
+                        ..add
+                    },
+                    false,
+                    &groups
+                )
+                .status,
+            ZclStatus::InvalidField
+        );
+        assert_eq!(
+            scenes
+                .add_scene(
+                    &AddScene {
+                        scene_id: 0x02,
+                        ..add
+                    },
+                    false,
+                    &groups
+                )
+                .status,
+            ZclStatus::InsufficientSpace
+        );
+        assert_eq!(
+            scenes
+                .copy_scene(
+                    &CopyScene {
+                        mode: 0,
+                        group_id_from: 0x0000,
+                        scene_id_from: 0x05,
+                        group_id_to: 0x0000,
+                        scene_id_to: 0x06,
+                    },
+                    &groups
+                )
+                .status,
+            ZclStatus::NotFound
+        );
+
+        let mut buf = [0u8; 4];
+        let membership = scenes
+            .get_scene_membership(&GetSceneMembership { group_id: 0x0000 }, &groups, &mut buf)
+            .expect("Could not get scene membership in test");
+        assert_eq!(membership.capacity, 0);
+        assert_eq!(membership.scene_list, &[0x01]);
+        assert_eq!(
+            scenes
+                .remove_scene(
+                    &RemoveScene {
+                        group_id: 0x0000,
+                        scene_id: 0x01,
+                    },
+                    &groups
+                )
+                .status,
+            ZclStatus::Success
+        );
+        assert_eq!(scenes.scene_count(), 0);
+    }
+
+    #[test]
+    fn reject_malformed_extension_field_sets() {
+        // given
+        let groups = GroupTable::<1>::default();
+        let mut scenes = SceneTable::<1>::default();
+        let add = AddScene {
+            group_id: 0x0000,
+            scene_id: 0x01,
+            transition_time: 0,
+            scene_name: CharacterString::default(),
+            extension_field_sets: &[0x06, 0x00, 0x01, 0x01, 0x08, 0x00, 0x02, 0xfe],
+        };
+
+        // when
+        let response = scenes.add_scene(&add, false, &groups);
+
+        // then
+        assert_eq!(response.status, ZclStatus::MalformedCommand);
+        assert_eq!(scenes.scene_count(), 0);
+    }
+
+    #[test]
+    fn truncate_scene_name_at_char_boundary() {
+        // given
+        let groups = GroupTable::<1>::default();
+        let mut scenes = SceneTable::<1>::new(true);
+        let add = AddScene {
+            group_id: 0x0000,
+            scene_id: 0x01,
+            transition_time: 0,
+            scene_name: CharacterString::new("Arbeitszimmer Büro")
+                .expect("Invalid CharacterString in test"),
+            extension_field_sets: &[],
+        };
+
+        // when
+        let response = scenes.add_scene(&add, false, &groups);
+
+        // then
+        assert_eq!(response.status, ZclStatus::Success);
+        let view = scenes.view_scene(
+            &ViewScene {
+                group_id: 0x0000,
+                scene_id: 0x01,
+            },
+            false,
+            &groups,
+        );
+        assert_eq!(view.scene_name.as_str(), Some("Arbeitszimmer B"));
+    }
+}
@@ -0,0 +1,252 @@
+//!
+//! 3.12 Time Cluster
+//!
+//! Attributes for an interface to a real time clock. [`TimeServer`] keeps the clock, derives
+//! the standard and local time from the time zone and daylight saving time attributes and
+//! tracks the synchronization with a time server.
+use crate::data_types::UtcTime;
+use crate::impl_byte;
+use crate::status::ZclStatus;
+
+/// Time cluster identifier
+pub const CLUSTER_ID: u16 = 0x000a;
+
+impl_byte! {
+    /// 3.12.2.2 Time Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        Time = 0x0000,
+        TimeStatus = 0x0001,
+        TimeZone = 0x0002,
+        DstStart = 0x0003,
+        DstEnd = 0x0004,
+        DstShift = 0x0005,
+        StandardTime = 0x0006,
+        LocalTime = 0x0007,
+        LastSetTime = 0x0008,
+        ValidUntilTime = 0x0009,
+    }
+}
+
+impl_byte! {
+    /// 3.12.2.2.2 `TimeStatus` Attribute
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct TimeStatus(pub u8);
+}
+
+impl TimeStatus {
+    /// The clock is a master clock and the `Time` attribute is read-only
+    pub const MASTER: u8 = 1 << 0;
+    /// The time has been synchronized with a time server
+    pub const SYNCHRONIZED: u8 = 1 << 1;
+    /// The time zone and daylight saving time attributes are set by the master clock
+    pub const MASTER_ZONE_DST: u8 = 1 << 2;
+    /// The clock supersedes other time servers
+    pub const SUPERSEDING: u8 = 1 << 3;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u8) -> bool {
+        self.0 & flags == flags
+    }
+
+    /// Returns `true` if a time server with this status is a better time source than one with
+    /// `other`.
+    ///
+    /// Superseding servers are preferred, then master clocks and then synchronized clocks.
+    pub fn is_preferred_over(self, other: Self) -> bool {
+        let rank = |status: Self| {
+            (
+                status.contains(Self::SUPERSEDING),
+                status.contains(Self::MASTER),
+                status.contains(Self::SYNCHRONIZED),
+            )
+        };
+        rank(self) > rank(other)
+    }
+}
+
+/// Time server
+///
+/// The application advances the clock with [`TimeServer::tick`] every second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeServer {
+    time: UtcTime,
+    /// `TimeStatus` attribute
+    pub time_status: TimeStatus,
+    /// `TimeZone` attribute, offset of the standard time from UTC in seconds
+    pub time_zone: i32,
+    /// `DstStart` attribute, UTC time at which the daylight saving time starts
+    pub dst_start: u32,
+    /// `DstEnd` attribute, UTC time at which the daylight saving time ends
+    pub dst_end: u32,
+    /// `DstShift` attribute, offset of the daylight saving time in seconds
+    pub dst_shift: i32,
+    last_set_time: UtcTime,
+    /// `ValidUntilTime` attribute, time until which the clock is considered accurate
+    pub valid_until_time: UtcTime,
+}
+
+impl Default for TimeServer {
+    fn default() -> Self {
+        Self::new(TimeStatus::default())
+    }
+}
+
+impl TimeServer {
+    /// Creates a server with an invalid time.
+    pub fn new(time_status: TimeStatus) -> Self {
+        Self {
+            time: UtcTime::NON_VALUE,
+            time_status,
+            time_zone: 0,
+            dst_start: 0,
+            dst_end: 0,
+            dst_shift: 0,
+            last_set_time: UtcTime::NON_VALUE,
+            valid_until_time: UtcTime::NON_VALUE,
+        }
+    }
+
+    /// Returns the `Time` attribute.
+    pub fn time(&self) -> UtcTime {
+        self.time
+    }
+
+    /// Returns the `LastSetTime` attribute.
+    pub fn last_set_time(&self) -> UtcTime {
+        self.last_set_time
+    }
+
+    /// Sets the time of the clock, e.g. from a local real time clock.
+    pub fn set_time(&mut self, time: UtcTime) {
+        self.time = time;
+        self.last_set_time = time;
+    }
+
+    /// Handles a write of the `Time` attribute, which is read-only on a master clock.
+    pub fn write_time(&mut self, time: UtcTime) -> ZclStatus {
+        if self.time_status.contains(TimeStatus::MASTER) {
+            return ZclStatus::ReadOnly;
+        }
+        self.set_time(time);
+        ZclStatus::Success
+    }
+
+    /// Synchronizes the clock with the time read from a time server.
+    ///
+    /// The time zone and daylight saving time attributes are only taken over from a master
+    /// clock with [`TimeStatus::MASTER_ZONE_DST`] set.
+    pub fn synchronize(&mut self, server: &Self) {
+        if self.time_status.contains(TimeStatus::MASTER) || server.time.value().is_none() {
+            return;
+        }
+        self.set_time(server.time);
+        self.valid_until_time = server.valid_until_time;
+        self.time_status.0 |= TimeStatus::SYNCHRONIZED;
+        if server.time_status.contains(TimeStatus::MASTER_ZONE_DST) {
+            self.time_zone = server.time_zone;
+            self.dst_start = server.dst_start;
+            self.dst_end = server.dst_end;
+            self.dst_shift = server.dst_shift;
+        }
+    }
+
+    /// Returns `true` if the time is set and still valid.
+    pub fn is_valid(&self) -> bool {
+        self.time.value().is_some()
+            && self
+                .valid_until_time
+                .value()
+                .map_or(true, |valid_until| self.time.get() < valid_until)
+    }
+
+    /// Returns `true` if daylight saving time is in effect.
+    pub fn is_dst(&self) -> bool {
+        self.time
+            .value()
+            .map_or(false, |time| self.dst_start <= time && time < self.dst_end)
+    }
+
+    /// Returns the `StandardTime` attribute, the time including the time zone offset.
+    pub fn standard_time(&self) -> Option<u32> {
+        self.time.checked_add_signed(self.time_zone)?.value()
+    }
+
+    /// Returns the `LocalTime` attribute, the standard time including the daylight saving time
+    /// offset.
+    pub fn local_time(&self) -> Option<u32> {
+        let shift = if self.is_dst() { self.dst_shift } else { 0 };
+        self.time
+            .checked_add_signed(self.time_zone)?
+            .checked_add_signed(shift)?
+            .value()
+    }
+
+    /// Advances the clock by one second.
+    pub fn tick(&mut self) {
+        self.time = self
+            .time
+            .checked_add_signed(1)
+            .unwrap_or(UtcTime::NON_VALUE);
+        if !self.is_valid() {
+            self.time_status.0 &= !TimeStatus::SYNCHRONIZED;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_time_with_dst() {
+        // given
+        let mut server = TimeServer::new(TimeStatus::default());
+        server.time_zone = 3600;
+        server.dst_start = 1000;
+        server.dst_end = 2000;
+        server.dst_shift = 3600;
+
+        // when
+        server.set_time(UtcTime::new(1500).expect("Invalid UtcTime in test"));
+
+        // then
+        assert!(server.is_dst());
+        assert_eq!(server.standard_time(), Some(5100));
+        assert_eq!(server.local_time(), Some(8700));
+        server.set_time(UtcTime::new(2000).expect("Invalid UtcTime in test"));
+        assert_eq!(server.local_time(), Some(5600));
+    }
+
+    #[test]
+    fn synchronize_with_master() {
+        // given
+        let mut master = TimeServer::new(TimeStatus(
+            TimeStatus::MASTER | TimeStatus::MASTER_ZONE_DST | TimeStatus::SYNCHRONIZED,
+        ));
+        master.set_time(UtcTime::new(100).expect("Invalid UtcTime in test"));
+        master.time_zone = -3600;
+        master.valid_until_time = UtcTime::new(102).expect("Invalid UtcTime in test");
+        let mut client = TimeServer::default();
+
+        // when
+        client.synchronize(&master);
+
+        // then
+        assert!(master.time_status.is_preferred_over(client.time_status));
+        assert_eq!(
+            master.write_time(UtcTime::new(0).expect("Invalid UtcTime in test")),
+            ZclStatus::ReadOnly
+        );
+        assert_eq!(client.time(), master.time());
+        assert_eq!(client.time_zone, -3600);
+        assert!(client.time_status.contains(TimeStatus::SYNCHRONIZED));
+        client.tick();
+        assert!(client.is_valid());
+        client.tick();
+        assert!(!client.is_valid());
+        assert!(!client.time_status.contains(TimeStatus::SYNCHRONIZED));
+    }
+}
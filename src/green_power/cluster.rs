@@ -0,0 +1,1049 @@
+//!
+//! A.3 Green Power Cluster
+//!
+//! Commands exchanged between Green Power proxies and sinks on behalf of Green Power Devices
+//! (GPDs), e.g. energy harvesting switches. A GPD is identified by a [`GpdId`], whose format
+//! depends on the `ApplicationID` sub-field in the options of each command. When writing a
+//! command, the `ApplicationID` and the presence flags of the optional fields are taken from
+//! the fields themselves.
+use core::convert::TryFrom;
+
+use byte::{ctx, BytesExt, TryRead, TryWrite};
+
+use crate::data_types::OctetString;
+use crate::impl_byte;
+
+/// Green Power cluster identifier
+pub const CLUSTER_ID: u16 = 0x0021;
+
+/// Endpoint of the Green Power cluster
+pub const ENDPOINT: u8 = 0xf2;
+
+/// Length of a GPD security key
+pub const KEY_LEN: usize = 16;
+
+/// GPD security key
+///
+/// The key is never printed by `Debug`.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct Key(pub [u8; KEY_LEN]);
+
+impl core::fmt::Debug for Key {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Key {{ .. }}")
+    }
+}
+
+impl<C: Default> TryRead<'_, C> for Key {
+    fn try_read(bytes: &[u8], _: C) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let key: &[u8] = bytes.read_with(offset, ctx::Bytes::Len(KEY_LEN))?;
+        let mut out = [0; KEY_LEN];
+        out.copy_from_slice(key);
+
+        Ok((Self(out), *offset))
+    }
+}
+
+impl<C: Default> TryWrite<C> for Key {
+    fn try_write(self, bytes: &mut [u8], _: C) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, &self.0[..], ())?;
+
+        Ok(*offset)
+    }
+}
+
+impl_byte! {
+    /// A.3.3.2 and A.3.4.2 Green Power Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        // Sink (server) attributes
+        MaxSinkTableEntries = 0x0000,
+        SinkTable = 0x0001,
+        CommunicationMode = 0x0002,
+        CommissioningExitMode = 0x0003,
+        CommissioningWindow = 0x0004,
+        SecurityLevel = 0x0005,
+        SinkFunctionality = 0x0006,
+        SinkActiveFunctionality = 0x0007,
+        // Proxy (client) attributes
+        MaxProxyTableEntries = 0x0010,
+        ProxyTable = 0x0011,
+        NotificationRetryNumber = 0x0012,
+        NotificationRetryTimer = 0x0013,
+        MaxSearchCounter = 0x0014,
+        BlockedGpdId = 0x0015,
+        ProxyFunctionality = 0x0016,
+        ProxyActiveFunctionality = 0x0017,
+        // Shared attributes
+        SharedSecurityKeyType = 0x0020,
+        SharedSecurityKey = 0x0021,
+        LinkKey = 0x0022,
+    }
+}
+
+impl_byte! {
+    /// A.3.3.4 Commands Received (by the sink)
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ReceivedCommandIdentifier {
+        Notification = 0x00,
+        PairingSearch = 0x01,
+        TunnelingStop = 0x03,
+        CommissioningNotification = 0x04,
+        SinkCommissioningMode = 0x05,
+        TranslationTableUpdate = 0x07,
+        TranslationTableRequest = 0x08,
+        PairingConfiguration = 0x09,
+        SinkTableRequest = 0x0a,
+        ProxyTableResponse = 0x0b,
+    }
+}
+
+impl_byte! {
+    /// A.3.4.2 Commands Generated (by the sink)
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum GeneratedCommandIdentifier {
+        NotificationResponse = 0x00,
+        Pairing = 0x01,
+        ProxyCommissioningMode = 0x02,
+        Response = 0x06,
+        TranslationTableResponse = 0x08,
+        SinkTableResponse = 0x0a,
+        ProxyTableRequest = 0x0b,
+    }
+}
+
+impl_byte! {
+    /// `ApplicationID` sub-field, selects the format of the [`GpdId`]
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ApplicationId {
+        /// The GPD is identified by its 32-bit `SrcID`
+        SrcId = 0b000,
+        /// The GPD is identified by its IEEE address and endpoint
+        Ieee = 0b010,
+    }
+}
+
+impl_byte! {
+    /// `SecurityLevel` sub-field
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SecurityLevel {
+        NoSecurity = 0b00,
+        Reserved = 0b01,
+        FrameCounterMic = 0b10,
+        EncryptionFrameCounterMic = 0b11,
+    }
+}
+
+impl_byte! {
+    /// `SecurityKeyType` sub-field
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SecurityKeyType {
+        NoKey = 0b000,
+        NwkKey = 0b001,
+        GpdGroupKey = 0b010,
+        NwkKeyDerivedGpdGroupKey = 0b011,
+        OutOfTheBoxGpdKey = 0b100,
+        DerivedIndividualGpdKey = 0b111,
+    }
+}
+
+impl_byte! {
+    /// `CommunicationMode` sub-field
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum CommunicationMode {
+        FullUnicast = 0b00,
+        DerivedGroupcast = 0b01,
+        PrecommissionedGroupcast = 0b10,
+        LightweightUnicast = 0b11,
+    }
+}
+
+/// Reads the sub-field of `width` bits at `shift`.
+pub(crate) fn sub_field(options: u32, shift: u32, width: u32) -> u8 {
+    #[allow(clippy::cast_possible_truncation)]
+    let value = ((options >> shift) & ((1 << width) - 1)) as u8;
+    value
+}
+
+/// Returns `options` with the sub-field of `width` bits at `shift` set to `value`.
+pub(crate) fn with_sub_field(options: u32, shift: u32, width: u32, value: u8) -> u32 {
+    let mask = ((1 << width) - 1) << shift;
+    (options & !mask) | ((u32::from(value) << shift) & mask)
+}
+
+/// Returns `options` with `flag` set or cleared.
+pub(crate) fn with_flag(options: u32, flag: u32, set: bool) -> u32 {
+    if set {
+        options | flag
+    } else {
+        options & !flag
+    }
+}
+
+/// Reads the `ApplicationID` from bits 0 - 2 of the options.
+pub(crate) fn application_id(options: u32) -> Option<ApplicationId> {
+    ApplicationId::try_from(sub_field(options, 0, 3)).ok()
+}
+
+/// Reads the `ApplicationID` from bits 0 - 2 of the options, failing on unsupported values.
+pub(crate) fn read_application_id(options: u32) -> byte::Result<ApplicationId> {
+    application_id(options).ok_or(byte::Error::BadInput {
+        err: "Unsupported ApplicationID",
+    })
+}
+
+/// GPD identifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpdId {
+    /// 32-bit source identifier
+    SrcId(u32),
+    /// IEEE address and endpoint
+    Ieee {
+        /// IEEE address of the GPD
+        address: u64,
+        /// Endpoint on the GPD
+        endpoint: u8,
+    },
+}
+
+impl GpdId {
+    /// Returns the `ApplicationID` addressing this identifier.
+    pub fn application_id(self) -> ApplicationId {
+        match self {
+            Self::SrcId(_) => ApplicationId::SrcId,
+            Self::Ieee { .. } => ApplicationId::Ieee,
+        }
+    }
+}
+
+impl TryRead<'_, ApplicationId> for GpdId {
+    fn try_read(bytes: &[u8], application_id: ApplicationId) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let gpd_id = match application_id {
+            ApplicationId::SrcId => Self::SrcId(bytes.read_with(offset, byte::LE)?),
+            ApplicationId::Ieee => Self::Ieee {
+                address: bytes.read_with(offset, byte::LE)?,
+                endpoint: bytes.read_with(offset, byte::LE)?,
+            },
+        };
+
+        Ok((gpd_id, *offset))
+    }
+}
+
+impl TryWrite for GpdId {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        match self {
+            Self::SrcId(src_id) => bytes.write_with(offset, src_id, byte::LE)?,
+            Self::Ieee { address, endpoint } => {
+                bytes.write_with(offset, address, byte::LE)?;
+                bytes.write_with(offset, endpoint, byte::LE)?;
+            }
+        }
+
+        Ok(*offset)
+    }
+}
+
+impl_byte! {
+    /// GPP-GPD link, the quality of the link between the GPD and the proxy
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct GppGpdLink(pub u8);
+}
+
+impl GppGpdLink {
+    /// Returns the RSSI sub-field, the RSSI in 2 dB steps above -110 dBm.
+    pub fn rssi(self) -> u8 {
+        self.0 & 0x3f
+    }
+
+    /// Returns the RSSI in dBm.
+    pub fn rssi_dbm(self) -> i16 {
+        i16::from(self.rssi()) * 2 - 110
+    }
+
+    /// Returns the `LinkQuality` sub-field.
+    pub fn link_quality(self) -> u8 {
+        self.0 >> 6
+    }
+}
+
+impl_byte! {
+    /// Information about the proxy which forwarded a GPD frame
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct ProxyInfo {
+        /// Short address of the proxy
+        pub gpp_short_address: u16,
+        /// Link quality between the GPD and the proxy
+        pub gpp_gpd_link: GppGpdLink,
+    }
+}
+
+impl_byte! {
+    /// A.3.3.4.1 GP Notification Options
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct NotificationOptions(pub u16);
+}
+
+impl NotificationOptions {
+    /// Forwarded to a sink by unicast
+    pub const ALSO_UNICAST: u16 = 1 << 3;
+    /// Forwarded to the groupcast derived from the GPD ID
+    pub const ALSO_DERIVED_GROUP: u16 = 1 << 4;
+    /// Forwarded to a commissioned group
+    pub const ALSO_COMMISSIONED_GROUP: u16 = 1 << 5;
+    /// The GPD stays in receive mode after transmitting
+    pub const RX_AFTER_TX: u16 = 1 << 11;
+    /// The transmit queue of the proxy for the GPD is full
+    pub const TX_QUEUE_FULL: u16 = 1 << 12;
+    /// The proxy is capable of bidirectional communication
+    pub const BIDIRECTIONAL_CAPABILITY: u16 = 1 << 13;
+    /// The proxy info fields are present
+    pub const PROXY_INFO_PRESENT: u16 = 1 << 14;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u16) -> bool {
+        self.0 & flags == flags
+    }
+
+    /// Returns the `ApplicationID` sub-field.
+    pub fn application_id(self) -> Option<ApplicationId> {
+        application_id(u32::from(self.0))
+    }
+
+    /// Returns the `SecurityLevel` sub-field.
+    pub fn security_level(self) -> Option<SecurityLevel> {
+        SecurityLevel::try_from(sub_field(u32::from(self.0), 6, 2)).ok()
+    }
+
+    /// Returns the `SecurityKeyType` sub-field.
+    pub fn security_key_type(self) -> Option<SecurityKeyType> {
+        SecurityKeyType::try_from(sub_field(u32::from(self.0), 8, 3)).ok()
+    }
+
+    /// Returns the options with the security sub-fields set.
+    #[must_use]
+    pub fn with_security(self, level: SecurityLevel, key_type: SecurityKeyType) -> Self {
+        let options = with_sub_field(u32::from(self.0), 6, 2, level as u8);
+        Self::from_bits(with_sub_field(options, 8, 3, key_type as u8))
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_bits(options: u32) -> Self {
+        Self(options as u16)
+    }
+}
+
+/// A.3.3.4.1 GP Notification Command
+///
+/// Sent by a proxy to forward a GPD command to the sinks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Notification<'a> {
+    /// Options
+    pub options: NotificationOptions,
+    /// GPD which sent the command
+    pub gpd_id: GpdId,
+    /// GPD security frame counter, or the MAC sequence number
+    pub security_frame_counter: u32,
+    /// GPD command identifier
+    pub command_id: u8,
+    /// GPD command payload
+    pub payload: OctetString<'a>,
+    /// Forwarding proxy
+    pub proxy_info: Option<ProxyInfo>,
+}
+
+impl<'a> TryRead<'a, ()> for Notification<'a> {
+    fn try_read(bytes: &'a [u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let options: NotificationOptions = bytes.read_with(offset, ())?;
+        let gpd_id = bytes.read_with(offset, read_application_id(u32::from(options.0))?)?;
+        let security_frame_counter = bytes.read_with(offset, byte::LE)?;
+        let command_id = bytes.read_with(offset, byte::LE)?;
+        let payload = bytes.read_with(offset, ())?;
+        let proxy_info = if options.contains(NotificationOptions::PROXY_INFO_PRESENT) {
+            Some(bytes.read_with(offset, ())?)
+        } else {
+            None
+        };
+
+        Ok((
+            Self {
+                options,
+                gpd_id,
+                security_frame_counter,
+                command_id,
+                payload,
+                proxy_info,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl TryWrite for Notification<'_> {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        let options = with_sub_field(
+            u32::from(self.options.0),
+            0,
+            3,
+            self.gpd_id.application_id() as u8,
+        );
+        let options = with_flag(
+            options,
+            u32::from(NotificationOptions::PROXY_INFO_PRESENT),
+            self.proxy_info.is_some(),
+        );
+        bytes.write_with(offset, NotificationOptions::from_bits(options), ())?;
+        bytes.write_with(offset, self.gpd_id, ())?;
+        bytes.write_with(offset, self.security_frame_counter, byte::LE)?;
+        bytes.write_with(offset, self.command_id, byte::LE)?;
+        bytes.write_with(offset, self.payload, ())?;
+        if let Some(proxy_info) = self.proxy_info {
+            bytes.write_with(offset, proxy_info, ())?;
+        }
+
+        Ok(*offset)
+    }
+}
+
+impl_byte! {
+    /// A.3.3.4.3 GP Commissioning Notification Options
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct CommissioningNotificationOptions(pub u16);
+}
+
+impl CommissioningNotificationOptions {
+    /// The GPD stays in receive mode after transmitting
+    pub const RX_AFTER_TX: u16 = 1 << 3;
+    /// The proxy failed to process the security of the GPD frame, the MIC is present
+    pub const SECURITY_PROCESSING_FAILED: u16 = 1 << 9;
+    /// The proxy is capable of bidirectional communication
+    pub const BIDIRECTIONAL_CAPABILITY: u16 = 1 << 10;
+    /// The proxy info fields are present
+    pub const PROXY_INFO_PRESENT: u16 = 1 << 11;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u16) -> bool {
+        self.0 & flags == flags
+    }
+
+    /// Returns the `ApplicationID` sub-field.
+    pub fn application_id(self) -> Option<ApplicationId> {
+        application_id(u32::from(self.0))
+    }
+
+    /// Returns the `SecurityLevel` sub-field.
+    pub fn security_level(self) -> Option<SecurityLevel> {
+        SecurityLevel::try_from(sub_field(u32::from(self.0), 4, 2)).ok()
+    }
+
+    /// Returns the `SecurityKeyType` sub-field.
+    pub fn security_key_type(self) -> Option<SecurityKeyType> {
+        SecurityKeyType::try_from(sub_field(u32::from(self.0), 6, 3)).ok()
+    }
+
+    /// Returns the options with the security sub-fields set.
+    #[must_use]
+    pub fn with_security(self, level: SecurityLevel, key_type: SecurityKeyType) -> Self {
+        let options = with_sub_field(u32::from(self.0), 4, 2, level as u8);
+        Self::from_bits(with_sub_field(options, 6, 3, key_type as u8))
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_bits(options: u32) -> Self {
+        Self(options as u16)
+    }
+}
+
+/// A.3.3.4.3 GP Commissioning Notification Command
+///
+/// Sent by a proxy in commissioning mode to forward a GPD commissioning frame to the sinks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommissioningNotification<'a> {
+    /// Options
+    pub options: CommissioningNotificationOptions,
+    /// GPD which sent the command
+    pub gpd_id: GpdId,
+    /// GPD security frame counter, or the MAC sequence number
+    pub security_frame_counter: u32,
+    /// GPD command identifier
+    pub command_id: u8,
+    /// GPD command payload
+    pub payload: OctetString<'a>,
+    /// Forwarding proxy
+    pub proxy_info: Option<ProxyInfo>,
+    /// MIC of the GPD frame, if the proxy failed to process the security
+    pub mic: Option<u32>,
+}
+
+impl<'a> TryRead<'a, ()> for CommissioningNotification<'a> {
+    fn try_read(bytes: &'a [u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let options: CommissioningNotificationOptions = bytes.read_with(offset, ())?;
+        let gpd_id = bytes.read_with(offset, read_application_id(u32::from(options.0))?)?;
+        let security_frame_counter = bytes.read_with(offset, byte::LE)?;
+        let command_id = bytes.read_with(offset, byte::LE)?;
+        let payload = bytes.read_with(offset, ())?;
+        let proxy_info = if options.contains(CommissioningNotificationOptions::PROXY_INFO_PRESENT) {
+            Some(bytes.read_with(offset, ())?)
+        } else {
+            None
+        };
+        let mic = if options.contains(CommissioningNotificationOptions::SECURITY_PROCESSING_FAILED)
+        {
+            Some(bytes.read_with(offset, byte::LE)?)
+        } else {
+            None
+        };
+
+        Ok((
+            Self {
+                options,
+                gpd_id,
+                security_frame_counter,
+                command_id,
+                payload,
+                proxy_info,
+                mic,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl TryWrite for CommissioningNotification<'_> {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        let options = with_sub_field(
+            u32::from(self.options.0),
+            0,
+            3,
+            self.gpd_id.application_id() as u8,
+        );
+        let options = with_flag(
+            options,
+            u32::from(CommissioningNotificationOptions::PROXY_INFO_PRESENT),
+            self.proxy_info.is_some(),
+        );
+        let options = with_flag(
+            options,
+            u32::from(CommissioningNotificationOptions::SECURITY_PROCESSING_FAILED),
+            self.mic.is_some(),
+        );
+        bytes.write_with(
+            offset,
+            CommissioningNotificationOptions::from_bits(options),
+            (),
+        )?;
+        bytes.write_with(offset, self.gpd_id, ())?;
+        bytes.write_with(offset, self.security_frame_counter, byte::LE)?;
+        bytes.write_with(offset, self.command_id, byte::LE)?;
+        bytes.write_with(offset, self.payload, ())?;
+        if let Some(proxy_info) = self.proxy_info {
+            bytes.write_with(offset, proxy_info, ())?;
+        }
+        if let Some(mic) = self.mic {
+            bytes.write_with(offset, mic, byte::LE)?;
+        }
+
+        Ok(*offset)
+    }
+}
+
+/// A.3.4.2.1 GP Pairing Options
+///
+/// Encoded in 24 bits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PairingOptions(pub u32);
+
+impl PairingOptions {
+    /// The sink is added to the proxy table entry of the GPD
+    pub const ADD_SINK: u32 = 1 << 3;
+    /// The proxy table entry of the GPD is removed
+    pub const REMOVE_GPD: u32 = 1 << 4;
+    /// The GPD is at a fixed location
+    pub const GPD_FIXED: u32 = 1 << 7;
+    /// The GPD uses incremental MAC sequence numbers
+    pub const GPD_MAC_SEQUENCE_NUMBER_CAPABILITIES: u32 = 1 << 8;
+    /// The GPD security frame counter is present
+    pub const GPD_SECURITY_FRAME_COUNTER_PRESENT: u32 = 1 << 14;
+    /// The GPD key is present
+    pub const GPD_SECURITY_KEY_PRESENT: u32 = 1 << 15;
+    /// The assigned alias is present
+    pub const ASSIGNED_ALIAS_PRESENT: u32 = 1 << 16;
+    /// The forwarding radius is present
+    pub const FORWARDING_RADIUS_PRESENT: u32 = 1 << 17;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u32) -> bool {
+        self.0 & flags == flags
+    }
+
+    /// Returns the `ApplicationID` sub-field.
+    pub fn application_id(self) -> Option<ApplicationId> {
+        application_id(self.0)
+    }
+
+    /// Returns the `CommunicationMode` sub-field.
+    pub fn communication_mode(self) -> CommunicationMode {
+        match sub_field(self.0, 5, 2) {
+            0b00 => CommunicationMode::FullUnicast,
+            0b01 => CommunicationMode::DerivedGroupcast,
+            0b10 => CommunicationMode::PrecommissionedGroupcast,
+            _ => CommunicationMode::LightweightUnicast,
+        }
+    }
+
+    /// Returns the `SecurityLevel` sub-field.
+    pub fn security_level(self) -> Option<SecurityLevel> {
+        SecurityLevel::try_from(sub_field(self.0, 9, 2)).ok()
+    }
+
+    /// Returns the `SecurityKeyType` sub-field.
+    pub fn security_key_type(self) -> Option<SecurityKeyType> {
+        SecurityKeyType::try_from(sub_field(self.0, 11, 3)).ok()
+    }
+
+    /// Returns the options with the `CommunicationMode` sub-field set.
+    #[must_use]
+    pub fn with_communication_mode(self, mode: CommunicationMode) -> Self {
+        Self(with_sub_field(self.0, 5, 2, mode as u8))
+    }
+
+    /// Returns the options with the security sub-fields set.
+    #[must_use]
+    pub fn with_security(self, level: SecurityLevel, key_type: SecurityKeyType) -> Self {
+        let options = with_sub_field(self.0, 9, 2, level as u8);
+        Self(with_sub_field(options, 11, 3, key_type as u8))
+    }
+}
+
+impl<C: Default> TryRead<'_, C> for PairingOptions {
+    fn try_read(bytes: &[u8], _: C) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let options: [u8; 3] = [
+            bytes.read_with(offset, byte::LE)?,
+            bytes.read_with(offset, byte::LE)?,
+            bytes.read_with(offset, byte::LE)?,
+        ];
+        let [low, mid, high] = options;
+
+        Ok((Self(u32::from_le_bytes([low, mid, high, 0])), *offset))
+    }
+}
+
+impl<C: Default> TryWrite<C> for PairingOptions {
+    fn try_write(self, bytes: &mut [u8], _: C) -> byte::Result<usize> {
+        let offset = &mut 0;
+        let [low, mid, high, _] = self.0.to_le_bytes();
+        bytes.write_with(offset, &[low, mid, high][..], ())?;
+
+        Ok(*offset)
+    }
+}
+
+/// Sink of a [`Pairing`], depending on the `CommunicationMode`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PairingSink {
+    /// Unicast sink, for the full and lightweight unicast communication modes
+    Unicast {
+        /// IEEE address of the sink
+        ieee_address: u64,
+        /// Network address of the sink
+        nwk_address: u16,
+    },
+    /// Group of the sinks, for the groupcast communication modes
+    Groupcast {
+        /// Group identifier
+        group_id: u16,
+    },
+}
+
+/// A.3.4.2.1 GP Pairing Command
+///
+/// Sent by a sink to add or remove a pairing in the proxy table of the proxies. When writing,
+/// `AddSink` is set if the device identifier is present and `RemoveGPD` if the sink is absent.
+/// A `CommunicationMode` not matching the kind of sink is replaced by full unicast or
+/// pre-commissioned groupcast.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pairing {
+    /// Options
+    pub options: PairingOptions,
+    /// GPD of the pairing
+    pub gpd_id: GpdId,
+    /// Sink of the pairing, absent if the GPD is removed
+    pub sink: Option<PairingSink>,
+    /// Device identifier of the GPD, present when adding a sink
+    pub device_id: Option<u8>,
+    /// GPD security frame counter
+    pub security_frame_counter: Option<u32>,
+    /// GPD security key
+    pub key: Option<Key>,
+    /// Alias assigned to the GPD
+    pub assigned_alias: Option<u16>,
+    /// Forwarding radius for groupcast forwarding
+    pub forwarding_radius: Option<u8>,
+}
+
+impl TryRead<'_, ()> for Pairing {
+    fn try_read(bytes: &[u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let options: PairingOptions = bytes.read_with(offset, ())?;
+        let gpd_id = bytes.read_with(offset, read_application_id(options.0)?)?;
+        let sink = if options.contains(PairingOptions::REMOVE_GPD) {
+            None
+        } else {
+            match options.communication_mode() {
+                CommunicationMode::FullUnicast | CommunicationMode::LightweightUnicast => {
+                    Some(PairingSink::Unicast {
+                        ieee_address: bytes.read_with(offset, byte::LE)?,
+                        nwk_address: bytes.read_with(offset, byte::LE)?,
+                    })
+                }
+                CommunicationMode::DerivedGroupcast
+                | CommunicationMode::PrecommissionedGroupcast => Some(PairingSink::Groupcast {
+                    group_id: bytes.read_with(offset, byte::LE)?,
+                }),
+            }
+        };
+        let device_id = read_if(bytes, offset, options.contains(PairingOptions::ADD_SINK))?;
+        let security_frame_counter = read_if(
+            bytes,
+            offset,
+            options.contains(PairingOptions::GPD_SECURITY_FRAME_COUNTER_PRESENT),
+        )?;
+        let key = read_if(
+            bytes,
+            offset,
+            options.contains(PairingOptions::GPD_SECURITY_KEY_PRESENT),
+        )?;
+        let assigned_alias = read_if(
+            bytes,
+            offset,
+            options.contains(PairingOptions::ASSIGNED_ALIAS_PRESENT),
+        )?;
+        let forwarding_radius = read_if(
+            bytes,
+            offset,
+            options.contains(PairingOptions::FORWARDING_RADIUS_PRESENT),
+        )?;
+
+        Ok((
+            Self {
+                options,
+                gpd_id,
+                sink,
+                device_id,
+                security_frame_counter,
+                key,
+                assigned_alias,
+                forwarding_radius,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl TryWrite for Pairing {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        let mut options = with_sub_field(self.options.0, 0, 3, self.gpd_id.application_id() as u8);
+        if self.sink.is_none() && self.device_id.is_some() {
+            return Err(byte::Error::BadInput {
+                err: "Device identifier without a sink",
+            });
+        }
+        let mode = self.options.communication_mode();
+        match self.sink {
+            Some(PairingSink::Unicast { .. })
+                if mode != CommunicationMode::FullUnicast
+                    && mode != CommunicationMode::LightweightUnicast =>
+            {
+                options = with_sub_field(options, 5, 2, CommunicationMode::FullUnicast as u8);
+            }
+            Some(PairingSink::Groupcast { .. })
+                if mode != CommunicationMode::DerivedGroupcast
+                    && mode != CommunicationMode::PrecommissionedGroupcast =>
+            {
+                options = with_sub_field(
+                    options,
+                    5,
+                    2,
+                    CommunicationMode::PrecommissionedGroupcast as u8,
+                );
+            }
+            _ => {}
+        }
+        for &(flag, present) in &[
+            (PairingOptions::ADD_SINK, self.device_id.is_some()),
+            (PairingOptions::REMOVE_GPD, self.sink.is_none()),
+            (
+                PairingOptions::GPD_SECURITY_FRAME_COUNTER_PRESENT,
+                self.security_frame_counter.is_some(),
+            ),
+            (PairingOptions::GPD_SECURITY_KEY_PRESENT, self.key.is_some()),
+            (
+                PairingOptions::ASSIGNED_ALIAS_PRESENT,
+                self.assigned_alias.is_some(),
+            ),
+            (
+                PairingOptions::FORWARDING_RADIUS_PRESENT,
+                self.forwarding_radius.is_some(),
+            ),
+        ] {
+            options = with_flag(options, flag, present);
+        }
+        bytes.write_with(offset, PairingOptions(options), ())?;
+        bytes.write_with(offset, self.gpd_id, ())?;
+        match self.sink {
+            Some(PairingSink::Unicast {
+                ieee_address,
+                nwk_address,
+            }) => {
+                bytes.write_with(offset, ieee_address, byte::LE)?;
+                bytes.write_with(offset, nwk_address, byte::LE)?;
+            }
+            Some(PairingSink::Groupcast { group_id }) => {
+                bytes.write_with(offset, group_id, byte::LE)?;
+            }
+            None => {}
+        }
+        if let Some(device_id) = self.device_id {
+            bytes.write_with(offset, device_id, byte::LE)?;
+        }
+        if let Some(security_frame_counter) = self.security_frame_counter {
+            bytes.write_with(offset, security_frame_counter, byte::LE)?;
+        }
+        if let Some(key) = self.key {
+            bytes.write_with(offset, key, ())?;
+        }
+        if let Some(assigned_alias) = self.assigned_alias {
+            bytes.write_with(offset, assigned_alias, byte::LE)?;
+        }
+        if let Some(forwarding_radius) = self.forwarding_radius {
+            bytes.write_with(offset, forwarding_radius, byte::LE)?;
+        }
+
+        Ok(*offset)
+    }
+}
+
+/// Reads an optional little endian field.
+pub(crate) fn read_if<'a, T: TryRead<'a, ctx::Endian>>(
+    bytes: &'a [u8],
+    offset: &mut usize,
+    present: bool,
+) -> byte::Result<Option<T>> {
+    if present {
+        Ok(Some(bytes.read_with(offset, byte::LE)?))
+    } else {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::{TryRead, TryWrite};
+
+    use super::*;
+
+    #[test]
+    fn notification_src_id() {
+        // given
+        let bytes = &[
+            0x08, 0x40, // options: also unicast, proxy info present
+            0x78, 0x56, 0x34, 0x12, // GPD SrcID
+            0x05, 0x00, 0x00, 0x00, // security frame counter
+            0x22, // toggle
+            0x00, // payload
+            0x34, 0x12, // GPP short address
+            0x96, // GPP-GPD link
+        ];
+
+        // when
+        let (notification, len) =
+            Notification::try_read(bytes, ()).expect("Could not read Notification in test");
+
+        // then
+        assert_eq!(len, bytes.len());
+        assert_eq!(notification.gpd_id, GpdId::SrcId(0x1234_5678));
+        assert_eq!(notification.command_id, 0x22);
+        assert!(notification.payload.is_empty());
+        assert!(notification
+            .options
+            .contains(NotificationOptions::ALSO_UNICAST));
+        let proxy_info = notification.proxy_info.expect("Missing proxy info in test");
+        assert_eq!(proxy_info.gpp_short_address, 0x1234);
+        assert_eq!(proxy_info.gpp_gpd_link.rssi_dbm(), -66);
+        assert_eq!(proxy_info.gpp_gpd_link.link_quality(), 2);
+        let mut buf = [0u8; 16];
+        let written = notification
+            .try_write(&mut buf, ())
+            .expect("Could not write Notification in test");
+        assert_eq!(&buf[..written], bytes);
+    }
+
+    #[test]
+    fn commissioning_notification_ieee() {
+        // given
+        let notification = CommissioningNotification {
+            options: CommissioningNotificationOptions::default()
+                .with_security(SecurityLevel::FrameCounterMic, SecurityKeyType::NwkKey),
+            gpd_id: GpdId::Ieee {
+                address: 0x0011_2233_4455_6677,
+                endpoint: 0x01,
+            },
+            security_frame_counter: 1,
+            command_id: 0xe0,
+            payload: OctetString::new(&[0x02]).expect("Invalid OctetString in test"),
+            proxy_info: None,
+            mic: Some(0xdead_beef),
+        };
+
+        // when
+        let mut buf = [0u8; 32];
+        let len = notification
+            .try_write(&mut buf, ())
+            .expect("Could not write CommissioningNotification in test");
+        let (read, _) = CommissioningNotification::try_read(&buf[..len], ())
+            .expect("Could not read CommissioningNotification in test");
+
+        // then
+        assert_eq!(&buf[..2], &[0x62, 0x02]);
+        assert_eq!(len, 22);
+        assert_eq!(read.options.application_id(), Some(ApplicationId::Ieee));
+        assert_eq!(
+            read.options.security_level(),
+            Some(SecurityLevel::FrameCounterMic)
+        );
+        assert_eq!(
+            read.options.security_key_type(),
+            Some(SecurityKeyType::NwkKey)
+        );
+        assert_eq!(read.gpd_id, notification.gpd_id);
+        assert_eq!(read.mic, Some(0xdead_beef));
+    }
+
+    #[test]
+    fn pairing_add_sink() {
+        // given
+        let pairing = Pairing {
+            options: PairingOptions(PairingOptions::ADD_SINK)
+                .with_communication_mode(CommunicationMode::PrecommissionedGroupcast),
+            gpd_id: GpdId::SrcId(0x0000_0001),
+            sink: Some(PairingSink::Groupcast { group_id: 0x0b84 }),
+            device_id: Some(0x02),
+            security_frame_counter: Some(0x10),
+            key: None,
+            assigned_alias: None,
+            forwarding_radius: Some(0x1e),
+        };
+
+        // when
+        let mut buf = [0u8; 32];
+        let len = pairing
+            .try_write(&mut buf, ())
+            .expect("Could not write Pairing in test");
+        let (read, read_len) =
+            Pairing::try_read(&buf[..len], ()).expect("Could not read Pairing in test");
+
+        // then
+        assert_eq!(
+            &buf[..len],
+            &[
+                0x48, 0x40, 0x02, // options
+                0x01, 0x00, 0x00, 0x00, // GPD SrcID
+                0x84, 0x0b, // sink group
+                0x02, // device id
+                0x10, 0x00, 0x00, 0x00, // security frame counter
+                0x1e, // forwarding radius
+            ]
+        );
+        assert_eq!(read_len, len);
+        assert_eq!(read.sink, pairing.sink);
+        assert_eq!(read.forwarding_radius, Some(0x1e));
+        assert!(matches!(
+            Pairing::try_read(&[0x03, 0x00, 0x00, 0x00], ()),
+            Err(byte::Error::BadInput { .. })
+        ));
+    }
+
+    #[test]
+    fn pairing_options_from_fields() {
+        // given
+        let remove_gpd = Pairing {
+            options: PairingOptions(PairingOptions::ADD_SINK),
+            gpd_id: GpdId::SrcId(0x0000_0001),
+            sink: None,
+            device_id: None,
+            security_frame_counter: None,
+            key: None,
+            assigned_alias: None,
+            forwarding_radius: None,
+        };
+        let add_unicast_sink = Pairing {
+            options: PairingOptions::default()
+                .with_communication_mode(CommunicationMode::DerivedGroupcast),
+            sink: Some(PairingSink::Unicast {
+                ieee_address: 0x0011_2233_4455_6677,
+                nwk_address: 0x1234,
+            }),
+            device_id: Some(0x02),
+            ..remove_gpd
+        };
+
+        // when
+        let mut buf = [0u8; 32];
+        let remove_len = remove_gpd
+            .try_write(&mut buf, ())
+            .expect("Could not write Pairing in test");
+        let (removed, _) =
+            Pairing::try_read(&buf[..remove_len], ()).expect("Could not read Pairing in test");
+        let add_len = add_unicast_sink
+            .try_write(&mut buf, ())
+            .expect("Could not write Pairing in test");
+        let (added, _) =
+            Pairing::try_read(&buf[..add_len], ()).expect("Could not read Pairing in test");
+
+        // then
+        assert_eq!(remove_len, 3 + 4);
+        assert!(removed.options.contains(PairingOptions::REMOVE_GPD));
+        assert!(!removed.options.contains(PairingOptions::ADD_SINK));
+        assert_eq!(removed.sink, None);
+        assert_eq!(add_len, 3 + 4 + 10 + 1);
+        assert!(added.options.contains(PairingOptions::ADD_SINK));
+        assert!(!added.options.contains(PairingOptions::REMOVE_GPD));
+        assert_eq!(
+            added.options.communication_mode(),
+            CommunicationMode::FullUnicast
+        );
+        assert_eq!(added.sink, add_unicast_sink.sink);
+        assert_eq!(added.device_id, Some(0x02));
+        assert!(matches!(
+            Pairing {
+                device_id: Some(0x02),
+                ..remove_gpd
+            }
+            .try_write(&mut buf, ()),
+            Err(byte::Error::BadInput { .. })
+        ));
+    }
+}
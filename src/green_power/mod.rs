@@ -0,0 +1,11 @@
+//!
+//! Green Power
+
+/// Provides the commands of the Green Power cluster and the addressing of Green Power Devices.
+pub mod cluster;
+
+/// Provides the encodings of the proxy and sink tables.
+pub mod tables;
+
+/// Provides the translation of Green Power Device commands to ZCL commands.
+pub mod translation;
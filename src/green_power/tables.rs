@@ -0,0 +1,1201 @@
+//!
+//! A.3.3.2.2 Sink Table and A.3.4.2.2 Proxy Table
+//!
+//! Encodings of the pairings of GPDs stored by sinks and proxies. The tables are exposed as
+//! long octet string attributes holding the entries back to back, and returned entry by entry
+//! in the GP Sink Table Response and GP Proxy Table Response commands.
+use core::convert::TryFrom;
+use core::marker::PhantomData;
+
+use byte::{ctx, BytesExt, TryRead, TryWrite};
+
+use super::cluster::{
+    application_id, read_application_id, read_if, sub_field, with_flag, with_sub_field,
+    ApplicationId, CommunicationMode, GpdId, Key, SecurityKeyType, SecurityLevel,
+};
+use crate::impl_byte;
+use crate::status::ZclStatus;
+
+impl_byte! {
+    /// Security options of a table entry
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct SecurityOptions(pub u8);
+}
+
+impl SecurityOptions {
+    /// Creates the security options.
+    pub fn new(level: SecurityLevel, key_type: SecurityKeyType) -> Self {
+        Self(level as u8 | (key_type as u8) << 2)
+    }
+
+    /// Returns the `SecurityLevel` sub-field.
+    pub fn security_level(self) -> Option<SecurityLevel> {
+        SecurityLevel::try_from(self.0 & 0b11).ok()
+    }
+
+    /// Returns the `SecurityKeyType` sub-field.
+    pub fn security_key_type(self) -> Option<SecurityKeyType> {
+        SecurityKeyType::try_from((self.0 >> 2) & 0b111).ok()
+    }
+}
+
+/// Security settings of a GPD in a table entry
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EntrySecurity {
+    /// Security level and key type
+    pub options: SecurityOptions,
+    /// GPD security key
+    pub key: Key,
+}
+
+impl_byte! {
+    /// Member of a sink group list
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct SinkGroup {
+        /// Group identifier
+        pub group_id: u16,
+        /// Alias used to forward to the group
+        pub alias: u16,
+    }
+}
+
+impl_byte! {
+    /// Member of a lightweight sink address list
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct SinkAddress {
+        /// IEEE address of the sink
+        pub ieee_address: u64,
+        /// Network address of the sink
+        pub nwk_address: u16,
+    }
+}
+
+/// List of fixed size entries, prefixed with the number of entries
+pub struct List<'a, T> {
+    count: u8,
+    bytes: &'a [u8],
+    entry: PhantomData<T>,
+}
+
+impl<T> Clone for List<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for List<'_, T> {}
+
+impl<T> Default for List<'_, T> {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            bytes: &[],
+            entry: PhantomData,
+        }
+    }
+}
+
+impl<T> PartialEq for List<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.count == other.count && self.bytes == other.bytes
+    }
+}
+
+impl<T> Eq for List<'_, T> {}
+
+impl<T> core::fmt::Debug for List<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "List {{ count: {}, bytes: {:?} }}",
+            self.count, self.bytes
+        )
+    }
+}
+
+impl<'a, T: TryRead<'a, ()> + TryWrite + Copy> List<'a, T> {
+    /// Encodes `entries` into `buf` and returns the list borrowing it.
+    pub fn encode(entries: &[T], buf: &'a mut [u8]) -> byte::Result<Self> {
+        let count = u8::try_from(entries.len()).map_err(|_| byte::Error::BadInput {
+            err: "Too many list entries",
+        })?;
+        let offset = &mut 0;
+        for entry in entries {
+            buf.write_with(offset, *entry, ())?;
+        }
+
+        Ok(Self {
+            count,
+            bytes: &buf[..*offset],
+            entry: PhantomData,
+        })
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        usize::from(self.count)
+    }
+
+    /// Returns `true` if the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Returns the entries.
+    pub fn entries(&self) -> Entries<'a, T> {
+        Entries::new(self.bytes, Some(self.len()))
+    }
+}
+
+impl<'a, T: TryRead<'a, ()>, C: Default> TryRead<'a, C> for List<'a, T> {
+    fn try_read(bytes: &'a [u8], _: C) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let count: u8 = bytes.read_with(offset, byte::LE)?;
+        let start = *offset;
+        for _ in 0..count {
+            let _: T = bytes.read_with(offset, ())?;
+        }
+
+        Ok((
+            Self {
+                count,
+                bytes: &bytes[start..*offset],
+                entry: PhantomData,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl<T, C: Default> TryWrite<C> for List<'_, T> {
+    fn try_write(self, bytes: &mut [u8], _: C) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, self.count, byte::LE)?;
+        bytes.write_with(offset, self.bytes, ())?;
+
+        Ok(*offset)
+    }
+}
+
+/// Iterator over encoded entries
+///
+/// Yields an error and stops at a malformed entry.
+#[derive(Debug, Clone)]
+pub struct Entries<'a, T> {
+    bytes: &'a [u8],
+    offset: usize,
+    remaining: Option<usize>,
+    entry: PhantomData<T>,
+}
+
+impl<'a, T> Entries<'a, T> {
+    /// Creates an iterator over `count` entries, or over all entries in `bytes` if `None`.
+    fn new(bytes: &'a [u8], count: Option<usize>) -> Self {
+        Self {
+            bytes,
+            offset: 0,
+            remaining: count,
+            entry: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: TryRead<'a, ()>> Iterator for Entries<'a, T> {
+    type Item = byte::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.remaining {
+            Some(remaining) => self.remaining = Some(remaining.checked_sub(1)?),
+            None if self.offset >= self.bytes.len() => return None,
+            None => {}
+        }
+        let entry = self.bytes.read_with(&mut self.offset, ());
+        if entry.is_err() {
+            self.remaining = Some(0);
+        }
+        Some(entry)
+    }
+}
+
+/// Returns `options` with the `SequenceNumberCapabilities` `flag` derived from the presence of
+/// the frame counter, which is required if the security fields are present.
+fn with_frame_counter_flag(
+    options: u32,
+    flag: u32,
+    security: bool,
+    frame_counter: bool,
+) -> byte::Result<u32> {
+    if security {
+        if frame_counter {
+            Ok(options)
+        } else {
+            Err(byte::Error::BadInput {
+                err: "Missing security frame counter",
+            })
+        }
+    } else {
+        Ok(with_flag(options, flag, frame_counter))
+    }
+}
+
+impl_byte! {
+    /// A.3.4.2.2.1 Proxy Table Entry Options
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct ProxyTableOptions(pub u16);
+}
+
+impl ProxyTableOptions {
+    /// The entry is active
+    pub const ENTRY_ACTIVE: u16 = 1 << 3;
+    /// The entry is valid
+    pub const ENTRY_VALID: u16 = 1 << 4;
+    /// The GPD uses incremental MAC sequence numbers
+    pub const SEQUENCE_NUMBER_CAPABILITIES: u16 = 1 << 5;
+    /// The lightweight sink address list is present
+    pub const LIGHTWEIGHT_UNICAST_GPS: u16 = 1 << 6;
+    /// Frames are forwarded to the groupcast derived from the GPD ID
+    pub const DERIVED_GROUP_GPS: u16 = 1 << 7;
+    /// The sink group list is present
+    pub const COMMISSIONED_GROUP_GPS: u16 = 1 << 8;
+    /// The proxy forwards the frames of the GPD first
+    pub const FIRST_TO_FORWARD: u16 = 1 << 9;
+    /// The GPD is in range of the proxy
+    pub const IN_RANGE: u16 = 1 << 10;
+    /// The GPD is at a fixed location
+    pub const GPD_FIXED: u16 = 1 << 11;
+    /// The proxy has routes to all unicast sinks
+    pub const HAS_ALL_UNICAST_ROUTES: u16 = 1 << 12;
+    /// The assigned alias is present
+    pub const ASSIGNED_ALIAS: u16 = 1 << 13;
+    /// The security fields are present
+    pub const SECURITY_USE: u16 = 1 << 14;
+    /// The extended options are present
+    pub const OPTIONS_EXTENSION: u16 = 1 << 15;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u16) -> bool {
+        self.0 & flags == flags
+    }
+
+    /// Returns the `ApplicationID` sub-field.
+    pub fn application_id(self) -> Option<ApplicationId> {
+        application_id(u32::from(self.0))
+    }
+}
+
+/// A.3.4.2.2.1 Proxy Table Entry
+///
+/// The extended options and the full unicast sink address list are not supported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProxyTableEntry<'a> {
+    /// Options
+    pub options: ProxyTableOptions,
+    /// GPD of the entry
+    pub gpd_id: GpdId,
+    /// Alias assigned to the GPD
+    pub assigned_alias: Option<u16>,
+    /// Security settings of the GPD
+    pub security: Option<EntrySecurity>,
+    /// GPD security frame counter, or the MAC sequence number
+    pub security_frame_counter: Option<u32>,
+    /// Sinks paired in lightweight unicast mode
+    pub lightweight_sink_addresses: Option<List<'a, SinkAddress>>,
+    /// Groups paired in pre-commissioned groupcast mode
+    pub sink_groups: Option<List<'a, SinkGroup>>,
+    /// Radius for groupcast forwarding
+    pub groupcast_radius: Option<u8>,
+    /// Number of unsuccessful searches for the sinks of an inactive or invalid entry
+    pub search_counter: Option<u8>,
+}
+
+impl<'a> TryRead<'a, ()> for ProxyTableEntry<'a> {
+    fn try_read(bytes: &'a [u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let options: ProxyTableOptions = bytes.read_with(offset, ())?;
+        if options.contains(ProxyTableOptions::OPTIONS_EXTENSION) {
+            return Err(byte::Error::BadInput {
+                err: "Proxy table options extension not supported",
+            });
+        }
+        let gpd_id = bytes.read_with(offset, read_application_id(u32::from(options.0))?)?;
+        let assigned_alias = read_if(
+            bytes,
+            offset,
+            options.contains(ProxyTableOptions::ASSIGNED_ALIAS),
+        )?;
+        let security_use = options.contains(ProxyTableOptions::SECURITY_USE);
+        let security_options: Option<SecurityOptions> = read_if(bytes, offset, security_use)?;
+        let security_frame_counter = read_if(
+            bytes,
+            offset,
+            security_use || options.contains(ProxyTableOptions::SEQUENCE_NUMBER_CAPABILITIES),
+        )?;
+        let security = match security_options {
+            Some(options) => Some(EntrySecurity {
+                options,
+                key: bytes.read_with(offset, ())?,
+            }),
+            None => None,
+        };
+        let lightweight_sink_addresses = read_if(
+            bytes,
+            offset,
+            options.contains(ProxyTableOptions::LIGHTWEIGHT_UNICAST_GPS),
+        )?;
+        let sink_groups = read_if(
+            bytes,
+            offset,
+            options.contains(ProxyTableOptions::COMMISSIONED_GROUP_GPS),
+        )?;
+        let groupcast_radius = read_if(
+            bytes,
+            offset,
+            options.contains(ProxyTableOptions::DERIVED_GROUP_GPS)
+                || options.contains(ProxyTableOptions::COMMISSIONED_GROUP_GPS),
+        )?;
+        let search_counter = read_if(
+            bytes,
+            offset,
+            !options.contains(ProxyTableOptions::ENTRY_ACTIVE | ProxyTableOptions::ENTRY_VALID),
+        )?;
+
+        Ok((
+            Self {
+                options,
+                gpd_id,
+                assigned_alias,
+                security,
+                security_frame_counter,
+                lightweight_sink_addresses,
+                sink_groups,
+                groupcast_radius,
+                search_counter,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl TryWrite for ProxyTableEntry<'_> {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        let mut options = with_sub_field(
+            u32::from(self.options.0),
+            0,
+            3,
+            self.gpd_id.application_id() as u8,
+        );
+        for &(flag, present) in &[
+            (
+                ProxyTableOptions::ASSIGNED_ALIAS,
+                self.assigned_alias.is_some(),
+            ),
+            (ProxyTableOptions::SECURITY_USE, self.security.is_some()),
+            (
+                ProxyTableOptions::LIGHTWEIGHT_UNICAST_GPS,
+                self.lightweight_sink_addresses.is_some(),
+            ),
+            (
+                ProxyTableOptions::COMMISSIONED_GROUP_GPS,
+                self.sink_groups.is_some(),
+            ),
+            (ProxyTableOptions::OPTIONS_EXTENSION, false),
+        ] {
+            options = with_flag(options, u32::from(flag), present);
+        }
+        options = with_frame_counter_flag(
+            options,
+            u32::from(ProxyTableOptions::SEQUENCE_NUMBER_CAPABILITIES),
+            self.security.is_some(),
+            self.security_frame_counter.is_some(),
+        )?;
+        let groupcast = self.options.contains(ProxyTableOptions::DERIVED_GROUP_GPS)
+            || self.sink_groups.is_some();
+        if self.groupcast_radius.is_some() != groupcast {
+            return Err(byte::Error::BadInput {
+                err: "Groupcast radius does not match the groupcast options",
+            });
+        }
+        let active_and_valid =
+            u32::from(ProxyTableOptions::ENTRY_ACTIVE | ProxyTableOptions::ENTRY_VALID);
+        match self.search_counter {
+            None => options |= active_and_valid,
+            Some(_) if options & active_and_valid == active_and_valid => {
+                return Err(byte::Error::BadInput {
+                    err: "Search counter of an active and valid entry",
+                })
+            }
+            Some(_) => {}
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.write_with(offset, ProxyTableOptions(options as u16), ())?;
+        bytes.write_with(offset, self.gpd_id, ())?;
+        if let Some(assigned_alias) = self.assigned_alias {
+            bytes.write_with(offset, assigned_alias, byte::LE)?;
+        }
+        if let Some(security) = self.security {
+            bytes.write_with(offset, security.options, ())?;
+        }
+        if let Some(security_frame_counter) = self.security_frame_counter {
+            bytes.write_with(offset, security_frame_counter, byte::LE)?;
+        }
+        if let Some(security) = self.security {
+            bytes.write_with(offset, security.key, ())?;
+        }
+        if let Some(lightweight_sink_addresses) = self.lightweight_sink_addresses {
+            bytes.write_with(offset, lightweight_sink_addresses, ())?;
+        }
+        if let Some(sink_groups) = self.sink_groups {
+            bytes.write_with(offset, sink_groups, ())?;
+        }
+        if let Some(groupcast_radius) = self.groupcast_radius {
+            bytes.write_with(offset, groupcast_radius, byte::LE)?;
+        }
+        if let Some(search_counter) = self.search_counter {
+            bytes.write_with(offset, search_counter, byte::LE)?;
+        }
+
+        Ok(*offset)
+    }
+}
+
+impl_byte! {
+    /// A.3.3.2.2.1 Sink Table Entry Options
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct SinkTableOptions(pub u16);
+}
+
+impl SinkTableOptions {
+    /// The GPD uses incremental MAC sequence numbers
+    pub const SEQUENCE_NUMBER_CAPABILITIES: u16 = 1 << 5;
+    /// The GPD stays in receive mode after transmitting
+    pub const RX_ON_CAPABILITY: u16 = 1 << 6;
+    /// The GPD is at a fixed location
+    pub const FIXED_LOCATION: u16 = 1 << 7;
+    /// The assigned alias is present
+    pub const ASSIGNED_ALIAS: u16 = 1 << 8;
+    /// The security fields are present
+    pub const SECURITY_USE: u16 = 1 << 9;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u16) -> bool {
+        self.0 & flags == flags
+    }
+
+    /// Returns the `ApplicationID` sub-field.
+    pub fn application_id(self) -> Option<ApplicationId> {
+        application_id(u32::from(self.0))
+    }
+
+    /// Returns the `CommunicationMode` sub-field.
+    pub fn communication_mode(self) -> CommunicationMode {
+        match sub_field(u32::from(self.0), 3, 2) {
+            0b00 => CommunicationMode::FullUnicast,
+            0b01 => CommunicationMode::DerivedGroupcast,
+            0b10 => CommunicationMode::PrecommissionedGroupcast,
+            _ => CommunicationMode::LightweightUnicast,
+        }
+    }
+
+    /// Returns the options with the `CommunicationMode` sub-field set.
+    #[must_use]
+    pub fn with_communication_mode(self, mode: CommunicationMode) -> Self {
+        #[allow(clippy::cast_possible_truncation)]
+        let options = with_sub_field(u32::from(self.0), 3, 2, mode as u8) as u16;
+        Self(options)
+    }
+}
+
+/// A.3.3.2.2.1 Sink Table Entry
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SinkTableEntry<'a> {
+    /// Options
+    pub options: SinkTableOptions,
+    /// GPD of the entry
+    pub gpd_id: GpdId,
+    /// Device identifier of the GPD
+    pub device_id: u8,
+    /// Groups of the pre-commissioned groupcast communication mode
+    pub sink_groups: Option<List<'a, SinkGroup>>,
+    /// Alias assigned to the GPD
+    pub assigned_alias: Option<u16>,
+    /// Radius for groupcast forwarding
+    pub groupcast_radius: u8,
+    /// Security settings of the GPD
+    pub security: Option<EntrySecurity>,
+    /// GPD security frame counter, or the MAC sequence number
+    pub security_frame_counter: Option<u32>,
+}
+
+impl<'a> TryRead<'a, ()> for SinkTableEntry<'a> {
+    fn try_read(bytes: &'a [u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let options: SinkTableOptions = bytes.read_with(offset, ())?;
+        let gpd_id = bytes.read_with(offset, read_application_id(u32::from(options.0))?)?;
+        let device_id = bytes.read_with(offset, byte::LE)?;
+        let sink_groups = read_if(
+            bytes,
+            offset,
+            options.communication_mode() == CommunicationMode::PrecommissionedGroupcast,
+        )?;
+        let assigned_alias = read_if(
+            bytes,
+            offset,
+            options.contains(SinkTableOptions::ASSIGNED_ALIAS),
+        )?;
+        let groupcast_radius = bytes.read_with(offset, byte::LE)?;
+        let security_use = options.contains(SinkTableOptions::SECURITY_USE);
+        let security_options: Option<SecurityOptions> = read_if(bytes, offset, security_use)?;
+        let security_frame_counter = read_if(
+            bytes,
+            offset,
+            security_use || options.contains(SinkTableOptions::SEQUENCE_NUMBER_CAPABILITIES),
+        )?;
+        let security = match security_options {
+            Some(options) => Some(EntrySecurity {
+                options,
+                key: bytes.read_with(offset, ())?,
+            }),
+            None => None,
+        };
+
+        Ok((
+            Self {
+                options,
+                gpd_id,
+                device_id,
+                sink_groups,
+                assigned_alias,
+                groupcast_radius,
+                security,
+                security_frame_counter,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl TryWrite for SinkTableEntry<'_> {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        let mut options = self.options;
+        if self.sink_groups.is_some() {
+            options = options.with_communication_mode(CommunicationMode::PrecommissionedGroupcast);
+        }
+        let mut bits = with_sub_field(
+            u32::from(options.0),
+            0,
+            3,
+            self.gpd_id.application_id() as u8,
+        );
+        bits = with_flag(
+            bits,
+            u32::from(SinkTableOptions::ASSIGNED_ALIAS),
+            self.assigned_alias.is_some(),
+        );
+        bits = with_flag(
+            bits,
+            u32::from(SinkTableOptions::SECURITY_USE),
+            self.security.is_some(),
+        );
+        bits = with_frame_counter_flag(
+            bits,
+            u32::from(SinkTableOptions::SEQUENCE_NUMBER_CAPABILITIES),
+            self.security.is_some(),
+            self.security_frame_counter.is_some(),
+        )?;
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.write_with(offset, SinkTableOptions(bits as u16), ())?;
+        bytes.write_with(offset, self.gpd_id, ())?;
+        bytes.write_with(offset, self.device_id, byte::LE)?;
+        if options.communication_mode() == CommunicationMode::PrecommissionedGroupcast {
+            bytes.write_with(offset, self.sink_groups.unwrap_or_default(), ())?;
+        }
+        if let Some(assigned_alias) = self.assigned_alias {
+            bytes.write_with(offset, assigned_alias, byte::LE)?;
+        }
+        bytes.write_with(offset, self.groupcast_radius, byte::LE)?;
+        if let Some(security) = self.security {
+            bytes.write_with(offset, security.options, ())?;
+        }
+        if let Some(security_frame_counter) = self.security_frame_counter {
+            bytes.write_with(offset, security_frame_counter, byte::LE)?;
+        }
+        if let Some(security) = self.security {
+            bytes.write_with(offset, security.key, ())?;
+        }
+
+        Ok(*offset)
+    }
+}
+
+/// Value of the `ProxyTable` or `SinkTable` attribute
+///
+/// Long octet string holding the entries back to back.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Table<'a> {
+    entries: &'a [u8],
+}
+
+impl<'a> Table<'a> {
+    /// Encodes proxy or sink table `entries` into `buf` and returns the table borrowing it.
+    pub fn encode<E: TryWrite + Copy>(entries: &[E], buf: &'a mut [u8]) -> byte::Result<Self> {
+        let offset = &mut 0;
+        for entry in entries {
+            buf.write_with(offset, *entry, ())?;
+        }
+        if u16::try_from(*offset).is_err() {
+            return Err(byte::Error::BadInput {
+                err: "Table too long",
+            });
+        }
+
+        Ok(Self {
+            entries: &buf[..*offset],
+        })
+    }
+
+    /// Returns the proxy table entries.
+    pub fn proxy_entries(&self) -> Entries<'a, ProxyTableEntry<'a>> {
+        Entries::new(self.entries, None)
+    }
+
+    /// Returns the sink table entries.
+    pub fn sink_entries(&self) -> Entries<'a, SinkTableEntry<'a>> {
+        Entries::new(self.entries, None)
+    }
+}
+
+impl<'a, C: Default> TryRead<'a, C> for Table<'a> {
+    fn try_read(bytes: &'a [u8], _: C) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let len: u16 = bytes.read_with(offset, byte::LE)?;
+        let entries = bytes.read_with(offset, ctx::Bytes::Len(usize::from(len)))?;
+
+        Ok((Self { entries }, *offset))
+    }
+}
+
+impl<C: Default> TryWrite<C> for Table<'_> {
+    fn try_write(self, bytes: &mut [u8], _: C) -> byte::Result<usize> {
+        let offset = &mut 0;
+        let len = u16::try_from(self.entries.len()).map_err(|_| byte::Error::BadInput {
+            err: "Table too long",
+        })?;
+        bytes.write_with(offset, len, byte::LE)?;
+        bytes.write_with(offset, self.entries, ())?;
+
+        Ok(*offset)
+    }
+}
+
+/// A.3.3.4.7 GP Sink Table Request and A.3.4.2.7 GP Proxy Table Request Commands
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableRequest {
+    /// Requests the entry of a GPD
+    GpdId(GpdId),
+    /// Requests the entries starting at an index
+    Index(u8),
+}
+
+impl TryRead<'_, ()> for TableRequest {
+    fn try_read(bytes: &[u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let options: u8 = bytes.read_with(offset, byte::LE)?;
+        let request = match sub_field(u32::from(options), 3, 2) {
+            0b00 => Self::GpdId(bytes.read_with(offset, read_application_id(u32::from(options))?)?),
+            0b01 => Self::Index(bytes.read_with(offset, byte::LE)?),
+            _ => {
+                return Err(byte::Error::BadInput {
+                    err: "Invalid table request type",
+                })
+            }
+        };
+
+        Ok((request, *offset))
+    }
+}
+
+impl TryWrite for TableRequest {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        match self {
+            Self::GpdId(gpd_id) => {
+                bytes.write_with(offset, gpd_id.application_id() as u8, byte::LE)?;
+                bytes.write_with(offset, gpd_id, ())?;
+            }
+            Self::Index(index) => {
+                bytes.write_with(offset, 0b01_u8 << 3, byte::LE)?;
+                bytes.write_with(offset, index, byte::LE)?;
+            }
+        }
+
+        Ok(*offset)
+    }
+}
+
+/// A.3.3.4.7 GP Sink Table Request Command
+pub type SinkTableRequest = TableRequest;
+
+/// A.3.4.2.7 GP Proxy Table Request Command
+pub type ProxyTableRequest = TableRequest;
+
+/// A.3.4.2.8 GP Sink Table Response and A.3.3.4.8 GP Proxy Table Response Commands
+pub struct TableResponse<'a, E> {
+    /// `Success`, or `NotFound` if the requested entries do not exist
+    pub status: ZclStatus,
+    /// Number of entries in the table
+    pub total_number_of_non_empty_entries: u8,
+    /// Index of the first returned entry
+    pub start_index: u8,
+    entries_count: u8,
+    entries: &'a [u8],
+    entry: PhantomData<E>,
+}
+
+/// A.3.4.2.8 GP Sink Table Response Command
+pub type SinkTableResponse<'a> = TableResponse<'a, SinkTableEntry<'a>>;
+
+/// A.3.3.4.8 GP Proxy Table Response Command
+pub type ProxyTableResponse<'a> = TableResponse<'a, ProxyTableEntry<'a>>;
+
+impl<E> Clone for TableResponse<'_, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E> Copy for TableResponse<'_, E> {}
+
+impl<E> PartialEq for TableResponse<'_, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.status == other.status
+            && self.total_number_of_non_empty_entries == other.total_number_of_non_empty_entries
+            && self.start_index == other.start_index
+            && self.entries_count == other.entries_count
+            && self.entries == other.entries
+    }
+}
+
+impl<E> Eq for TableResponse<'_, E> {}
+
+impl<E> core::fmt::Debug for TableResponse<'_, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "TableResponse {{ status: {:?}, total_number_of_non_empty_entries: {}, start_index: \
+             {}, entries_count: {} }}",
+            self.status,
+            self.total_number_of_non_empty_entries,
+            self.start_index,
+            self.entries_count
+        )
+    }
+}
+
+impl<'a, E: TryRead<'a, ()> + TryWrite + Copy> TableResponse<'a, E> {
+    /// Encodes the entries starting at `start_index` into `buf` and returns the response
+    /// borrowing it.
+    ///
+    /// Returns a `NotFound` response if `entries` is empty.
+    pub fn encode(
+        total_number_of_non_empty_entries: u8,
+        start_index: u8,
+        entries: &[E],
+        buf: &'a mut [u8],
+    ) -> byte::Result<Self> {
+        let entries_count = u8::try_from(entries.len()).map_err(|_| byte::Error::BadInput {
+            err: "Too many table entries",
+        })?;
+        let offset = &mut 0;
+        for entry in entries {
+            buf.write_with(offset, *entry, ())?;
+        }
+        let status = if entries.is_empty() {
+            ZclStatus::NotFound
+        } else {
+            ZclStatus::Success
+        };
+
+        Ok(Self {
+            status,
+            total_number_of_non_empty_entries,
+            start_index,
+            entries_count,
+            entries: &buf[..*offset],
+            entry: PhantomData,
+        })
+    }
+
+    /// Returns the number of returned entries.
+    pub fn entries_count(&self) -> u8 {
+        self.entries_count
+    }
+
+    /// Returns the returned entries.
+    pub fn entries(&self) -> Entries<'a, E> {
+        Entries::new(self.entries, Some(usize::from(self.entries_count)))
+    }
+}
+
+impl<'a, E> TryRead<'a, ()> for TableResponse<'a, E> {
+    fn try_read(bytes: &'a [u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let status = bytes.read_with(offset, ())?;
+        let total_number_of_non_empty_entries = bytes.read_with(offset, byte::LE)?;
+        let start_index = bytes.read_with(offset, byte::LE)?;
+        let entries_count = bytes.read_with(offset, byte::LE)?;
+        let entries = bytes.read_with(offset, ctx::Bytes::Len(bytes.len() - *offset))?;
+
+        Ok((
+            Self {
+                status,
+                total_number_of_non_empty_entries,
+                start_index,
+                entries_count,
+                entries,
+                entry: PhantomData,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl<E> TryWrite for TableResponse<'_, E> {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, self.status, ())?;
+        bytes.write_with(offset, self.total_number_of_non_empty_entries, byte::LE)?;
+        bytes.write_with(offset, self.start_index, byte::LE)?;
+        bytes.write_with(offset, self.entries_count, byte::LE)?;
+        bytes.write_with(offset, self.entries, ())?;
+
+        Ok(*offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::{TryRead, TryWrite};
+
+    use super::*;
+
+    const KEY: Key = Key([
+        0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee,
+        0xff,
+    ]);
+
+    #[test]
+    fn proxy_table_entry() {
+        // given
+        let groups_buf = &mut [0u8; 8];
+        let sink_groups = List::encode(
+            &[
+                SinkGroup {
+                    group_id: 0x0b84,
+                    alias: 0xffff,
+                },
+                SinkGroup {
+                    group_id: 0x0001,
+                    alias: 0x1234,
+                },
+            ],
+            groups_buf,
+        )
+        .expect("Could not encode List in test");
+        let entry = ProxyTableEntry {
+            options: ProxyTableOptions(
+                ProxyTableOptions::ENTRY_ACTIVE | ProxyTableOptions::ENTRY_VALID,
+            ),
+            gpd_id: GpdId::Ieee {
+                address: 0x0011_2233_4455_6677,
+                endpoint: 0x01,
+            },
+            assigned_alias: None,
+            security: Some(EntrySecurity {
+                options: SecurityOptions::new(
+                    SecurityLevel::EncryptionFrameCounterMic,
+                    SecurityKeyType::OutOfTheBoxGpdKey,
+                ),
+                key: KEY,
+            }),
+            security_frame_counter: Some(0x0000_1000),
+            lightweight_sink_addresses: None,
+            sink_groups: Some(sink_groups),
+            groupcast_radius: Some(0x0a),
+            search_counter: None,
+        };
+
+        // when
+        let mut buf = [0u8; 64];
+        let len = entry
+            .try_write(&mut buf, ())
+            .expect("Could not write ProxyTableEntry in test");
+        let (read, read_len) = ProxyTableEntry::try_read(&buf[..len], ())
+            .expect("Could not read ProxyTableEntry in test");
+
+        // then
+        assert_eq!(&buf[..2], &[0x1a, 0x41]);
+        assert_eq!(len, 2 + 9 + 1 + 4 + 16 + 9 + 1);
+        assert_eq!(read_len, len);
+        assert_eq!(read.options.0, 0x411a);
+        assert_eq!(read.gpd_id, entry.gpd_id);
+        assert_eq!(read.security, entry.security);
+        assert_eq!(read.security_frame_counter, Some(0x0000_1000));
+        assert_eq!(read.groupcast_radius, Some(0x0a));
+        assert_eq!(read.search_counter, None);
+        assert_eq!(
+            read.security
+                .map(|security| security.options.security_key_type()),
+            Some(Some(SecurityKeyType::OutOfTheBoxGpdKey))
+        );
+        let groups = read.sink_groups.expect("Missing sink groups in test");
+        assert_eq!(groups.len(), 2);
+        assert_eq!(
+            groups
+                .entries()
+                .nth(1)
+                .and_then(Result::ok)
+                .map(|group| group.alias),
+            Some(0x1234)
+        );
+    }
+
+    #[test]
+    fn proxy_table_entry_round_trip() {
+        // given
+        let entry = ProxyTableEntry {
+            options: ProxyTableOptions(
+                ProxyTableOptions::DERIVED_GROUP_GPS | ProxyTableOptions::ENTRY_VALID,
+            ),
+            gpd_id: GpdId::SrcId(0x1234_5678),
+            assigned_alias: Some(0x4321),
+            security: None,
+            security_frame_counter: Some(0x07),
+            lightweight_sink_addresses: None,
+            sink_groups: None,
+            groupcast_radius: Some(0x0a),
+            search_counter: Some(0x03),
+        };
+
+        // when
+        let mut buf = [0u8; 32];
+        let len = entry
+            .try_write(&mut buf, ())
+            .expect("Could not write ProxyTableEntry in test");
+        let (read, read_len) = ProxyTableEntry::try_read(&buf[..len], ())
+            .expect("Could not read ProxyTableEntry in test");
+        let mut rewritten = [0u8; 32];
+        let rewritten_len = read
+            .try_write(&mut rewritten, ())
+            .expect("Could not write ProxyTableEntry in test");
+
+        // then
+        assert_eq!(read_len, len);
+        assert_eq!(
+            read,
+            ProxyTableEntry {
+                options: ProxyTableOptions(
+                    ProxyTableOptions::DERIVED_GROUP_GPS
+                        | ProxyTableOptions::ENTRY_VALID
+                        | ProxyTableOptions::SEQUENCE_NUMBER_CAPABILITIES
+                        | ProxyTableOptions::ASSIGNED_ALIAS
+                ),
+                ..entry
+            }
+        );
+        assert_eq!(&rewritten[..rewritten_len], &buf[..len]);
+    }
+
+    #[test]
+    fn reject_inconsistent_proxy_table_entry() {
+        // given
+        let entry = ProxyTableEntry {
+            options: ProxyTableOptions::default(),
+            gpd_id: GpdId::SrcId(0x1234_5678),
+            assigned_alias: None,
+            security: None,
+            security_frame_counter: None,
+            lightweight_sink_addresses: None,
+            sink_groups: None,
+            groupcast_radius: None,
+            search_counter: None,
+        };
+        let mut buf = [0u8; 32];
+
+        // when
+        let radius = ProxyTableEntry {
+            groupcast_radius: Some(0x0a),
+            ..entry
+        }
+        .try_write(&mut buf, ());
+        let search_counter = ProxyTableEntry {
+            options: ProxyTableOptions(
+                ProxyTableOptions::ENTRY_ACTIVE | ProxyTableOptions::ENTRY_VALID,
+            ),
+            search_counter: Some(0x01),
+            ..entry
+        }
+        .try_write(&mut buf, ());
+        let frame_counter = ProxyTableEntry {
+            security: Some(EntrySecurity {
+                options: SecurityOptions::new(
+                    SecurityLevel::FrameCounterMic,
+                    SecurityKeyType::NwkKey,
+                ),
+                key: KEY,
+            }),
+            ..entry
+        }
+        .try_write(&mut buf, ());
+
+        // then
+        assert!(matches!(radius, Err(byte::Error::BadInput { .. })));
+        assert!(matches!(search_counter, Err(byte::Error::BadInput { .. })));
+        assert!(matches!(frame_counter, Err(byte::Error::BadInput { .. })));
+        assert_eq!(entry.try_write(&mut buf, ()).ok(), Some(2 + 4));
+        assert_eq!(&buf[..2], &[0x18, 0x00]);
+    }
+
+    #[test]
+    fn sink_table_attribute() {
+        // given
+        let bytes = &[
+            0x0c, 0x00, // length
+            0x20, 0x00, // options: sequence number capabilities
+            0x78, 0x56, 0x34, 0x12, // GPD SrcID
+            0x02, // device id: on/off switch
+            0xff, // groupcast radius
+            0x07, 0x00, 0x00, 0x00, // MAC sequence number
+        ];
+
+        // when
+        let (table, _) = Table::try_read(&bytes[..], ()).expect("Could not read Table in test");
+
+        // then
+        let mut entries = table.sink_entries();
+        let entry = entries
+            .next()
+            .expect("Missing sink table entry in test")
+            .expect("Could not read SinkTableEntry in test");
+        assert!(entries.next().is_none());
+        assert_eq!(entry.gpd_id, GpdId::SrcId(0x1234_5678));
+        assert_eq!(entry.device_id, 0x02);
+        assert_eq!(
+            entry.options.communication_mode(),
+            CommunicationMode::FullUnicast
+        );
+        assert_eq!(entry.security_frame_counter, Some(7));
+        assert_eq!(entry.security, None);
+    }
+
+    #[test]
+    fn encode_table() {
+        // given
+        let entry = SinkTableEntry {
+            options: SinkTableOptions::default(),
+            gpd_id: GpdId::SrcId(0x1234_5678),
+            device_id: 0x02,
+            sink_groups: None,
+            assigned_alias: None,
+            groupcast_radius: 0xff,
+            security: None,
+            security_frame_counter: Some(0x07),
+        };
+        let entries_buf = &mut [0u8; 32];
+        let table =
+            Table::encode(&[entry, entry], entries_buf).expect("Could not encode Table in test");
+
+        // when
+        let mut buf = [0u8; 32];
+        let len = table
+            .try_write(&mut buf, ())
+            .expect("Could not write Table in test");
+        let (read, read_len) =
+            Table::try_read(&buf[..len], ()).expect("Could not read Table in test");
+
+        // then
+        assert_eq!(len, 2 + 2 * 12);
+        assert_eq!(read_len, len);
+        assert_eq!(&buf[..4], &[0x18, 0x00, 0x20, 0x00]);
+        assert_eq!(read, table);
+        let mut entries = read.sink_entries();
+        assert!(matches!(entries.next(), Some(Ok(read_entry)) if read_entry.options.0 == 0x0020));
+        assert!(matches!(entries.next(), Some(Ok(_))));
+        assert!(entries.next().is_none());
+    }
+
+    #[test]
+    fn malformed_table_entry() {
+        // given
+        let bytes = &[
+            0x0e, 0x00, // length
+            0x20, 0x00, // options: sequence number capabilities
+            0x78, 0x56, 0x34, 0x12, // GPD SrcID
+            0x02, // device id: on/off switch
+            0xff, // groupcast radius
+            0x07, 0x00, 0x00, 0x00, // MAC sequence number
+            0x20, 0x00, // truncated entry
+        ];
+
+        // when
+        let (table, _) = Table::try_read(&bytes[..], ()).expect("Could not read Table in test");
+
+        // then
+        let mut entries = table.sink_entries();
+        assert!(matches!(entries.next(), Some(Ok(_))));
+        assert!(matches!(entries.next(), Some(Err(_))));
+        assert!(entries.next().is_none());
+    }
+
+    #[test]
+    fn sink_table_response() {
+        // given
+        let entry = SinkTableEntry {
+            options: SinkTableOptions::default(),
+            gpd_id: GpdId::SrcId(0x0000_0042),
+            device_id: 0x02,
+            sink_groups: Some(List::default()),
+            assigned_alias: Some(0x0042),
+            groupcast_radius: 0x05,
+            security: None,
+            security_frame_counter: None,
+        };
+        let entries_buf = &mut [0u8; 16];
+        let response = SinkTableResponse::encode(3, 1, &[entry], entries_buf)
+            .expect("Could not encode SinkTableResponse in test");
+
+        // when
+        let mut buf = [0u8; 32];
+        let len = response
+            .try_write(&mut buf, ())
+            .expect("Could not write SinkTableResponse in test");
+        let (read, _) = SinkTableResponse::try_read(&buf[..len], ())
+            .expect("Could not read SinkTableResponse in test");
+
+        // then
+        assert_eq!(read.status, ZclStatus::Success);
+        assert_eq!(read.total_number_of_non_empty_entries, 3);
+        assert_eq!(read.start_index, 1);
+        let read_entry = read
+            .entries()
+            .next()
+            .expect("Missing entry in test")
+            .expect("Could not read SinkTableEntry in test");
+        assert_eq!(
+            read_entry.options.communication_mode(),
+            CommunicationMode::PrecommissionedGroupcast
+        );
+        assert_eq!(read_entry.assigned_alias, Some(0x0042));
+        assert_eq!(read_entry.sink_groups.map(|groups| groups.len()), Some(0));
+        let mut request = [0u8; 2];
+        let request_len = TableRequest::Index(4)
+            .try_write(&mut request, ())
+            .expect("Could not write TableRequest in test");
+        assert_eq!(&request[..request_len], &[0x08, 0x04]);
+        assert!(matches!(
+            TableRequest::try_read(&request, ()),
+            Ok((TableRequest::Index(4), 2))
+        ));
+    }
+}
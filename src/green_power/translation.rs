@@ -0,0 +1,412 @@
+//!
+//! A.3.6 Translation Table
+//!
+//! Mapping of the commands sent by GPDs to the ZCL commands executed by the sink. The default
+//! mapping for the commands of generic switches is provided by [`GpdCommandId::zcl_command`],
+//! [`TranslationEntry`] encodes the entries of the GP Translation Table Update command.
+use byte::{BytesExt, TryRead, TryWrite};
+
+use crate::closures::door_lock;
+use crate::data_types::OctetString;
+use crate::general::{identify, level_control, on_off, scenes};
+use crate::impl_byte;
+use crate::lighting::color_control;
+
+/// Home Automation profile identifier
+pub const HOME_AUTOMATION_PROFILE_ID: u16 = 0x0104;
+
+/// Payload length marking a payload derived from the GPD command
+pub const PAYLOAD_DERIVED: u8 = 0xfe;
+
+/// Payload length marking a payload copied from the GPD command
+pub const PAYLOAD_COPIED: u8 = 0xff;
+
+impl_byte! {
+    /// A.4.1 GPD Command Identifiers
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum GpdCommandId {
+        Identify = 0x00,
+        RecallScene0 = 0x10,
+        RecallScene1 = 0x11,
+        RecallScene2 = 0x12,
+        RecallScene3 = 0x13,
+        RecallScene4 = 0x14,
+        RecallScene5 = 0x15,
+        RecallScene6 = 0x16,
+        RecallScene7 = 0x17,
+        StoreScene0 = 0x18,
+        StoreScene1 = 0x19,
+        StoreScene2 = 0x1a,
+        StoreScene3 = 0x1b,
+        StoreScene4 = 0x1c,
+        StoreScene5 = 0x1d,
+        StoreScene6 = 0x1e,
+        StoreScene7 = 0x1f,
+        Off = 0x20,
+        On = 0x21,
+        Toggle = 0x22,
+        Release = 0x23,
+        MoveUp = 0x30,
+        MoveDown = 0x31,
+        StepUp = 0x32,
+        StepDown = 0x33,
+        LevelControlStop = 0x34,
+        MoveUpWithOnOff = 0x35,
+        MoveDownWithOnOff = 0x36,
+        StepUpWithOnOff = 0x37,
+        StepDownWithOnOff = 0x38,
+        MoveHueStop = 0x40,
+        MoveHueUp = 0x41,
+        MoveHueDown = 0x42,
+        StepHueUp = 0x43,
+        StepHueDown = 0x44,
+        MoveSaturationStop = 0x45,
+        MoveSaturationUp = 0x46,
+        MoveSaturationDown = 0x47,
+        StepSaturationUp = 0x48,
+        StepSaturationDown = 0x49,
+        MoveColor = 0x4a,
+        StepColor = 0x4b,
+        LockDoor = 0x50,
+        UnlockDoor = 0x51,
+        Press1Of1 = 0x60,
+        Release1Of1 = 0x61,
+        Press1Of2 = 0x62,
+        Release1Of2 = 0x63,
+        Press2Of2 = 0x64,
+        Release2Of2 = 0x65,
+        ShortPress1Of1 = 0x66,
+        ShortPress1Of2 = 0x67,
+        ShortPress2Of2 = 0x68,
+        AttributeReporting = 0xa0,
+        ManufacturerSpecificAttributeReporting = 0xa1,
+        MultiClusterReporting = 0xa2,
+        ManufacturerSpecificMultiClusterReporting = 0xa3,
+        RequestAttributes = 0xa4,
+        ReadAttributesResponse = 0xa5,
+        ZclTunneling = 0xa6,
+        Commissioning = 0xe0,
+        Decommissioning = 0xe1,
+        Success = 0xe2,
+        ChannelRequest = 0xe3,
+        ApplicationDescription = 0xe4,
+    }
+}
+
+/// ZCL command a GPD command translates to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZclCommand {
+    /// Cluster of the command
+    pub cluster_id: u16,
+    /// Command identifier within the cluster
+    pub command_id: u8,
+}
+
+impl ZclCommand {
+    const fn new(cluster_id: u16, command_id: u8) -> Self {
+        Self {
+            cluster_id,
+            command_id,
+        }
+    }
+}
+
+impl GpdCommandId {
+    /// Returns the ZCL command of the default translation table.
+    ///
+    /// The payload, e.g. the move mode or the scene identifier, is derived from the GPD
+    /// command. Returns `None` for commands without a default translation.
+    pub fn zcl_command(self) -> Option<ZclCommand> {
+        use color_control::ReceivedCommandIdentifier as ColorControl;
+        use door_lock::ReceivedCommandIdentifier as DoorLock;
+        use level_control::ReceivedCommandIdentifier as LevelControl;
+        use on_off::ReceivedCommandIdentifier as OnOff;
+        use scenes::ReceivedCommandIdentifier as Scenes;
+
+        let command = match self {
+            Self::Identify => ZclCommand::new(
+                identify::CLUSTER_ID,
+                identify::ReceivedCommandIdentifier::Identify as u8,
+            ),
+            Self::RecallScene0
+            | Self::RecallScene1
+            | Self::RecallScene2
+            | Self::RecallScene3
+            | Self::RecallScene4
+            | Self::RecallScene5
+            | Self::RecallScene6
+            | Self::RecallScene7 => ZclCommand::new(scenes::CLUSTER_ID, Scenes::RecallScene as u8),
+            Self::StoreScene0
+            | Self::StoreScene1
+            | Self::StoreScene2
+            | Self::StoreScene3
+            | Self::StoreScene4
+            | Self::StoreScene5
+            | Self::StoreScene6
+            | Self::StoreScene7 => ZclCommand::new(scenes::CLUSTER_ID, Scenes::StoreScene as u8),
+            Self::Off => ZclCommand::new(on_off::CLUSTER_ID, OnOff::Off as u8),
+            Self::On => ZclCommand::new(on_off::CLUSTER_ID, OnOff::On as u8),
+            Self::Toggle => ZclCommand::new(on_off::CLUSTER_ID, OnOff::Toggle as u8),
+            Self::MoveUp | Self::MoveDown => {
+                ZclCommand::new(level_control::CLUSTER_ID, LevelControl::Move as u8)
+            }
+            Self::StepUp | Self::StepDown => {
+                ZclCommand::new(level_control::CLUSTER_ID, LevelControl::Step as u8)
+            }
+            Self::LevelControlStop => {
+                ZclCommand::new(level_control::CLUSTER_ID, LevelControl::Stop as u8)
+            }
+            Self::MoveUpWithOnOff | Self::MoveDownWithOnOff => {
+                ZclCommand::new(level_control::CLUSTER_ID, LevelControl::MoveWithOnOff as u8)
+            }
+            Self::StepUpWithOnOff | Self::StepDownWithOnOff => {
+                ZclCommand::new(level_control::CLUSTER_ID, LevelControl::StepWithOnOff as u8)
+            }
+            Self::MoveHueStop | Self::MoveHueUp | Self::MoveHueDown => {
+                ZclCommand::new(color_control::CLUSTER_ID, ColorControl::MoveHue as u8)
+            }
+            Self::StepHueUp | Self::StepHueDown => {
+                ZclCommand::new(color_control::CLUSTER_ID, ColorControl::StepHue as u8)
+            }
+            Self::MoveSaturationStop | Self::MoveSaturationUp | Self::MoveSaturationDown => {
+                ZclCommand::new(
+                    color_control::CLUSTER_ID,
+                    ColorControl::MoveSaturation as u8,
+                )
+            }
+            Self::StepSaturationUp | Self::StepSaturationDown => ZclCommand::new(
+                color_control::CLUSTER_ID,
+                ColorControl::StepSaturation as u8,
+            ),
+            Self::MoveColor => {
+                ZclCommand::new(color_control::CLUSTER_ID, ColorControl::MoveColor as u8)
+            }
+            Self::StepColor => {
+                ZclCommand::new(color_control::CLUSTER_ID, ColorControl::StepColor as u8)
+            }
+            Self::LockDoor => ZclCommand::new(door_lock::CLUSTER_ID, DoorLock::LockDoor as u8),
+            Self::UnlockDoor => ZclCommand::new(door_lock::CLUSTER_ID, DoorLock::UnlockDoor as u8),
+            _ => return None,
+        };
+
+        Some(command)
+    }
+
+    /// Returns the scene identifier of the Recall Scene and Store Scene commands.
+    pub fn scene_id(self) -> Option<u8> {
+        let id = self as u8;
+        match id {
+            0x10..=0x17 => Some(id - 0x10),
+            0x18..=0x1f => Some(id - 0x18),
+            _ => None,
+        }
+    }
+}
+
+/// Payload of the ZCL command of a translation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranslationPayload<'a> {
+    /// The payload is derived from the GPD command as in the default translation table, e.g.
+    /// the move mode and the rate of a Move command
+    Derived,
+    /// The payload of the GPD command is copied
+    Copied,
+    /// Fixed payload of less than [`PAYLOAD_DERIVED`] octets
+    Fixed(OctetString<'a>),
+}
+
+impl<'a> TryRead<'a, ()> for TranslationPayload<'a> {
+    fn try_read(bytes: &'a [u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let payload = match bytes.read_with::<u8>(&mut 0, byte::LE)? {
+            PAYLOAD_DERIVED => {
+                *offset += 1;
+                Self::Derived
+            }
+            PAYLOAD_COPIED => {
+                *offset += 1;
+                Self::Copied
+            }
+            _ => Self::Fixed(bytes.read_with(offset, ())?),
+        };
+
+        Ok((payload, *offset))
+    }
+}
+
+impl TryWrite for TranslationPayload<'_> {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        match self {
+            Self::Derived => bytes.write_with(offset, PAYLOAD_DERIVED, byte::LE)?,
+            Self::Copied => bytes.write_with(offset, PAYLOAD_COPIED, byte::LE)?,
+            Self::Fixed(payload) if payload.len() >= usize::from(PAYLOAD_DERIVED) => {
+                return Err(byte::Error::BadInput {
+                    err: "Translation payload too long",
+                })
+            }
+            Self::Fixed(payload) => bytes.write_with(offset, payload, ())?,
+        }
+
+        Ok(*offset)
+    }
+}
+
+impl_byte! {
+    /// A.3.3.4.5 GP Translation Table Update Translation
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct TranslationEntry<'a> {
+        /// Index of the entry in the translation table
+        pub index: u8,
+        /// GPD command to translate
+        pub gpd_command_id: u8,
+        /// Endpoint executing the ZCL command
+        pub endpoint: u8,
+        /// Profile of the ZCL command
+        pub profile: u16,
+        /// Cluster of the ZCL command
+        pub cluster_id: u16,
+        /// Command identifier of the ZCL command
+        pub command_id: u8,
+        /// Payload of the ZCL command
+        #[ctx = ()]
+        pub payload: TranslationPayload<'a>,
+    }
+}
+
+impl TranslationEntry<'_> {
+    /// Creates an entry from the default translation of a GPD command with the payload derived
+    /// from the GPD command, returns `None` if the command has no default translation.
+    pub fn from_default(index: u8, gpd_command_id: GpdCommandId, endpoint: u8) -> Option<Self> {
+        let command = gpd_command_id.zcl_command()?;
+
+        Some(Self {
+            index,
+            gpd_command_id: gpd_command_id as u8,
+            endpoint,
+            profile: HOME_AUTOMATION_PROFILE_ID,
+            cluster_id: command.cluster_id,
+            command_id: command.command_id,
+            payload: TranslationPayload::Derived,
+        })
+    }
+
+    /// Returns the ZCL command of the entry.
+    pub fn zcl_command(&self) -> ZclCommand {
+        ZclCommand::new(self.cluster_id, self.command_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::TryFrom;
+
+    use byte::{TryRead, TryWrite};
+
+    use super::*;
+
+    #[test]
+    fn default_translation() {
+        // given
+        let toggle = GpdCommandId::try_from(0x22).expect("Invalid GpdCommandId in test");
+
+        // when
+        let command = toggle.zcl_command();
+
+        // then
+        assert_eq!(command, Some(ZclCommand::new(0x0006, 0x02)));
+        assert_eq!(
+            GpdCommandId::StepDownWithOnOff.zcl_command(),
+            Some(ZclCommand::new(0x0008, 0x06))
+        );
+        assert_eq!(
+            GpdCommandId::StoreScene3.zcl_command(),
+            Some(ZclCommand::new(0x0005, 0x04))
+        );
+        assert_eq!(GpdCommandId::StoreScene3.scene_id(), Some(3));
+        assert_eq!(GpdCommandId::Commissioning.zcl_command(), None);
+    }
+
+    #[test]
+    fn translation_entry() {
+        // given
+        let entry = TranslationEntry::from_default(0, GpdCommandId::MoveHueUp, 0x0b)
+            .expect("Missing default translation in test");
+
+        // when
+        let mut buf = [0u8; 16];
+        let len = entry
+            .try_write(&mut buf, ())
+            .expect("Could not write TranslationEntry in test");
+
+        // then
+        assert_eq!(
+            &buf[..len],
+            &[0x00, 0x41, 0x0b, 0x04, 0x01, 0x00, 0x03, 0x01, 0xfe]
+        );
+        assert_eq!(entry.zcl_command(), ZclCommand::new(0x0300, 0x01));
+    }
+
+    #[test]
+    fn move_translation() {
+        // given
+        let move_hue_up = GpdCommandId::try_from(0x41).expect("Invalid GpdCommandId in test");
+        let entry = TranslationEntry::from_default(1, move_hue_up, 0x0b)
+            .expect("Missing default translation in test");
+
+        // when
+        let mut buf = [0u8; 16];
+        let len = entry
+            .try_write(&mut buf, ())
+            .expect("Could not write TranslationEntry in test");
+        let (read, read_len) = TranslationEntry::try_read(&buf[..len], ())
+            .expect("Could not read TranslationEntry in test");
+
+        // then
+        assert_eq!(read_len, len);
+        assert_eq!(read, entry);
+        assert_eq!(
+            read.zcl_command(),
+            ZclCommand::new(
+                color_control::CLUSTER_ID,
+                color_control::ReceivedCommandIdentifier::MoveHue as u8
+            )
+        );
+        assert_eq!(read.payload, TranslationPayload::Derived);
+        let (fixed, _) = TranslationPayload::try_read(&[0x02, 0x01, 0x0a], ())
+            .expect("Could not read TranslationPayload in test");
+        assert_eq!(
+            fixed,
+            TranslationPayload::Fixed(
+                OctetString::new(&[0x01, 0x0a]).expect("Invalid OctetString in test")
+            )
+        );
+    }
+
+    #[test]
+    fn reject_too_long_fixed_payload() {
+        // given
+        let data = [0u8; 0xfe];
+        let payload = TranslationPayload::Fixed(
+            OctetString::new(&data).expect("Invalid OctetString in test"),
+        );
+
+        // when
+        let mut buf = [0u8; 0x100];
+        let result = payload.try_write(&mut buf, ());
+
+        // then
+        assert!(matches!(result, Err(byte::Error::BadInput { .. })));
+        let fixed = TranslationPayload::Fixed(
+            OctetString::new(&data[..0xfd]).expect("Invalid OctetString in test"),
+        );
+        let len = fixed
+            .try_write(&mut buf, ())
+            .expect("Could not write TranslationPayload in test");
+        let (read, _) = TranslationPayload::try_read(&buf[..len], ())
+            .expect("Could not read TranslationPayload in test");
+        assert_eq!(read, fixed);
+    }
+}
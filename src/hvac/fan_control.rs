@@ -0,0 +1,196 @@
+//!
+//! 6.4 Fan Control Cluster
+//!
+//! Attributes for controlling the speed of a fan. The allowed fan modes are determined by the
+//! fan mode sequence, [`FanControl`] rejects writes of fan modes outside of the sequence.
+use crate::impl_byte;
+use crate::status::ZclStatus;
+
+/// Fan Control cluster identifier
+pub const CLUSTER_ID: u16 = 0x0202;
+
+impl_byte! {
+    /// 6.4.2.2 Fan Control Server Attributes
+    #[repr(u16)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        /// Current speed of the fan.
+        FanMode = 0x0000,
+        /// Possible fan speeds.
+        FanModeSequence = 0x0001,
+    }
+}
+
+impl_byte! {
+    /// 6.4.2.2.1 `FanMode` Attribute
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum FanMode {
+        /// Fan is off
+        Off = 0x00,
+        /// Low speed
+        Low = 0x01,
+        /// Medium speed
+        Medium = 0x02,
+        /// High speed
+        High = 0x03,
+        /// Fan is on
+        On = 0x04,
+        /// Speed is determined by the heating/cooling device
+        Auto = 0x05,
+        /// Speed is determined by occupancy
+        Smart = 0x06,
+    }
+}
+
+impl_byte! {
+    /// 6.4.2.2.2 `FanModeSequence` Attribute
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum FanModeSequence {
+        /// Low, Medium and High
+        LowMedHigh = 0x00,
+        /// Low and High
+        LowHigh = 0x01,
+        /// Low, Medium, High and Auto
+        LowMedHighAuto = 0x02,
+        /// Low, High and Auto
+        LowHighAuto = 0x03,
+        /// On and Auto
+        OnAuto = 0x04,
+    }
+}
+
+impl FanModeSequence {
+    /// Returns `true` if the sequence allows the fan mode.
+    ///
+    /// `Off` and `On` are always allowed, `Smart` is allowed by the sequences with `Auto`.
+    pub fn supports(self, mode: FanMode) -> bool {
+        let auto = matches!(
+            self,
+            Self::LowMedHighAuto | Self::LowHighAuto | Self::OnAuto
+        );
+        match mode {
+            FanMode::Off | FanMode::On => true,
+            FanMode::Low | FanMode::High => self != Self::OnAuto,
+            FanMode::Medium => matches!(self, Self::LowMedHigh | Self::LowMedHighAuto),
+            FanMode::Auto | FanMode::Smart => auto,
+        }
+    }
+}
+
+/// Fan Control attributes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FanControl {
+    fan_mode: FanMode,
+    fan_mode_sequence: FanModeSequence,
+}
+
+impl Default for FanControl {
+    fn default() -> Self {
+        Self::new(FanModeSequence::LowMedHighAuto)
+    }
+}
+
+impl FanControl {
+    /// Creates the attributes with the fan in `Auto` mode if the sequence allows it, `Off`
+    /// otherwise.
+    pub fn new(fan_mode_sequence: FanModeSequence) -> Self {
+        let fan_mode = if fan_mode_sequence.supports(FanMode::Auto) {
+            FanMode::Auto
+        } else {
+            FanMode::Off
+        };
+        Self {
+            fan_mode,
+            fan_mode_sequence,
+        }
+    }
+
+    /// Returns the `FanMode` attribute.
+    pub fn fan_mode(&self) -> FanMode {
+        self.fan_mode
+    }
+
+    /// Returns the `FanModeSequence` attribute.
+    pub fn fan_mode_sequence(&self) -> FanModeSequence {
+        self.fan_mode_sequence
+    }
+
+    /// Writes the `FanMode` attribute.
+    ///
+    /// Returns [`ZclStatus::InvalidValue`] if the fan mode sequence does not allow the mode.
+    pub fn set_fan_mode(&mut self, fan_mode: FanMode) -> ZclStatus {
+        if self.fan_mode_sequence.supports(fan_mode) {
+            self.fan_mode = fan_mode;
+            ZclStatus::Success
+        } else {
+            ZclStatus::InvalidValue
+        }
+    }
+
+    /// Writes the `FanModeSequence` attribute.
+    ///
+    /// The fan is turned off if the new sequence does not allow the current fan mode.
+    pub fn set_fan_mode_sequence(&mut self, fan_mode_sequence: FanModeSequence) {
+        self.fan_mode_sequence = fan_mode_sequence;
+        if !fan_mode_sequence.supports(self.fan_mode) {
+            self.fan_mode = FanMode::Off;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::TryFrom;
+
+    use super::*;
+
+    #[test]
+    fn fan_mode_transitions() {
+        // given
+        let mut fan = FanControl::new(FanModeSequence::LowHigh);
+
+        // when
+        let medium = fan.set_fan_mode(FanMode::Medium);
+        let high = fan.set_fan_mode(FanMode::High);
+
+        // then
+        assert_eq!(medium, ZclStatus::InvalidValue);
+        assert_eq!(high, ZclStatus::Success);
+        assert_eq!(fan.fan_mode(), FanMode::High);
+        assert_eq!(fan.set_fan_mode(FanMode::Smart), ZclStatus::InvalidValue);
+
+        fan.set_fan_mode_sequence(FanModeSequence::OnAuto);
+        assert_eq!(fan.fan_mode(), FanMode::Off);
+        assert_eq!(fan.set_fan_mode(FanMode::Smart), ZclStatus::Success);
+        assert_eq!(FanControl::default().fan_mode(), FanMode::Auto);
+    }
+
+    #[test]
+    fn reject_fan_modes_outside_of_sequence() {
+        // given
+        let rejected = [
+            (FanModeSequence::LowMedHigh, FanMode::Auto),
+            (FanModeSequence::LowMedHigh, FanMode::Smart),
+            (FanModeSequence::LowHigh, FanMode::Auto),
+            (FanModeSequence::LowHighAuto, FanMode::Medium),
+            (FanModeSequence::OnAuto, FanMode::Low),
+            (FanModeSequence::OnAuto, FanMode::Medium),
+            (FanModeSequence::OnAuto, FanMode::High),
+        ];
+
+        for &(sequence, mode) in &rejected {
+            // when
+            let mut fan = FanControl::new(sequence);
+            let before = fan.fan_mode();
+            let status = fan.set_fan_mode(mode);
+
+            // then
+            assert_eq!(status, ZclStatus::InvalidValue, "{:?} {:?}", sequence, mode);
+            assert_eq!(fan.fan_mode(), before);
+        }
+        assert!(FanMode::try_from(0x07).is_err());
+        assert!(FanModeSequence::try_from(0x05).is_err());
+    }
+}
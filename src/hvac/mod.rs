@@ -0,0 +1,12 @@
+//!
+//! HVAC Clusters
+
+/// Provides attributes and commands for configuring and controlling a thermostat, including
+/// the weekly schedule.
+pub mod thermostat;
+
+/// Provides attributes for controlling the speed of a fan.
+pub mod fan_control;
+
+/// Provides attributes for configuring the user interface of a thermostat.
+pub mod thermostat_ui_configuration;
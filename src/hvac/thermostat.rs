@@ -0,0 +1,789 @@
+//!
+//! 6.3 Thermostat Cluster
+//!
+//! Attributes and commands for configuring and controlling a thermostat. Temperatures are given
+//! in 1/100th of a degree Celsius. [`Setpoints`] enforces the setpoint limits and the deadband
+//! between heating and cooling setpoints.
+use byte::{ctx, BytesExt, TryRead, TryWrite};
+
+use crate::impl_byte;
+use crate::status::ZclStatus;
+
+/// Thermostat cluster identifier
+pub const CLUSTER_ID: u16 = 0x0201;
+
+/// Value of a temperature attribute indicating an invalid or unknown temperature
+pub const INVALID_TEMPERATURE: i16 = -0x8000;
+
+/// Converts a temperature attribute (1/100th of a degree Celsius) to degrees Celsius.
+///
+/// Returns `None` for [`INVALID_TEMPERATURE`].
+pub fn celsius(temperature: i16) -> Option<f32> {
+    if temperature == INVALID_TEMPERATURE {
+        None
+    } else {
+        Some(f32::from(temperature) / 100.0)
+    }
+}
+
+impl_byte! {
+    /// 6.3.2.2 Thermostat Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        // Thermostat Information
+        LocalTemperature = 0x0000,
+        OutdoorTemperature = 0x0001,
+        Occupancy = 0x0002,
+        AbsMinHeatSetpointLimit = 0x0003,
+        AbsMaxHeatSetpointLimit = 0x0004,
+        AbsMinCoolSetpointLimit = 0x0005,
+        AbsMaxCoolSetpointLimit = 0x0006,
+        PiCoolingDemand = 0x0007,
+        PiHeatingDemand = 0x0008,
+        HvacSystemTypeConfiguration = 0x0009,
+        // Thermostat Settings
+        LocalTemperatureCalibration = 0x0010,
+        OccupiedCoolingSetpoint = 0x0011,
+        OccupiedHeatingSetpoint = 0x0012,
+        UnoccupiedCoolingSetpoint = 0x0013,
+        UnoccupiedHeatingSetpoint = 0x0014,
+        MinHeatSetpointLimit = 0x0015,
+        MaxHeatSetpointLimit = 0x0016,
+        MinCoolSetpointLimit = 0x0017,
+        MaxCoolSetpointLimit = 0x0018,
+        MinSetpointDeadBand = 0x0019,
+        RemoteSensing = 0x001a,
+        ControlSequenceOfOperation = 0x001b,
+        SystemMode = 0x001c,
+        AlarmMask = 0x001d,
+        ThermostatRunningMode = 0x001e,
+        // Thermostat Schedule & HVAC Relay
+        StartOfWeek = 0x0020,
+        NumberOfWeeklyTransitions = 0x0021,
+        NumberOfDailyTransitions = 0x0022,
+        TemperatureSetpointHold = 0x0023,
+        TemperatureSetpointHoldDuration = 0x0024,
+        ThermostatProgrammingOperationMode = 0x0025,
+        ThermostatRunningState = 0x0029,
+        // Thermostat Setpoint Change Tracking
+        SetpointChangeSource = 0x0030,
+        SetpointChangeAmount = 0x0031,
+        SetpointChangeSourceTimestamp = 0x0032,
+        OccupiedSetback = 0x0034,
+        OccupiedSetbackMin = 0x0035,
+        OccupiedSetbackMax = 0x0036,
+        UnoccupiedSetback = 0x0037,
+        UnoccupiedSetbackMin = 0x0038,
+        UnoccupiedSetbackMax = 0x0039,
+        EmergencyHeatDelta = 0x003a,
+        // AC Information
+        AcType = 0x0040,
+        AcCapacity = 0x0041,
+        AcRefrigerantType = 0x0042,
+        AcCompressorType = 0x0043,
+        AcErrorCode = 0x0044,
+        AcLouverPosition = 0x0045,
+        AcCoilTemperature = 0x0046,
+        AcCapacityFormat = 0x0047,
+    }
+}
+
+impl_byte! {
+    /// 6.3.2.3 Commands Received
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ReceivedCommandIdentifier {
+        SetpointRaiseLower = 0x00,
+        SetWeeklySchedule = 0x01,
+        GetWeeklySchedule = 0x02,
+        ClearWeeklySchedule = 0x03,
+        GetRelayStatusLog = 0x04,
+    }
+}
+
+impl_byte! {
+    /// 6.3.2.4 Commands Generated
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum GeneratedCommandIdentifier {
+        GetWeeklyScheduleResponse = 0x00,
+        GetRelayStatusLogResponse = 0x01,
+    }
+}
+
+impl_byte! {
+    /// 6.3.2.2.2.12 `ControlSequenceOfOperation` Attribute
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ControlSequenceOfOperation {
+        CoolingOnly = 0x00,
+        CoolingWithReheat = 0x01,
+        HeatingOnly = 0x02,
+        HeatingWithReheat = 0x03,
+        CoolingAndHeating = 0x04,
+        CoolingAndHeatingWithReheat = 0x05,
+    }
+}
+
+impl ControlSequenceOfOperation {
+    /// Returns `true` if the sequence allows heating.
+    pub fn heating(self) -> bool {
+        !matches!(self, Self::CoolingOnly | Self::CoolingWithReheat)
+    }
+
+    /// Returns `true` if the sequence allows cooling.
+    pub fn cooling(self) -> bool {
+        !matches!(self, Self::HeatingOnly | Self::HeatingWithReheat)
+    }
+}
+
+impl_byte! {
+    /// 6.3.2.2.2.13 `SystemMode` Attribute
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SystemMode {
+        Off = 0x00,
+        Auto = 0x01,
+        Cool = 0x03,
+        Heat = 0x04,
+        EmergencyHeating = 0x05,
+        Precooling = 0x06,
+        FanOnly = 0x07,
+        Dry = 0x08,
+        Sleep = 0x09,
+    }
+}
+
+impl_byte! {
+    /// 6.3.2.2.2.15 `ThermostatRunningMode` Attribute
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ThermostatRunningMode {
+        Off = 0x00,
+        Cool = 0x03,
+        Heat = 0x04,
+    }
+}
+
+impl_byte! {
+    /// 6.3.2.2.1.3 `Occupancy` Attribute
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct Occupancy(pub u8);
+}
+
+impl Occupancy {
+    /// The space is occupied
+    pub const OCCUPIED: u8 = 1 << 0;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u8) -> bool {
+        self.0 & flags == flags
+    }
+}
+
+impl_byte! {
+    /// 6.3.2.2.3.7 `ThermostatRunningState` Attribute
+    ///
+    /// Also used for the relay status of the Get Relay Status Log Response.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct RunningState(pub u16);
+}
+
+impl RunningState {
+    /// Heat stage 1 is on
+    pub const HEAT: u16 = 1 << 0;
+    /// Cool stage 1 is on
+    pub const COOL: u16 = 1 << 1;
+    /// Fan stage 1 is on
+    pub const FAN: u16 = 1 << 2;
+    /// Heat stage 2 is on
+    pub const HEAT_SECOND_STAGE: u16 = 1 << 3;
+    /// Cool stage 2 is on
+    pub const COOL_SECOND_STAGE: u16 = 1 << 4;
+    /// Fan stage 2 is on
+    pub const FAN_SECOND_STAGE: u16 = 1 << 5;
+    /// Fan stage 3 is on
+    pub const FAN_THIRD_STAGE: u16 = 1 << 6;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u16) -> bool {
+        self.0 & flags == flags
+    }
+}
+
+impl_byte! {
+    /// Mode of the Setpoint Raise/Lower command
+    ///
+    /// See Section 6.3.2.3.1.2
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SetpointMode {
+        /// Adjust the heating setpoint
+        Heat = 0x00,
+        /// Adjust the cooling setpoint
+        Cool = 0x01,
+        /// Adjust both setpoints
+        Both = 0x02,
+    }
+}
+
+impl_byte! {
+    /// 6.3.2.3.1 Setpoint Raise/Lower Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct SetpointRaiseLower {
+        /// Setpoints to adjust
+        pub mode: SetpointMode,
+        /// Signed amount in 0.1°C steps
+        pub amount: i8,
+    }
+}
+
+impl_byte! {
+    /// Days of the weekly schedule commands
+    ///
+    /// See Section 6.3.2.3.2.3
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct DayOfWeek(pub u8);
+}
+
+impl DayOfWeek {
+    /// Sunday
+    pub const SUNDAY: u8 = 1 << 0;
+    /// Monday
+    pub const MONDAY: u8 = 1 << 1;
+    /// Tuesday
+    pub const TUESDAY: u8 = 1 << 2;
+    /// Wednesday
+    pub const WEDNESDAY: u8 = 1 << 3;
+    /// Thursday
+    pub const THURSDAY: u8 = 1 << 4;
+    /// Friday
+    pub const FRIDAY: u8 = 1 << 5;
+    /// Saturday
+    pub const SATURDAY: u8 = 1 << 6;
+    /// Away or vacation
+    pub const AWAY_OR_VACATION: u8 = 1 << 7;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u8) -> bool {
+        self.0 & flags == flags
+    }
+}
+
+impl_byte! {
+    /// Setpoints of the weekly schedule commands
+    ///
+    /// Determines which setpoints are present in each transition.
+    ///
+    /// See Section 6.3.2.3.2.4
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct ModeForSequence(pub u8);
+}
+
+impl ModeForSequence {
+    /// Heating setpoint is present
+    pub const HEAT: u8 = 1 << 0;
+    /// Cooling setpoint is present
+    pub const COOL: u8 = 1 << 1;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u8) -> bool {
+        self.0 & flags == flags
+    }
+
+    /// Returns the encoded length of a single transition.
+    pub fn transition_len(self) -> usize {
+        let mut len = 2;
+        if self.contains(Self::HEAT) {
+            len += 2;
+        }
+        if self.contains(Self::COOL) {
+            len += 2;
+        }
+        len
+    }
+}
+
+/// Transition of a weekly schedule
+///
+/// Encoded according to the [`ModeForSequence`] of the command, setpoints not included in the
+/// mode are ignored when writing and `None` when reading.
+///
+/// See Section 6.3.2.3.2.5
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    /// Minutes since midnight
+    pub transition_time: u16,
+    /// Heating setpoint
+    pub heat_setpoint: Option<i16>,
+    /// Cooling setpoint
+    pub cool_setpoint: Option<i16>,
+}
+
+impl TryRead<'_, ModeForSequence> for Transition {
+    fn try_read(bytes: &[u8], mode: ModeForSequence) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let transition_time = bytes.read_with(offset, byte::LE)?;
+        let heat_setpoint = if mode.contains(ModeForSequence::HEAT) {
+            Some(bytes.read_with(offset, byte::LE)?)
+        } else {
+            None
+        };
+        let cool_setpoint = if mode.contains(ModeForSequence::COOL) {
+            Some(bytes.read_with(offset, byte::LE)?)
+        } else {
+            None
+        };
+
+        Ok((
+            Self {
+                transition_time,
+                heat_setpoint,
+                cool_setpoint,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl TryWrite<ModeForSequence> for Transition {
+    fn try_write(self, bytes: &mut [u8], mode: ModeForSequence) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, self.transition_time, byte::LE)?;
+        if mode.contains(ModeForSequence::HEAT) {
+            let setpoint = self.heat_setpoint.unwrap_or(INVALID_TEMPERATURE);
+            bytes.write_with(offset, setpoint, byte::LE)?;
+        }
+        if mode.contains(ModeForSequence::COOL) {
+            let setpoint = self.cool_setpoint.unwrap_or(INVALID_TEMPERATURE);
+            bytes.write_with(offset, setpoint, byte::LE)?;
+        }
+
+        Ok(*offset)
+    }
+}
+
+/// Iterates the transitions of a weekly schedule command.
+#[derive(Debug, Clone)]
+pub struct Transitions<'a> {
+    bytes: &'a [u8],
+    mode: ModeForSequence,
+    offset: usize,
+}
+
+impl Iterator for Transitions<'_> {
+    type Item = Transition;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset >= self.bytes.len() {
+            return None;
+        }
+        self.bytes.read_with(&mut self.offset, self.mode).ok()
+    }
+}
+
+/// 6.3.2.3.2 Set Weekly Schedule Command
+///
+/// Also used for the Get Weekly Schedule Response command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetWeeklySchedule<'a> {
+    /// Days the transitions apply to
+    pub day_of_week_for_sequence: DayOfWeek,
+    /// Setpoints included in the transitions
+    pub mode_for_sequence: ModeForSequence,
+    /// Raw transitions, see [`Self::transitions`]
+    pub transitions: &'a [u8],
+}
+
+/// 6.3.2.4.1 Get Weekly Schedule Response Command
+pub type GetWeeklyScheduleResponse<'a> = SetWeeklySchedule<'a>;
+
+impl<'a> SetWeeklySchedule<'a> {
+    /// Maximum number of transitions of a single command
+    pub const MAX_TRANSITIONS: usize = 10;
+
+    /// Returns the number of transitions.
+    pub fn number_of_transitions(&self) -> usize {
+        self.transitions.len() / self.mode_for_sequence.transition_len()
+    }
+
+    /// Iterates the transitions.
+    pub fn transitions(&self) -> Transitions<'a> {
+        Transitions {
+            bytes: self.transitions,
+            mode: self.mode_for_sequence,
+            offset: 0,
+        }
+    }
+
+    /// Encodes `transitions` according to `mode_for_sequence` into `buf` and returns the command.
+    pub fn encode(
+        day_of_week_for_sequence: DayOfWeek,
+        mode_for_sequence: ModeForSequence,
+        transitions: &[Transition],
+        buf: &'a mut [u8],
+    ) -> byte::Result<Self> {
+        if transitions.len() > Self::MAX_TRANSITIONS {
+            return Err(byte::Error::BadInput {
+                err: "Too many transitions",
+            });
+        }
+        let offset = &mut 0;
+        for transition in transitions {
+            buf.write_with(offset, *transition, mode_for_sequence)?;
+        }
+
+        Ok(Self {
+            day_of_week_for_sequence,
+            mode_for_sequence,
+            transitions: &buf[..*offset],
+        })
+    }
+}
+
+impl<'a> TryRead<'a, ()> for SetWeeklySchedule<'a> {
+    fn try_read(bytes: &'a [u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let number_of_transitions: u8 = bytes.read_with(offset, byte::LE)?;
+        let day_of_week_for_sequence = bytes.read_with(offset, ())?;
+        let mode_for_sequence: ModeForSequence = bytes.read_with(offset, ())?;
+        let len = usize::from(number_of_transitions) * mode_for_sequence.transition_len();
+        let transitions = bytes.read_with(offset, ctx::Bytes::Len(len))?;
+
+        Ok((
+            Self {
+                day_of_week_for_sequence,
+                mode_for_sequence,
+                transitions,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl TryWrite for SetWeeklySchedule<'_> {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.write_with(offset, self.number_of_transitions() as u8, byte::LE)?;
+        bytes.write_with(offset, self.day_of_week_for_sequence, ())?;
+        bytes.write_with(offset, self.mode_for_sequence, ())?;
+        bytes.write(offset, self.transitions)?;
+
+        Ok(*offset)
+    }
+}
+
+impl_byte! {
+    /// 6.3.2.3.3 Get Weekly Schedule Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GetWeeklySchedule {
+        /// Days to return
+        pub days_to_return: DayOfWeek,
+        /// Setpoints to return
+        pub mode_to_return: ModeForSequence,
+    }
+}
+
+impl_byte! {
+    /// 6.3.2.4.2 Get Relay Status Log Response Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GetRelayStatusLogResponse {
+        /// Minutes since midnight
+        pub time_of_day: u16,
+        /// Relay status
+        pub relay_status: RunningState,
+        /// `LocalTemperature` when the relay status was recorded
+        pub local_temperature: i16,
+        /// Humidity in percent, `0xff` if unknown
+        pub humidity_in_percentage: u8,
+        /// Setpoint when the relay status was recorded
+        pub set_point: i16,
+        /// Number of unread entries in the log
+        pub unread_entries: u16,
+    }
+}
+
+/// Setpoint attributes with their limits
+///
+/// Writes are validated against the heating and cooling setpoint limits. If the control sequence
+/// allows heating and cooling, the heating setpoint is kept at least `MinSetpointDeadBand` below
+/// the cooling setpoint by moving the other setpoint, or rejected if that is not possible.
+///
+/// See Section 6.3.2.2.2
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Setpoints {
+    /// `OccupiedCoolingSetpoint` attribute
+    pub occupied_cooling: i16,
+    /// `OccupiedHeatingSetpoint` attribute
+    pub occupied_heating: i16,
+    /// `UnoccupiedCoolingSetpoint` attribute
+    pub unoccupied_cooling: i16,
+    /// `UnoccupiedHeatingSetpoint` attribute
+    pub unoccupied_heating: i16,
+    /// `MinHeatSetpointLimit` attribute
+    pub min_heat_limit: i16,
+    /// `MaxHeatSetpointLimit` attribute
+    pub max_heat_limit: i16,
+    /// `MinCoolSetpointLimit` attribute
+    pub min_cool_limit: i16,
+    /// `MaxCoolSetpointLimit` attribute
+    pub max_cool_limit: i16,
+    /// `MinSetpointDeadBand` attribute in 0.1°C steps
+    pub min_setpoint_dead_band: i8,
+    /// `ControlSequenceOfOperation` attribute
+    pub control_sequence_of_operation: ControlSequenceOfOperation,
+}
+
+impl Default for Setpoints {
+    fn default() -> Self {
+        Self {
+            occupied_cooling: 2600,
+            occupied_heating: 2000,
+            unoccupied_cooling: 2600,
+            unoccupied_heating: 2000,
+            min_heat_limit: 700,
+            max_heat_limit: 3000,
+            min_cool_limit: 1600,
+            max_cool_limit: 3200,
+            min_setpoint_dead_band: 25,
+            control_sequence_of_operation: ControlSequenceOfOperation::CoolingAndHeating,
+        }
+    }
+}
+
+impl Setpoints {
+    /// Returns the deadband in 1/100th of a degree Celsius, zero if the deadband does not apply.
+    fn dead_band(&self) -> i16 {
+        let sequence = self.control_sequence_of_operation;
+        if sequence.heating() && sequence.cooling() {
+            i16::from(self.min_setpoint_dead_band) * 10
+        } else {
+            0
+        }
+    }
+
+    /// Validates new setpoints and restores the deadband, returns the new heating and cooling
+    /// setpoints.
+    fn adjust(
+        &self,
+        (heating, cooling): (i16, i16),
+        new_heating: Option<i16>,
+        new_cooling: Option<i16>,
+    ) -> Result<(i16, i16), ZclStatus> {
+        let mut heating = new_heating.unwrap_or(heating);
+        let mut cooling = new_cooling.unwrap_or(cooling);
+        let heat_range = self.min_heat_limit..=self.max_heat_limit;
+        let cool_range = self.min_cool_limit..=self.max_cool_limit;
+        if new_heating.is_some() && !heat_range.contains(&heating) {
+            return Err(ZclStatus::InvalidValue);
+        }
+        if new_cooling.is_some() && !cool_range.contains(&cooling) {
+            return Err(ZclStatus::InvalidValue);
+        }
+
+        let dead_band = self.dead_band();
+        if dead_band > 0 && heating > cooling.saturating_sub(dead_band) {
+            match (new_heating, new_cooling) {
+                (Some(_), None) => cooling = heating.saturating_add(dead_band),
+                (None, Some(_)) => heating = cooling.saturating_sub(dead_band),
+                _ => return Err(ZclStatus::InvalidValue),
+            }
+            if !heat_range.contains(&heating) || !cool_range.contains(&cooling) {
+                return Err(ZclStatus::InvalidValue);
+            }
+        }
+        Ok((heating, cooling))
+    }
+
+    fn set_occupied(&mut self, heating: Option<i16>, cooling: Option<i16>) -> ZclStatus {
+        match self.adjust(
+            (self.occupied_heating, self.occupied_cooling),
+            heating,
+            cooling,
+        ) {
+            Ok((heating, cooling)) => {
+                self.occupied_heating = heating;
+                self.occupied_cooling = cooling;
+                ZclStatus::Success
+            }
+            Err(status) => status,
+        }
+    }
+
+    fn set_unoccupied(&mut self, heating: Option<i16>, cooling: Option<i16>) -> ZclStatus {
+        match self.adjust(
+            (self.unoccupied_heating, self.unoccupied_cooling),
+            heating,
+            cooling,
+        ) {
+            Ok((heating, cooling)) => {
+                self.unoccupied_heating = heating;
+                self.unoccupied_cooling = cooling;
+                ZclStatus::Success
+            }
+            Err(status) => status,
+        }
+    }
+
+    /// Writes the `OccupiedHeatingSetpoint` attribute.
+    pub fn set_occupied_heating(&mut self, setpoint: i16) -> ZclStatus {
+        self.set_occupied(Some(setpoint), None)
+    }
+
+    /// Writes the `OccupiedCoolingSetpoint` attribute.
+    pub fn set_occupied_cooling(&mut self, setpoint: i16) -> ZclStatus {
+        self.set_occupied(None, Some(setpoint))
+    }
+
+    /// Writes the `UnoccupiedHeatingSetpoint` attribute.
+    pub fn set_unoccupied_heating(&mut self, setpoint: i16) -> ZclStatus {
+        self.set_unoccupied(Some(setpoint), None)
+    }
+
+    /// Writes the `UnoccupiedCoolingSetpoint` attribute.
+    pub fn set_unoccupied_cooling(&mut self, setpoint: i16) -> ZclStatus {
+        self.set_unoccupied(None, Some(setpoint))
+    }
+
+    /// Handles the Setpoint Raise/Lower command, which adjusts the occupied setpoints.
+    ///
+    /// See Section 6.3.2.3.1
+    pub fn setpoint_raise_lower(&mut self, command: SetpointRaiseLower) -> ZclStatus {
+        let amount = i16::from(command.amount) * 10;
+        let heating = self.occupied_heating.saturating_add(amount);
+        let cooling = self.occupied_cooling.saturating_add(amount);
+        match command.mode {
+            SetpointMode::Heat => self.set_occupied(Some(heating), None),
+            SetpointMode::Cool => self.set_occupied(None, Some(cooling)),
+            SetpointMode::Both => self.set_occupied(Some(heating), Some(cooling)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::TryRead;
+
+    use super::*;
+
+    #[test]
+    fn unpack_set_weekly_schedule() {
+        // given
+        let input = [
+            0x02, // number of transitions
+            0x3e, // monday - friday
+            0x01, // heat
+            0x68, 0x01, 0x34, 0x08, // 06:00 21.00°C
+            0x4c, 0x04, 0x08, 0x07, // 18:20 18.00°C
+        ];
+
+        // when
+        let (command, len) = SetWeeklySchedule::try_read(&input, ())
+            .expect("Could not read SetWeeklySchedule in test");
+
+        // then
+        assert_eq!(len, input.len());
+        assert!(command
+            .day_of_week_for_sequence
+            .contains(DayOfWeek::MONDAY | DayOfWeek::FRIDAY));
+        assert_eq!(command.number_of_transitions(), 2);
+        let mut transitions = command.transitions();
+        assert_eq!(
+            transitions.next(),
+            Some(Transition {
+                transition_time: 360,
+                heat_setpoint: Some(2100),
+                cool_setpoint: None,
+            })
+        );
+        assert_eq!(transitions.next().map(|t| t.transition_time), Some(1100));
+        assert_eq!(transitions.next(), None);
+    }
+
+    #[test]
+    fn pack_set_weekly_schedule() {
+        // given
+        let transitions = [Transition {
+            transition_time: 480,
+            heat_setpoint: Some(2000),
+            cool_setpoint: Some(2600),
+        }];
+        let mut buf = [0u8; 16];
+
+        // when
+        let command = SetWeeklySchedule::encode(
+            DayOfWeek(DayOfWeek::SATURDAY),
+            ModeForSequence(ModeForSequence::HEAT | ModeForSequence::COOL),
+            &transitions,
+            &mut buf,
+        )
+        .expect("Could not encode SetWeeklySchedule in test");
+        let mut out = [0u8; 16];
+        let len = command
+            .try_write(&mut out, ())
+            .expect("Could not write SetWeeklySchedule in test");
+
+        // then
+        assert_eq!(
+            &out[..len],
+            &[0x01, 0x40, 0x03, 0xe0, 0x01, 0xd0, 0x07, 0x28, 0x0a]
+        );
+    }
+
+    #[test]
+    fn setpoint_dead_band() {
+        // given
+        let mut setpoints = Setpoints::default();
+
+        // when
+        let status = setpoints.set_occupied_heating(2500);
+
+        // then
+        assert_eq!(status, ZclStatus::Success);
+        assert_eq!(setpoints.occupied_cooling, 2750);
+        assert_eq!(
+            setpoints.set_occupied_cooling(3300),
+            ZclStatus::InvalidValue
+        );
+        assert_eq!(
+            setpoints.set_occupied_heating(3000),
+            ZclStatus::InvalidValue
+        );
+        assert_eq!(setpoints.occupied_heating, 2500);
+        assert_eq!(
+            setpoints.setpoint_raise_lower(SetpointRaiseLower {
+                mode: SetpointMode::Both,
+                amount: -10,
+            }),
+            ZclStatus::Success
+        );
+        assert_eq!(setpoints.occupied_heating, 2400);
+        assert_eq!(setpoints.occupied_cooling, 2650);
+    }
+
+    #[test]
+    fn heating_only_has_no_dead_band() {
+        // given
+        let mut setpoints = Setpoints {
+            control_sequence_of_operation: ControlSequenceOfOperation::HeatingOnly,
+            ..Setpoints::default()
+        };
+
+        // when
+        let status = setpoints.set_occupied_heating(2600);
+
+        // then
+        assert_eq!(status, ZclStatus::Success);
+        assert_eq!(setpoints.occupied_cooling, 2600);
+        assert_eq!(celsius(setpoints.occupied_heating), Some(26.0));
+        assert_eq!(celsius(INVALID_TEMPERATURE), None);
+    }
+}
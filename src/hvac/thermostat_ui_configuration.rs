@@ -0,0 +1,152 @@
+//!
+//! 6.6 Thermostat User Interface Configuration Cluster
+//!
+//! Attributes for configuring the user interface of a thermostat, e.g. the displayed temperature
+//! unit and the keypad lockout.
+use crate::hvac::thermostat::celsius;
+use crate::impl_byte;
+
+/// Thermostat User Interface Configuration cluster identifier
+pub const CLUSTER_ID: u16 = 0x0204;
+
+impl_byte! {
+    /// 6.6.2.2 Thermostat User Interface Configuration Server Attributes
+    #[repr(u16)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        /// Unit of the displayed temperatures.
+        TemperatureDisplayMode = 0x0000,
+        /// Level of functionality available on the keypad.
+        KeypadLockout = 0x0001,
+        /// Whether local schedule programming is visible.
+        ScheduleProgrammingVisibility = 0x0002,
+    }
+}
+
+impl_byte! {
+    /// 6.6.2.2.1 `TemperatureDisplayMode` Attribute
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum TemperatureDisplayMode {
+        Celsius = 0x00,
+        Fahrenheit = 0x01,
+    }
+}
+
+impl TemperatureDisplayMode {
+    /// Converts a temperature attribute (1/100th of a degree Celsius) to the display unit.
+    ///
+    /// Returns `None` for an invalid temperature.
+    pub fn display(self, temperature: i16) -> Option<f32> {
+        let degrees = celsius(temperature)?;
+        match self {
+            Self::Celsius => Some(degrees),
+            Self::Fahrenheit => Some(degrees * 9.0 / 5.0 + 32.0),
+        }
+    }
+}
+
+impl_byte! {
+    /// 6.6.2.2.2 `KeypadLockout` Attribute
+    ///
+    /// Higher levels restrict more functionality, the exact restrictions are manufacturer
+    /// specific.
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum KeypadLockout {
+        NoLockout = 0x00,
+        Level1 = 0x01,
+        Level2 = 0x02,
+        Level3 = 0x03,
+        Level4 = 0x04,
+        Level5 = 0x05,
+    }
+}
+
+impl KeypadLockout {
+    /// Returns the lockout level, `0` if the keypad is not locked.
+    pub fn level(self) -> u8 {
+        self as u8
+    }
+
+    /// Returns `true` if the keypad is locked at any level.
+    pub fn is_locked(self) -> bool {
+        self != Self::NoLockout
+    }
+}
+
+impl_byte! {
+    /// 6.6.2.2.3 `ScheduleProgrammingVisibility` Attribute
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ScheduleProgrammingVisibility {
+        /// Local schedule programming is enabled
+        Enabled = 0x00,
+        /// Local schedule programming is hidden
+        Disabled = 0x01,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::TryRead;
+
+    use super::*;
+
+    #[test]
+    fn display_temperature() {
+        // given
+        let temperature = 2150;
+
+        // when
+        let fahrenheit = TemperatureDisplayMode::Fahrenheit.display(temperature);
+
+        // then
+        assert_eq!(fahrenheit, Some(70.7));
+        assert_eq!(TemperatureDisplayMode::Celsius.display(-0x8000), None);
+        let (lockout, _) =
+            KeypadLockout::try_read(&[0x03], ()).expect("Could not read KeypadLockout in test");
+        assert_eq!(lockout.level(), 3);
+        assert!(lockout.is_locked());
+        assert!(KeypadLockout::try_read(&[0x06], ()).is_err());
+    }
+
+    #[test]
+    fn reject_invalid_attribute_values() {
+        // given
+        let read_display_mode = |mode| TemperatureDisplayMode::try_read(&[mode], ());
+        let read_lockout = |lockout| KeypadLockout::try_read(&[lockout], ());
+
+        // when
+        let display_modes = [
+            read_display_mode(0x00),
+            read_display_mode(0x01),
+            read_display_mode(0x02),
+        ];
+        let lockouts = [
+            read_lockout(0x00),
+            read_lockout(0x05),
+            read_lockout(0x06),
+            read_lockout(0xff),
+        ];
+
+        // then
+        assert!(matches!(
+            display_modes[0],
+            Ok((TemperatureDisplayMode::Celsius, 1))
+        ));
+        assert!(matches!(
+            display_modes[1],
+            Ok((TemperatureDisplayMode::Fahrenheit, 1))
+        ));
+        assert!(display_modes[2].is_err());
+        assert!(matches!(lockouts[0], Ok((KeypadLockout::NoLockout, 1))));
+        assert!(!KeypadLockout::NoLockout.is_locked());
+        assert!(matches!(lockouts[1], Ok((KeypadLockout::Level5, 1))));
+        assert!(lockouts[2].is_err());
+        assert!(lockouts[3].is_err());
+        assert!(ScheduleProgrammingVisibility::try_read(&[0x02], ()).is_err());
+    }
+}
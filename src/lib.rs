@@ -28,11 +28,38 @@
     clippy::ignored_unit_patterns
 )]
 
+/// Closures
+pub mod closures;
+
 mod common;
+pub mod data_types;
 /// General ZCL Frame
 pub mod frame;
 
+/// General
+pub mod general;
+
+/// Green Power
+pub mod green_power;
+
 pub mod header;
 
+/// HVAC
+pub mod hvac;
+
+/// Lighting
+pub mod lighting;
+
 /// Measurement
 pub mod measurement;
+
+/// OTA Upgrade
+pub mod ota;
+
+/// Security and Safety
+pub mod security;
+
+/// Smart Energy
+pub mod smart_energy;
+
+pub mod status;
@@ -0,0 +1,1553 @@
+//!
+//! 5.2 Color Control Cluster
+//!
+//! Attributes and commands for controlling the color of a color capable light, either as
+//! hue/saturation, CIE xy or color temperature. Also provides `no_std` conversions between CIE xy,
+//! HSV, color temperature in mireds and sRGB, see [`Color`]. [`ColorControlServer`] holds the
+//! attributes that are part of a scene.
+use core::convert::TryFrom;
+
+use byte::{BytesExt, TryRead, TryWrite};
+
+use crate::general::level_control::read_options;
+use crate::general::scenes::{ColorControlExtensionFieldSet, SceneExtension};
+use crate::impl_byte;
+use crate::status::ZclStatus;
+
+/// Color Control cluster identifier
+pub const CLUSTER_ID: u16 = 0x0300;
+
+/// Largest value of `CurrentHue` and `CurrentSaturation`
+pub const MAX_HUE_SATURATION: u8 = 0xfe;
+
+/// Largest value of `CurrentX` and `CurrentY`
+pub const MAX_XY: u16 = 0xfeff;
+
+impl_byte! {
+    /// 5.2.2.2 Color Control Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        // Color Information
+        CurrentHue = 0x0000,
+        CurrentSaturation = 0x0001,
+        RemainingTime = 0x0002,
+        CurrentX = 0x0003,
+        CurrentY = 0x0004,
+        DriftCompensation = 0x0005,
+        CompensationText = 0x0006,
+        ColorTemperatureMireds = 0x0007,
+        ColorMode = 0x0008,
+        Options = 0x000f,
+        EnhancedCurrentHue = 0x4000,
+        EnhancedColorMode = 0x4001,
+        ColorLoopActive = 0x4002,
+        ColorLoopDirection = 0x4003,
+        ColorLoopTime = 0x4004,
+        ColorLoopStartEnhancedHue = 0x4005,
+        ColorLoopStoredEnhancedHue = 0x4006,
+        ColorCapabilities = 0x400a,
+        ColorTempPhysicalMinMireds = 0x400b,
+        ColorTempPhysicalMaxMireds = 0x400c,
+        CoupleColorTempToLevelMinMireds = 0x400d,
+        StartUpColorTemperatureMireds = 0x4010,
+        // Defined Primaries Information
+        NumberOfPrimaries = 0x0010,
+        Primary1X = 0x0011,
+        Primary1Y = 0x0012,
+        Primary1Intensity = 0x0013,
+        Primary2X = 0x0015,
+        Primary2Y = 0x0016,
+        Primary2Intensity = 0x0017,
+        Primary3X = 0x0019,
+        Primary3Y = 0x001a,
+        Primary3Intensity = 0x001b,
+        // Additional Defined Primaries Information
+        Primary4X = 0x0020,
+        Primary4Y = 0x0021,
+        Primary4Intensity = 0x0022,
+        Primary5X = 0x0024,
+        Primary5Y = 0x0025,
+        Primary5Intensity = 0x0026,
+        Primary6X = 0x0028,
+        Primary6Y = 0x0029,
+        Primary6Intensity = 0x002a,
+        // Defined Color Point Settings
+        WhitePointX = 0x0030,
+        WhitePointY = 0x0031,
+        ColorPointRX = 0x0032,
+        ColorPointRY = 0x0033,
+        ColorPointRIntensity = 0x0034,
+        ColorPointGX = 0x0036,
+        ColorPointGY = 0x0037,
+        ColorPointGIntensity = 0x0038,
+        ColorPointBX = 0x003a,
+        ColorPointBY = 0x003b,
+        ColorPointBIntensity = 0x003c,
+    }
+}
+
+impl_byte! {
+    /// 5.2.2.3 Commands Received
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ReceivedCommandIdentifier {
+        MoveToHue = 0x00,
+        MoveHue = 0x01,
+        StepHue = 0x02,
+        MoveToSaturation = 0x03,
+        MoveSaturation = 0x04,
+        StepSaturation = 0x05,
+        MoveToHueAndSaturation = 0x06,
+        MoveToColor = 0x07,
+        MoveColor = 0x08,
+        StepColor = 0x09,
+        MoveToColorTemperature = 0x0a,
+        EnhancedMoveToHue = 0x40,
+        EnhancedMoveHue = 0x41,
+        EnhancedStepHue = 0x42,
+        EnhancedMoveToHueAndSaturation = 0x43,
+        ColorLoopSet = 0x44,
+        StopMoveStep = 0x47,
+        MoveColorTemperature = 0x4b,
+        StepColorTemperature = 0x4c,
+    }
+}
+
+impl_byte! {
+    /// 5.2.2.2.1.6 `DriftCompensation` Attribute
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum DriftCompensation {
+        None = 0x00,
+        OtherOrUnknown = 0x01,
+        TemperatureMonitoring = 0x02,
+        OpticalLuminanceMonitoringAndFeedback = 0x03,
+        OpticalColorMonitoringAndFeedback = 0x04,
+    }
+}
+
+impl_byte! {
+    /// 5.2.2.2.1.9 `ColorMode` Attribute
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ColorMode {
+        /// `CurrentHue` and `CurrentSaturation`
+        HueAndSaturation = 0x00,
+        /// `CurrentX` and `CurrentY`
+        Xy = 0x01,
+        /// `ColorTemperatureMireds`
+        ColorTemperature = 0x02,
+    }
+}
+
+impl_byte! {
+    /// 5.2.2.2.1.11 `EnhancedColorMode` Attribute
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum EnhancedColorMode {
+        /// `CurrentHue` and `CurrentSaturation`
+        HueAndSaturation = 0x00,
+        /// `CurrentX` and `CurrentY`
+        Xy = 0x01,
+        /// `ColorTemperatureMireds`
+        ColorTemperature = 0x02,
+        /// `EnhancedCurrentHue` and `CurrentSaturation`
+        EnhancedHueAndSaturation = 0x03,
+    }
+}
+
+/// 5.2.2.2.1.10 `Options` Attribute
+///
+/// Shared with the Level Control cluster, only [`Options::EXECUTE_IF_OFF`] applies to the Color
+/// Control cluster.
+pub use crate::general::level_control::Options;
+
+impl_byte! {
+    /// 5.2.2.2.1.19 `ColorCapabilities` Attribute
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct ColorCapabilities(pub u16);
+}
+
+impl ColorCapabilities {
+    /// Hue and saturation are supported
+    pub const HUE_SATURATION: u16 = 1 << 0;
+    /// Enhanced hue is supported
+    pub const ENHANCED_HUE: u16 = 1 << 1;
+    /// Color loop is supported
+    pub const COLOR_LOOP: u16 = 1 << 2;
+    /// CIE xy is supported
+    pub const XY: u16 = 1 << 3;
+    /// Color temperature is supported
+    pub const COLOR_TEMPERATURE: u16 = 1 << 4;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u16) -> bool {
+        self.0 & flags == flags
+    }
+}
+
+/// Options mask and options override fields of the commands
+///
+/// The fields were added in a later revision and are read as zero if they are missing.
+///
+/// See Section 5.2.2.3.1.5
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OptionsOverride {
+    /// Bits of `Options` to override
+    pub options_mask: Options,
+    /// Values of the overridden bits
+    pub options_override: Options,
+}
+
+impl OptionsOverride {
+    /// Returns the options to use for the command, i.e. the bits of `options_mask` are taken from
+    /// `options_override`, all other bits from `options`.
+    pub fn apply(self, options: Options) -> Options {
+        options.apply(self.options_mask, self.options_override)
+    }
+}
+
+impl<C: Default> TryRead<'_, C> for OptionsOverride {
+    fn try_read(bytes: &[u8], _: C) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let (options_mask, options_override) = read_options(bytes, offset)?;
+
+        Ok((
+            Self {
+                options_mask,
+                options_override,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl<C: Default> TryWrite<C> for OptionsOverride {
+    fn try_write(self, bytes: &mut [u8], _: C) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, self.options_mask, ())?;
+        bytes.write_with(offset, self.options_override, ())?;
+
+        Ok(*offset)
+    }
+}
+
+impl_byte! {
+    /// Direction of the Move To Hue command
+    ///
+    /// See Section 5.2.2.3.2.2
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Direction {
+        ShortestDistance = 0x00,
+        LongestDistance = 0x01,
+        Up = 0x02,
+        Down = 0x03,
+    }
+}
+
+impl_byte! {
+    /// Move mode of the move commands
+    ///
+    /// See Section 5.2.2.3.3.1
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum MoveMode {
+        Stop = 0x00,
+        Up = 0x01,
+        Down = 0x03,
+    }
+}
+
+impl_byte! {
+    /// Step mode of the step commands
+    ///
+    /// See Section 5.2.2.3.4.1
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum StepMode {
+        Up = 0x01,
+        Down = 0x03,
+    }
+}
+
+impl_byte! {
+    /// 5.2.2.3.2 Move To Hue Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct MoveToHue {
+        pub hue: u8,
+        pub direction: Direction,
+        pub transition_time: u16,
+        pub options: OptionsOverride,
+    }
+}
+
+impl_byte! {
+    /// 5.2.2.3.3 Move Hue Command
+    ///
+    /// Also used for the Move Saturation command.
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct MoveHue {
+        pub move_mode: MoveMode,
+        /// Rate in steps per second
+        pub rate: u8,
+        pub options: OptionsOverride,
+    }
+}
+
+/// 5.2.2.3.6 Move Saturation Command
+pub type MoveSaturation = MoveHue;
+
+impl_byte! {
+    /// 5.2.2.3.4 Step Hue Command
+    ///
+    /// Also used for the Step Saturation command.
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct StepHue {
+        pub step_mode: StepMode,
+        pub step_size: u8,
+        /// Transition time in 1/10th of a second
+        pub transition_time: u8,
+        pub options: OptionsOverride,
+    }
+}
+
+/// 5.2.2.3.7 Step Saturation Command
+pub type StepSaturation = StepHue;
+
+impl_byte! {
+    /// 5.2.2.3.5 Move To Saturation Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct MoveToSaturation {
+        pub saturation: u8,
+        pub transition_time: u16,
+        pub options: OptionsOverride,
+    }
+}
+
+impl_byte! {
+    /// 5.2.2.3.8 Move To Hue And Saturation Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct MoveToHueAndSaturation {
+        pub hue: u8,
+        pub saturation: u8,
+        pub transition_time: u16,
+        pub options: OptionsOverride,
+    }
+}
+
+impl_byte! {
+    /// 5.2.2.3.9 Move To Color Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct MoveToColor {
+        pub color_x: u16,
+        pub color_y: u16,
+        pub transition_time: u16,
+        pub options: OptionsOverride,
+    }
+}
+
+impl_byte! {
+    /// 5.2.2.3.10 Move Color Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct MoveColor {
+        /// Rate of `CurrentX` in units per second
+        pub rate_x: i16,
+        /// Rate of `CurrentY` in units per second
+        pub rate_y: i16,
+        pub options: OptionsOverride,
+    }
+}
+
+impl_byte! {
+    /// 5.2.2.3.11 Step Color Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct StepColor {
+        pub step_x: i16,
+        pub step_y: i16,
+        pub transition_time: u16,
+        pub options: OptionsOverride,
+    }
+}
+
+impl_byte! {
+    /// 5.2.2.3.12 Move To Color Temperature Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct MoveToColorTemperature {
+        pub color_temperature_mireds: u16,
+        pub transition_time: u16,
+        pub options: OptionsOverride,
+    }
+}
+
+impl_byte! {
+    /// 5.2.2.3.13 Enhanced Move To Hue Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct EnhancedMoveToHue {
+        pub enhanced_hue: u16,
+        pub direction: Direction,
+        pub transition_time: u16,
+        pub options: OptionsOverride,
+    }
+}
+
+impl_byte! {
+    /// 5.2.2.3.14 Enhanced Move Hue Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct EnhancedMoveHue {
+        pub move_mode: MoveMode,
+        /// Rate in steps per second
+        pub rate: u16,
+        pub options: OptionsOverride,
+    }
+}
+
+impl_byte! {
+    /// 5.2.2.3.15 Enhanced Step Hue Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct EnhancedStepHue {
+        pub step_mode: StepMode,
+        pub step_size: u16,
+        pub transition_time: u16,
+        pub options: OptionsOverride,
+    }
+}
+
+impl_byte! {
+    /// 5.2.2.3.16 Enhanced Move To Hue And Saturation Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct EnhancedMoveToHueAndSaturation {
+        pub enhanced_hue: u16,
+        pub saturation: u8,
+        pub transition_time: u16,
+        pub options: OptionsOverride,
+    }
+}
+
+impl_byte! {
+    /// Update flags of the Color Loop Set command
+    ///
+    /// See Section 5.2.2.3.17.1
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct UpdateFlags(pub u8);
+}
+
+impl UpdateFlags {
+    /// Update `ColorLoopActive` from the action field
+    pub const UPDATE_ACTION: u8 = 1 << 0;
+    /// Update `ColorLoopDirection` from the direction field
+    pub const UPDATE_DIRECTION: u8 = 1 << 1;
+    /// Update `ColorLoopTime` from the time field
+    pub const UPDATE_TIME: u8 = 1 << 2;
+    /// Update `ColorLoopStartEnhancedHue` from the start hue field
+    pub const UPDATE_START_HUE: u8 = 1 << 3;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u8) -> bool {
+        self.0 & flags == flags
+    }
+}
+
+impl_byte! {
+    /// Action of the Color Loop Set command
+    ///
+    /// See Section 5.2.2.3.17.2
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ColorLoopAction {
+        /// Deactivate the color loop
+        Deactivate = 0x00,
+        /// Activate the color loop from `ColorLoopStartEnhancedHue`
+        ActivateFromStartHue = 0x01,
+        /// Activate the color loop from `EnhancedCurrentHue`
+        ActivateFromEnhancedCurrentHue = 0x02,
+    }
+}
+
+impl_byte! {
+    /// Direction of the Color Loop Set command
+    ///
+    /// See Section 5.2.2.3.17.3
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ColorLoopDirection {
+        Decrement = 0x00,
+        Increment = 0x01,
+    }
+}
+
+impl_byte! {
+    /// 5.2.2.3.17 Color Loop Set Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ColorLoopSet {
+        pub update_flags: UpdateFlags,
+        pub action: ColorLoopAction,
+        pub direction: ColorLoopDirection,
+        /// Time in seconds for a full color loop
+        pub time: u16,
+        pub start_hue: u16,
+        pub options: OptionsOverride,
+    }
+}
+
+impl_byte! {
+    /// 5.2.2.3.18 Stop Move Step Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct StopMoveStep {
+        pub options: OptionsOverride,
+    }
+}
+
+impl_byte! {
+    /// 5.2.2.3.19 Move Color Temperature Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct MoveColorTemperature {
+        pub move_mode: MoveMode,
+        /// Rate in mireds per second
+        pub rate: u16,
+        /// Lower bound of the move, `0` means `ColorTempPhysicalMinMireds`
+        pub color_temperature_minimum_mireds: u16,
+        /// Upper bound of the move, `0` means `ColorTempPhysicalMaxMireds`
+        pub color_temperature_maximum_mireds: u16,
+        pub options: OptionsOverride,
+    }
+}
+
+impl_byte! {
+    /// 5.2.2.3.20 Step Color Temperature Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct StepColorTemperature {
+        pub step_mode: StepMode,
+        pub step_size: u16,
+        pub transition_time: u16,
+        /// Lower bound of the step, `0` means `ColorTempPhysicalMinMireds`
+        pub color_temperature_minimum_mireds: u16,
+        /// Upper bound of the step, `0` means `ColorTempPhysicalMaxMireds`
+        pub color_temperature_maximum_mireds: u16,
+        pub options: OptionsOverride,
+    }
+}
+
+/// Physical color temperature range of a device in mireds
+///
+/// See Sections 5.2.2.2.1.20 and 5.2.2.2.1.21
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MiredsRange {
+    /// `ColorTempPhysicalMinMireds` attribute
+    pub min: u16,
+    /// `ColorTempPhysicalMaxMireds` attribute
+    pub max: u16,
+}
+
+impl Default for MiredsRange {
+    fn default() -> Self {
+        Self {
+            min: 0x0000,
+            max: 0xfeff,
+        }
+    }
+}
+
+impl MiredsRange {
+    /// Limits `mireds` to the physical range.
+    pub fn clamp(self, mireds: u16) -> u16 {
+        mireds.max(self.min).min(self.max)
+    }
+
+    /// Returns the range of a Move or Step Color Temperature command, where a bound of `0` means
+    /// the physical limit.
+    #[must_use]
+    pub fn with_bounds(self, minimum: u16, maximum: u16) -> Self {
+        let min = if minimum == 0 {
+            self.min
+        } else {
+            self.clamp(minimum)
+        };
+        let max = if maximum == 0 {
+            self.max
+        } else {
+            self.clamp(maximum)
+        };
+        Self { min, max }
+    }
+}
+
+/// Color Control server holding the color attributes of a light
+///
+/// The application applies the attribute values to the light. Colors of modes not supported by
+/// the `ColorCapabilities` are rejected and color temperatures are limited to the physical range.
+/// Scenes capture and recall the attributes through [`SceneExtension`], the color mode is left
+/// unchanged by a recall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorControlServer {
+    capabilities: ColorCapabilities,
+    mireds_range: MiredsRange,
+    color_mode: EnhancedColorMode,
+    current_x: u16,
+    current_y: u16,
+    enhanced_current_hue: u16,
+    current_saturation: u8,
+    color_loop_active: bool,
+    color_loop_direction: ColorLoopDirection,
+    color_loop_time: u16,
+    color_temperature_mireds: u16,
+    options: Options,
+}
+
+impl Default for ColorControlServer {
+    fn default() -> Self {
+        Self::new(
+            ColorCapabilities(ColorCapabilities::XY),
+            MiredsRange::default(),
+        )
+    }
+}
+
+impl ColorControlServer {
+    /// Creates a server with the given capabilities, physical color temperature range and the
+    /// default attribute values.
+    pub fn new(capabilities: ColorCapabilities, mireds_range: MiredsRange) -> Self {
+        Self {
+            capabilities,
+            mireds_range,
+            color_mode: EnhancedColorMode::Xy,
+            current_x: 0x616b,
+            current_y: 0x607d,
+            enhanced_current_hue: 0x0000,
+            current_saturation: 0x00,
+            color_loop_active: false,
+            color_loop_direction: ColorLoopDirection::Decrement,
+            color_loop_time: 0x0019,
+            color_temperature_mireds: mireds_range.clamp(0x00fa),
+            options: Options::default(),
+        }
+    }
+
+    /// Returns the `ColorCapabilities` attribute.
+    pub fn capabilities(&self) -> ColorCapabilities {
+        self.capabilities
+    }
+
+    /// Returns the `ColorTempPhysicalMinMireds` and `ColorTempPhysicalMaxMireds` attributes.
+    pub fn mireds_range(&self) -> MiredsRange {
+        self.mireds_range
+    }
+
+    /// Returns the `EnhancedColorMode` attribute.
+    pub fn enhanced_color_mode(&self) -> EnhancedColorMode {
+        self.color_mode
+    }
+
+    /// Returns the `ColorMode` attribute.
+    pub fn color_mode(&self) -> ColorMode {
+        match self.color_mode {
+            EnhancedColorMode::HueAndSaturation | EnhancedColorMode::EnhancedHueAndSaturation => {
+                ColorMode::HueAndSaturation
+            }
+            EnhancedColorMode::Xy => ColorMode::Xy,
+            EnhancedColorMode::ColorTemperature => ColorMode::ColorTemperature,
+        }
+    }
+
+    /// Returns the `CurrentHue` attribute.
+    pub fn current_hue(&self) -> u8 {
+        self.enhanced_current_hue.to_be_bytes()[0]
+    }
+
+    /// Returns the `EnhancedCurrentHue` attribute.
+    pub fn enhanced_current_hue(&self) -> u16 {
+        self.enhanced_current_hue
+    }
+
+    /// Returns the `CurrentSaturation` attribute.
+    pub fn current_saturation(&self) -> u8 {
+        self.current_saturation
+    }
+
+    /// Returns the `CurrentX` attribute.
+    pub fn current_x(&self) -> u16 {
+        self.current_x
+    }
+
+    /// Returns the `CurrentY` attribute.
+    pub fn current_y(&self) -> u16 {
+        self.current_y
+    }
+
+    /// Returns the `ColorTemperatureMireds` attribute.
+    pub fn color_temperature_mireds(&self) -> u16 {
+        self.color_temperature_mireds
+    }
+
+    /// Returns the `ColorLoopActive` attribute.
+    pub fn color_loop_active(&self) -> bool {
+        self.color_loop_active
+    }
+
+    /// Returns the `ColorLoopDirection` attribute.
+    pub fn color_loop_direction(&self) -> ColorLoopDirection {
+        self.color_loop_direction
+    }
+
+    /// Returns the `ColorLoopTime` attribute in seconds.
+    pub fn color_loop_time(&self) -> u16 {
+        self.color_loop_time
+    }
+
+    /// Returns the `Options` attribute.
+    pub fn options(&self) -> Options {
+        self.options
+    }
+
+    /// Sets the `Options` attribute.
+    pub fn set_options(&mut self, options: Options) {
+        self.options = options;
+    }
+
+    /// Returns `true` if a command with the given options shall be executed, `on_off` is the
+    /// `OnOff` attribute of the On/Off cluster.
+    ///
+    /// See Section 5.2.2.3.1.5
+    pub fn execute(&self, on_off: bool, options: OptionsOverride) -> bool {
+        on_off
+            || options
+                .apply(self.options)
+                .contains(Options::EXECUTE_IF_OFF)
+    }
+
+    /// Returns the current color.
+    pub fn color(&self) -> Color {
+        match self.color_mode {
+            EnhancedColorMode::HueAndSaturation => Color::HueSaturation {
+                hue: self.current_hue(),
+                saturation: self.current_saturation,
+            },
+            EnhancedColorMode::EnhancedHueAndSaturation => Color::EnhancedHueSaturation {
+                enhanced_hue: self.enhanced_current_hue,
+                saturation: self.current_saturation,
+            },
+            EnhancedColorMode::Xy => Color::Xy {
+                x: self.current_x,
+                y: self.current_y,
+            },
+            EnhancedColorMode::ColorTemperature => {
+                Color::ColorTemperature(self.color_temperature_mireds)
+            }
+        }
+    }
+
+    /// Returns `true` if the color mode is supported by the `ColorCapabilities`.
+    pub fn supports(&self, color_mode: EnhancedColorMode) -> bool {
+        self.capabilities.contains(match color_mode {
+            EnhancedColorMode::HueAndSaturation => ColorCapabilities::HUE_SATURATION,
+            EnhancedColorMode::EnhancedHueAndSaturation => ColorCapabilities::ENHANCED_HUE,
+            EnhancedColorMode::Xy => ColorCapabilities::XY,
+            EnhancedColorMode::ColorTemperature => ColorCapabilities::COLOR_TEMPERATURE,
+        })
+    }
+
+    /// Sets the current color and the color mode of its representation, a color temperature is
+    /// limited to the physical range.
+    ///
+    /// Returns `UnsupportedAttribute` if the color mode is not supported.
+    pub fn set_color(&mut self, color: Color) -> ZclStatus {
+        if !self.supports(color.color_mode()) {
+            return ZclStatus::UnsupportedAttribute;
+        }
+        match color {
+            Color::HueSaturation { hue, saturation } => {
+                self.enhanced_current_hue = u16::from(hue) << 8;
+                self.current_saturation = saturation;
+            }
+            Color::EnhancedHueSaturation {
+                enhanced_hue,
+                saturation,
+            } => {
+                self.enhanced_current_hue = enhanced_hue;
+                self.current_saturation = saturation;
+            }
+            Color::Xy { x, y } => {
+                self.current_x = x.min(MAX_XY);
+                self.current_y = y.min(MAX_XY);
+            }
+            Color::ColorTemperature(mireds) => {
+                self.color_temperature_mireds = self.mireds_range.clamp(mireds);
+            }
+        }
+        self.color_mode = color.color_mode();
+        ZclStatus::Success
+    }
+}
+
+impl SceneExtension for ColorControlServer {
+    fn cluster_id(&self) -> u16 {
+        CLUSTER_ID
+    }
+
+    fn store_scene(&self, data: &mut [u8]) -> byte::Result<usize> {
+        let offset = &mut 0;
+        data.write_with(
+            offset,
+            ColorControlExtensionFieldSet {
+                current_x: self.current_x,
+                current_y: self.current_y,
+                enhanced_current_hue: self.enhanced_current_hue,
+                current_saturation: self.current_saturation,
+                color_loop_active: u8::from(self.color_loop_active),
+                color_loop_direction: self.color_loop_direction as u8,
+                color_loop_time: self.color_loop_time,
+                color_temperature_mireds: self.color_temperature_mireds,
+            },
+            (),
+        )?;
+        Ok(*offset)
+    }
+
+    fn recall_scene(&mut self, data: &[u8], _transition_time: u16) {
+        let (set, _) = match ColorControlExtensionFieldSet::try_read(data, ()) {
+            Ok(set) => set,
+            Err(_) => return,
+        };
+        let capabilities = self.capabilities;
+        if capabilities.contains(ColorCapabilities::XY) {
+            self.current_x = set.current_x.min(MAX_XY);
+            self.current_y = set.current_y.min(MAX_XY);
+        }
+        if capabilities.contains(ColorCapabilities::ENHANCED_HUE) {
+            self.enhanced_current_hue = set.enhanced_current_hue;
+        } else if capabilities.contains(ColorCapabilities::HUE_SATURATION) {
+            self.enhanced_current_hue = set.enhanced_current_hue & 0xff00;
+        }
+        if capabilities.contains(ColorCapabilities::HUE_SATURATION)
+            || capabilities.contains(ColorCapabilities::ENHANCED_HUE)
+        {
+            self.current_saturation = set.current_saturation.min(MAX_HUE_SATURATION);
+        }
+        if capabilities.contains(ColorCapabilities::COLOR_LOOP) {
+            self.color_loop_active = set.color_loop_active != 0;
+            if let Ok(direction) = ColorLoopDirection::try_from(set.color_loop_direction) {
+                self.color_loop_direction = direction;
+            }
+            self.color_loop_time = set.color_loop_time;
+        }
+        if capabilities.contains(ColorCapabilities::COLOR_TEMPERATURE) {
+            self.color_temperature_mireds = self.mireds_range.clamp(set.color_temperature_mireds);
+        }
+    }
+}
+
+/// Base 2 logarithm for positive, finite values.
+fn log2(value: f32) -> f32 {
+    let bits = value.to_bits();
+    #[allow(clippy::cast_possible_wrap)]
+    let exponent = ((bits >> 23) & 0xff) as i32 - 127;
+    // mantissa in [1, 2)
+    let mantissa = f32::from_bits((bits & 0x007f_ffff) | 0x3f80_0000);
+    // ln(m) = 2 * atanh((m - 1) / (m + 1))
+    let t = (mantissa - 1.0) / (mantissa + 1.0);
+    let t2 = t * t;
+    let ln = 2.0 * t * (1.0 + t2 * (1.0 / 3.0 + t2 * (1.0 / 5.0 + t2 * (1.0 / 7.0 + t2 / 9.0))));
+    #[allow(clippy::cast_precision_loss)]
+    let exponent = exponent as f32;
+    exponent + ln * core::f32::consts::LOG2_E
+}
+
+/// Base 2 exponential.
+fn exp2(value: f32) -> f32 {
+    if value < -126.0 {
+        return 0.0;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let mut integer = value as i32;
+    #[allow(clippy::cast_precision_loss)]
+    if (integer as f32) > value {
+        integer -= 1;
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let x = (value - integer as f32) * core::f32::consts::LN_2;
+    // e^x for x in [0, ln 2)
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    for n in 1..10_u8 {
+        term *= x / f32::from(n);
+        sum += term;
+    }
+    #[allow(clippy::cast_sign_loss)]
+    let scale = f32::from_bits(((integer + 127) as u32) << 23);
+    sum * scale
+}
+
+/// `base` to the power of `exponent` for non-negative `base`.
+fn powf(base: f32, exponent: f32) -> f32 {
+    if base <= 0.0 {
+        0.0
+    } else {
+        exp2(exponent * log2(base))
+    }
+}
+
+/// Rounds a non-negative value to the nearest integer and limits it to `max`.
+fn round(value: f32, max: u16) -> u16 {
+    if value <= 0.0 {
+        return 0;
+    }
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let value = (value + 0.5).min(f32::from(max)) as u16;
+    value
+}
+
+/// Limits `value` to `[0, 1]`.
+fn unit(value: f32) -> f32 {
+    value.clamp(0.0, 1.0)
+}
+
+/// Gamma encoded sRGB color with components in `[0, 1]`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Rgb {
+    /// Red
+    pub r: f32,
+    /// Green
+    pub g: f32,
+    /// Blue
+    pub b: f32,
+}
+
+impl Rgb {
+    /// Creates a color from 8-bit components.
+    pub fn from_u8(r: u8, g: u8, b: u8) -> Self {
+        Self {
+            r: f32::from(r) / 255.0,
+            g: f32::from(g) / 255.0,
+            b: f32::from(b) / 255.0,
+        }
+    }
+
+    /// Returns the color as 8-bit components.
+    pub fn to_u8(self) -> (u8, u8, u8) {
+        #[allow(clippy::cast_possible_truncation)]
+        let component = |v: f32| round(unit(v) * 255.0, 0xff) as u8;
+        (component(self.r), component(self.g), component(self.b))
+    }
+
+    fn to_linear(self) -> [f32; 3] {
+        let decode = |v: f32| {
+            let v = unit(v);
+            if v <= 0.040_45 {
+                v / 12.92
+            } else {
+                powf((v + 0.055) / 1.055, 2.4)
+            }
+        };
+        [decode(self.r), decode(self.g), decode(self.b)]
+    }
+
+    fn from_linear([r, g, b]: [f32; 3]) -> Self {
+        let encode = |v: f32| {
+            let v = unit(v);
+            if v <= 0.003_130_8 {
+                v * 12.92
+            } else {
+                1.055 * powf(v, 1.0 / 2.4) - 0.055
+            }
+        };
+        Self {
+            r: encode(r),
+            g: encode(g),
+            b: encode(b),
+        }
+    }
+}
+
+/// Color in the HSV color space
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Hsv {
+    /// Hue in degrees `[0, 360)`
+    pub hue: f32,
+    /// Saturation in `[0, 1]`
+    pub saturation: f32,
+    /// Value in `[0, 1]`
+    pub value: f32,
+}
+
+impl Hsv {
+    /// Creates a fully bright color from the `CurrentHue` and `CurrentSaturation` attributes.
+    pub fn from_attributes(hue: u8, saturation: u8) -> Self {
+        Self {
+            hue: f32::from(hue.min(MAX_HUE_SATURATION)) * 360.0 / 254.0,
+            saturation: f32::from(saturation.min(MAX_HUE_SATURATION)) / 254.0,
+            value: 1.0,
+        }
+    }
+
+    /// Creates a fully bright color from the `EnhancedCurrentHue` and `CurrentSaturation`
+    /// attributes.
+    pub fn from_enhanced_attributes(enhanced_hue: u16, saturation: u8) -> Self {
+        Self {
+            hue: f32::from(enhanced_hue) * 360.0 / 65536.0,
+            ..Self::from_attributes(0, saturation)
+        }
+    }
+
+    /// Returns the `CurrentHue` and `CurrentSaturation` attributes.
+    pub fn to_attributes(self) -> (u8, u8) {
+        #[allow(clippy::cast_possible_truncation)]
+        let hue = round(self.normalized_hue() * 254.0 / 360.0, 254) as u8;
+        #[allow(clippy::cast_possible_truncation)]
+        let saturation = round(unit(self.saturation) * 254.0, 254) as u8;
+        (hue, saturation)
+    }
+
+    /// Returns the `EnhancedCurrentHue` and `CurrentSaturation` attributes.
+    pub fn to_enhanced_attributes(self) -> (u16, u8) {
+        let (_, saturation) = self.to_attributes();
+        let hue = round(self.normalized_hue() * 65536.0 / 360.0, 0xffff);
+        (hue, saturation)
+    }
+
+    fn normalized_hue(self) -> f32 {
+        let hue = self.hue % 360.0;
+        if hue < 0.0 {
+            hue + 360.0
+        } else {
+            hue
+        }
+    }
+}
+
+impl From<Hsv> for Rgb {
+    #[allow(clippy::many_single_char_names)]
+    fn from(hsv: Hsv) -> Self {
+        let value = unit(hsv.value);
+        let chroma = value * unit(hsv.saturation);
+        let sector = hsv.normalized_hue() / 60.0;
+        let x = chroma * (1.0 - ((sector % 2.0) - 1.0).abs());
+        let (r, g, b) = if sector < 1.0 {
+            (chroma, x, 0.0)
+        } else if sector < 2.0 {
+            (x, chroma, 0.0)
+        } else if sector < 3.0 {
+            (0.0, chroma, x)
+        } else if sector < 4.0 {
+            (0.0, x, chroma)
+        } else if sector < 5.0 {
+            (x, 0.0, chroma)
+        } else {
+            (chroma, 0.0, x)
+        };
+        let m = value - chroma;
+        Self {
+            r: r + m,
+            g: g + m,
+            b: b + m,
+        }
+    }
+}
+
+impl From<Rgb> for Hsv {
+    #[allow(clippy::float_cmp)]
+    fn from(rgb: Rgb) -> Self {
+        let (r, g, b) = (unit(rgb.r), unit(rgb.g), unit(rgb.b));
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+        let hue = if delta <= 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        Self {
+            hue: if hue < 0.0 { hue + 360.0 } else { hue },
+            saturation: if max <= 0.0 { 0.0 } else { delta / max },
+            value: max,
+        }
+    }
+}
+
+/// Chromaticity in the CIE 1931 color space
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Xy {
+    /// x coordinate
+    pub x: f32,
+    /// y coordinate
+    pub y: f32,
+}
+
+impl Xy {
+    /// Creates a chromaticity from the `CurrentX` and `CurrentY` attributes.
+    pub fn from_attributes(current_x: u16, current_y: u16) -> Self {
+        Self {
+            x: f32::from(current_x.min(MAX_XY)) / 65536.0,
+            y: f32::from(current_y.min(MAX_XY)) / 65536.0,
+        }
+    }
+
+    /// Returns the `CurrentX` and `CurrentY` attributes.
+    pub fn to_attributes(self) -> (u16, u16) {
+        (
+            round(self.x * 65536.0, MAX_XY),
+            round(self.y * 65536.0, MAX_XY),
+        )
+    }
+
+    /// Returns the chromaticity of the black body radiator with the given color temperature in
+    /// mireds (Kim et al. approximation of the Planckian locus, valid from 1667K to 25000K).
+    pub fn from_mireds(mireds: u16) -> Self {
+        // 1667K..25000K
+        let kelvin = 1_000_000.0 / f32::from(mireds.clamp(40, 600));
+        let t = 1000.0 / kelvin;
+        let (t2, t3) = (t * t, t * t * t);
+        let x = if kelvin <= 4000.0 {
+            -0.266_123_9 * t3 - 0.234_358_9 * t2 + 0.877_695_6 * t + 0.179_910
+        } else {
+            -3.025_847 * t3 + 2.107_037_9 * t2 + 0.222_634_7 * t + 0.240_390
+        };
+        let (x2, x3) = (x * x, x * x * x);
+        let y = if kelvin <= 2222.0 {
+            -1.106_381_4 * x3 - 1.348_110_2 * x2 + 2.185_558_3 * x - 0.202_196_83
+        } else if kelvin <= 4000.0 {
+            -0.954_947_6 * x3 - 1.374_185_9 * x2 + 2.091_37 * x - 0.167_488_67
+        } else {
+            3.081_758 * x3 - 5.873_387 * x2 + 3.751_13 * x - 0.370_014_83
+        };
+        Self { x, y }
+    }
+
+    /// Returns the correlated color temperature in mireds (cubic approximation of the isotemperature
+    /// lines).
+    pub fn to_mireds(self) -> u16 {
+        let n = (self.x - 0.3320) / (0.1858 - self.y);
+        let kelvin = 449.0 * n * n * n + 3525.0 * n * n + 6823.3 * n + 5520.33;
+        if kelvin <= 0.0 {
+            return MAX_XY;
+        }
+        round(1_000_000.0 / kelvin, MAX_XY)
+    }
+}
+
+impl From<Rgb> for Xy {
+    #[allow(clippy::many_single_char_names)]
+    fn from(rgb: Rgb) -> Self {
+        let [r, g, b] = rgb.to_linear();
+        let x = 0.412_4 * r + 0.357_6 * g + 0.180_5 * b;
+        let y = 0.212_6 * r + 0.715_2 * g + 0.072_2 * b;
+        let z = 0.019_3 * r + 0.119_2 * g + 0.950_5 * b;
+        let sum = x + y + z;
+        if sum <= 0.0 {
+            // D65 white point for black
+            return Self {
+                x: 0.312_7,
+                y: 0.329_0,
+            };
+        }
+        Self {
+            x: x / sum,
+            y: y / sum,
+        }
+    }
+}
+
+impl From<Xy> for Rgb {
+    /// Returns the brightest color with the chromaticity, colors outside of the sRGB gamut are
+    /// clipped.
+    #[allow(clippy::many_single_char_names)]
+    fn from(xy: Xy) -> Self {
+        if xy.y <= 0.0 {
+            return Self::default();
+        }
+        let x = xy.x / xy.y;
+        let z = (1.0 - xy.x - xy.y) / xy.y;
+        let r = 3.240_6 * x - 1.537_2 - 0.498_6 * z;
+        let g = -0.968_9 * x + 1.875_8 + 0.041_5 * z;
+        let b = 0.055_7 * x - 0.204 + 1.057 * z;
+        let (r, g, b) = (r.max(0.0), g.max(0.0), b.max(0.0));
+        let max = r.max(g).max(b);
+        if max <= 0.0 {
+            return Self::default();
+        }
+        Self::from_linear([r / max, g / max, b / max])
+    }
+}
+
+/// Color as set by the commands of the cluster
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    /// `CurrentHue` and `CurrentSaturation`
+    HueSaturation {
+        /// `CurrentHue`
+        hue: u8,
+        /// `CurrentSaturation`
+        saturation: u8,
+    },
+    /// `EnhancedCurrentHue` and `CurrentSaturation`
+    EnhancedHueSaturation {
+        /// `EnhancedCurrentHue`
+        enhanced_hue: u16,
+        /// `CurrentSaturation`
+        saturation: u8,
+    },
+    /// `CurrentX` and `CurrentY`
+    Xy {
+        /// `CurrentX`
+        x: u16,
+        /// `CurrentY`
+        y: u16,
+    },
+    /// `ColorTemperatureMireds`
+    ColorTemperature(u16),
+}
+
+impl Color {
+    /// Converts an sRGB color to the best representation supported by a device.
+    ///
+    /// The preference is xy, enhanced hue, hue and then color temperature, which is limited to
+    /// the physical range of the device. Returns `None` if the device supports no color mode.
+    pub fn from_rgb(
+        rgb: Rgb,
+        capabilities: ColorCapabilities,
+        mireds: MiredsRange,
+    ) -> Option<Self> {
+        if capabilities.contains(ColorCapabilities::XY) {
+            let (x, y) = Xy::from(rgb).to_attributes();
+            Some(Self::Xy { x, y })
+        } else if capabilities.contains(ColorCapabilities::ENHANCED_HUE) {
+            let (enhanced_hue, saturation) = Hsv::from(rgb).to_enhanced_attributes();
+            Some(Self::EnhancedHueSaturation {
+                enhanced_hue,
+                saturation,
+            })
+        } else if capabilities.contains(ColorCapabilities::HUE_SATURATION) {
+            let (hue, saturation) = Hsv::from(rgb).to_attributes();
+            Some(Self::HueSaturation { hue, saturation })
+        } else if capabilities.contains(ColorCapabilities::COLOR_TEMPERATURE) {
+            Some(Self::ColorTemperature(
+                mireds.clamp(Xy::from(rgb).to_mireds()),
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the color mode of the representation.
+    pub fn color_mode(self) -> EnhancedColorMode {
+        match self {
+            Self::HueSaturation { .. } => EnhancedColorMode::HueAndSaturation,
+            Self::EnhancedHueSaturation { .. } => EnhancedColorMode::EnhancedHueAndSaturation,
+            Self::Xy { .. } => EnhancedColorMode::Xy,
+            Self::ColorTemperature(_) => EnhancedColorMode::ColorTemperature,
+        }
+    }
+
+    /// Returns the chromaticity of the color.
+    pub fn to_xy(self) -> Xy {
+        match self {
+            Self::Xy { x, y } => Xy::from_attributes(x, y),
+            Self::ColorTemperature(mireds) => Xy::from_mireds(mireds),
+            hsv => Xy::from(hsv.to_rgb()),
+        }
+    }
+
+    /// Returns the fully bright sRGB color.
+    pub fn to_rgb(self) -> Rgb {
+        match self {
+            Self::HueSaturation { hue, saturation } => Hsv::from_attributes(hue, saturation).into(),
+            Self::EnhancedHueSaturation {
+                enhanced_hue,
+                saturation,
+            } => Hsv::from_enhanced_attributes(enhanced_hue, saturation).into(),
+            xy => xy.to_xy().into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::TryRead;
+
+    use super::*;
+
+    fn assert_near(actual: f32, expected: f32, tolerance: f32) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{} is not within {} of {}",
+            actual,
+            tolerance,
+            expected
+        );
+    }
+
+    #[test]
+    fn unpack_move_to_color() {
+        // given
+        let without_options = [0x6e, 0x51, 0x86, 0x54, 0x0a, 0x00];
+        let with_options = [0x6e, 0x51, 0x86, 0x54, 0x0a, 0x00, 0x01, 0x01];
+
+        // when
+        let (short, _) = MoveToColor::try_read(&without_options, ())
+            .expect("Could not read MoveToColor in test");
+        let (long, len) =
+            MoveToColor::try_read(&with_options, ()).expect("Could not read MoveToColor in test");
+
+        // then
+        assert_eq!(short.color_x, 0x516e);
+        assert_eq!(short.color_y, 0x5486);
+        assert_eq!(short.options, OptionsOverride::default());
+        assert_eq!(len, 8);
+        assert!(long
+            .options
+            .apply(Options::default())
+            .contains(Options::EXECUTE_IF_OFF));
+    }
+
+    #[test]
+    fn unpack_color_loop_set() {
+        // given
+        let input = [0x0f, 0x02, 0x01, 0x3c, 0x00, 0x00, 0x80];
+
+        // when
+        let (command, _) =
+            ColorLoopSet::try_read(&input, ()).expect("Could not read ColorLoopSet in test");
+
+        // then
+        assert!(command.update_flags.contains(UpdateFlags::UPDATE_START_HUE));
+        assert_eq!(
+            command.action,
+            ColorLoopAction::ActivateFromEnhancedCurrentHue
+        );
+        assert_eq!(command.direction, ColorLoopDirection::Increment);
+        assert_eq!(command.time, 60);
+        assert_eq!(command.start_hue, 0x8000);
+    }
+
+    #[test]
+    fn powf_approximation() {
+        assert_near(powf(0.5, 2.4), 0.189_464_6, 1e-5);
+        assert_near(powf(0.2, 1.0 / 2.4), 0.511_402, 1e-5);
+        assert_near(powf(8.0, 1.0), 8.0, 1e-4);
+    }
+
+    #[test]
+    fn rgb_to_xy() {
+        // given
+        let red = Rgb::from_u8(255, 0, 0);
+        let white = Rgb::from_u8(255, 255, 255);
+
+        // when
+        let red = Xy::from(red);
+        let white = Xy::from(white);
+
+        // then
+        assert_near(red.x, 0.64, 1e-3);
+        assert_near(red.y, 0.33, 1e-3);
+        assert_near(white.x, 0.3127, 1e-3);
+        assert_near(white.y, 0.3290, 1e-3);
+        assert_eq!(Rgb::from(red).to_u8(), (255, 0, 0));
+    }
+
+    #[test]
+    fn hsv_round_trip() {
+        // given
+        let rgb = Rgb::from_u8(0, 128, 255);
+
+        // when
+        let hsv = Hsv::from(rgb);
+
+        // then
+        assert_near(hsv.hue, 209.9, 0.1);
+        assert_eq!(Rgb::from(hsv).to_u8(), (0, 128, 255));
+        assert_eq!(Hsv::from_attributes(0, 254).to_attributes(), (0, 254));
+        assert_eq!(
+            Hsv::from_enhanced_attributes(0x8000, 254).to_enhanced_attributes(),
+            (0x8000, 254)
+        );
+    }
+
+    #[test]
+    fn mireds_to_xy() {
+        // given
+        let mireds = 153; // 6535K
+
+        // when
+        let xy = Xy::from_mireds(mireds);
+
+        // then
+        assert_near(xy.x, 0.3125, 2e-3);
+        assert_near(xy.y, 0.3226, 2e-3);
+        let back = xy.to_mireds();
+        assert!((151..=155).contains(&back), "{}", back);
+    }
+
+    #[test]
+    fn color_respects_capabilities() {
+        // given
+        let rgb = Rgb::from_u8(255, 147, 41); // ~2000K
+        let limits = MiredsRange { min: 153, max: 370 };
+
+        // when
+        let xy = Color::from_rgb(rgb, ColorCapabilities(ColorCapabilities::XY), limits);
+        let temperature = Color::from_rgb(
+            rgb,
+            ColorCapabilities(ColorCapabilities::COLOR_TEMPERATURE),
+            limits,
+        );
+
+        // then
+        assert_eq!(xy.map(Color::color_mode), Some(EnhancedColorMode::Xy));
+        assert_eq!(temperature, Some(Color::ColorTemperature(370)));
+        assert_eq!(
+            Color::from_rgb(rgb, ColorCapabilities::default(), limits),
+            None
+        );
+        assert_eq!(
+            limits.with_bounds(0, 300),
+            MiredsRange { min: 153, max: 300 }
+        );
+    }
+
+    #[test]
+    fn store_and_recall_color_scene() {
+        // given
+        let mut server = ColorControlServer::new(
+            ColorCapabilities(ColorCapabilities::XY | ColorCapabilities::ENHANCED_HUE),
+            MiredsRange::default(),
+        );
+        server.set_color(Color::EnhancedHueSaturation {
+            enhanced_hue: 0x1234,
+            saturation: 0x80,
+        });
+        let mut data = [0u8; 13];
+
+        // when
+        let len = server
+            .store_scene(&mut data)
+            .expect("Could not store color scene in test");
+        server.set_color(Color::Xy {
+            x: 0x1000,
+            y: 0x2000,
+        });
+        server.recall_scene(&data[..len], 0);
+
+        // then
+        assert_eq!(len, 13);
+        assert_eq!(
+            data,
+            [0x6b, 0x61, 0x7d, 0x60, 0x34, 0x12, 0x80, 0x00, 0x00, 0x19, 0x00, 0xfa, 0x00]
+        );
+        assert_eq!(server.enhanced_color_mode(), EnhancedColorMode::Xy);
+        assert_eq!(server.current_hue(), 0x12);
+        assert_eq!(server.current_saturation(), 0x80);
+        assert_eq!(server.current_x(), 0x616b);
+    }
+
+    #[test]
+    fn execute_if_off() {
+        // given
+        let mut server = ColorControlServer::default();
+        let execute_if_off = OptionsOverride {
+            options_mask: Options(Options::EXECUTE_IF_OFF),
+            options_override: Options(Options::EXECUTE_IF_OFF),
+        };
+
+        // then
+        assert!(server.execute(true, OptionsOverride::default()));
+        assert!(!server.execute(false, OptionsOverride::default()));
+        assert!(server.execute(false, execute_if_off));
+        server.set_options(Options(Options::EXECUTE_IF_OFF));
+        assert!(server.execute(false, OptionsOverride::default()));
+        assert!(!server.execute(
+            false,
+            OptionsOverride {
+                options_mask: Options(Options::EXECUTE_IF_OFF),
+                options_override: Options::default(),
+            }
+        ));
+    }
+
+    #[test]
+    fn reject_unsupported_color_modes() {
+        // given
+        let mut server = ColorControlServer::new(
+            ColorCapabilities(ColorCapabilities::HUE_SATURATION),
+            MiredsRange::default(),
+        );
+
+        // when
+        let xy = server.set_color(Color::Xy {
+            x: 0x1000,
+            y: 0x2000,
+        });
+        let temperature = server.set_color(Color::ColorTemperature(0x0100));
+        let hue = server.set_color(Color::HueSaturation {
+            hue: 0x40,
+            saturation: 0x80,
+        });
+
+        // then
+        assert_eq!(xy, ZclStatus::UnsupportedAttribute);
+        assert_eq!(temperature, ZclStatus::UnsupportedAttribute);
+        assert_eq!(hue, ZclStatus::Success);
+        assert_eq!(server.current_x(), 0x616b);
+        assert_eq!(server.color_temperature_mireds(), 0x00fa);
+        assert_eq!(
+            server.enhanced_color_mode(),
+            EnhancedColorMode::HueAndSaturation
+        );
+    }
+
+    #[test]
+    fn clamp_color_temperature() {
+        // given
+        let mut server = ColorControlServer::new(
+            ColorCapabilities(ColorCapabilities::COLOR_TEMPERATURE),
+            MiredsRange { min: 153, max: 370 },
+        );
+        let mut data = [0u8; 13];
+
+        // when
+        let warm = server.set_color(Color::ColorTemperature(500));
+        let warm_mireds = server.color_temperature_mireds();
+        server.set_color(Color::ColorTemperature(100));
+        let cold_mireds = server.color_temperature_mireds();
+        let len = server
+            .store_scene(&mut data)
+            .expect("Could not store color scene in test");
+        data[11..13].copy_from_slice(&600_u16.to_le_bytes());
+        data[..2].copy_from_slice(&0x1000_u16.to_le_bytes());
+        server.recall_scene(&data[..len], 0);
+
+        // then
+        assert_eq!(warm, ZclStatus::Success);
+        assert_eq!(warm_mireds, 370);
+        assert_eq!(cold_mireds, 153);
+        assert_eq!(server.color_temperature_mireds(), 370);
+        assert_eq!(server.current_x(), 0x616b);
+    }
+}
@@ -0,0 +1,6 @@
+//!
+//! Lighting Clusters
+
+/// Provides attributes and commands for controlling the color of a color capable light, and
+/// conversions between the color spaces.
+pub mod color_control;
@@ -0,0 +1,236 @@
+//!
+//! 4.13 Concentration Measurement Clusters
+//!
+//! All concentration measurement clusters (`0x040C` - `0x042D`) share the same attribute set and
+//! only differ in their cluster identifier. The cluster is therefore implemented once and
+//! parameterized by the cluster identifier, see [`ConcentrationMeasurement`].
+use byte::{BytesExt, TryRead, TryWrite};
+
+use crate::impl_byte;
+
+impl_byte! {
+    /// 4.13.2.2 Concentration Measurement Information Attribute Set
+    #[repr(u16)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        /// Represents the concentration as a fraction of 1 (one).
+        MeasuredValue = 0x0000,
+        /// Indicates the minimum value of `MeasuredValue` that can be measured.
+        MinMeasuredValue = 0x0001,
+        /// Indicates the maximum value of `MeasuredValue` that can be measured.
+        MaxMeasuredValue = 0x0002,
+        /// Indicates the magnitude of the possible error that is associated with `MeasuredValue`.
+        Tolerance = 0x0003,
+    }
+}
+
+/// 4.13.2.2 Concentration Measurement Information Attribute Set
+///
+/// The const parameter `ID` is the cluster identifier, use one of the aliases below (e.g.
+/// [`CarbonDioxideConcentration`]) instead of naming the identifier directly.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConcentrationMeasurement<const ID: u16> {
+    /// `MeasuredValue`, `NaN` if the value is invalid or unknown.
+    pub measured_value: f32,
+    /// `MinMeasuredValue`, `NaN` if not defined.
+    pub min_measured_value: f32,
+    /// `MaxMeasuredValue`, `NaN` if not defined.
+    pub max_measured_value: f32,
+    /// `Tolerance`
+    pub tolerance: f32,
+}
+
+impl<const ID: u16> ConcentrationMeasurement<ID> {
+    /// Cluster identifier of this concentration measurement cluster.
+    pub const CLUSTER_ID: u16 = ID;
+
+    /// Creates a new attribute set.
+    ///
+    /// Returns `None` if the measured value is out of the given range.
+    pub fn new(measured_value: f32, min: f32, max: f32, tolerance: f32) -> Option<Self> {
+        if min > max || measured_value < min || measured_value > max {
+            return None;
+        }
+
+        Some(Self {
+            measured_value,
+            min_measured_value: min,
+            max_measured_value: max,
+            tolerance,
+        })
+    }
+
+    /// Returns the measured value or `None` if it is invalid.
+    ///
+    /// See Section 4.13.2.2.1
+    pub fn measured_value(&self) -> Option<f32> {
+        if self.measured_value.is_nan() {
+            None
+        } else {
+            Some(self.measured_value)
+        }
+    }
+}
+
+impl<const ID: u16> Default for ConcentrationMeasurement<ID> {
+    fn default() -> Self {
+        Self {
+            measured_value: f32::NAN,
+            min_measured_value: f32::NAN,
+            max_measured_value: f32::NAN,
+            tolerance: 0.0,
+        }
+    }
+}
+
+impl<const ID: u16, C: Default> TryRead<'_, C> for ConcentrationMeasurement<ID> {
+    fn try_read(bytes: &[u8], _: C) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let measured_value = bytes.read_with(offset, byte::LE)?;
+        let min_measured_value = bytes.read_with(offset, byte::LE)?;
+        let max_measured_value = bytes.read_with(offset, byte::LE)?;
+        let tolerance = bytes.read_with(offset, byte::LE)?;
+
+        Ok((
+            Self {
+                measured_value,
+                min_measured_value,
+                max_measured_value,
+                tolerance,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl<const ID: u16, C: Default> TryWrite<C> for ConcentrationMeasurement<ID> {
+    fn try_write(self, bytes: &mut [u8], _: C) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, self.measured_value, byte::LE)?;
+        bytes.write_with(offset, self.min_measured_value, byte::LE)?;
+        bytes.write_with(offset, self.max_measured_value, byte::LE)?;
+        bytes.write_with(offset, self.tolerance, byte::LE)?;
+
+        Ok(*offset)
+    }
+}
+
+/// Carbon Monoxide (CO) Concentration Measurement
+pub type CarbonMonoxideConcentration = ConcentrationMeasurement<0x040C>;
+/// Carbon Dioxide (CO2) Concentration Measurement
+pub type CarbonDioxideConcentration = ConcentrationMeasurement<0x040D>;
+/// Ethylene (C2H4) Concentration Measurement
+pub type EthyleneConcentration = ConcentrationMeasurement<0x040E>;
+/// Ethylene Oxide (C2H4O) Concentration Measurement
+pub type EthyleneOxideConcentration = ConcentrationMeasurement<0x040F>;
+/// Hydrogen (H) Concentration Measurement
+pub type HydrogenConcentration = ConcentrationMeasurement<0x0410>;
+/// Hydrogen Sulfide (H2S) Concentration Measurement
+pub type HydrogenSulfideConcentration = ConcentrationMeasurement<0x0411>;
+/// Nitric Oxide (NO) Concentration Measurement
+pub type NitricOxideConcentration = ConcentrationMeasurement<0x0412>;
+/// Nitrogen Dioxide (NO2) Concentration Measurement
+pub type NitrogenDioxideConcentration = ConcentrationMeasurement<0x0413>;
+/// Oxygen (O2) Concentration Measurement
+pub type OxygenConcentration = ConcentrationMeasurement<0x0414>;
+/// Ozone (O3) Concentration Measurement
+pub type OzoneConcentration = ConcentrationMeasurement<0x0415>;
+/// Sulfur Dioxide (SO2) Concentration Measurement
+pub type SulfurDioxideConcentration = ConcentrationMeasurement<0x0416>;
+/// Dissolved Oxygen (DO) Concentration Measurement
+pub type DissolvedOxygenConcentration = ConcentrationMeasurement<0x0417>;
+/// Bromate Concentration Measurement
+pub type BromateConcentration = ConcentrationMeasurement<0x0418>;
+/// Chloramines Concentration Measurement
+pub type ChloraminesConcentration = ConcentrationMeasurement<0x0419>;
+/// Chlorine Concentration Measurement
+pub type ChlorineConcentration = ConcentrationMeasurement<0x041A>;
+/// Fecal coliform & E. Coli Concentration Measurement
+pub type FecalColiformConcentration = ConcentrationMeasurement<0x041B>;
+/// Fluoride Concentration Measurement
+pub type FluorideConcentration = ConcentrationMeasurement<0x041C>;
+/// Haloacetic Acids Concentration Measurement
+pub type HaloaceticAcidsConcentration = ConcentrationMeasurement<0x041D>;
+/// Total Trihalomethanes Concentration Measurement
+pub type TotalTrihalomethanesConcentration = ConcentrationMeasurement<0x041E>;
+/// Total Coliform Bacteria Concentration Measurement
+pub type TotalColiformBacteriaConcentration = ConcentrationMeasurement<0x041F>;
+/// Turbidity Concentration Measurement
+pub type TurbidityConcentration = ConcentrationMeasurement<0x0420>;
+/// Copper Concentration Measurement
+pub type CopperConcentration = ConcentrationMeasurement<0x0421>;
+/// Lead Concentration Measurement
+pub type LeadConcentration = ConcentrationMeasurement<0x0422>;
+/// Manganese Concentration Measurement
+pub type ManganeseConcentration = ConcentrationMeasurement<0x0423>;
+/// Sulfate Concentration Measurement
+pub type SulfateConcentration = ConcentrationMeasurement<0x0424>;
+/// Bromodichloromethane Concentration Measurement
+pub type BromodichloromethaneConcentration = ConcentrationMeasurement<0x0425>;
+/// Bromoform Concentration Measurement
+pub type BromoformConcentration = ConcentrationMeasurement<0x0426>;
+/// Chlorodibromomethane Concentration Measurement
+pub type ChlorodibromomethaneConcentration = ConcentrationMeasurement<0x0427>;
+/// Chloroform Concentration Measurement
+pub type ChloroformConcentration = ConcentrationMeasurement<0x0428>;
+/// Sodium Concentration Measurement
+pub type SodiumConcentration = ConcentrationMeasurement<0x0429>;
+/// Particulate Matter 2.5 (PM2.5) Concentration Measurement
+pub type Pm25Concentration = ConcentrationMeasurement<0x042A>;
+/// Formaldehyde (CH2O) Concentration Measurement
+pub type FormaldehydeConcentration = ConcentrationMeasurement<0x042B>;
+/// Particulate Matter 1 (PM1) Concentration Measurement
+pub type Pm1Concentration = ConcentrationMeasurement<0x042C>;
+/// Particulate Matter 10 (PM10) Concentration Measurement
+pub type Pm10Concentration = ConcentrationMeasurement<0x042D>;
+
+#[cfg(test)]
+mod tests {
+    use byte::{TryRead, TryWrite};
+
+    use super::*;
+
+    #[test]
+    fn concentration_cluster_ids() {
+        assert_eq!(CarbonMonoxideConcentration::CLUSTER_ID, 0x040C);
+        assert_eq!(CarbonDioxideConcentration::CLUSTER_ID, 0x040D);
+        assert_eq!(Pm25Concentration::CLUSTER_ID, 0x042A);
+        assert_eq!(FormaldehydeConcentration::CLUSTER_ID, 0x042B);
+        assert_eq!(Pm10Concentration::CLUSTER_ID, 0x042D);
+    }
+
+    #[test]
+    fn unpack_concentration_measurement() {
+        // given
+        let mut input = [0u8; 16];
+        input[0..4].copy_from_slice(&0.000_42_f32.to_le_bytes());
+        input[4..8].copy_from_slice(&0.0_f32.to_le_bytes());
+        input[8..12].copy_from_slice(&0.01_f32.to_le_bytes());
+        input[12..16].copy_from_slice(&0.000_01_f32.to_le_bytes());
+
+        // when
+        let (co2, len) = CarbonDioxideConcentration::try_read(&input, ())
+            .expect("Could not read CarbonDioxideConcentration in test");
+
+        // then
+        assert_eq!(len, 16);
+        assert_eq!(co2.measured_value(), Some(0.000_42));
+        assert!((co2.max_measured_value - 0.01).abs() < f32::EPSILON);
+
+        let mut buf = [0u8; 16];
+        co2.try_write(&mut buf, ())
+            .expect("Could not write CarbonDioxideConcentration in test");
+        assert_eq!(buf, input);
+    }
+
+    #[test]
+    fn invalid_measured_value() {
+        // given
+        let pm25 = Pm25Concentration::default();
+
+        // then
+        assert_eq!(pm25.measured_value(), None);
+        assert!(Pm25Concentration::new(1000.0, 0.0, 999.0, 1.0).is_none());
+        assert!(Pm25Concentration::new(12.0, 0.0, 999.0, 1.0).is_some());
+    }
+}
@@ -0,0 +1,764 @@
+//!
+//! 4.9 Electrical Measurement Cluster
+//!
+//! Raw attribute values are scaled by their multiplier and divisor attributes, use
+//! [`AcFormatting`] and [`DcFormatting`] to convert them into volts, amps and watts.
+use byte::{ctx, BytesExt, TryRead, TryWrite};
+
+use crate::impl_byte;
+
+/// Electrical Measurement cluster identifier
+pub const CLUSTER_ID: u16 = 0x0B04;
+
+impl_byte! {
+    /// 4.9.2.2 Attributes of the Electrical Measurement cluster
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        // Basic Information
+        MeasurementType = 0x0000,
+        // DC Measurement
+        DcVoltage = 0x0100,
+        DcVoltageMin = 0x0101,
+        DcVoltageMax = 0x0102,
+        DcCurrent = 0x0103,
+        DcCurrentMin = 0x0104,
+        DcCurrentMax = 0x0105,
+        DcPower = 0x0106,
+        DcPowerMin = 0x0107,
+        DcPowerMax = 0x0108,
+        // DC Formatting
+        DcVoltageMultiplier = 0x0200,
+        DcVoltageDivisor = 0x0201,
+        DcCurrentMultiplier = 0x0202,
+        DcCurrentDivisor = 0x0203,
+        DcPowerMultiplier = 0x0204,
+        DcPowerDivisor = 0x0205,
+        // AC (Non-phase Specific) Measurements
+        AcFrequency = 0x0300,
+        AcFrequencyMin = 0x0301,
+        AcFrequencyMax = 0x0302,
+        NeutralCurrent = 0x0303,
+        TotalActivePower = 0x0304,
+        TotalReactivePower = 0x0305,
+        TotalApparentPower = 0x0306,
+        Measured1stHarmonicCurrent = 0x0307,
+        Measured3rdHarmonicCurrent = 0x0308,
+        Measured5thHarmonicCurrent = 0x0309,
+        Measured7thHarmonicCurrent = 0x030A,
+        Measured9thHarmonicCurrent = 0x030B,
+        Measured11thHarmonicCurrent = 0x030C,
+        MeasuredPhase1stHarmonicCurrent = 0x030D,
+        MeasuredPhase3rdHarmonicCurrent = 0x030E,
+        MeasuredPhase5thHarmonicCurrent = 0x030F,
+        MeasuredPhase7thHarmonicCurrent = 0x0310,
+        MeasuredPhase9thHarmonicCurrent = 0x0311,
+        MeasuredPhase11thHarmonicCurrent = 0x0312,
+        // AC (Non-phase Specific) Formatting
+        AcFrequencyMultiplier = 0x0400,
+        AcFrequencyDivisor = 0x0401,
+        PowerMultiplier = 0x0402,
+        PowerDivisor = 0x0403,
+        HarmonicCurrentMultiplier = 0x0404,
+        PhaseHarmonicCurrentMultiplier = 0x0405,
+        // AC (Single Phase or Phase A) Measurements
+        LineCurrent = 0x0501,
+        ActiveCurrent = 0x0502,
+        ReactiveCurrent = 0x0503,
+        RmsVoltage = 0x0505,
+        RmsVoltageMin = 0x0506,
+        RmsVoltageMax = 0x0507,
+        RmsCurrent = 0x0508,
+        RmsCurrentMin = 0x0509,
+        RmsCurrentMax = 0x050A,
+        ActivePower = 0x050B,
+        ActivePowerMin = 0x050C,
+        ActivePowerMax = 0x050D,
+        ReactivePower = 0x050E,
+        ApparentPower = 0x050F,
+        PowerFactor = 0x0510,
+        AverageRmsVoltageMeasurementPeriod = 0x0511,
+        AverageRmsOverVoltageCounter = 0x0512,
+        AverageRmsUnderVoltageCounter = 0x0513,
+        RmsExtremeOverVoltagePeriod = 0x0514,
+        RmsExtremeUnderVoltagePeriod = 0x0515,
+        RmsVoltageSagPeriod = 0x0516,
+        RmsVoltageSwellPeriod = 0x0517,
+        // AC Formatting
+        AcVoltageMultiplier = 0x0600,
+        AcVoltageDivisor = 0x0601,
+        AcCurrentMultiplier = 0x0602,
+        AcCurrentDivisor = 0x0603,
+        AcPowerMultiplier = 0x0604,
+        AcPowerDivisor = 0x0605,
+        // DC Manufacturer Threshold Alarms
+        DcOverloadAlarmsMask = 0x0700,
+        DcVoltageOverload = 0x0701,
+        DcCurrentOverload = 0x0702,
+        // AC Manufacturer Threshold Alarms
+        AcAlarmsMask = 0x0800,
+        AcVoltageOverload = 0x0801,
+        AcCurrentOverload = 0x0802,
+        AcActivePowerOverload = 0x0803,
+        AcReactivePowerOverload = 0x0804,
+        AverageRmsOverVoltage = 0x0805,
+        AverageRmsUnderVoltage = 0x0806,
+        RmsExtremeOverVoltage = 0x0807,
+        RmsExtremeUnderVoltage = 0x0808,
+        RmsVoltageSag = 0x0809,
+        RmsVoltageSwell = 0x080A,
+        // AC Phase B Measurements
+        LineCurrentPhB = 0x0901,
+        ActiveCurrentPhB = 0x0902,
+        ReactiveCurrentPhB = 0x0903,
+        RmsVoltagePhB = 0x0905,
+        RmsVoltageMinPhB = 0x0906,
+        RmsVoltageMaxPhB = 0x0907,
+        RmsCurrentPhB = 0x0908,
+        RmsCurrentMinPhB = 0x0909,
+        RmsCurrentMaxPhB = 0x090A,
+        ActivePowerPhB = 0x090B,
+        ActivePowerMinPhB = 0x090C,
+        ActivePowerMaxPhB = 0x090D,
+        ReactivePowerPhB = 0x090E,
+        ApparentPowerPhB = 0x090F,
+        PowerFactorPhB = 0x0910,
+        AverageRmsVoltageMeasurementPeriodPhB = 0x0911,
+        AverageRmsOverVoltageCounterPhB = 0x0912,
+        AverageRmsUnderVoltageCounterPhB = 0x0913,
+        RmsExtremeOverVoltagePeriodPhB = 0x0914,
+        RmsExtremeUnderVoltagePeriodPhB = 0x0915,
+        RmsVoltageSagPeriodPhB = 0x0916,
+        RmsVoltageSwellPeriodPhB = 0x0917,
+        // AC Phase C Measurements
+        LineCurrentPhC = 0x0A01,
+        ActiveCurrentPhC = 0x0A02,
+        ReactiveCurrentPhC = 0x0A03,
+        RmsVoltagePhC = 0x0A05,
+        RmsVoltageMinPhC = 0x0A06,
+        RmsVoltageMaxPhC = 0x0A07,
+        RmsCurrentPhC = 0x0A08,
+        RmsCurrentMinPhC = 0x0A09,
+        RmsCurrentMaxPhC = 0x0A0A,
+        ActivePowerPhC = 0x0A0B,
+        ActivePowerMinPhC = 0x0A0C,
+        ActivePowerMaxPhC = 0x0A0D,
+        ReactivePowerPhC = 0x0A0E,
+        ApparentPowerPhC = 0x0A0F,
+        PowerFactorPhC = 0x0A10,
+        AverageRmsVoltageMeasurementPeriodPhC = 0x0A11,
+        AverageRmsOverVoltageCounterPhC = 0x0A12,
+        AverageRmsUnderVoltageCounterPhC = 0x0A13,
+        RmsExtremeOverVoltagePeriodPhC = 0x0A14,
+        RmsExtremeUnderVoltagePeriodPhC = 0x0A15,
+        RmsVoltageSagPeriodPhC = 0x0A16,
+        RmsVoltageSwellPeriodPhC = 0x0A17,
+    }
+}
+
+/// Phase of a poly-phase measurement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Single phase or phase A (attribute set `0x05`)
+    A,
+    /// Phase B (attribute set `0x09`)
+    B,
+    /// Phase C (attribute set `0x0A`)
+    C,
+}
+
+impl AttributeIdentifier {
+    /// Returns the identifier of the same attribute for another phase.
+    ///
+    /// Returns `None` if the attribute is not part of a per-phase attribute set.
+    pub fn with_phase(self, phase: Phase) -> Option<Self> {
+        use core::convert::TryFrom;
+
+        let id = self as u16;
+        let set = match phase {
+            Phase::A => 0x0500,
+            Phase::B => 0x0900,
+            Phase::C => 0x0A00,
+        };
+        match id & 0xff00 {
+            0x0500 | 0x0900 | 0x0A00 => Self::try_from(set | (id & 0x00ff)).ok(),
+            _ => None,
+        }
+    }
+}
+
+impl_byte! {
+    /// 4.9.2.2.1.1 `MeasurementType` Attribute
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct MeasurementType(pub u32);
+}
+
+impl MeasurementType {
+    /// Active measurement (AC)
+    pub const ACTIVE_MEASUREMENT_AC: u32 = 1 << 0;
+    /// Reactive measurement (AC)
+    pub const REACTIVE_MEASUREMENT_AC: u32 = 1 << 1;
+    /// Apparent measurement (AC)
+    pub const APPARENT_MEASUREMENT_AC: u32 = 1 << 2;
+    /// Phase A measurement
+    pub const PHASE_A_MEASUREMENT: u32 = 1 << 3;
+    /// Phase B measurement
+    pub const PHASE_B_MEASUREMENT: u32 = 1 << 4;
+    /// Phase C measurement
+    pub const PHASE_C_MEASUREMENT: u32 = 1 << 5;
+    /// DC measurement
+    pub const DC_MEASUREMENT: u32 = 1 << 6;
+    /// Harmonics measurement
+    pub const HARMONICS_MEASUREMENT: u32 = 1 << 7;
+    /// Power quality measurement
+    pub const POWER_QUALITY_MEASUREMENT: u32 = 1 << 8;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u32) -> bool {
+        self.0 & flags == flags
+    }
+
+    /// Returns `true` if measurements for the given phase are supported.
+    pub fn supports_phase(self, phase: Phase) -> bool {
+        match phase {
+            Phase::A => self.contains(Self::PHASE_A_MEASUREMENT),
+            Phase::B => self.contains(Self::PHASE_B_MEASUREMENT),
+            Phase::C => self.contains(Self::PHASE_C_MEASUREMENT),
+        }
+    }
+}
+
+/// Applies `multiplier / divisor` to a raw value. A divisor of zero is treated as one.
+fn scale(raw: f32, multiplier: u32, divisor: u32) -> f32 {
+    let divisor = if divisor == 0 { 1 } else { divisor };
+    #[allow(clippy::cast_precision_loss)]
+    let factor = multiplier as f32 / divisor as f32;
+    raw * factor
+}
+
+/// 4.9.2.2.7 AC Formatting Attribute Set
+///
+/// Together with the `PowerMultiplier`/`PowerDivisor` attributes these values are used to
+/// convert the AC measurements into volts, amps and watts.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AcFormatting {
+    pub voltage_multiplier: u16,
+    pub voltage_divisor: u16,
+    pub current_multiplier: u16,
+    pub current_divisor: u16,
+    pub power_multiplier: u16,
+    pub power_divisor: u16,
+}
+
+impl Default for AcFormatting {
+    fn default() -> Self {
+        Self {
+            voltage_multiplier: 1,
+            voltage_divisor: 1,
+            current_multiplier: 1,
+            current_divisor: 1,
+            power_multiplier: 1,
+            power_divisor: 1,
+        }
+    }
+}
+
+impl AcFormatting {
+    /// Converts `RMSVoltage` (and related attributes) into volts.
+    pub fn volts(&self, raw: u16) -> f32 {
+        scale(
+            f32::from(raw),
+            u32::from(self.voltage_multiplier),
+            u32::from(self.voltage_divisor),
+        )
+    }
+
+    /// Converts `RMSCurrent` (and related attributes) into amps.
+    pub fn amps(&self, raw: u16) -> f32 {
+        scale(
+            f32::from(raw),
+            u32::from(self.current_multiplier),
+            u32::from(self.current_divisor),
+        )
+    }
+
+    /// Converts `ActivePower` or `ReactivePower` into watts (`VAr`).
+    pub fn watts(&self, raw: i16) -> f32 {
+        scale(
+            f32::from(raw),
+            u32::from(self.power_multiplier),
+            u32::from(self.power_divisor),
+        )
+    }
+
+    /// Converts `ApparentPower` into volt-amperes.
+    pub fn volt_amperes(&self, raw: u16) -> f32 {
+        scale(
+            f32::from(raw),
+            u32::from(self.power_multiplier),
+            u32::from(self.power_divisor),
+        )
+    }
+}
+
+/// 4.9.2.2.3 DC Formatting Attribute Set
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DcFormatting {
+    pub voltage_multiplier: u16,
+    pub voltage_divisor: u16,
+    pub current_multiplier: u16,
+    pub current_divisor: u16,
+    pub power_multiplier: u16,
+    pub power_divisor: u16,
+}
+
+impl Default for DcFormatting {
+    fn default() -> Self {
+        Self {
+            voltage_multiplier: 1,
+            voltage_divisor: 1,
+            current_multiplier: 1,
+            current_divisor: 1,
+            power_multiplier: 1,
+            power_divisor: 1,
+        }
+    }
+}
+
+impl DcFormatting {
+    /// Converts `DCVoltage` (and related attributes) into volts.
+    pub fn volts(&self, raw: i16) -> f32 {
+        scale(
+            f32::from(raw),
+            u32::from(self.voltage_multiplier),
+            u32::from(self.voltage_divisor),
+        )
+    }
+
+    /// Converts `DCCurrent` (and related attributes) into amps.
+    pub fn amps(&self, raw: i16) -> f32 {
+        scale(
+            f32::from(raw),
+            u32::from(self.current_multiplier),
+            u32::from(self.current_divisor),
+        )
+    }
+
+    /// Converts `DCPower` (and related attributes) into watts.
+    pub fn watts(&self, raw: i16) -> f32 {
+        scale(
+            f32::from(raw),
+            u32::from(self.power_multiplier),
+            u32::from(self.power_divisor),
+        )
+    }
+}
+
+/// 4.9.2.2.5 AC (Non-phase Specific) Formatting `PowerMultiplier` and `PowerDivisor`
+///
+/// Used for `TotalActivePower`, `TotalReactivePower` and `TotalApparentPower`.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TotalPowerFormatting {
+    pub multiplier: u32,
+    pub divisor: u32,
+}
+
+impl Default for TotalPowerFormatting {
+    fn default() -> Self {
+        Self {
+            multiplier: 1,
+            divisor: 1,
+        }
+    }
+}
+
+impl TotalPowerFormatting {
+    /// Converts `TotalActivePower` or `TotalReactivePower` into watts (`VAr`).
+    #[allow(clippy::cast_precision_loss)]
+    pub fn watts(&self, raw: i32) -> f32 {
+        scale(raw as f32, self.multiplier, self.divisor)
+    }
+}
+
+/// Measurements of a single phase.
+///
+/// See Section 4.9.2.2.6
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AcPhaseMeasurement {
+    pub rms_voltage: u16,
+    pub rms_current: u16,
+    pub active_power: i16,
+    pub reactive_power: i16,
+    pub apparent_power: u16,
+    /// Power factor in the range -100 to +100.
+    pub power_factor: i8,
+}
+
+impl AcPhaseMeasurement {
+    /// RMS voltage in volts
+    pub fn volts(&self, formatting: &AcFormatting) -> f32 {
+        formatting.volts(self.rms_voltage)
+    }
+
+    /// RMS current in amps
+    pub fn amps(&self, formatting: &AcFormatting) -> f32 {
+        formatting.amps(self.rms_current)
+    }
+
+    /// Active power in watts
+    pub fn watts(&self, formatting: &AcFormatting) -> f32 {
+        formatting.watts(self.active_power)
+    }
+
+    /// Apparent power in volt-amperes
+    pub fn volt_amperes(&self, formatting: &AcFormatting) -> f32 {
+        formatting.volt_amperes(self.apparent_power)
+    }
+
+    /// Power factor in the range -1.0 to 1.0
+    pub fn power_factor(&self) -> f32 {
+        f32::from(self.power_factor) / 100.0
+    }
+}
+
+impl_byte! {
+    /// 4.9.2.3 Commands Received
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ReceivedCommandIdentifier {
+        /// Retrieves the power profiling information
+        GetProfileInfo = 0x00,
+        /// Retrieves an electricity measurement profile
+        GetMeasurementProfile = 0x01,
+    }
+}
+
+impl_byte! {
+    /// 4.9.2.4 Commands Generated
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum GeneratedCommandIdentifier {
+        /// Response to [`ReceivedCommandIdentifier::GetProfileInfo`]
+        GetProfileInfoResponse = 0x00,
+        /// Response to [`ReceivedCommandIdentifier::GetMeasurementProfile`]
+        GetMeasurementProfileResponse = 0x01,
+    }
+}
+
+impl_byte! {
+    /// Profile interval period
+    ///
+    /// See Section 4.9.2.4.1.2
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ProfileIntervalPeriod {
+        Daily = 0x00,
+        SixtyMinutes = 0x01,
+        ThirtyMinutes = 0x02,
+        FifteenMinutes = 0x03,
+        TenMinutes = 0x04,
+        SevenAndHalfMinutes = 0x05,
+        FiveMinutes = 0x06,
+        TwoAndHalfMinutes = 0x07,
+    }
+}
+
+impl ProfileIntervalPeriod {
+    /// Duration of the interval in seconds.
+    pub fn seconds(self) -> u32 {
+        match self {
+            Self::Daily => 86_400,
+            Self::SixtyMinutes => 3_600,
+            Self::ThirtyMinutes => 1_800,
+            Self::FifteenMinutes => 900,
+            Self::TenMinutes => 600,
+            Self::SevenAndHalfMinutes => 450,
+            Self::FiveMinutes => 300,
+            Self::TwoAndHalfMinutes => 150,
+        }
+    }
+}
+
+impl_byte! {
+    /// Status of the [`GetMeasurementProfileResponse`]
+    ///
+    /// See Section 4.9.2.4.2.2
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum MeasurementProfileStatus {
+        Success = 0x00,
+        AttributeProfileNotSupported = 0x01,
+        InvalidStartTime = 0x02,
+        MoreIntervalsRequestedThanCanBeReturned = 0x03,
+        NoIntervalsAvailableForTheRequestedTime = 0x04,
+    }
+}
+
+impl_byte! {
+    /// 4.9.2.3.2 Get Measurement Profile Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GetMeasurementProfile {
+        /// Attribute to be profiled
+        pub attribute_id: u16,
+        /// UTC time of the first interval
+        pub start_time: u32,
+        /// Number of intervals requested
+        pub number_of_intervals: u8,
+    }
+}
+
+/// 4.9.2.4.1 Get Profile Info Response Command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GetProfileInfoResponse<'a> {
+    /// Total number of supported profiles
+    pub profile_count: u8,
+    /// Time frame used to capture the profile
+    pub profile_interval_period: ProfileIntervalPeriod,
+    /// Maximum number of intervals the device is capable of returning in one response
+    pub max_number_of_intervals: u8,
+    /// Raw list of profiled attribute identifiers, see [`Self::attributes`]
+    pub list_of_attributes: &'a [u8],
+}
+
+impl GetProfileInfoResponse<'_> {
+    /// Iterates the identifiers of all profiled attributes.
+    pub fn attributes(&self) -> impl Iterator<Item = u16> + '_ {
+        self.list_of_attributes
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+    }
+}
+
+impl<'a> TryRead<'a, ()> for GetProfileInfoResponse<'a> {
+    fn try_read(bytes: &'a [u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let profile_count = bytes.read_with(offset, byte::LE)?;
+        let profile_interval_period = bytes.read_with(offset, byte::LE)?;
+        let max_number_of_intervals = bytes.read_with(offset, byte::LE)?;
+        let len = usize::from(profile_count) * 2;
+        if bytes.len() - *offset != len {
+            return Err(byte::Error::BadInput {
+                err: "List of attributes does not match profile count",
+            });
+        }
+        let list_of_attributes = bytes.read_with(offset, ctx::Bytes::Len(len))?;
+
+        Ok((
+            Self {
+                profile_count,
+                profile_interval_period,
+                max_number_of_intervals,
+                list_of_attributes,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl TryWrite for GetProfileInfoResponse<'_> {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, self.profile_count, byte::LE)?;
+        bytes.write_with(offset, self.profile_interval_period, byte::LE)?;
+        bytes.write_with(offset, self.max_number_of_intervals, byte::LE)?;
+        bytes.write(offset, self.list_of_attributes)?;
+
+        Ok(*offset)
+    }
+}
+
+/// 4.9.2.4.2 Get Measurement Profile Response Command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GetMeasurementProfileResponse<'a> {
+    /// UTC time of the first interval
+    pub start_time: u32,
+    /// Status of the request
+    pub status: MeasurementProfileStatus,
+    /// Time frame used to capture the profile
+    pub profile_interval_period: ProfileIntervalPeriod,
+    /// Number of intervals contained in `intervals`
+    pub number_of_intervals_delivered: u8,
+    /// Profiled attribute
+    pub attribute_id: u16,
+    /// Raw interval values, encoded with the data type of the profiled attribute
+    pub intervals: &'a [u8],
+}
+
+impl<'a> TryRead<'a, ()> for GetMeasurementProfileResponse<'a> {
+    fn try_read(bytes: &'a [u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let start_time = bytes.read_with(offset, byte::LE)?;
+        let status = bytes.read_with(offset, byte::LE)?;
+        let profile_interval_period = bytes.read_with(offset, byte::LE)?;
+        let number_of_intervals_delivered = bytes.read_with(offset, byte::LE)?;
+        let attribute_id = bytes.read_with(offset, byte::LE)?;
+        let intervals = bytes.read_with(offset, ctx::Bytes::Len(bytes.len() - *offset))?;
+
+        Ok((
+            Self {
+                start_time,
+                status,
+                profile_interval_period,
+                number_of_intervals_delivered,
+                attribute_id,
+                intervals,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl TryWrite for GetMeasurementProfileResponse<'_> {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, self.start_time, byte::LE)?;
+        bytes.write_with(offset, self.status, byte::LE)?;
+        bytes.write_with(offset, self.profile_interval_period, byte::LE)?;
+        bytes.write_with(offset, self.number_of_intervals_delivered, byte::LE)?;
+        bytes.write_with(offset, self.attribute_id, byte::LE)?;
+        bytes.write(offset, self.intervals)?;
+
+        Ok(*offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::TryRead;
+
+    use super::*;
+
+    #[test]
+    fn scale_ac_measurements() {
+        // given
+        let formatting = AcFormatting {
+            voltage_multiplier: 1,
+            voltage_divisor: 10,
+            current_multiplier: 1,
+            current_divisor: 1000,
+            power_multiplier: 1,
+            power_divisor: 10,
+        };
+        let measurement = AcPhaseMeasurement {
+            rms_voltage: 2301,
+            rms_current: 1520,
+            active_power: 3345,
+            reactive_power: 0,
+            apparent_power: 40_000,
+            power_factor: 95,
+        };
+
+        // then
+        assert!((measurement.volts(&formatting) - 230.1).abs() < 0.001);
+        assert!((measurement.amps(&formatting) - 1.52).abs() < 0.001);
+        assert!((measurement.watts(&formatting) - 334.5).abs() < 0.001);
+        assert!((measurement.volt_amperes(&formatting) - 4000.0).abs() < 0.001);
+        assert!((measurement.power_factor() - 0.95).abs() < 0.001);
+    }
+
+    #[test]
+    fn zero_divisor_is_treated_as_one() {
+        // given
+        let formatting = DcFormatting {
+            voltage_divisor: 0,
+            ..DcFormatting::default()
+        };
+
+        // then
+        assert!((formatting.volts(-12) + 12.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn attribute_for_phase() {
+        assert_eq!(
+            AttributeIdentifier::RmsVoltage.with_phase(Phase::B),
+            Some(AttributeIdentifier::RmsVoltagePhB)
+        );
+        assert_eq!(
+            AttributeIdentifier::ActivePowerPhB.with_phase(Phase::C),
+            Some(AttributeIdentifier::ActivePowerPhC)
+        );
+        assert_eq!(
+            AttributeIdentifier::PowerFactorPhC.with_phase(Phase::A),
+            Some(AttributeIdentifier::PowerFactor)
+        );
+        assert_eq!(AttributeIdentifier::DcVoltage.with_phase(Phase::B), None);
+        assert!(MeasurementType(0b0001_1001).supports_phase(Phase::B));
+        assert!(!MeasurementType(0b0001_1001).supports_phase(Phase::C));
+    }
+
+    #[test]
+    fn unpack_get_measurement_profile_response() {
+        // given
+        let input = [
+            0x00, 0x10, 0x00, 0x00, // start time
+            0x00, // status
+            0x03, // profile interval period
+            0x02, // number of intervals delivered
+            0x05, 0x05, // attribute id
+            0xfd, 0x08, 0xfe, 0x08, // intervals
+        ];
+
+        // when
+        let (response, len) = GetMeasurementProfileResponse::try_read(&input, ())
+            .expect("Could not read GetMeasurementProfileResponse in test");
+
+        // then
+        assert_eq!(len, input.len());
+        assert_eq!(response.start_time, 0x1000);
+        assert_eq!(response.status, MeasurementProfileStatus::Success);
+        assert_eq!(
+            response.profile_interval_period,
+            ProfileIntervalPeriod::FifteenMinutes
+        );
+        assert_eq!(
+            response.attribute_id,
+            AttributeIdentifier::RmsVoltage as u16
+        );
+        assert_eq!(response.intervals, &[0xfd, 0x08, 0xfe, 0x08]);
+    }
+
+    #[test]
+    fn unpack_get_profile_info_response() {
+        // given
+        let input = [0x02, 0x01, 0x18, 0x05, 0x05, 0x0b, 0x05];
+
+        // when
+        let (response, _) = GetProfileInfoResponse::try_read(&input, ())
+            .expect("Could not read GetProfileInfoResponse in test");
+
+        // then
+        assert_eq!(
+            response.profile_interval_period,
+            ProfileIntervalPeriod::SixtyMinutes
+        );
+        let mut attributes = response.attributes();
+        assert_eq!(attributes.next(), Some(0x0505));
+        assert_eq!(attributes.next(), Some(0x050b));
+        assert_eq!(attributes.next(), None);
+    }
+
+    #[test]
+    fn reject_invalid_list_of_attributes() {
+        // given
+        let odd = [0x01, 0x01, 0x18, 0x05, 0x05, 0x0b];
+        let mismatch = [0x01, 0x01, 0x18, 0x05, 0x05, 0x0b, 0x05];
+
+        // then
+        assert!(matches!(
+            GetProfileInfoResponse::try_read(&odd, ()),
+            Err(byte::Error::BadInput { .. })
+        ));
+        assert!(matches!(
+            GetProfileInfoResponse::try_read(&mismatch, ()),
+            Err(byte::Error::BadInput { .. })
+        ));
+    }
+}
@@ -1,3 +1,11 @@
+/// Provides an interface to concentration measurement functionality (CO2, PM2.5, formaldehyde,
+/// ...), including configuration and provision of notifications of concentration measurements.
+pub mod concentration;
+
+/// Provides an interface to electrical measurement functionality (voltage, current, power, ...)
+/// of single and poly-phase AC and DC devices.
+pub mod electrical;
+
 /// Provides an interface to Illuminance level sensing functionality, including configuration and
 /// provision of notifications of wheter the illuminance is within, above or below a target band.
 pub mod illuminance;
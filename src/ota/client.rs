@@ -0,0 +1,465 @@
+//!
+//! 11.13 OTA Upgrade Client
+//!
+//! Transport-agnostic state machine of the OTA Upgrade client. The handlers return the
+//! [`ClientAction`] to perform, the image data is written to an [`ImageSink`]. The download can
+//! be resumed after a reboot from the persisted [`OtaClient::file_offset`] with
+//! [`OtaClient::resume`].
+use core::convert::TryFrom;
+
+use crate::ota::upgrade::{
+    ImageBlockRequest, ImageBlockResponse, ImageInfo, ImageNotify, ImageUpgradeStatus,
+    QueryNextImageRequest, QueryNextImageResponse, UpgradeEndRequest, UpgradeEndResponse,
+    FILE_VERSION_WILDCARD, IMAGE_TYPE_WILDCARD, MANUFACTURER_CODE_WILDCARD,
+};
+use crate::ota::StorageError;
+use crate::status::ZclStatus;
+
+/// Storage of the downloaded image, similar to a seekable writer
+pub trait ImageSink {
+    /// Writes `data` starting at `offset`.
+    fn write_at(&mut self, offset: u32, data: &[u8]) -> Result<(), StorageError>;
+}
+
+impl ImageSink for [u8] {
+    fn write_at(&mut self, offset: u32, data: &[u8]) -> Result<(), StorageError> {
+        let start = usize::try_from(offset).map_err(|_| StorageError::Failed)?;
+        let end = start.checked_add(data.len()).ok_or(StorageError::Failed)?;
+        self.get_mut(start..end)
+            .ok_or(StorageError::Failed)?
+            .copy_from_slice(data);
+
+        Ok(())
+    }
+}
+
+/// Configuration of the OTA Upgrade client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientConfig {
+    /// Manufacturer code of the device
+    pub manufacturer_code: u16,
+    /// Image type of the device
+    pub image_type: u16,
+    /// Version of the running firmware
+    pub current_file_version: u32,
+    /// Hardware version of the device
+    pub hardware_version: Option<u16>,
+    /// Maximum size of a requested block
+    pub maximum_data_size: u8,
+    /// Number of retries of a request without response before the download is aborted
+    pub max_retries: u8,
+}
+
+/// Request of the client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientRequest {
+    /// Query Next Image Request command
+    QueryNextImage(QueryNextImageRequest),
+    /// Image Block Request command
+    ImageBlock(ImageBlockRequest),
+    /// Upgrade End Request command
+    UpgradeEnd(UpgradeEndRequest),
+}
+
+/// Action to perform after a client event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientAction {
+    /// Nothing to do, e.g. for an unexpected response
+    None,
+    /// Send the request to the server
+    Send(ClientRequest),
+    /// Send the request to the server after the delay in milliseconds
+    SendAfter(u32, ClientRequest),
+    /// The download is complete, verify the image and call [`OtaClient::verify`]
+    Verify,
+    /// Switch to the new image after the delay in seconds, `None` waits for another Upgrade
+    /// End Response
+    Upgrade(Option<u32>),
+    /// The download was aborted
+    Aborted,
+}
+
+/// State of the OTA Upgrade client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientState {
+    /// No upgrade in progress
+    Idle,
+    /// Waiting for the Query Next Image Response
+    QueryingNextImage,
+    /// Waiting for an Image Block Response
+    Downloading,
+    /// Waiting for the verification of the downloaded image
+    Verifying,
+    /// Waiting for the Upgrade End Response
+    UpgradeEnd,
+    /// Waiting for the upgrade
+    WaitingToUpgrade,
+}
+
+/// OTA Upgrade client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OtaClient {
+    config: ClientConfig,
+    state: ClientState,
+    image: Option<ImageInfo>,
+    file_offset: u32,
+    minimum_block_period: u16,
+    retries: u8,
+}
+
+impl OtaClient {
+    /// Creates an idle client.
+    pub fn new(config: ClientConfig) -> Self {
+        Self {
+            config,
+            state: ClientState::Idle,
+            image: None,
+            file_offset: 0,
+            minimum_block_period: 0,
+            retries: 0,
+        }
+    }
+
+    /// Creates a client resuming the download of `image` at the persisted `file_offset`.
+    ///
+    /// Returns the client and the request to continue the download.
+    pub fn resume(
+        config: ClientConfig,
+        image: ImageInfo,
+        file_offset: u32,
+    ) -> (Self, ClientAction) {
+        let mut client = Self::new(config);
+        client.image = Some(image);
+        client.file_offset = file_offset.min(image.image_size);
+        client.state = ClientState::Downloading;
+        let action = client.next_block();
+
+        (client, action)
+    }
+
+    /// Returns the state of the client.
+    pub fn state(&self) -> ClientState {
+        self.state
+    }
+
+    /// Returns the `ImageUpgradeStatus` attribute.
+    pub fn image_upgrade_status(&self) -> ImageUpgradeStatus {
+        match self.state {
+            ClientState::Idle | ClientState::QueryingNextImage => ImageUpgradeStatus::Normal,
+            ClientState::Downloading => ImageUpgradeStatus::DownloadInProgress,
+            ClientState::Verifying | ClientState::UpgradeEnd => {
+                ImageUpgradeStatus::DownloadComplete
+            }
+            ClientState::WaitingToUpgrade => ImageUpgradeStatus::WaitingToUpgrade,
+        }
+    }
+
+    /// Returns the `FileOffset` attribute, persist it to resume the download after a reboot.
+    pub fn file_offset(&self) -> u32 {
+        self.file_offset
+    }
+
+    /// Returns the image being downloaded.
+    pub fn image(&self) -> Option<ImageInfo> {
+        self.image
+    }
+
+    /// Returns the Query Next Image Request to send.
+    pub fn query_next_image(&mut self) -> ClientAction {
+        self.state = ClientState::QueryingNextImage;
+        self.retries = 0;
+        ClientAction::Send(self.query_next_image_request())
+    }
+
+    /// Handles the Image Notify command.
+    ///
+    /// `random` is a random number between 1 and 100 compared to the query jitter.
+    ///
+    /// See Section 11.13.3
+    pub fn image_notify(&mut self, notify: ImageNotify, random: u8) -> ClientAction {
+        let matches = notify.manufacturer_code.map_or(true, |code| {
+            code == self.config.manufacturer_code || code == MANUFACTURER_CODE_WILDCARD
+        }) && notify.image_type.map_or(true, |image_type| {
+            image_type == self.config.image_type || image_type == IMAGE_TYPE_WILDCARD
+        }) && notify.new_file_version.map_or(true, |version| {
+            version != self.config.current_file_version || version == FILE_VERSION_WILDCARD
+        });
+
+        if self.state != ClientState::Idle || !matches || random > notify.query_jitter {
+            return ClientAction::None;
+        }
+        self.query_next_image()
+    }
+
+    /// Handles the Query Next Image Response command.
+    ///
+    /// See Section 11.13.5
+    pub fn query_next_image_response(&mut self, response: QueryNextImageResponse) -> ClientAction {
+        if self.state != ClientState::QueryingNextImage {
+            return ClientAction::None;
+        }
+
+        match response.image() {
+            Some(image)
+                if image.manufacturer_code == self.config.manufacturer_code
+                    && image.image_type == self.config.image_type =>
+            {
+                self.image = Some(image);
+                self.file_offset = 0;
+                self.minimum_block_period = 0;
+                self.retries = 0;
+                self.state = ClientState::Downloading;
+                self.next_block()
+            }
+            _ => {
+                self.reset();
+                ClientAction::None
+            }
+        }
+    }
+
+    /// Handles the Image Block Response command, the received data is written to `sink`.
+    ///
+    /// See Section 11.13.8
+    pub fn image_block_response<S: ImageSink + ?Sized>(
+        &mut self,
+        response: ImageBlockResponse<'_>,
+        sink: &mut S,
+    ) -> ClientAction {
+        let image = match self.image {
+            Some(image) if self.state == ClientState::Downloading => image,
+            _ => return ClientAction::None,
+        };
+
+        match response {
+            ImageBlockResponse::Success(block) => {
+                if block.manufacturer_code != image.manufacturer_code
+                    || block.image_type != image.image_type
+                    || block.file_version != image.file_version
+                    || block.file_offset != self.file_offset
+                    || block.data.is_empty()
+                {
+                    return self.timeout();
+                }
+                if sink.write_at(block.file_offset, block.data).is_err() {
+                    return self.abort();
+                }
+                self.retries = 0;
+                self.file_offset = self
+                    .file_offset
+                    .saturating_add(u32::try_from(block.data.len()).unwrap_or(u32::MAX))
+                    .min(image.image_size);
+                self.next_block()
+            }
+            ImageBlockResponse::WaitForData(wait) => {
+                self.minimum_block_period = wait.minimum_block_period;
+                ClientAction::SendAfter(
+                    wait.delay().saturating_mul(1000),
+                    self.image_block_request(image),
+                )
+            }
+            ImageBlockResponse::Abort => self.abort(),
+        }
+    }
+
+    /// Reports the result of the image verification after [`ClientAction::Verify`].
+    ///
+    /// Returns the Upgrade End Request to send.
+    pub fn verify(&mut self, valid: bool) -> ClientAction {
+        if self.state != ClientState::Verifying {
+            return ClientAction::None;
+        }
+        let status = if valid {
+            self.state = ClientState::UpgradeEnd;
+            ZclStatus::Success
+        } else {
+            ZclStatus::InvalidImage
+        };
+        let request = self.upgrade_end_request(status);
+        if !valid {
+            self.reset();
+        }
+
+        ClientAction::Send(request)
+    }
+
+    /// Handles the Upgrade End Response command.
+    ///
+    /// See Section 11.13.10
+    pub fn upgrade_end_response(&mut self, response: UpgradeEndResponse) -> ClientAction {
+        let matches = self.image.map_or(false, |image| {
+            response.manufacturer_code == image.manufacturer_code
+                && response.image_type == image.image_type
+                && response.file_version == image.file_version
+        });
+        if !matches
+            || !(self.state == ClientState::UpgradeEnd
+                || self.state == ClientState::WaitingToUpgrade)
+        {
+            return ClientAction::None;
+        }
+        self.state = ClientState::WaitingToUpgrade;
+
+        ClientAction::Upgrade(response.delay())
+    }
+
+    /// Handles a missing or invalid response, the request is repeated until the retries are
+    /// exhausted.
+    pub fn timeout(&mut self) -> ClientAction {
+        let request = match (self.state, self.image) {
+            (ClientState::QueryingNextImage, _) => self.query_next_image_request(),
+            (ClientState::Downloading, Some(image)) => self.image_block_request(image),
+            (ClientState::UpgradeEnd, _) => self.upgrade_end_request(ZclStatus::Success),
+            _ => return ClientAction::None,
+        };
+
+        if self.retries >= self.config.max_retries {
+            return self.abort();
+        }
+        self.retries += 1;
+        ClientAction::Send(request)
+    }
+
+    /// Aborts the upgrade.
+    pub fn abort(&mut self) -> ClientAction {
+        self.reset();
+        ClientAction::Aborted
+    }
+
+    fn reset(&mut self) {
+        self.state = ClientState::Idle;
+        self.image = None;
+        self.file_offset = 0;
+        self.retries = 0;
+    }
+
+    fn next_block(&mut self) -> ClientAction {
+        let image = match self.image {
+            Some(image) => image,
+            None => return self.abort(),
+        };
+        if self.file_offset >= image.image_size {
+            self.state = ClientState::Verifying;
+            return ClientAction::Verify;
+        }
+
+        let request = self.image_block_request(image);
+        if self.minimum_block_period == 0 {
+            ClientAction::Send(request)
+        } else {
+            ClientAction::SendAfter(u32::from(self.minimum_block_period), request)
+        }
+    }
+
+    fn query_next_image_request(&self) -> ClientRequest {
+        ClientRequest::QueryNextImage(QueryNextImageRequest {
+            field_control: if self.config.hardware_version.is_some() {
+                QueryNextImageRequest::HARDWARE_VERSION_PRESENT
+            } else {
+                0
+            },
+            manufacturer_code: self.config.manufacturer_code,
+            image_type: self.config.image_type,
+            current_file_version: self.config.current_file_version,
+            hardware_version: self.config.hardware_version,
+        })
+    }
+
+    fn image_block_request(&self, image: ImageInfo) -> ClientRequest {
+        let remaining = image.image_size.saturating_sub(self.file_offset);
+        ClientRequest::ImageBlock(ImageBlockRequest {
+            field_control: 0,
+            manufacturer_code: image.manufacturer_code,
+            image_type: image.image_type,
+            file_version: image.file_version,
+            file_offset: self.file_offset,
+            maximum_data_size: u8::try_from(remaining)
+                .map_or(self.config.maximum_data_size, |remaining| {
+                    remaining.min(self.config.maximum_data_size)
+                }),
+            request_node_address: None,
+            minimum_block_period: None,
+        })
+    }
+
+    fn upgrade_end_request(&self, status: ZclStatus) -> ClientRequest {
+        let image = self.image.unwrap_or(ImageInfo {
+            manufacturer_code: self.config.manufacturer_code,
+            image_type: self.config.image_type,
+            file_version: FILE_VERSION_WILDCARD,
+            image_size: 0,
+        });
+        ClientRequest::UpgradeEnd(UpgradeEndRequest {
+            status,
+            manufacturer_code: image.manufacturer_code,
+            image_type: image.image_type,
+            file_version: image.file_version,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ota::upgrade::ImageNotifyPayloadType;
+
+    const CONFIG: ClientConfig = ClientConfig {
+        manufacturer_code: 0x1234,
+        image_type: 1,
+        current_file_version: 1,
+        hardware_version: Some(1),
+        maximum_data_size: 32,
+        max_retries: 2,
+    };
+
+    #[test]
+    fn retries_and_abort() {
+        // given
+        let mut client = OtaClient::new(CONFIG);
+        client.query_next_image();
+
+        // when
+        let first = client.timeout();
+        let second = client.timeout();
+        let third = client.timeout();
+
+        // then
+        assert!(matches!(
+            first,
+            ClientAction::Send(ClientRequest::QueryNextImage(_))
+        ));
+        assert!(matches!(second, ClientAction::Send(_)));
+        assert_eq!(third, ClientAction::Aborted);
+        assert_eq!(client.state(), ClientState::Idle);
+        assert_eq!(
+            client.image_notify(
+                ImageNotify {
+                    payload_type: ImageNotifyPayloadType::QueryJitter,
+                    query_jitter: 10,
+                    manufacturer_code: None,
+                    image_type: None,
+                    new_file_version: None,
+                },
+                50
+            ),
+            ClientAction::None
+        );
+    }
+
+    #[test]
+    fn sink_rejects_out_of_range_writes() {
+        // given
+        let mut sink = [0u8; 4];
+
+        // when
+        let inside = sink.write_at(2, &[0xaa, 0xbb]);
+        let outside = sink.write_at(3, &[0xaa, 0xbb]);
+        let overflow = sink.write_at(u32::MAX, &[0xaa]);
+
+        // then
+        assert_eq!(inside, Ok(()));
+        assert_eq!(sink, [0x00, 0x00, 0xaa, 0xbb]);
+        assert_eq!(outside, Err(StorageError::Failed));
+        assert_eq!(overflow, Err(StorageError::Failed));
+    }
+}
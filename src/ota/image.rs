@@ -0,0 +1,324 @@
+//!
+//! 11.4 OTA Upgrade File Format
+//!
+//! Zero-copy parser for OTA upgrade files. [`OtaFile::parse`] validates the header and the file
+//! size, the sub-elements are borrowed from the file by [`OtaFile::sub_elements`].
+use core::convert::TryFrom;
+
+use byte::{BytesExt, TryRead};
+
+use crate::impl_byte;
+
+/// Magic number at the start of every OTA upgrade file
+pub const FILE_IDENTIFIER: u32 = 0x0bee_f11e;
+
+/// Supported OTA header version
+pub const HEADER_VERSION: u16 = 0x0100;
+
+/// Length of the OTA header string
+pub const HEADER_STRING_LEN: usize = 32;
+
+/// Length of a sub-element header (tag ID and length)
+pub const SUB_ELEMENT_HEADER_LEN: usize = 6;
+
+impl_byte! {
+    /// 11.4.2.4 OTA Header Field Control
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct HeaderFieldControl(pub u16);
+}
+
+impl HeaderFieldControl {
+    /// The security credential version is present
+    pub const SECURITY_CREDENTIAL_VERSION_PRESENT: u16 = 1 << 0;
+    /// The file is device specific, the upgrade file destination is present
+    pub const DEVICE_SPECIFIC_FILE: u16 = 1 << 1;
+    /// The minimum and maximum hardware versions are present
+    pub const HARDWARE_VERSIONS_PRESENT: u16 = 1 << 2;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u16) -> bool {
+        self.0 & flags == flags
+    }
+}
+
+impl_byte! {
+    /// 11.4.2 OTA Header
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct OtaHeader<'a> {
+        /// See [`FILE_IDENTIFIER`]
+        pub file_identifier: u32,
+        /// See [`HEADER_VERSION`]
+        pub header_version: u16,
+        /// Length of the header including the optional fields
+        pub header_length: u16,
+        /// Optional fields present in the header
+        pub header_field_control: HeaderFieldControl,
+        /// Manufacturer code of the image
+        pub manufacturer_code: u16,
+        /// Image type of the image
+        pub image_type: u16,
+        /// File version of the image
+        pub file_version: u32,
+        /// `ZigBee` stack version of the image
+        pub zigbee_stack_version: u16,
+        /// Human readable description of the image
+        #[ctx = byte::ctx::Bytes::Len(HEADER_STRING_LEN)]
+        pub header_string: &'a [u8],
+        /// Size of the file including the header
+        pub total_image_size: u32,
+        /// Security credential version required to install the image
+        #[parse_if = header_field_control.contains(HeaderFieldControl::SECURITY_CREDENTIAL_VERSION_PRESENT)]
+        pub security_credential_version: Option<u8>,
+        /// IEEE address of the device the file is meant for
+        #[parse_if = header_field_control.contains(HeaderFieldControl::DEVICE_SPECIFIC_FILE)]
+        pub upgrade_file_destination: Option<u64>,
+        /// Minimum hardware version the image can be installed on
+        #[parse_if = header_field_control.contains(HeaderFieldControl::HARDWARE_VERSIONS_PRESENT)]
+        pub minimum_hardware_version: Option<u16>,
+        /// Maximum hardware version the image can be installed on
+        #[parse_if = header_field_control.contains(HeaderFieldControl::HARDWARE_VERSIONS_PRESENT)]
+        pub maximum_hardware_version: Option<u16>,
+    }
+}
+
+impl OtaHeader<'_> {
+    /// Validates the file identifier, the header version and that the header length covers the
+    /// `parsed_len` octets of the header fields and lies within the total image size.
+    pub(crate) fn validate(&self, parsed_len: usize) -> byte::Result<()> {
+        if self.file_identifier != FILE_IDENTIFIER {
+            return Err(byte::Error::BadInput {
+                err: "Invalid OTA file identifier",
+            });
+        }
+        if self.header_version != HEADER_VERSION {
+            return Err(byte::Error::BadInput {
+                err: "Unsupported OTA header version",
+            });
+        }
+        let header_length = u32::from(self.header_length);
+        if usize::from(self.header_length) < parsed_len || header_length > self.total_image_size {
+            return Err(byte::Error::BadInput {
+                err: "Invalid OTA header length",
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the header string without the trailing null padding.
+    pub fn header_str(&self) -> Option<&str> {
+        let len = self
+            .header_string
+            .iter()
+            .position(|b| *b == 0)
+            .unwrap_or(self.header_string.len());
+        core::str::from_utf8(&self.header_string[..len]).ok()
+    }
+
+    /// Returns `true` if the image can be installed on the given hardware version.
+    pub fn supports_hardware_version(&self, hardware_version: u16) -> bool {
+        self.minimum_hardware_version
+            .map_or(true, |min| hardware_version >= min)
+            && self
+                .maximum_hardware_version
+                .map_or(true, |max| hardware_version <= max)
+    }
+}
+
+impl_byte! {
+    /// 11.4.3 Sub-element tag identifiers
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum TagId {
+        UpgradeImage = 0x0000,
+        EcdsaSignatureCryptoSuite1 = 0x0001,
+        EcdsaSigningCertificateCryptoSuite1 = 0x0002,
+        ImageIntegrityCode = 0x0003,
+        PictureData = 0x0004,
+        EcdsaSignatureCryptoSuite2 = 0x0005,
+        EcdsaSigningCertificateCryptoSuite2 = 0x0006,
+    }
+}
+
+/// 11.4.3 Sub-element
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubElement<'a> {
+    /// Raw tag ID, see [`TagId`] (`0xf000` - `0xffff` are manufacturer specific)
+    pub tag_id: u16,
+    /// Data of the sub-element
+    pub data: &'a [u8],
+}
+
+impl SubElement<'_> {
+    /// Returns the tag, `None` for reserved or manufacturer specific tags.
+    pub fn tag(&self) -> Option<TagId> {
+        TagId::try_from(self.tag_id).ok()
+    }
+}
+
+impl<'a> TryRead<'a, ()> for SubElement<'a> {
+    fn try_read(bytes: &'a [u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let tag_id = bytes.read_with(offset, byte::LE)?;
+        let length: u32 = bytes.read_with(offset, byte::LE)?;
+        let length = usize::try_from(length).map_err(|_| byte::Error::BadInput {
+            err: "Sub-element too long",
+        })?;
+        let data = bytes.read_with(offset, byte::ctx::Bytes::Len(length))?;
+
+        Ok((Self { tag_id, data }, *offset))
+    }
+}
+
+/// Iterator over the sub-elements of an [`OtaFile`]
+///
+/// Stops at the end of the file or at a truncated sub-element.
+#[derive(Debug, Clone)]
+pub struct SubElements<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Iterator for SubElements<'a> {
+    type Item = SubElement<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.data.read_with(&mut self.offset, ()).ok()
+    }
+}
+
+/// OTA upgrade file borrowed from a buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OtaFile<'a> {
+    /// Header of the file
+    pub header: OtaHeader<'a>,
+    data: &'a [u8],
+}
+
+impl<'a> OtaFile<'a> {
+    /// Parses and validates the header of the file in `bytes`.
+    ///
+    /// The header length must cover the parsed header fields, unknown header versions are
+    /// rejected.
+    ///
+    /// `bytes` may be longer than the file, the sub-elements are limited to the total image size.
+    pub fn parse(bytes: &'a [u8]) -> byte::Result<Self> {
+        let (header, header_len) = OtaHeader::try_read(bytes, ())?;
+        header.validate(header_len)?;
+        let data = usize::try_from(header.total_image_size)
+            .ok()
+            .and_then(|size| bytes.get(..size))
+            .ok_or(byte::Error::Incomplete)?;
+
+        Ok(Self { header, data })
+    }
+
+    /// Returns the raw file including the header.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns the sub-elements following the header.
+    pub fn sub_elements(&self) -> SubElements<'a> {
+        SubElements {
+            data: self.data,
+            offset: usize::from(self.header.header_length),
+        }
+    }
+
+    /// Returns the first sub-element with the given tag.
+    pub fn sub_element(&self, tag: TagId) -> Option<SubElement<'a>> {
+        self.sub_elements()
+            .find(|element| element.tag_id == tag as u16)
+    }
+
+    /// Returns the upgrade image.
+    pub fn upgrade_image(&self) -> Option<&'a [u8]> {
+        self.sub_element(TagId::UpgradeImage)
+            .map(|element| element.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rustfmt::skip]
+    const FILE: [u8; 80] = [
+        0x1e, 0xf1, 0xee, 0x0b, // file identifier
+        0x00, 0x01, // header version
+        0x3c, 0x00, // header length
+        0x04, 0x00, // hardware versions present
+        0x34, 0x12, // manufacturer code
+        0x01, 0x00, // image type
+        0x02, 0x00, 0x00, 0x00, // file version
+        0x02, 0x00, // stack version
+        b'f', b'w', 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0x4e, 0x00, 0x00, 0x00, // total image size
+        0x01, 0x00, // minimum hardware version
+        0x03, 0x00, // maximum hardware version
+        0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0xde, 0xad, 0xbe, 0xef, // upgrade image
+        0x03, 0x00, 0x02, 0x00, 0x00, 0x00, 0x55, 0xaa, // image integrity code
+        0x00, 0x00, // trailing data beyond the file
+    ];
+
+    #[test]
+    fn parse_file() {
+        // given
+        let bytes = &FILE[..];
+
+        // when
+        let file = OtaFile::parse(bytes).expect("Could not parse OtaFile in test");
+
+        // then
+        assert_eq!(file.header.manufacturer_code, 0x1234);
+        assert_eq!(file.header.header_str(), Some("fw"));
+        assert!(file.header.supports_hardware_version(2));
+        assert!(!file.header.supports_hardware_version(4));
+        assert_eq!(file.as_bytes().len(), 0x4e);
+        assert_eq!(file.upgrade_image(), Some(&[0xde, 0xad, 0xbe, 0xef][..]));
+        let tags = file.sub_elements().map(|element| element.tag());
+        assert!(
+            tags.eq([Some(TagId::UpgradeImage), Some(TagId::ImageIntegrityCode)]
+                .iter()
+                .copied())
+        );
+    }
+
+    #[test]
+    fn reject_invalid_file() {
+        // given
+        let mut bytes = FILE;
+        bytes[0] = 0;
+
+        // when
+        let result = OtaFile::parse(&bytes);
+
+        // then
+        assert!(result.is_err());
+        assert!(OtaFile::parse(&FILE[..40]).is_err());
+    }
+
+    #[test]
+    fn reject_invalid_header() {
+        // given
+        let mut short_header = FILE;
+        short_header[6] = 0x00;
+        let mut long_header = FILE;
+        long_header[6] = 0x4f;
+        let mut version = FILE;
+        version[5] = 0x02;
+
+        // when
+        let short_header = OtaFile::parse(&short_header);
+        let long_header = OtaFile::parse(&long_header);
+        let version = OtaFile::parse(&version);
+
+        // then
+        assert!(matches!(short_header, Err(byte::Error::BadInput { .. })));
+        assert!(matches!(long_header, Err(byte::Error::BadInput { .. })));
+        assert!(matches!(version, Err(byte::Error::BadInput { .. })));
+    }
+}
@@ -0,0 +1,23 @@
+//!
+//! OTA Upgrade
+
+/// Provides the commands of the OTA Upgrade cluster.
+pub mod upgrade;
+
+/// Provides a zero-copy parser for OTA upgrade files.
+pub mod image;
+
+/// Provides the OTA Upgrade server serving images from an [`server::ImageSource`].
+pub mod server;
+
+/// Provides the OTA Upgrade client state machine.
+pub mod client;
+
+/// Error of an [`server::ImageSource`] or [`client::ImageSink`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageError {
+    /// The data is not available yet, retry later
+    WouldBlock,
+    /// The storage failed or the offset is out of range
+    Failed,
+}
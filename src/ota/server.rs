@@ -0,0 +1,488 @@
+//!
+//! 11.13 OTA Upgrade Server
+//!
+//! [`OtaServer`] answers the queries and block requests of the clients for one image, which is
+//! read block by block from an [`ImageSource`]. The image does not need to fit into memory.
+use core::convert::TryFrom;
+
+use byte::TryRead;
+
+use crate::ota::image::OtaHeader;
+use crate::ota::upgrade::{
+    ImageBlock, ImageBlockRequest, ImageBlockResponse, ImageInfo, ImageNotify,
+    ImageNotifyPayloadType, QueryNextImageRequest, QueryNextImageResponse, UpgradeEndRequest,
+    UpgradeEndResponse, WaitForData, FILE_VERSION_WILDCARD, IMAGE_TYPE_WILDCARD,
+    MANUFACTURER_CODE_WILDCARD,
+};
+use crate::ota::StorageError;
+use crate::status::ZclStatus;
+
+/// Maximum length of an OTA header including all optional fields
+pub const MAX_HEADER_LEN: usize = 69;
+
+/// Source of an OTA upgrade file, similar to a seekable reader
+pub trait ImageSource {
+    /// Reads up to `buf.len()` octets starting at `offset` into `buf`.
+    ///
+    /// Returns the number of octets read, `0` at the end of the file. Counts beyond `buf.len()`
+    /// are limited to `buf.len()` by the server.
+    fn read_at(&mut self, offset: u32, buf: &mut [u8]) -> Result<usize, StorageError>;
+}
+
+impl ImageSource for &[u8] {
+    fn read_at(&mut self, offset: u32, buf: &mut [u8]) -> Result<usize, StorageError> {
+        let data = usize::try_from(offset)
+            .ok()
+            .and_then(|offset| self.get(offset..))
+            .ok_or(StorageError::Failed)?;
+        let len = data.len().min(buf.len());
+        buf[..len].copy_from_slice(&data[..len]);
+
+        Ok(len)
+    }
+}
+
+/// OTA Upgrade server for a single image
+#[derive(Debug)]
+pub struct OtaServer<S> {
+    source: S,
+    image: ImageInfo,
+    minimum_hardware_version: Option<u16>,
+    maximum_hardware_version: Option<u16>,
+    /// Minimum delay between blocks in milliseconds
+    pub minimum_block_period: u16,
+    /// Delay in seconds until a client should retry a block which is not available yet
+    pub wait_for_data_delay: u32,
+}
+
+impl<S: ImageSource> OtaServer<S> {
+    /// Creates a server for the image in `source`, the OTA header is read and validated.
+    pub fn new(mut source: S) -> byte::Result<Self> {
+        let buf = &mut [0; MAX_HEADER_LEN];
+        let len = source.read_at(0, buf).map_err(|_| byte::Error::BadInput {
+            err: "Could not read OTA header",
+        })?;
+        let (header, header_len) = OtaHeader::try_read(&buf[..len.min(buf.len())], ())?;
+        header.validate(header_len)?;
+
+        Ok(Self {
+            source,
+            image: ImageInfo {
+                manufacturer_code: header.manufacturer_code,
+                image_type: header.image_type,
+                file_version: header.file_version,
+                image_size: header.total_image_size,
+            },
+            minimum_hardware_version: header.minimum_hardware_version,
+            maximum_hardware_version: header.maximum_hardware_version,
+            minimum_block_period: 0,
+            wait_for_data_delay: 1,
+        })
+    }
+
+    /// Returns the served image.
+    pub fn image(&self) -> ImageInfo {
+        self.image
+    }
+
+    /// Returns the Image Notify command announcing the image.
+    pub fn image_notify(&self, query_jitter: u8) -> ImageNotify {
+        ImageNotify {
+            payload_type:
+                ImageNotifyPayloadType::QueryJitterManufacturerCodeImageTypeAndNewFileVersion,
+            query_jitter,
+            manufacturer_code: Some(self.image.manufacturer_code),
+            image_type: Some(self.image.image_type),
+            new_file_version: Some(self.image.file_version),
+        }
+    }
+
+    /// Handles the Query Next Image Request command.
+    ///
+    /// See Section 11.13.4
+    pub fn query_next_image(&self, request: QueryNextImageRequest) -> QueryNextImageResponse {
+        let hardware_supported = request.hardware_version.map_or(true, |version| {
+            self.minimum_hardware_version
+                .map_or(true, |min| version >= min)
+                && self
+                    .maximum_hardware_version
+                    .map_or(true, |max| version <= max)
+        });
+
+        if request.manufacturer_code == self.image.manufacturer_code
+            && request.image_type == self.image.image_type
+            && request.current_file_version != self.image.file_version
+            && hardware_supported
+        {
+            QueryNextImageResponse::with_image(self.image)
+        } else {
+            QueryNextImageResponse::with_status(ZclStatus::NoImageAvailable)
+        }
+    }
+
+    /// Handles the Image Block Request command, the block is read into `buf`.
+    ///
+    /// See Section 11.13.6
+    pub fn image_block<'a>(
+        &mut self,
+        request: ImageBlockRequest,
+        buf: &'a mut [u8],
+    ) -> ImageBlockResponse<'a> {
+        if !self.serves(
+            request.manufacturer_code,
+            request.image_type,
+            request.file_version,
+        ) || request.file_offset > self.image.image_size
+        {
+            return ImageBlockResponse::Abort;
+        }
+
+        let remaining = self.image.image_size - request.file_offset;
+        let len = usize::from(request.maximum_data_size)
+            .min(buf.len())
+            .min(usize::try_from(remaining).unwrap_or(usize::MAX));
+        match self.source.read_at(request.file_offset, &mut buf[..len]) {
+            Ok(read) => {
+                let len = read.min(len);
+                ImageBlockResponse::Success(ImageBlock {
+                    manufacturer_code: self.image.manufacturer_code,
+                    image_type: self.image.image_type,
+                    file_version: self.image.file_version,
+                    file_offset: request.file_offset,
+                    data: &buf[..len],
+                })
+            }
+            Err(StorageError::WouldBlock) => ImageBlockResponse::WaitForData(WaitForData {
+                current_time: 0,
+                request_time: self.wait_for_data_delay,
+                minimum_block_period: self.minimum_block_period,
+            }),
+            Err(StorageError::Failed) => ImageBlockResponse::Abort,
+        }
+    }
+
+    /// Handles the Upgrade End Request command.
+    ///
+    /// Returns the response telling the client to upgrade `upgrade_delay` seconds from now, or
+    /// `None` if the download failed and a Default Response is sent.
+    ///
+    /// See Section 11.13.9
+    pub fn upgrade_end(
+        &self,
+        request: UpgradeEndRequest,
+        upgrade_delay: u32,
+    ) -> Option<UpgradeEndResponse> {
+        if !request.status.is_success()
+            || !self.serves(
+                request.manufacturer_code,
+                request.image_type,
+                request.file_version,
+            )
+        {
+            return None;
+        }
+
+        Some(UpgradeEndResponse {
+            manufacturer_code: self.image.manufacturer_code,
+            image_type: self.image.image_type,
+            file_version: self.image.file_version,
+            current_time: 0,
+            upgrade_time: upgrade_delay,
+        })
+    }
+
+    fn serves(&self, manufacturer_code: u16, image_type: u16, file_version: u32) -> bool {
+        (manufacturer_code == self.image.manufacturer_code
+            || manufacturer_code == MANUFACTURER_CODE_WILDCARD)
+            && (image_type == self.image.image_type || image_type == IMAGE_TYPE_WILDCARD)
+            && (file_version == self.image.file_version || file_version == FILE_VERSION_WILDCARD)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::TryWrite;
+
+    use super::*;
+    use crate::ota::client::{ClientAction, ClientConfig, ClientRequest, ClientState, OtaClient};
+    use crate::ota::image::OtaFile;
+    use crate::ota::upgrade::ImageUpgradeStatus;
+
+    /// OTA file with a 56 octet header and an upgrade image of 40 octets
+    #[rustfmt::skip]
+    const FILE: [u8; 102] = [
+        0x1e, 0xf1, 0xee, 0x0b, // file identifier
+        0x00, 0x01, // header version
+        0x38, 0x00, // header length
+        0x00, 0x00, // header field control
+        0x34, 0x12, // manufacturer code
+        0x01, 0x00, // image type
+        0x02, 0x00, 0x00, 0x00, // file version
+        0x02, 0x00, // stack version
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0x66, 0x00, 0x00, 0x00, // total image size
+        0x00, 0x00, 0x28, 0x00, 0x00, 0x00, // upgrade image
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19,
+        20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39,
+    ];
+
+    #[test]
+    fn query_next_image() {
+        // given
+        let server = OtaServer::new(&FILE[..]).expect("Could not create OtaServer in test");
+        let mut request = QueryNextImageRequest {
+            field_control: 0,
+            manufacturer_code: 0x1234,
+            image_type: 1,
+            current_file_version: 1,
+            hardware_version: None,
+        };
+
+        // when
+        let response = server.query_next_image(request);
+
+        // then
+        assert_eq!(
+            response.image(),
+            Some(ImageInfo {
+                manufacturer_code: 0x1234,
+                image_type: 1,
+                file_version: 2,
+                image_size: 102,
+            })
+        );
+        request.current_file_version = 2;
+        assert_eq!(
+            server.query_next_image(request).status,
+            ZclStatus::NoImageAvailable
+        );
+    }
+
+    #[test]
+    fn image_block_beyond_file() {
+        // given
+        let mut server = OtaServer::new(&FILE[..]).expect("Could not create OtaServer in test");
+        let request = ImageBlockRequest {
+            field_control: 0,
+            manufacturer_code: 0x1234,
+            image_type: 1,
+            file_version: 2,
+            file_offset: 96,
+            maximum_data_size: 64,
+            request_node_address: None,
+            minimum_block_period: None,
+        };
+        let buf = &mut [0; 64];
+
+        // when
+        let response = server.image_block(request, buf);
+
+        // then
+        let last = match response {
+            ImageBlockResponse::Success(block) => block.data,
+            _ => &[],
+        };
+        assert_eq!(last, &[34, 35, 36, 37, 38, 39]);
+        let request = ImageBlockRequest {
+            file_offset: 103,
+            ..request
+        };
+        assert_eq!(server.image_block(request, buf), ImageBlockResponse::Abort);
+    }
+
+    /// Source reporting more octets than requested
+    struct OverreportingSource<'a>(&'a [u8]);
+
+    impl ImageSource for OverreportingSource<'_> {
+        fn read_at(&mut self, offset: u32, buf: &mut [u8]) -> Result<usize, StorageError> {
+            self.0.read_at(offset, buf).map(|len| len + 16)
+        }
+    }
+
+    #[test]
+    fn limit_overreported_reads() {
+        // given
+        let mut server = OtaServer::new(OverreportingSource(&FILE[..]))
+            .expect("Could not create OtaServer in test");
+        let request = ImageBlockRequest {
+            field_control: 0,
+            manufacturer_code: 0x1234,
+            image_type: 1,
+            file_version: 2,
+            file_offset: 96,
+            maximum_data_size: 4,
+            request_node_address: None,
+            minimum_block_period: None,
+        };
+        let buf = &mut [0; 64];
+
+        // when
+        let response = server.image_block(request, buf);
+
+        // then
+        let data = match response {
+            ImageBlockResponse::Success(block) => block.data,
+            _ => &[],
+        };
+        assert_eq!(data, &[34, 35, 36, 37]);
+    }
+
+    #[test]
+    fn reject_invalid_header() {
+        // given
+        let mut version = FILE;
+        version[5] = 0x02;
+        let mut header_length = FILE;
+        header_length[6] = 0x10;
+
+        // then
+        assert!(OtaServer::new(&version[..]).is_err());
+        assert!(OtaServer::new(&header_length[..]).is_err());
+    }
+
+    const CONFIG: ClientConfig = ClientConfig {
+        manufacturer_code: 0x1234,
+        image_type: 1,
+        current_file_version: 1,
+        hardware_version: Some(1),
+        maximum_data_size: 32,
+        max_retries: 2,
+    };
+
+    /// Source which is not ready for every other read
+    struct FlakySource<'a> {
+        data: &'a [u8],
+        ready: bool,
+    }
+
+    impl ImageSource for FlakySource<'_> {
+        fn read_at(&mut self, offset: u32, buf: &mut [u8]) -> Result<usize, StorageError> {
+            self.ready = !self.ready || offset == 0;
+            if self.ready {
+                self.data.read_at(offset, buf)
+            } else {
+                Err(StorageError::WouldBlock)
+            }
+        }
+    }
+
+    /// Passes the request through the codecs to the server and the response back to the client.
+    fn exchange<S: ImageSource>(
+        client: &mut OtaClient,
+        server: &mut OtaServer<S>,
+        sink: &mut [u8],
+        request: ClientRequest,
+    ) -> ClientAction {
+        let buf = &mut [0u8; 128];
+        let block = &mut [0u8; 64];
+        match request {
+            ClientRequest::QueryNextImage(request) => {
+                let len = request.try_write(buf, ()).expect("Could not write request");
+                let (request, _) = QueryNextImageRequest::try_read(&buf[..len], ())
+                    .expect("Could not read request");
+                let len = server
+                    .query_next_image(request)
+                    .try_write(buf, ())
+                    .expect("Could not write response");
+                let (response, _) = QueryNextImageResponse::try_read(&buf[..len], ())
+                    .expect("Could not read response");
+                client.query_next_image_response(response)
+            }
+            ClientRequest::ImageBlock(request) => {
+                let len = request.try_write(buf, ()).expect("Could not write request");
+                let (request, _) =
+                    ImageBlockRequest::try_read(&buf[..len], ()).expect("Could not read request");
+                let len = server
+                    .image_block(request, block)
+                    .try_write(buf, ())
+                    .expect("Could not write response");
+                let (response, _) =
+                    ImageBlockResponse::try_read(&buf[..len], ()).expect("Could not read response");
+                client.image_block_response(response, sink)
+            }
+            ClientRequest::UpgradeEnd(request) => server
+                .upgrade_end(request, 5)
+                .map_or(ClientAction::None, |response| {
+                    client.upgrade_end_response(response)
+                }),
+        }
+    }
+
+    /// Runs the client against the server until the download completes or fails.
+    fn run<S: ImageSource>(
+        client: &mut OtaClient,
+        server: &mut OtaServer<S>,
+        sink: &mut [u8],
+        mut action: ClientAction,
+    ) -> (ClientAction, usize) {
+        let mut waits = 0;
+        loop {
+            action = match action {
+                ClientAction::Send(request) => exchange(client, server, sink, request),
+                ClientAction::SendAfter(_, request) => {
+                    waits += 1;
+                    exchange(client, server, sink, request)
+                }
+                ClientAction::Verify => {
+                    let valid =
+                        OtaFile::parse(sink).map_or(false, |file| file.upgrade_image().is_some());
+                    client.verify(valid)
+                }
+                action => return (action, waits),
+            };
+        }
+    }
+
+    #[test]
+    fn download_from_server() {
+        // given
+        let mut server = OtaServer::new(FlakySource {
+            data: &FILE,
+            ready: false,
+        })
+        .expect("Could not create OtaServer in test");
+        let mut client = OtaClient::new(CONFIG);
+        let sink = &mut [0u8; FILE.len()];
+
+        // when
+        let notify = client.image_notify(server.image_notify(100), 50);
+        let (action, waits) = run(&mut client, &mut server, sink, notify);
+
+        // then
+        assert_eq!(action, ClientAction::Upgrade(Some(5)));
+        assert_eq!(client.state(), ClientState::WaitingToUpgrade);
+        assert_eq!(
+            client.image_upgrade_status(),
+            ImageUpgradeStatus::WaitingToUpgrade
+        );
+        assert_eq!(&sink[..], &FILE[..]);
+        assert!(waits > 0);
+    }
+
+    #[test]
+    fn resume_after_reboot() {
+        // given
+        let mut server = OtaServer::new(&FILE[..]).expect("Could not create OtaServer in test");
+        let mut client = OtaClient::new(CONFIG);
+        let sink = &mut [0u8; FILE.len()];
+        if let ClientAction::Send(request) = client.query_next_image() {
+            if let ClientAction::Send(request) = exchange(&mut client, &mut server, sink, request) {
+                exchange(&mut client, &mut server, sink, request);
+            }
+        }
+        let (image, file_offset) = (
+            client.image().expect("No image in test"),
+            client.file_offset(),
+        );
+
+        // when
+        let (mut client, action) = OtaClient::resume(CONFIG, image, file_offset);
+        let (action, _) = run(&mut client, &mut server, sink, action);
+
+        // then
+        assert_eq!(file_offset, 32);
+        assert_eq!(action, ClientAction::Upgrade(Some(5)));
+        assert_eq!(&sink[..], &FILE[..]);
+    }
+}
@@ -0,0 +1,598 @@
+//!
+//! 11.13 OTA Upgrade Cluster
+//!
+//! Commands for discovering and downloading new firmware images. The server runs on the upgrade
+//! server (e.g. a gateway) and answers the queries and block requests of the clients.
+use core::convert::TryFrom;
+
+use byte::{BytesExt, TryRead, TryWrite};
+
+use crate::impl_byte;
+use crate::status::ZclStatus;
+
+/// OTA Upgrade cluster identifier
+pub const CLUSTER_ID: u16 = 0x0019;
+
+/// Manufacturer code matching all manufacturers
+pub const MANUFACTURER_CODE_WILDCARD: u16 = 0xffff;
+
+/// Image type matching all image types
+pub const IMAGE_TYPE_WILDCARD: u16 = 0xffff;
+
+/// File version matching all file versions
+pub const FILE_VERSION_WILDCARD: u32 = 0xffff_ffff;
+
+/// Upgrade time of the Upgrade End Response telling the client to wait for another response
+pub const UPGRADE_TIME_WAIT_FOR_UPGRADE_COMMAND: u32 = 0xffff_ffff;
+
+impl_byte! {
+    /// 11.10 OTA Upgrade Client Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        UpgradeServerId = 0x0000,
+        FileOffset = 0x0001,
+        CurrentFileVersion = 0x0002,
+        CurrentZigbeeStackVersion = 0x0003,
+        DownloadedFileVersion = 0x0004,
+        DownloadedZigbeeStackVersion = 0x0005,
+        ImageUpgradeStatus = 0x0006,
+        ManufacturerId = 0x0007,
+        ImageTypeId = 0x0008,
+        MinimumBlockPeriod = 0x0009,
+        ImageStamp = 0x000a,
+        UpgradeActivationPolicy = 0x000b,
+        UpgradeTimeoutPolicy = 0x000c,
+    }
+}
+
+impl_byte! {
+    /// 11.13 Commands Received by the server
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ReceivedCommandIdentifier {
+        QueryNextImageRequest = 0x01,
+        ImageBlockRequest = 0x03,
+        ImagePageRequest = 0x04,
+        UpgradeEndRequest = 0x06,
+        QueryDeviceSpecificFileRequest = 0x08,
+    }
+}
+
+impl_byte! {
+    /// 11.13 Commands Generated by the server
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum GeneratedCommandIdentifier {
+        ImageNotify = 0x00,
+        QueryNextImageResponse = 0x02,
+        ImageBlockResponse = 0x05,
+        UpgradeEndResponse = 0x07,
+        QueryDeviceSpecificFileResponse = 0x09,
+    }
+}
+
+impl_byte! {
+    /// 11.10.7 `ImageUpgradeStatus` Attribute
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ImageUpgradeStatus {
+        Normal = 0x00,
+        DownloadInProgress = 0x01,
+        DownloadComplete = 0x02,
+        WaitingToUpgrade = 0x03,
+        CountDown = 0x04,
+        WaitForMore = 0x05,
+        WaitingToUpgradeViaExternalEvent = 0x06,
+    }
+}
+
+impl_byte! {
+    /// Payload type of the Image Notify command
+    ///
+    /// See Section 11.13.3.2
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ImageNotifyPayloadType {
+        QueryJitter = 0x00,
+        QueryJitterAndManufacturerCode = 0x01,
+        QueryJitterManufacturerCodeAndImageType = 0x02,
+        QueryJitterManufacturerCodeImageTypeAndNewFileVersion = 0x03,
+    }
+}
+
+impl_byte! {
+    /// 11.13.3 Image Notify Command
+    ///
+    /// The optional fields are present depending on the payload type.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ImageNotify {
+        /// Fields present in the command
+        pub payload_type: ImageNotifyPayloadType,
+        /// Percentage (1 - 100) of clients which should query the server
+        pub query_jitter: u8,
+        /// Manufacturer code of the image
+        #[parse_if = payload_type as u8 >= ImageNotifyPayloadType::QueryJitterAndManufacturerCode as u8]
+        pub manufacturer_code: Option<u16>,
+        /// Image type of the image
+        #[parse_if = payload_type as u8 >= ImageNotifyPayloadType::QueryJitterManufacturerCodeAndImageType as u8]
+        pub image_type: Option<u16>,
+        /// File version of the image
+        #[parse_if = payload_type as u8 >= ImageNotifyPayloadType::QueryJitterManufacturerCodeImageTypeAndNewFileVersion as u8]
+        pub new_file_version: Option<u32>,
+    }
+}
+
+impl_byte! {
+    /// 11.13.4 Query Next Image Request Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct QueryNextImageRequest {
+        /// See [`QueryNextImageRequest::HARDWARE_VERSION_PRESENT`]
+        pub field_control: u8,
+        /// Manufacturer code of the client
+        pub manufacturer_code: u16,
+        /// Image type of the client
+        pub image_type: u16,
+        /// Current file version of the client
+        pub current_file_version: u32,
+        /// Hardware version of the client
+        #[parse_if = field_control & Self::HARDWARE_VERSION_PRESENT != 0]
+        pub hardware_version: Option<u16>,
+    }
+}
+
+impl QueryNextImageRequest {
+    /// The hardware version is present
+    pub const HARDWARE_VERSION_PRESENT: u8 = 1 << 0;
+}
+
+impl_byte! {
+    /// 11.13.5 Query Next Image Response Command
+    ///
+    /// The image is only present on success.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct QueryNextImageResponse {
+        /// `Success`, `NoImageAvailable` or `NotAuthorized`
+        pub status: ZclStatus,
+        /// Manufacturer code of the image
+        #[parse_if = status.is_success()]
+        pub manufacturer_code: Option<u16>,
+        /// Image type of the image
+        #[parse_if = status.is_success()]
+        pub image_type: Option<u16>,
+        /// File version of the image
+        #[parse_if = status.is_success()]
+        pub file_version: Option<u32>,
+        /// Total size of the image file in octets
+        #[parse_if = status.is_success()]
+        pub image_size: Option<u32>,
+    }
+}
+
+impl QueryNextImageResponse {
+    /// Creates a response without an image.
+    pub fn with_status(status: ZclStatus) -> Self {
+        Self {
+            status,
+            manufacturer_code: None,
+            image_type: None,
+            file_version: None,
+            image_size: None,
+        }
+    }
+
+    /// Creates a successful response offering the image.
+    pub fn with_image(image: ImageInfo) -> Self {
+        Self {
+            status: ZclStatus::Success,
+            manufacturer_code: Some(image.manufacturer_code),
+            image_type: Some(image.image_type),
+            file_version: Some(image.file_version),
+            image_size: Some(image.image_size),
+        }
+    }
+
+    /// Returns the offered image, `None` if no image is available.
+    pub fn image(&self) -> Option<ImageInfo> {
+        match (
+            self.manufacturer_code,
+            self.image_type,
+            self.file_version,
+            self.image_size,
+        ) {
+            (Some(manufacturer_code), Some(image_type), Some(file_version), Some(image_size))
+                if self.status.is_success() =>
+            {
+                Some(ImageInfo {
+                    manufacturer_code,
+                    image_type,
+                    file_version,
+                    image_size,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Image offered by the server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageInfo {
+    /// Manufacturer code of the image
+    pub manufacturer_code: u16,
+    /// Image type of the image
+    pub image_type: u16,
+    /// File version of the image
+    pub file_version: u32,
+    /// Total size of the image file in octets
+    pub image_size: u32,
+}
+
+/// 11.13.12 Query Device Specific File Response Command
+pub type QueryDeviceSpecificFileResponse = QueryNextImageResponse;
+
+impl_byte! {
+    /// 11.13.6 Image Block Request Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ImageBlockRequest {
+        /// See [`ImageBlockRequest::REQUEST_NODE_ADDRESS_PRESENT`] and
+        /// [`ImageBlockRequest::MINIMUM_BLOCK_PERIOD_PRESENT`]
+        pub field_control: u8,
+        /// Manufacturer code of the image
+        pub manufacturer_code: u16,
+        /// Image type of the image
+        pub image_type: u16,
+        /// File version of the image
+        pub file_version: u32,
+        /// Offset of the requested block in the image file
+        pub file_offset: u32,
+        /// Maximum size of the requested block
+        pub maximum_data_size: u8,
+        /// IEEE address of the client
+        #[parse_if = field_control & Self::REQUEST_NODE_ADDRESS_PRESENT != 0]
+        pub request_node_address: Option<u64>,
+        /// Minimum delay between blocks in milliseconds
+        #[parse_if = field_control & Self::MINIMUM_BLOCK_PERIOD_PRESENT != 0]
+        pub minimum_block_period: Option<u16>,
+    }
+}
+
+impl ImageBlockRequest {
+    /// The request node address is present
+    pub const REQUEST_NODE_ADDRESS_PRESENT: u8 = 1 << 0;
+    /// The minimum block period is present
+    pub const MINIMUM_BLOCK_PERIOD_PRESENT: u8 = 1 << 1;
+}
+
+impl_byte! {
+    /// 11.13.7 Image Page Request Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ImagePageRequest {
+        /// See [`ImagePageRequest::REQUEST_NODE_ADDRESS_PRESENT`]
+        pub field_control: u8,
+        /// Manufacturer code of the image
+        pub manufacturer_code: u16,
+        /// Image type of the image
+        pub image_type: u16,
+        /// File version of the image
+        pub file_version: u32,
+        /// Offset of the requested page in the image file
+        pub file_offset: u32,
+        /// Maximum size of each block
+        pub maximum_data_size: u8,
+        /// Number of octets to send before the client requests the next page
+        pub page_size: u16,
+        /// Delay between the blocks in milliseconds
+        pub response_spacing: u16,
+        /// IEEE address of the client
+        #[parse_if = field_control & Self::REQUEST_NODE_ADDRESS_PRESENT != 0]
+        pub request_node_address: Option<u64>,
+    }
+}
+
+impl ImagePageRequest {
+    /// The request node address is present
+    pub const REQUEST_NODE_ADDRESS_PRESENT: u8 = 1 << 0;
+}
+
+/// Block of image data of a successful Image Block Response
+///
+/// The data size field is derived from the length of the data.
+///
+/// See Section 11.13.8
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageBlock<'a> {
+    /// Manufacturer code of the image
+    pub manufacturer_code: u16,
+    /// Image type of the image
+    pub image_type: u16,
+    /// File version of the image
+    pub file_version: u32,
+    /// Offset of the block in the image file
+    pub file_offset: u32,
+    /// Image data of at most 255 octets
+    pub data: &'a [u8],
+}
+
+impl<'a> TryRead<'a, ()> for ImageBlock<'a> {
+    fn try_read(bytes: &'a [u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let manufacturer_code = bytes.read_with(offset, byte::LE)?;
+        let image_type = bytes.read_with(offset, byte::LE)?;
+        let file_version = bytes.read_with(offset, byte::LE)?;
+        let file_offset = bytes.read_with(offset, byte::LE)?;
+        let data_size: u8 = bytes.read_with(offset, byte::LE)?;
+        let data = bytes.read_with(offset, byte::ctx::Bytes::Len(usize::from(data_size)))?;
+
+        Ok((
+            Self {
+                manufacturer_code,
+                image_type,
+                file_version,
+                file_offset,
+                data,
+            },
+            *offset,
+        ))
+    }
+}
+
+impl TryWrite for ImageBlock<'_> {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        let data_size = u8::try_from(self.data.len()).map_err(|_| byte::Error::BadInput {
+            err: "Image block too long",
+        })?;
+        bytes.write_with(offset, self.manufacturer_code, byte::LE)?;
+        bytes.write_with(offset, self.image_type, byte::LE)?;
+        bytes.write_with(offset, self.file_version, byte::LE)?;
+        bytes.write_with(offset, self.file_offset, byte::LE)?;
+        bytes.write_with(offset, data_size, byte::LE)?;
+        bytes.write_with(offset, self.data, ())?;
+
+        Ok(*offset)
+    }
+}
+
+impl_byte! {
+    /// Delay of an Image Block Response with status `WaitForData`
+    ///
+    /// See Section 11.13.8
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct WaitForData {
+        /// Current UTC time of the server
+        pub current_time: u32,
+        /// UTC time at which the client should request the block again
+        pub request_time: u32,
+        /// Minimum delay between blocks in milliseconds
+        pub minimum_block_period: u16,
+    }
+}
+
+impl WaitForData {
+    /// Returns the delay in seconds until the client should request the block again.
+    pub fn delay(&self) -> u32 {
+        self.request_time.saturating_sub(self.current_time)
+    }
+}
+
+/// 11.13.8 Image Block Response Command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageBlockResponse<'a> {
+    /// The requested block
+    Success(ImageBlock<'a>),
+    /// The client should request the block again later
+    WaitForData(WaitForData),
+    /// The client should abort the download
+    Abort,
+}
+
+impl<'a> TryRead<'a, ()> for ImageBlockResponse<'a> {
+    fn try_read(bytes: &'a [u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let status = bytes.read_with(offset, ())?;
+        let response = match status {
+            ZclStatus::Success => Self::Success(bytes.read_with(offset, ())?),
+            ZclStatus::WaitForData => Self::WaitForData(bytes.read_with(offset, ())?),
+            ZclStatus::Abort => Self::Abort,
+            _ => {
+                return Err(byte::Error::BadInput {
+                    err: "Invalid status for ImageBlockResponse",
+                })
+            }
+        };
+
+        Ok((response, *offset))
+    }
+}
+
+impl TryWrite for ImageBlockResponse<'_> {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        match self {
+            Self::Success(block) => {
+                bytes.write_with(offset, ZclStatus::Success, ())?;
+                bytes.write_with(offset, block, ())?;
+            }
+            Self::WaitForData(wait) => {
+                bytes.write_with(offset, ZclStatus::WaitForData, ())?;
+                bytes.write_with(offset, wait, ())?;
+            }
+            Self::Abort => bytes.write_with(offset, ZclStatus::Abort, ())?,
+        }
+
+        Ok(*offset)
+    }
+}
+
+impl_byte! {
+    /// 11.13.9 Upgrade End Request Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct UpgradeEndRequest {
+        /// `Success`, `InvalidImage`, `RequireMoreImage` or `Abort`
+        pub status: ZclStatus,
+        /// Manufacturer code of the image
+        pub manufacturer_code: u16,
+        /// Image type of the image
+        pub image_type: u16,
+        /// File version of the image
+        pub file_version: u32,
+    }
+}
+
+impl_byte! {
+    /// 11.13.10 Upgrade End Response Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct UpgradeEndResponse {
+        /// Manufacturer code of the image
+        pub manufacturer_code: u16,
+        /// Image type of the image
+        pub image_type: u16,
+        /// File version of the image
+        pub file_version: u32,
+        /// Current UTC time of the server, `0` if not supported
+        pub current_time: u32,
+        /// UTC time at which the client should upgrade, see
+        /// [`UPGRADE_TIME_WAIT_FOR_UPGRADE_COMMAND`]
+        pub upgrade_time: u32,
+    }
+}
+
+impl UpgradeEndResponse {
+    /// Returns the delay in seconds until the client should upgrade.
+    ///
+    /// Returns `None` if the client should wait for another Upgrade End Response.
+    pub fn delay(&self) -> Option<u32> {
+        (self.upgrade_time != UPGRADE_TIME_WAIT_FOR_UPGRADE_COMMAND)
+            .then(|| self.upgrade_time.saturating_sub(self.current_time))
+    }
+}
+
+impl_byte! {
+    /// 11.13.11 Query Device Specific File Request Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct QueryDeviceSpecificFileRequest {
+        /// IEEE address of the client
+        pub request_node_address: u64,
+        /// Manufacturer code of the file
+        pub manufacturer_code: u16,
+        /// Image type of the file
+        pub image_type: u16,
+        /// File version of the file
+        pub file_version: u32,
+        /// Current `ZigBee` stack version of the client
+        pub current_zigbee_stack_version: u16,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::{TryRead, TryWrite};
+
+    use super::*;
+
+    #[test]
+    fn unpack_image_notify() {
+        // given
+        let input = [0x02, 0x64, 0x34, 0x12, 0x01, 0x00];
+
+        // when
+        let (notify, len) =
+            ImageNotify::try_read(&input, ()).expect("Could not read ImageNotify in test");
+
+        // then
+        assert_eq!(len, input.len());
+        assert_eq!(notify.query_jitter, 100);
+        assert_eq!(notify.manufacturer_code, Some(0x1234));
+        assert_eq!(notify.image_type, Some(0x0001));
+        assert_eq!(notify.new_file_version, None);
+    }
+
+    #[test]
+    fn unpack_query_next_image_request() {
+        // given
+        let input = [
+            0x01, 0x34, 0x12, 0x01, 0x00, 0x04, 0x03, 0x02, 0x01, 0x02, 0x00,
+        ];
+
+        // when
+        let (request, _) = QueryNextImageRequest::try_read(&input, ())
+            .expect("Could not read QueryNextImageRequest in test");
+
+        // then
+        assert_eq!(request.current_file_version, 0x0102_0304);
+        assert_eq!(request.hardware_version, Some(2));
+    }
+
+    #[test]
+    fn image_block_response() {
+        // given
+        let data = [0xaa, 0xbb, 0xcc];
+        let response = ImageBlockResponse::Success(ImageBlock {
+            manufacturer_code: 0x1234,
+            image_type: 1,
+            file_version: 2,
+            file_offset: 0x40,
+            data: &data,
+        });
+
+        // when
+        let mut buf = [0u8; 32];
+        let len = response
+            .try_write(&mut buf, ())
+            .expect("Could not write ImageBlockResponse in test");
+
+        // then
+        assert_eq!(len, 17);
+        assert_eq!(
+            &buf[..len],
+            &[
+                0x00, 0x34, 0x12, 0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x00, 0x03,
+                0xaa, 0xbb, 0xcc
+            ]
+        );
+        let (read, _) = ImageBlockResponse::try_read(&buf[..len], ())
+            .expect("Could not read ImageBlockResponse in test");
+        assert_eq!(read, response);
+        let data = [0u8; 256];
+        let block = ImageBlock {
+            manufacturer_code: 0x1234,
+            image_type: 1,
+            file_version: 2,
+            file_offset: 0x40,
+            data: &data,
+        };
+        let mut buf = [0u8; 272];
+        assert!(matches!(
+            block.try_write(&mut buf, ()),
+            Err(byte::Error::BadInput { .. })
+        ));
+    }
+
+    #[test]
+    fn unpack_wait_for_data() {
+        // given
+        let input = [
+            0x97, 0x10, 0x00, 0x00, 0x00, 0x1a, 0x00, 0x00, 0x00, 0xf4, 0x01,
+        ];
+
+        // when
+        let (response, _) = ImageBlockResponse::try_read(&input, ())
+            .expect("Could not read ImageBlockResponse in test");
+
+        // then
+        let wait = WaitForData {
+            current_time: 0x10,
+            request_time: 0x1a,
+            minimum_block_period: 500,
+        };
+        assert_eq!(response, ImageBlockResponse::WaitForData(wait));
+        assert_eq!(wait.delay(), 10);
+        assert!(ImageBlockResponse::try_read(&[0x01], ()).is_err());
+    }
+}
@@ -0,0 +1,527 @@
+//!
+//! 8.3 IAS ACE Cluster
+//!
+//! Commands of the IAS Ancillary Control Equipment (e.g. keypads or remote controls) for arming
+//! and disarming an alarm panel, bypassing zones and raising emergency, fire or panic alarms.
+//! The server side is implemented by the CIE.
+use core::convert::TryFrom;
+
+use byte::{BytesExt, TryRead, TryWrite};
+
+use crate::data_types::CharacterString;
+use crate::impl_byte;
+use crate::security::ias_zone::{ZoneStatus, ZoneType};
+
+/// IAS ACE cluster identifier
+pub const CLUSTER_ID: u16 = 0x0501;
+
+impl_byte! {
+    /// 8.3.2.4 Commands Received
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ReceivedCommandIdentifier {
+        Arm = 0x00,
+        Bypass = 0x01,
+        Emergency = 0x02,
+        Fire = 0x03,
+        Panic = 0x04,
+        GetZoneIdMap = 0x05,
+        GetZoneInformation = 0x06,
+        GetPanelStatus = 0x07,
+        GetBypassedZoneList = 0x08,
+        GetZoneStatus = 0x09,
+    }
+}
+
+impl_byte! {
+    /// 8.3.2.5 Commands Generated
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum GeneratedCommandIdentifier {
+        ArmResponse = 0x00,
+        GetZoneIdMapResponse = 0x01,
+        GetZoneInformationResponse = 0x02,
+        ZoneStatusChanged = 0x03,
+        PanelStatusChanged = 0x04,
+        GetPanelStatusResponse = 0x05,
+        SetBypassedZoneList = 0x06,
+        BypassResponse = 0x07,
+        GetZoneStatusResponse = 0x08,
+    }
+}
+
+impl_byte! {
+    /// Arm mode of the Arm command
+    ///
+    /// See Section 8.3.2.4.1
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ArmMode {
+        Disarm = 0x00,
+        ArmDayHomeZonesOnly = 0x01,
+        ArmNightSleepZonesOnly = 0x02,
+        ArmAllZones = 0x03,
+    }
+}
+
+impl_byte! {
+    /// Arm notification of the Arm Response command
+    ///
+    /// See Section 8.3.2.5.1
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ArmNotification {
+        AllZonesDisarmed = 0x00,
+        OnlyDayHomeZonesArmed = 0x01,
+        OnlyNightSleepZonesArmed = 0x02,
+        AllZonesArmed = 0x03,
+        InvalidArmDisarmCode = 0x04,
+        NotReadyToArm = 0x05,
+        AlreadyDisarmed = 0x06,
+    }
+}
+
+impl_byte! {
+    /// Status of the alarm panel
+    ///
+    /// See Section 8.3.2.5.5
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PanelStatus {
+        PanelDisarmed = 0x00,
+        ArmedStay = 0x01,
+        ArmedNight = 0x02,
+        ArmedAway = 0x03,
+        ExitDelay = 0x04,
+        EntryDelay = 0x05,
+        NotReadyToArm = 0x06,
+        InAlarm = 0x07,
+        ArmingStay = 0x08,
+        ArmingNight = 0x09,
+        ArmingAway = 0x0a,
+    }
+}
+
+/// Audible notification of the status change commands
+///
+/// See Section 8.3.2.5.4
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudibleNotification {
+    /// No sound
+    Mute,
+    /// Default sound of the device
+    DefaultSound,
+    /// Manufacturer specific sound in the range `0x80..=0xff`
+    ManufacturerSpecific(u8),
+}
+
+impl TryFrom<u8> for AudibleNotification {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0x00 => Ok(Self::Mute),
+            0x01 => Ok(Self::DefaultSound),
+            0x80..=0xff => Ok(Self::ManufacturerSpecific(value)),
+            _ => Err(value),
+        }
+    }
+}
+
+impl From<AudibleNotification> for u8 {
+    fn from(notification: AudibleNotification) -> Self {
+        match notification {
+            AudibleNotification::Mute => 0x00,
+            AudibleNotification::DefaultSound => 0x01,
+            AudibleNotification::ManufacturerSpecific(value) => value,
+        }
+    }
+}
+
+impl<C: Default> TryRead<'_, C> for AudibleNotification {
+    fn try_read(bytes: &[u8], _: C) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let value: u8 = bytes.read_with(offset, byte::LE)?;
+        let notification = Self::try_from(value).map_err(|_| byte::Error::BadInput {
+            err: "Invalid value for AudibleNotification",
+        })?;
+
+        Ok((notification, *offset))
+    }
+}
+
+impl<C: Default> TryWrite<C> for AudibleNotification {
+    fn try_write(self, bytes: &mut [u8], _: C) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, u8::from(self), byte::LE)?;
+
+        Ok(*offset)
+    }
+}
+
+impl_byte! {
+    /// Alarm status of the alarm panel
+    ///
+    /// See Section 8.3.2.5.5
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AlarmStatus {
+        NoAlarm = 0x00,
+        Burglar = 0x01,
+        Fire = 0x02,
+        Emergency = 0x03,
+        PolicePanic = 0x04,
+        FirePanic = 0x05,
+        EmergencyPanic = 0x06,
+    }
+}
+
+impl_byte! {
+    /// Result for a zone of the Bypass Response command
+    ///
+    /// See Section 8.3.2.5.8
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BypassResult {
+        ZoneBypassed = 0x00,
+        ZoneNotBypassed = 0x01,
+        NotAllowed = 0x02,
+        InvalidZoneId = 0x03,
+        UnknownZoneId = 0x04,
+        InvalidArmDisarmCode = 0x05,
+    }
+}
+
+impl_byte! {
+    /// 8.3.2.4.1 Arm Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Arm<'a> {
+        /// Requested arm mode
+        pub arm_mode: ArmMode,
+        /// Arm/disarm code entered by the user
+        pub arm_disarm_code: CharacterString<'a>,
+        /// `ZoneID` of the ACE
+        pub zone_id: u8,
+    }
+}
+
+impl_byte! {
+    /// 8.3.2.4.2 Bypass Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Bypass<'a> {
+        /// Number of zones
+        pub number_of_zones: u8,
+        /// Zone IDs to bypass
+        #[ctx = byte::ctx::Bytes::Len(usize::from(number_of_zones))]
+        pub zone_ids: &'a [u8],
+        /// Arm/disarm code entered by the user
+        pub arm_disarm_code: CharacterString<'a>,
+    }
+}
+
+impl_byte! {
+    /// 8.3.2.4.7 Get Zone Information Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GetZoneInformation {
+        /// Requested zone
+        pub zone_id: u8,
+    }
+}
+
+impl_byte! {
+    /// 8.3.2.4.10 Get Zone Status Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GetZoneStatus {
+        pub starting_zone_id: u8,
+        pub max_number_of_zone_ids: u8,
+        /// Only return zones matching the mask if non-zero
+        pub zone_status_mask_flag: u8,
+        pub zone_status_mask: ZoneStatus,
+    }
+}
+
+impl GetZoneStatus {
+    /// Returns `true` if a zone with the given status is requested.
+    pub fn matches(&self, zone_status: ZoneStatus) -> bool {
+        self.zone_status_mask_flag == 0 || zone_status.0 & self.zone_status_mask.0 != 0
+    }
+}
+
+impl_byte! {
+    /// 8.3.2.5.1 Arm Response Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ArmResponse {
+        /// Result of the Arm command
+        pub arm_notification: ArmNotification,
+    }
+}
+
+/// 8.3.2.5.2 Get Zone ID Map Response Command
+///
+/// Bitmap of the allocated zone IDs, bit `n` of section `m` stands for zone ID `16 * m + n`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GetZoneIdMapResponse {
+    /// Zone ID map sections
+    pub zone_id_map_sections: [u16; 16],
+}
+
+impl GetZoneIdMapResponse {
+    /// Creates a map of the given zone IDs.
+    pub fn new(zone_ids: impl IntoIterator<Item = u8>) -> Self {
+        let mut map = Self::default();
+        for zone_id in zone_ids {
+            map.zone_id_map_sections[usize::from(zone_id / 16)] |= 1 << (zone_id % 16);
+        }
+        map
+    }
+
+    /// Returns `true` if the zone ID is allocated.
+    pub fn contains(&self, zone_id: u8) -> bool {
+        self.zone_id_map_sections[usize::from(zone_id / 16)] & (1 << (zone_id % 16)) != 0
+    }
+
+    /// Returns the allocated zone IDs.
+    pub fn zone_ids(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..=u8::MAX).filter(move |zone_id| self.contains(*zone_id))
+    }
+}
+
+impl TryRead<'_, ()> for GetZoneIdMapResponse {
+    fn try_read(bytes: &[u8], _: ()) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let mut map = Self::default();
+        for section in &mut map.zone_id_map_sections {
+            *section = bytes.read_with(offset, byte::LE)?;
+        }
+
+        Ok((map, *offset))
+    }
+}
+
+impl TryWrite for GetZoneIdMapResponse {
+    fn try_write(self, bytes: &mut [u8], _: ()) -> byte::Result<usize> {
+        let offset = &mut 0;
+        for section in &self.zone_id_map_sections {
+            bytes.write_with(offset, *section, byte::LE)?;
+        }
+
+        Ok(*offset)
+    }
+}
+
+impl_byte! {
+    /// 8.3.2.5.3 Get Zone Information Response Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GetZoneInformationResponse<'a> {
+        pub zone_id: u8,
+        pub zone_type: ZoneType,
+        pub ieee_address: u64,
+        pub zone_label: CharacterString<'a>,
+    }
+}
+
+impl_byte! {
+    /// 8.3.2.5.4 Zone Status Changed Command
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ZoneStatusChanged<'a> {
+        pub zone_id: u8,
+        pub zone_status: ZoneStatus,
+        pub audible_notification: AudibleNotification,
+        pub zone_label: CharacterString<'a>,
+    }
+}
+
+impl_byte! {
+    /// 8.3.2.5.5 Panel Status Changed Command
+    ///
+    /// Also used for the Get Panel Status Response command.
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct PanelStatusChanged {
+        pub panel_status: PanelStatus,
+        /// Seconds remaining of the exit or entry delay
+        pub seconds_remaining: u8,
+        pub audible_notification: AudibleNotification,
+        pub alarm_status: AlarmStatus,
+    }
+}
+
+/// 8.3.2.5.6 Get Panel Status Response Command
+pub type GetPanelStatusResponse = PanelStatusChanged;
+
+impl_byte! {
+    /// 8.3.2.5.7 Set Bypassed Zone List Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct SetBypassedZoneList<'a> {
+        /// Number of zones
+        pub number_of_zones: u8,
+        /// Bypassed zone IDs
+        #[ctx = byte::ctx::Bytes::Len(usize::from(number_of_zones))]
+        pub zone_ids: &'a [u8],
+    }
+}
+
+impl_byte! {
+    /// 8.3.2.5.8 Bypass Response Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BypassResponse<'a> {
+        /// Number of zones
+        pub number_of_zones: u8,
+        /// Raw [`BypassResult`] for each zone of the Bypass command
+        #[ctx = byte::ctx::Bytes::Len(usize::from(number_of_zones))]
+        pub bypass_result: &'a [u8],
+    }
+}
+
+impl BypassResponse<'_> {
+    /// Returns the results, `None` for an unknown result.
+    pub fn results(&self) -> impl Iterator<Item = Option<BypassResult>> + '_ {
+        self.bypass_result
+            .iter()
+            .map(|result| BypassResult::try_from(*result).ok())
+    }
+}
+
+impl_byte! {
+    /// 8.3.2.5.9 Get Zone Status Response Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GetZoneStatusResponse<'a> {
+        /// Non-zero if all zones matching the request are included
+        pub zone_status_complete: u8,
+        /// Number of zones
+        pub number_of_zones: u8,
+        /// Raw zone ID and [`ZoneStatus`] records
+        #[ctx = byte::ctx::Bytes::Len(usize::from(number_of_zones) * 3)]
+        pub zone_status_records: &'a [u8],
+    }
+}
+
+impl GetZoneStatusResponse<'_> {
+    /// Returns the zone ID and status of the zones.
+    pub fn zones(&self) -> ZoneStatusRecords<'_> {
+        ZoneStatusRecords {
+            data: self.zone_status_records,
+            offset: 0,
+        }
+    }
+}
+
+/// Iterator over the zone status records of a [`GetZoneStatusResponse`]
+#[derive(Debug, Clone)]
+pub struct ZoneStatusRecords<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl Iterator for ZoneStatusRecords<'_> {
+    type Item = (u8, ZoneStatus);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let zone_id = self.data.read_with(&mut self.offset, byte::LE).ok()?;
+        let zone_status = self.data.read_with(&mut self.offset, ()).ok()?;
+        Some((zone_id, zone_status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::{TryRead, TryWrite};
+
+    use super::*;
+
+    #[test]
+    fn unpack_arm() {
+        // given
+        let input = [0x03, 0x04, b'1', b'2', b'3', b'4', 0x02];
+
+        // when
+        let (arm, len) = Arm::try_read(&input, ()).expect("Could not read Arm in test");
+
+        // then
+        assert_eq!(len, input.len());
+        assert_eq!(arm.arm_mode, ArmMode::ArmAllZones);
+        assert_eq!(arm.arm_disarm_code.as_str(), Some("1234"));
+        assert_eq!(arm.zone_id, 2);
+    }
+
+    #[test]
+    fn unpack_bypass() {
+        // given
+        let input = [0x02, 0x01, 0x05, 0x00];
+
+        // when
+        let (bypass, len) = Bypass::try_read(&input, ()).expect("Could not read Bypass in test");
+
+        // then
+        assert_eq!(len, input.len());
+        assert_eq!(bypass.zone_ids, &[0x01, 0x05]);
+        assert!(bypass.arm_disarm_code.is_empty());
+    }
+
+    #[test]
+    fn zone_id_map() {
+        // given
+        let map = GetZoneIdMapResponse::new([0, 17, 255].iter().copied());
+
+        // when
+        let mut buf = [0u8; 32];
+        let len = map
+            .try_write(&mut buf, ())
+            .expect("Could not write GetZoneIdMapResponse in test");
+
+        // then
+        assert_eq!(len, 32);
+        assert_eq!(&buf[..4], &[0x01, 0x00, 0x02, 0x00]);
+        assert_eq!(&buf[30..], &[0x00, 0x80]);
+        let (read, _) = GetZoneIdMapResponse::try_read(&buf, ())
+            .expect("Could not read GetZoneIdMapResponse in test");
+        assert!(read.zone_ids().eq([0, 17, 255].iter().copied()));
+    }
+
+    #[test]
+    fn unpack_get_zone_status_response() {
+        // given
+        let input = [0x01, 0x02, 0x00, 0x01, 0x00, 0x03, 0x04, 0x00];
+
+        // when
+        let (response, _) = GetZoneStatusResponse::try_read(&input, ())
+            .expect("Could not read GetZoneStatusResponse in test");
+
+        // then
+        assert!(response.zones().eq([
+            (0, ZoneStatus(ZoneStatus::ALARM1)),
+            (3, ZoneStatus(ZoneStatus::TAMPER))
+        ]
+        .iter()
+        .copied()));
+    }
+
+    #[test]
+    fn manufacturer_specific_audible_notification() {
+        // given
+        let input = [0x81, 0x02];
+
+        // when
+        let (notification, len) = AudibleNotification::try_read(&input, ())
+            .expect("Could not read AudibleNotification in test");
+
+        // then
+        assert_eq!(len, 1);
+        assert_eq!(
+            notification,
+            AudibleNotification::ManufacturerSpecific(0x81)
+        );
+        assert_eq!(u8::from(notification), 0x81);
+        assert!(AudibleNotification::try_read(&input[1..], ()).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+//!
+//! 8.4 IAS WD Cluster
+//!
+//! Attributes and commands for warning devices (sirens and strobes). The warning and squawk
+//! settings are packed into single octets, [`WarningInfo`] and [`SquawkInfo`] provide typed
+//! access to their bitfields.
+use core::convert::TryFrom;
+
+use crate::impl_byte;
+
+/// IAS WD cluster identifier
+pub const CLUSTER_ID: u16 = 0x0502;
+
+impl_byte! {
+    /// 8.4.2.2 IAS WD Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        MaxDuration = 0x0000,
+    }
+}
+
+impl_byte! {
+    /// 8.4.2.3 Commands Received
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ReceivedCommandIdentifier {
+        StartWarning = 0x00,
+        Squawk = 0x01,
+    }
+}
+
+impl_byte! {
+    /// Warning mode of the Start Warning command
+    ///
+    /// See Section 8.4.2.3.1
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum WarningMode {
+        Stop = 0x00,
+        Burglar = 0x01,
+        Fire = 0x02,
+        Emergency = 0x03,
+        PolicePanic = 0x04,
+        FirePanic = 0x05,
+        EmergencyPanic = 0x06,
+    }
+}
+
+impl_byte! {
+    /// Squawk mode of the Squawk command
+    ///
+    /// See Section 8.4.2.3.2
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SquawkMode {
+        SystemArmed = 0x00,
+        SystemDisarmed = 0x01,
+    }
+}
+
+impl_byte! {
+    /// Siren, strobe and squawk level
+    ///
+    /// See Section 8.4.2.3.1
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum IntensityLevel {
+        Low = 0x00,
+        Medium = 0x01,
+        High = 0x02,
+        VeryHigh = 0x03,
+    }
+}
+
+impl IntensityLevel {
+    fn from_bits(bits: u8) -> Self {
+        Self::try_from(bits & 0x03).unwrap_or(Self::Low)
+    }
+}
+
+impl_byte! {
+    /// Warning mode (bits 4-7), strobe (bits 2-3) and siren level (bits 0-1) of the Start
+    /// Warning command
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct WarningInfo(pub u8);
+}
+
+impl WarningInfo {
+    /// Packs the warning settings.
+    pub fn new(warning_mode: WarningMode, strobe: bool, siren_level: IntensityLevel) -> Self {
+        Self((warning_mode as u8) << 4 | u8::from(strobe) << 2 | siren_level as u8)
+    }
+
+    /// Returns the warning mode, `None` if reserved.
+    pub fn warning_mode(self) -> Option<WarningMode> {
+        WarningMode::try_from(self.0 >> 4).ok()
+    }
+
+    /// Returns `true` if the strobe is used in parallel to the warning.
+    pub fn strobe(self) -> bool {
+        (self.0 >> 2) & 0x03 == 1
+    }
+
+    /// Returns the siren level.
+    pub fn siren_level(self) -> IntensityLevel {
+        IntensityLevel::from_bits(self.0)
+    }
+}
+
+impl_byte! {
+    /// Squawk mode (bits 4-7), strobe (bit 3) and squawk level (bits 0-1) of the Squawk command
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct SquawkInfo(pub u8);
+}
+
+impl SquawkInfo {
+    /// Packs the squawk settings.
+    pub fn new(squawk_mode: SquawkMode, strobe: bool, squawk_level: IntensityLevel) -> Self {
+        Self((squawk_mode as u8) << 4 | u8::from(strobe) << 3 | squawk_level as u8)
+    }
+
+    /// Returns the squawk mode, `None` if reserved.
+    pub fn squawk_mode(self) -> Option<SquawkMode> {
+        SquawkMode::try_from(self.0 >> 4).ok()
+    }
+
+    /// Returns `true` if the strobe flashes with the squawk.
+    pub fn strobe(self) -> bool {
+        self.0 & (1 << 3) != 0
+    }
+
+    /// Returns the squawk level.
+    pub fn squawk_level(self) -> IntensityLevel {
+        IntensityLevel::from_bits(self.0)
+    }
+}
+
+impl_byte! {
+    /// 8.4.2.3.1 Start Warning Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct StartWarning {
+        /// Warning mode, strobe and siren level
+        pub warning_info: WarningInfo,
+        /// Duration of the warning in seconds
+        pub warning_duration: u16,
+        /// Duty cycle of the strobe in multiples of 10 %
+        pub strobe_duty_cycle: u8,
+        /// Intensity of the strobe
+        pub strobe_level: IntensityLevel,
+    }
+}
+
+impl StartWarning {
+    /// Returns the warning duration limited to the `MaxDuration` attribute.
+    pub fn duration(&self, max_duration: u16) -> u16 {
+        self.warning_duration.min(max_duration)
+    }
+}
+
+impl_byte! {
+    /// 8.4.2.3.2 Squawk Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Squawk {
+        /// Squawk mode, strobe and squawk level
+        pub squawk_info: SquawkInfo,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::{TryRead, TryWrite};
+
+    use super::*;
+
+    #[test]
+    fn unpack_start_warning() {
+        // given
+        let input = [0x26, 0x3c, 0x00, 0x05, 0x03];
+
+        // when
+        let (command, len) =
+            StartWarning::try_read(&input, ()).expect("Could not read StartWarning in test");
+
+        // then
+        assert_eq!(len, input.len());
+        assert_eq!(command.warning_info.warning_mode(), Some(WarningMode::Fire));
+        assert!(command.warning_info.strobe());
+        assert_eq!(command.warning_info.siren_level(), IntensityLevel::High);
+        assert_eq!(command.duration(30), 30);
+        assert_eq!(command.strobe_level, IntensityLevel::VeryHigh);
+    }
+
+    #[test]
+    fn pack_squawk() {
+        // given
+        let command = Squawk {
+            squawk_info: SquawkInfo::new(SquawkMode::SystemDisarmed, true, IntensityLevel::Medium),
+        };
+
+        // when
+        let mut buf = [0u8; 1];
+        command
+            .try_write(&mut buf, ())
+            .expect("Could not write Squawk in test");
+
+        // then
+        assert_eq!(buf, [0x19]);
+        assert_eq!(
+            command.squawk_info.squawk_mode(),
+            Some(SquawkMode::SystemDisarmed)
+        );
+        assert!(command.squawk_info.strobe());
+    }
+}
@@ -0,0 +1,788 @@
+//!
+//! 8.2 IAS Zone Cluster
+//!
+//! Attributes and commands for IAS security zone devices (e.g. door/window contacts or motion
+//! sensors) and their enrollment with an IAS CIE (Control and Indicating Equipment).
+//! [`IasZoneServer`] implements the zone side, [`IasCie`] keeps the zone table of the CIE.
+use core::convert::TryFrom;
+
+use byte::{BytesExt, TryRead, TryWrite};
+
+use crate::impl_byte;
+use crate::status::ZclStatus;
+
+/// IAS Zone cluster identifier
+pub const CLUSTER_ID: u16 = 0x0500;
+
+/// Zone ID of a zone which is not enrolled
+pub const INVALID_ZONE_ID: u8 = 0xff;
+
+impl_byte! {
+    /// 8.2.2.2 IAS Zone Server Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        // Zone Information
+        ZoneState = 0x0000,
+        ZoneType = 0x0001,
+        ZoneStatus = 0x0002,
+        // Zone Settings
+        IasCieAddress = 0x0010,
+        ZoneId = 0x0011,
+        NumberOfZoneSensitivityLevelsSupported = 0x0012,
+        CurrentZoneSensitivityLevel = 0x0013,
+    }
+}
+
+impl_byte! {
+    /// 8.2.2.3 Commands Received
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ReceivedCommandIdentifier {
+        ZoneEnrollResponse = 0x00,
+        InitiateNormalOperationMode = 0x01,
+        InitiateTestMode = 0x02,
+    }
+}
+
+impl_byte! {
+    /// 8.2.2.4 Commands Generated
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum GeneratedCommandIdentifier {
+        ZoneStatusChangeNotification = 0x00,
+        ZoneEnrollRequest = 0x01,
+    }
+}
+
+impl_byte! {
+    /// 8.2.2.2.1.1 `ZoneState` Attribute
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ZoneState {
+        NotEnrolled = 0x00,
+        Enrolled = 0x01,
+    }
+}
+
+/// 8.2.2.2.1.2 `ZoneType` Attribute
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZoneType {
+    StandardCie,
+    MotionSensor,
+    ContactSwitch,
+    DoorWindowHandle,
+    FireSensor,
+    WaterSensor,
+    CarbonMonoxideSensor,
+    PersonalEmergencyDevice,
+    VibrationMovementSensor,
+    RemoteControl,
+    KeyFob,
+    Keypad,
+    StandardWarningDevice,
+    GlassBreakSensor,
+    SecurityRepeater,
+    Invalid,
+    /// Manufacturer specific zone type in the range `0x8000..=0xfffe`
+    ManufacturerSpecific(u16),
+}
+
+impl TryFrom<u16> for ZoneType {
+    type Error = u16;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        let zone_type = match value {
+            0x0000 => Self::StandardCie,
+            0x000d => Self::MotionSensor,
+            0x0015 => Self::ContactSwitch,
+            0x0016 => Self::DoorWindowHandle,
+            0x0028 => Self::FireSensor,
+            0x002a => Self::WaterSensor,
+            0x002b => Self::CarbonMonoxideSensor,
+            0x002c => Self::PersonalEmergencyDevice,
+            0x002d => Self::VibrationMovementSensor,
+            0x010f => Self::RemoteControl,
+            0x0115 => Self::KeyFob,
+            0x021d => Self::Keypad,
+            0x0225 => Self::StandardWarningDevice,
+            0x0226 => Self::GlassBreakSensor,
+            0x0229 => Self::SecurityRepeater,
+            0xffff => Self::Invalid,
+            0x8000..=0xfffe => Self::ManufacturerSpecific(value),
+            _ => return Err(value),
+        };
+        Ok(zone_type)
+    }
+}
+
+impl From<ZoneType> for u16 {
+    fn from(zone_type: ZoneType) -> Self {
+        match zone_type {
+            ZoneType::StandardCie => 0x0000,
+            ZoneType::MotionSensor => 0x000d,
+            ZoneType::ContactSwitch => 0x0015,
+            ZoneType::DoorWindowHandle => 0x0016,
+            ZoneType::FireSensor => 0x0028,
+            ZoneType::WaterSensor => 0x002a,
+            ZoneType::CarbonMonoxideSensor => 0x002b,
+            ZoneType::PersonalEmergencyDevice => 0x002c,
+            ZoneType::VibrationMovementSensor => 0x002d,
+            ZoneType::RemoteControl => 0x010f,
+            ZoneType::KeyFob => 0x0115,
+            ZoneType::Keypad => 0x021d,
+            ZoneType::StandardWarningDevice => 0x0225,
+            ZoneType::GlassBreakSensor => 0x0226,
+            ZoneType::SecurityRepeater => 0x0229,
+            ZoneType::Invalid => 0xffff,
+            ZoneType::ManufacturerSpecific(value) => value,
+        }
+    }
+}
+
+impl<C: Default> TryRead<'_, C> for ZoneType {
+    fn try_read(bytes: &[u8], _: C) -> byte::Result<(Self, usize)> {
+        let offset = &mut 0;
+        let value: u16 = bytes.read_with(offset, byte::LE)?;
+        let zone_type = Self::try_from(value).map_err(|_| byte::Error::BadInput {
+            err: "Invalid value for ZoneType",
+        })?;
+
+        Ok((zone_type, *offset))
+    }
+}
+
+impl<C: Default> TryWrite<C> for ZoneType {
+    fn try_write(self, bytes: &mut [u8], _: C) -> byte::Result<usize> {
+        let offset = &mut 0;
+        bytes.write_with(offset, u16::from(self), byte::LE)?;
+
+        Ok(*offset)
+    }
+}
+
+impl_byte! {
+    /// 8.2.2.2.1.3 `ZoneStatus` Attribute
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct ZoneStatus(pub u16);
+}
+
+impl ZoneStatus {
+    /// Alarm 1 (e.g. opened or alarmed)
+    pub const ALARM1: u16 = 1 << 0;
+    /// Alarm 2
+    pub const ALARM2: u16 = 1 << 1;
+    /// Tampered
+    pub const TAMPER: u16 = 1 << 2;
+    /// Low battery
+    pub const BATTERY: u16 = 1 << 3;
+    /// Reports its status periodically
+    pub const SUPERVISION_REPORTS: u16 = 1 << 4;
+    /// Reports the restore of an alarm
+    pub const RESTORE_REPORTS: u16 = 1 << 5;
+    /// Trouble or failure
+    pub const TROUBLE: u16 = 1 << 6;
+    /// AC (mains) fault
+    pub const AC: u16 = 1 << 7;
+    /// In test mode
+    pub const TEST: u16 = 1 << 8;
+    /// Defective battery
+    pub const BATTERY_DEFECT: u16 = 1 << 9;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u16) -> bool {
+        self.0 & flags == flags
+    }
+
+    /// Sets or clears the bits of `flags`.
+    pub fn set(&mut self, flags: u16, value: bool) {
+        if value {
+            self.0 |= flags;
+        } else {
+            self.0 &= !flags;
+        }
+    }
+}
+
+impl_byte! {
+    /// Response code of the Zone Enroll Response command
+    ///
+    /// See Section 8.2.2.3.1
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum EnrollResponseCode {
+        Success = 0x00,
+        NotSupported = 0x01,
+        NoEnrollPermit = 0x02,
+        TooManyZones = 0x03,
+    }
+}
+
+impl_byte! {
+    /// 8.2.2.3.1 Zone Enroll Response Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ZoneEnrollResponse {
+        /// Result of the enrollment
+        pub enroll_response_code: EnrollResponseCode,
+        /// Zone ID assigned by the CIE
+        pub zone_id: u8,
+    }
+}
+
+impl_byte! {
+    /// 8.2.2.3.3 Initiate Test Mode Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct InitiateTestMode {
+        /// Duration of the test mode in seconds
+        pub test_mode_duration: u8,
+        /// Sensitivity level used during the test mode
+        pub current_zone_sensitivity_level: u8,
+    }
+}
+
+impl_byte! {
+    /// 8.2.2.4.1 Zone Status Change Notification Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ZoneStatusChangeNotification {
+        /// Current `ZoneStatus`
+        pub zone_status: ZoneStatus,
+        /// Reserved, set to zero
+        pub extended_status: u8,
+        /// `ZoneID` of the zone
+        pub zone_id: u8,
+        /// Time between the status change and the notification in quarter seconds
+        pub delay: u16,
+    }
+}
+
+impl_byte! {
+    /// 8.2.2.4.2 Zone Enroll Request Command
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ZoneEnrollRequest {
+        /// `ZoneType` of the zone
+        pub zone_type: ZoneType,
+        /// Manufacturer code of the zone
+        pub manufacturer_code: u16,
+    }
+}
+
+/// Enrollment method between a zone and the CIE
+///
+/// See Section 8.2.2.2.3
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnrollmentMode {
+    /// The CIE writes its address and sends an unsolicited Zone Enroll Response.
+    AutoEnrollResponse,
+    /// The CIE writes its address and the zone sends a Zone Enroll Request once the user trips
+    /// it.
+    TripToPair,
+    /// The zone sends a Zone Enroll Request as soon as the CIE has written its address.
+    AutoEnrollRequest,
+}
+
+/// IAS Zone server
+///
+/// Tracks the enrollment with the CIE, the test mode and generates the notifications for status
+/// changes. The returned commands are sent to the address in [`IasZoneServer::ias_cie_address`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IasZoneServer {
+    enrollment_mode: EnrollmentMode,
+    zone_type: ZoneType,
+    manufacturer_code: u16,
+    zone_state: ZoneState,
+    zone_status: ZoneStatus,
+    ias_cie_address: u64,
+    zone_id: u8,
+    enroll_requested: bool,
+    test_mode_time: u8,
+}
+
+impl IasZoneServer {
+    /// Creates a zone which is not enrolled.
+    pub fn new(
+        enrollment_mode: EnrollmentMode,
+        zone_type: ZoneType,
+        manufacturer_code: u16,
+    ) -> Self {
+        Self {
+            enrollment_mode,
+            zone_type,
+            manufacturer_code,
+            zone_state: ZoneState::NotEnrolled,
+            zone_status: ZoneStatus::default(),
+            ias_cie_address: 0,
+            zone_id: INVALID_ZONE_ID,
+            enroll_requested: false,
+            test_mode_time: 0,
+        }
+    }
+
+    /// Returns the `ZoneState` attribute.
+    pub fn zone_state(&self) -> ZoneState {
+        self.zone_state
+    }
+
+    /// Returns the `ZoneType` attribute.
+    pub fn zone_type(&self) -> ZoneType {
+        self.zone_type
+    }
+
+    /// Returns the `ZoneStatus` attribute.
+    pub fn zone_status(&self) -> ZoneStatus {
+        self.zone_status
+    }
+
+    /// Returns the `IAS_CIE_Address` attribute.
+    pub fn ias_cie_address(&self) -> u64 {
+        self.ias_cie_address
+    }
+
+    /// Returns the `ZoneID` attribute.
+    pub fn zone_id(&self) -> u8 {
+        self.zone_id
+    }
+
+    /// Returns `true` while the zone is in test mode.
+    pub fn is_test_mode(&self) -> bool {
+        self.zone_status.contains(ZoneStatus::TEST)
+    }
+
+    /// Writes the `IAS_CIE_Address` attribute.
+    ///
+    /// A new address unenrolls the zone. Returns the Zone Enroll Request to send in auto enroll
+    /// request mode.
+    pub fn set_ias_cie_address(&mut self, address: u64) -> Option<ZoneEnrollRequest> {
+        if address != self.ias_cie_address {
+            self.ias_cie_address = address;
+            self.unenroll();
+        }
+
+        if self.enrollment_mode == EnrollmentMode::AutoEnrollRequest {
+            self.enroll_request()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the Zone Enroll Request to send, e.g. when the user trips the zone in trip-to-pair
+    /// mode.
+    ///
+    /// Returns `None` if the zone is enrolled or no CIE address has been written.
+    pub fn enroll_request(&mut self) -> Option<ZoneEnrollRequest> {
+        if self.zone_state == ZoneState::Enrolled || self.ias_cie_address == 0 {
+            return None;
+        }
+        self.enroll_requested = true;
+
+        Some(ZoneEnrollRequest {
+            zone_type: self.zone_type,
+            manufacturer_code: self.manufacturer_code,
+        })
+    }
+
+    /// Handles the Zone Enroll Response command.
+    ///
+    /// Unsolicited responses are only accepted in auto enroll response mode.
+    ///
+    /// See Section 8.2.2.3.1
+    pub fn zone_enroll_response(&mut self, response: ZoneEnrollResponse) -> ZclStatus {
+        let solicited =
+            self.enroll_requested || self.enrollment_mode == EnrollmentMode::AutoEnrollResponse;
+        if self.ias_cie_address == 0 || !solicited {
+            return ZclStatus::Failure;
+        }
+        self.enroll_requested = false;
+
+        if response.enroll_response_code == EnrollResponseCode::Success
+            && response.zone_id != INVALID_ZONE_ID
+        {
+            self.zone_state = ZoneState::Enrolled;
+            self.zone_id = response.zone_id;
+        } else {
+            self.unenroll();
+        }
+        ZclStatus::Success
+    }
+
+    /// Resets the zone to not enrolled.
+    pub fn unenroll(&mut self) {
+        self.zone_state = ZoneState::NotEnrolled;
+        self.zone_id = INVALID_ZONE_ID;
+        self.enroll_requested = false;
+    }
+
+    /// Updates the `ZoneStatus` attribute.
+    ///
+    /// Returns the notification to send if the status changed and the zone is enrolled.
+    pub fn set_zone_status(
+        &mut self,
+        zone_status: ZoneStatus,
+    ) -> Option<ZoneStatusChangeNotification> {
+        let test = self.zone_status.contains(ZoneStatus::TEST);
+        let mut zone_status = zone_status;
+        zone_status.set(ZoneStatus::TEST, test);
+        if zone_status == self.zone_status {
+            return None;
+        }
+        self.zone_status = zone_status;
+        self.notification()
+    }
+
+    /// Handles the Initiate Normal Operation Mode command.
+    ///
+    /// See Section 8.2.2.3.2
+    pub fn initiate_normal_operation_mode(
+        &mut self,
+    ) -> (ZclStatus, Option<ZoneStatusChangeNotification>) {
+        if self.zone_state != ZoneState::Enrolled {
+            return (ZclStatus::Failure, None);
+        }
+
+        (ZclStatus::Success, self.leave_test_mode())
+    }
+
+    /// Handles the Initiate Test Mode command.
+    ///
+    /// See Section 8.2.2.3.3
+    pub fn initiate_test_mode(
+        &mut self,
+        command: InitiateTestMode,
+    ) -> (ZclStatus, Option<ZoneStatusChangeNotification>) {
+        if self.zone_state != ZoneState::Enrolled {
+            return (ZclStatus::Failure, None);
+        }
+        self.test_mode_time = command.test_mode_duration;
+        if self.is_test_mode() {
+            return (ZclStatus::Success, None);
+        }
+        self.zone_status.set(ZoneStatus::TEST, true);
+
+        (ZclStatus::Success, self.notification())
+    }
+
+    /// Advances the test mode by one second.
+    ///
+    /// Returns the notification to send when the test mode ends.
+    pub fn tick(&mut self) -> Option<ZoneStatusChangeNotification> {
+        if !self.is_test_mode() {
+            return None;
+        }
+        self.test_mode_time = self.test_mode_time.saturating_sub(1);
+        if self.test_mode_time == 0 {
+            self.leave_test_mode()
+        } else {
+            None
+        }
+    }
+
+    fn leave_test_mode(&mut self) -> Option<ZoneStatusChangeNotification> {
+        self.test_mode_time = 0;
+        if !self.is_test_mode() {
+            return None;
+        }
+        self.zone_status.set(ZoneStatus::TEST, false);
+        self.notification()
+    }
+
+    fn notification(&self) -> Option<ZoneStatusChangeNotification> {
+        (self.zone_state == ZoneState::Enrolled).then(|| ZoneStatusChangeNotification {
+            zone_status: self.zone_status,
+            extended_status: 0,
+            zone_id: self.zone_id,
+            delay: 0,
+        })
+    }
+}
+
+/// Zone enrolled with the CIE
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Zone {
+    /// Assigned `ZoneID`
+    pub zone_id: u8,
+    /// `ZoneType` of the zone
+    pub zone_type: ZoneType,
+    /// IEEE address of the zone
+    pub ieee_address: u64,
+    /// Last reported `ZoneStatus`
+    pub zone_status: ZoneStatus,
+}
+
+/// Zone table of an IAS CIE with room for `N` zones
+///
+/// Assigns the zone IDs and answers the Zone Enroll Requests according to the enrollment mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IasCie<const N: usize> {
+    enrollment_mode: EnrollmentMode,
+    permit_enroll: bool,
+    zones: [Option<Zone>; N],
+}
+
+impl<const N: usize> IasCie<N> {
+    /// Creates an empty zone table.
+    pub fn new(enrollment_mode: EnrollmentMode) -> Self {
+        Self {
+            enrollment_mode,
+            permit_enroll: false,
+            zones: [None; N],
+        }
+    }
+
+    /// Allows or denies enrollment requests in trip-to-pair mode.
+    pub fn set_permit_enroll(&mut self, permit_enroll: bool) {
+        self.permit_enroll = permit_enroll;
+    }
+
+    /// Returns the enrolled zones.
+    pub fn zones(&self) -> impl Iterator<Item = &Zone> {
+        self.zones.iter().flatten()
+    }
+
+    /// Returns the zone with the given ID.
+    pub fn zone(&self, zone_id: u8) -> Option<&Zone> {
+        self.zones().find(|zone| zone.zone_id == zone_id)
+    }
+
+    /// Removes the zone with the given ID, returns `false` if not found.
+    pub fn remove_zone(&mut self, zone_id: u8) -> bool {
+        self.zones
+            .iter_mut()
+            .find(|zone| zone.map_or(false, |zone| zone.zone_id == zone_id))
+            .map_or(false, |zone| {
+                *zone = None;
+                true
+            })
+    }
+
+    /// Handles a Zone Enroll Request command of the zone with the given IEEE address.
+    ///
+    /// See Section 8.2.2.4.2
+    pub fn zone_enroll_request(
+        &mut self,
+        ieee_address: u64,
+        request: ZoneEnrollRequest,
+    ) -> ZoneEnrollResponse {
+        let permitted = match self.enrollment_mode {
+            EnrollmentMode::TripToPair => self.permit_enroll,
+            EnrollmentMode::AutoEnrollRequest => true,
+            EnrollmentMode::AutoEnrollResponse => false,
+        };
+        if !permitted {
+            return ZoneEnrollResponse {
+                enroll_response_code: EnrollResponseCode::NoEnrollPermit,
+                zone_id: INVALID_ZONE_ID,
+            };
+        }
+        if request.zone_type == ZoneType::Invalid {
+            return ZoneEnrollResponse {
+                enroll_response_code: EnrollResponseCode::NotSupported,
+                zone_id: INVALID_ZONE_ID,
+            };
+        }
+
+        self.enroll(ieee_address, request.zone_type)
+    }
+
+    /// Enrolls a zone in auto enroll response mode after the CIE address has been written.
+    ///
+    /// Returns the unsolicited Zone Enroll Response to send to the zone.
+    pub fn auto_enroll_response(
+        &mut self,
+        ieee_address: u64,
+        zone_type: ZoneType,
+    ) -> Option<ZoneEnrollResponse> {
+        (self.enrollment_mode == EnrollmentMode::AutoEnrollResponse)
+            .then(|| self.enroll(ieee_address, zone_type))
+    }
+
+    /// Handles a Zone Status Change Notification command.
+    ///
+    /// Returns the updated zone, or `None` if the zone is not enrolled with this CIE.
+    ///
+    /// See Section 8.2.2.4.1
+    pub fn zone_status_change_notification(
+        &mut self,
+        ieee_address: u64,
+        notification: ZoneStatusChangeNotification,
+    ) -> Option<&Zone> {
+        let zone = self.zones.iter_mut().flatten().find(|zone| {
+            zone.zone_id == notification.zone_id && zone.ieee_address == ieee_address
+        })?;
+        zone.zone_status = notification.zone_status;
+
+        Some(zone)
+    }
+
+    fn enroll(&mut self, ieee_address: u64, zone_type: ZoneType) -> ZoneEnrollResponse {
+        let success = |zone_id| ZoneEnrollResponse {
+            enroll_response_code: EnrollResponseCode::Success,
+            zone_id,
+        };
+
+        if let Some(zone) = self
+            .zones
+            .iter_mut()
+            .flatten()
+            .find(|zone| zone.ieee_address == ieee_address)
+        {
+            zone.zone_type = zone_type;
+            return success(zone.zone_id);
+        }
+
+        let zone_id = (0..INVALID_ZONE_ID).find(|zone_id| self.zone(*zone_id).is_none());
+        match (zone_id, self.zones.iter_mut().find(|zone| zone.is_none())) {
+            (Some(zone_id), Some(entry)) => {
+                *entry = Some(Zone {
+                    zone_id,
+                    zone_type,
+                    ieee_address,
+                    zone_status: ZoneStatus::default(),
+                });
+                success(zone_id)
+            }
+            _ => ZoneEnrollResponse {
+                enroll_response_code: EnrollResponseCode::TooManyZones,
+                zone_id: INVALID_ZONE_ID,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::TryRead;
+
+    use super::*;
+
+    const CIE_ADDRESS: u64 = 0x0011_2233_4455_6677;
+    const ZONE_ADDRESS: u64 = 0x8899_aabb_ccdd_eeff;
+
+    #[test]
+    fn unpack_zone_status_change_notification() {
+        // given
+        let input = [0x01, 0x01, 0x00, 0x05, 0x04, 0x00];
+
+        // when
+        let (notification, len) = ZoneStatusChangeNotification::try_read(&input, ())
+            .expect("Could not read ZoneStatusChangeNotification in test");
+
+        // then
+        assert_eq!(len, input.len());
+        assert!(notification
+            .zone_status
+            .contains(ZoneStatus::ALARM1 | ZoneStatus::TEST));
+        assert_eq!(notification.zone_id, 5);
+        assert_eq!(notification.delay, 4);
+    }
+
+    #[test]
+    fn auto_enroll_request() {
+        // given
+        let mut zone = IasZoneServer::new(
+            EnrollmentMode::AutoEnrollRequest,
+            ZoneType::ContactSwitch,
+            0x1234,
+        );
+        let mut cie = IasCie::<2>::new(EnrollmentMode::AutoEnrollRequest);
+
+        // when
+        let request = zone
+            .set_ias_cie_address(CIE_ADDRESS)
+            .expect("No enroll request in test");
+        let response = cie.zone_enroll_request(ZONE_ADDRESS, request);
+        let status = zone.zone_enroll_response(response);
+
+        // then
+        assert_eq!(status, ZclStatus::Success);
+        assert_eq!(zone.zone_state(), ZoneState::Enrolled);
+        assert_eq!(zone.zone_id(), 0);
+        let notification = zone
+            .set_zone_status(ZoneStatus(ZoneStatus::ALARM1))
+            .expect("No notification in test");
+        let entry = cie
+            .zone_status_change_notification(ZONE_ADDRESS, notification)
+            .expect("Zone not found in test");
+        assert!(entry.zone_status.contains(ZoneStatus::ALARM1));
+    }
+
+    #[test]
+    fn trip_to_pair() {
+        // given
+        let mut zone =
+            IasZoneServer::new(EnrollmentMode::TripToPair, ZoneType::MotionSensor, 0x1234);
+        let mut cie = IasCie::<1>::new(EnrollmentMode::TripToPair);
+        assert!(zone.set_ias_cie_address(CIE_ADDRESS).is_none());
+        let unsolicited = ZoneEnrollResponse {
+            enroll_response_code: EnrollResponseCode::Success,
+            zone_id: 3,
+        };
+        assert_eq!(zone.zone_enroll_response(unsolicited), ZclStatus::Failure);
+
+        // when
+        let request = zone.enroll_request().expect("No enroll request in test");
+        let denied = cie.zone_enroll_request(ZONE_ADDRESS, request);
+        cie.set_permit_enroll(true);
+        let request = zone.enroll_request().expect("No enroll request in test");
+        let response = cie.zone_enroll_request(ZONE_ADDRESS, request);
+        zone.zone_enroll_response(response);
+
+        // then
+        assert_eq!(
+            denied.enroll_response_code,
+            EnrollResponseCode::NoEnrollPermit
+        );
+        assert_eq!(zone.zone_state(), ZoneState::Enrolled);
+        let full = cie.zone_enroll_request(ZONE_ADDRESS + 1, request);
+        assert_eq!(full.enroll_response_code, EnrollResponseCode::TooManyZones);
+    }
+
+    #[test]
+    fn auto_enroll_response_and_test_mode() {
+        // given
+        let mut zone =
+            IasZoneServer::new(EnrollmentMode::AutoEnrollResponse, ZoneType::FireSensor, 0);
+        let mut cie = IasCie::<4>::new(EnrollmentMode::AutoEnrollResponse);
+        zone.set_ias_cie_address(CIE_ADDRESS);
+        let response = cie
+            .auto_enroll_response(ZONE_ADDRESS, zone.zone_type())
+            .expect("No enroll response in test");
+        zone.zone_enroll_response(response);
+
+        // when
+        let (status, notification) = zone.initiate_test_mode(InitiateTestMode {
+            test_mode_duration: 2,
+            current_zone_sensitivity_level: 0,
+        });
+
+        // then
+        assert_eq!(status, ZclStatus::Success);
+        assert!(notification.map_or(false, |n| n.zone_status.contains(ZoneStatus::TEST)));
+        assert!(zone.tick().is_none());
+        let notification = zone.tick().expect("No notification in test");
+        assert!(!notification.zone_status.contains(ZoneStatus::TEST));
+        assert!(!zone.is_test_mode());
+    }
+
+    #[test]
+    fn manufacturer_specific_zone_type() {
+        // given
+        let input = [0x34, 0x81];
+
+        // when
+        let (zone_type, len) =
+            ZoneType::try_read(&input, ()).expect("Could not read ZoneType in test");
+
+        // then
+        assert_eq!(len, 2);
+        assert_eq!(zone_type, ZoneType::ManufacturerSpecific(0x8134));
+        assert_eq!(u16::from(zone_type), 0x8134);
+        assert_eq!(ZoneType::try_from(0xffff), Ok(ZoneType::Invalid));
+        assert_eq!(ZoneType::try_from(0x0015), Ok(ZoneType::ContactSwitch));
+        assert_eq!(ZoneType::try_from(0x7fff), Err(0x7fff));
+    }
+}
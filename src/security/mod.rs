@@ -0,0 +1,11 @@
+//!
+//! Security and Safety Clusters
+
+/// Provides attributes and commands for enrolling and reporting IAS security zones.
+pub mod ias_zone;
+
+/// Provides commands for arming and controlling an alarm panel through ancillary control equipment.
+pub mod ias_ace;
+
+/// Provides attributes and commands for controlling a warning device (siren and strobe).
+pub mod ias_wd;
@@ -0,0 +1,510 @@
+//!
+//! D.3 Metering Cluster
+//!
+//! Summation and demand values are transmitted as raw integers which are scaled by the
+//! `Multiplier` and `Divisor` attributes and displayed according to the `SummationFormatting`
+//! and `DemandFormatting` attributes, see [`Metering`].
+use crate::data_types::{Int24, Uint24, Uint48};
+use crate::impl_byte;
+
+/// Metering cluster identifier
+pub const CLUSTER_ID: u16 = 0x0702;
+
+impl_byte! {
+    /// D.3.2.2 Metering Server Cluster Attributes
+    #[repr(u16)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AttributeIdentifier {
+        // Reading Information Set
+        CurrentSummationDelivered = 0x0000,
+        CurrentSummationReceived = 0x0001,
+        CurrentMaxDemandDelivered = 0x0002,
+        CurrentMaxDemandReceived = 0x0003,
+        DftSummation = 0x0004,
+        DailyFreezeTime = 0x0005,
+        PowerFactor = 0x0006,
+        ReadingSnapShotTime = 0x0007,
+        CurrentMaxDemandDeliveredTime = 0x0008,
+        CurrentMaxDemandReceivedTime = 0x0009,
+        DefaultUpdatePeriod = 0x000A,
+        FastPollUpdatePeriod = 0x000B,
+        CurrentBlockPeriodConsumptionDelivered = 0x000C,
+        DailyConsumptionTarget = 0x000D,
+        CurrentBlock = 0x000E,
+        ProfileIntervalPeriod = 0x000F,
+        PresetReadingTime = 0x0011,
+        VolumePerReport = 0x0012,
+        FlowRestriction = 0x0013,
+        SupplyStatus = 0x0014,
+        // Meter Status
+        Status = 0x0200,
+        // Formatting
+        UnitOfMeasure = 0x0300,
+        Multiplier = 0x0301,
+        Divisor = 0x0302,
+        SummationFormatting = 0x0303,
+        DemandFormatting = 0x0304,
+        HistoricalConsumptionFormatting = 0x0305,
+        MeteringDeviceType = 0x0306,
+        SiteId = 0x0307,
+        MeterSerialNumber = 0x0308,
+        // Historical Consumption
+        InstantaneousDemand = 0x0400,
+        CurrentDayConsumptionDelivered = 0x0401,
+        CurrentDayConsumptionReceived = 0x0402,
+        PreviousDayConsumptionDelivered = 0x0403,
+        PreviousDayConsumptionReceived = 0x0404,
+    }
+}
+
+impl_byte! {
+    /// Unit of measure of the summation and demand attributes.
+    ///
+    /// Values `0x80` and above indicate that the raw values are BCD encoded.
+    ///
+    /// See Section D.3.2.2.4.1
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum UnitOfMeasure {
+        KilowattHours = 0x00,
+        CubicMeters = 0x01,
+        CubicFeet = 0x02,
+        CentumCubicFeet = 0x03,
+        UsGallons = 0x04,
+        ImperialGallons = 0x05,
+        Btus = 0x06,
+        Liters = 0x07,
+        KilopascalGauge = 0x08,
+        KilopascalAbsolute = 0x09,
+        MegaCubicFeet = 0x0A,
+        Unitless = 0x0B,
+        Megajoule = 0x0C,
+        KiloVoltAmpereReactiveHours = 0x0D,
+        KilowattHoursBcd = 0x80,
+        CubicMetersBcd = 0x81,
+        CubicFeetBcd = 0x82,
+        CentumCubicFeetBcd = 0x83,
+        UsGallonsBcd = 0x84,
+        ImperialGallonsBcd = 0x85,
+        BtusBcd = 0x86,
+        LitersBcd = 0x87,
+        KilopascalGaugeBcd = 0x88,
+        KilopascalAbsoluteBcd = 0x89,
+        MegaCubicFeetBcd = 0x8A,
+        UnitlessBcd = 0x8B,
+        MegajouleBcd = 0x8C,
+        KiloVoltAmpereReactiveHoursBcd = 0x8D,
+    }
+}
+
+impl UnitOfMeasure {
+    /// Returns `true` if the raw values are BCD encoded.
+    pub fn is_bcd(self) -> bool {
+        (self as u8) & 0x80 != 0
+    }
+
+    /// Returns `true` if the unit is kWh (binary or BCD).
+    pub fn is_kilowatt_hours(self) -> bool {
+        (self as u8) & 0x7f == Self::KilowattHours as u8
+    }
+
+    /// Returns `true` if the unit is m³ (binary or BCD).
+    pub fn is_cubic_meters(self) -> bool {
+        (self as u8) & 0x7f == Self::CubicMeters as u8
+    }
+}
+
+impl_byte! {
+    /// D.3.2.2.4.7 `MeteringDeviceType` Attribute
+    #[repr(u8)]
+    #[allow(missing_docs)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum MeteringDeviceType {
+        ElectricMetering = 0,
+        GasMetering = 1,
+        WaterMetering = 2,
+        ThermalMetering = 3,
+        PressureMetering = 4,
+        HeatMetering = 5,
+        CoolingMetering = 6,
+        ElectricVehicleMetering = 7,
+        PvGenerationMetering = 8,
+        WindTurbineGenerationMetering = 9,
+        WaterTurbineGenerationMetering = 10,
+        MicroGenerationMetering = 11,
+        SolarHotWaterGenerationMetering = 12,
+        MirroredElectricMetering = 127,
+        MirroredGasMetering = 128,
+        MirroredWaterMetering = 129,
+        MirroredThermalMetering = 130,
+        MirroredPressureMetering = 131,
+        MirroredHeatMetering = 132,
+        MirroredCoolingMetering = 133,
+        MirroredElectricVehicleMetering = 134,
+        MirroredPvGenerationMetering = 135,
+        MirroredWindTurbineGenerationMetering = 136,
+        MirroredWaterTurbineGenerationMetering = 137,
+        MirroredMicroGenerationMetering = 138,
+        MirroredSolarHotWaterGenerationMetering = 139,
+    }
+}
+
+impl_byte! {
+    /// D.3.2.2.2.1 `Status` Attribute (electricity meter)
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct MeteringStatus(pub u8);
+}
+
+impl MeteringStatus {
+    /// Check Meter
+    pub const CHECK_METER: u8 = 1 << 0;
+    /// Low Battery
+    pub const LOW_BATTERY: u8 = 1 << 1;
+    /// Tamper Detect
+    pub const TAMPER_DETECT: u8 = 1 << 2;
+    /// Power Failure
+    pub const POWER_FAILURE: u8 = 1 << 3;
+    /// Power Quality
+    pub const POWER_QUALITY: u8 = 1 << 4;
+    /// Leak Detect
+    pub const LEAK_DETECT: u8 = 1 << 5;
+    /// Service Disconnect Open
+    pub const SERVICE_DISCONNECT_OPEN: u8 = 1 << 6;
+
+    /// Returns `true` if all bits of `flags` are set.
+    pub fn contains(self, flags: u8) -> bool {
+        self.0 & flags == flags
+    }
+}
+
+impl_byte! {
+    /// Formatting of summation and demand values.
+    ///
+    /// See Section D.3.2.2.4.4
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct Formatting(pub u8);
+}
+
+impl Formatting {
+    /// Creates a new formatting.
+    ///
+    /// `left` is limited to 15 and `right` to 7 digits.
+    pub fn new(left: u8, right: u8, suppress_leading_zeros: bool) -> Self {
+        let mut value = ((left & 0x0f) << offset::DIGITS_LEFT) | (right & mask::DIGITS_RIGHT);
+        if suppress_leading_zeros {
+            value |= mask::SUPPRESS_LEADING_ZEROS;
+        }
+        Self(value)
+    }
+
+    /// Number of digits to display to the right of the decimal point.
+    pub fn digits_right(self) -> u8 {
+        self.0 & mask::DIGITS_RIGHT
+    }
+
+    /// Number of digits to display to the left of the decimal point.
+    pub fn digits_left(self) -> u8 {
+        (self.0 & mask::DIGITS_LEFT) >> offset::DIGITS_LEFT
+    }
+
+    /// If set, leading zeros are not displayed.
+    pub fn suppress_leading_zeros(self) -> bool {
+        self.0 & mask::SUPPRESS_LEADING_ZEROS != 0
+    }
+
+    /// Writes a fixed point number with `digits_right()` fractional digits into `buf`.
+    ///
+    /// Only the `digits_left()` least significant integer digits are displayed, like on a
+    /// meter display.
+    fn write(self, negative: bool, fixed: u128, buf: &mut [u8]) -> Option<&str> {
+        let right = usize::from(self.digits_right());
+        let left = usize::from(self.digits_left()).max(1);
+
+        let mut digits = [0u8; 15];
+        let mut integer = fixed / pow10(self.digits_right());
+        for d in digits[..left].iter_mut().rev() {
+            #[allow(clippy::cast_possible_truncation)]
+            let digit = (integer % 10) as u8;
+            *d = b'0' + digit;
+            integer /= 10;
+        }
+        let mut start = 0;
+        if self.suppress_leading_zeros() {
+            while start + 1 < left && digits[start] == b'0' {
+                start += 1;
+            }
+        }
+
+        let len = usize::from(negative) + (left - start) + if right > 0 { right + 1 } else { 0 };
+        let out = buf.get_mut(..len)?;
+        let mut pos = 0;
+        if negative {
+            out[pos] = b'-';
+            pos += 1;
+        }
+        out[pos..pos + left - start].copy_from_slice(&digits[start..left]);
+        pos += left - start;
+        if right > 0 {
+            out[pos] = b'.';
+            pos += 1;
+            let mut fraction = fixed % pow10(self.digits_right());
+            for d in out[pos..].iter_mut().rev() {
+                #[allow(clippy::cast_possible_truncation)]
+                let digit = (fraction % 10) as u8;
+                *d = b'0' + digit;
+                fraction /= 10;
+            }
+        }
+
+        core::str::from_utf8(out).ok()
+    }
+}
+
+mod mask {
+    pub(super) const DIGITS_RIGHT: u8 = 0b0000_0111; // 3 bits
+    pub(super) const DIGITS_LEFT: u8 = 0b0111_1000; // 4 bits
+    pub(super) const SUPPRESS_LEADING_ZEROS: u8 = 0b1000_0000; // 1 bit
+}
+mod offset {
+    pub(super) const DIGITS_LEFT: u8 = 3;
+}
+
+fn pow10(exp: u8) -> u128 {
+    (0..exp).fold(1, |acc, _| acc * 10)
+}
+
+/// Decodes a BCD encoded value, returns `None` if a nibble is not a decimal digit.
+fn bcd_to_binary(bcd: u64) -> Option<u64> {
+    let mut value = 0;
+    for i in (0..16).rev() {
+        let nibble = (bcd >> (i * 4)) & 0x0f;
+        if nibble > 9 {
+            return None;
+        }
+        value = value * 10 + nibble;
+    }
+    Some(value)
+}
+
+/// Metering Server Attributes
+///
+/// Holds the core reading, status and formatting attributes and converts the raw summation and
+/// demand values into real units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Metering {
+    /// `CurrentSummationDelivered`
+    pub current_summation_delivered: Uint48,
+    /// `CurrentSummationReceived`
+    pub current_summation_received: Uint48,
+    /// `InstantaneousDemand`
+    pub instantaneous_demand: Int24,
+    /// `Status`
+    pub status: MeteringStatus,
+    /// `UnitOfMeasure`
+    pub unit_of_measure: UnitOfMeasure,
+    /// `Multiplier`
+    pub multiplier: Uint24,
+    /// `Divisor`
+    pub divisor: Uint24,
+    /// `SummationFormatting`
+    pub summation_formatting: Formatting,
+    /// `DemandFormatting`
+    pub demand_formatting: Formatting,
+    /// `MeteringDeviceType`
+    pub metering_device_type: MeteringDeviceType,
+}
+
+impl Default for Metering {
+    fn default() -> Self {
+        Self {
+            current_summation_delivered: Uint48::default(),
+            current_summation_received: Uint48::NON_VALUE,
+            instantaneous_demand: Int24::default(),
+            status: MeteringStatus::default(),
+            unit_of_measure: UnitOfMeasure::KilowattHours,
+            multiplier: Uint24::new(1).unwrap_or_default(),
+            divisor: Uint24::new(1).unwrap_or_default(),
+            summation_formatting: Formatting::default(),
+            demand_formatting: Formatting::default(),
+            metering_device_type: MeteringDeviceType::ElectricMetering,
+        }
+    }
+}
+
+impl Metering {
+    /// Decodes a raw summation or demand magnitude according to `UnitOfMeasure`.
+    fn decode(&self, raw: u64) -> Option<u64> {
+        if self.unit_of_measure.is_bcd() {
+            bcd_to_binary(raw)
+        } else {
+            Some(raw)
+        }
+    }
+
+    /// Returns `(multiplier, divisor)`, a divisor of zero is treated as one.
+    fn factors(&self) -> (u64, u64) {
+        let multiplier = self.multiplier.value().unwrap_or(1);
+        let divisor = self.divisor.value().filter(|d| *d != 0).unwrap_or(1);
+        (u64::from(multiplier), u64::from(divisor))
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn scale(&self, value: u64) -> f64 {
+        let (multiplier, divisor) = self.factors();
+        value as f64 * multiplier as f64 / divisor as f64
+    }
+
+    fn fixed(&self, value: u64, formatting: Formatting) -> u128 {
+        let (multiplier, divisor) = self.factors();
+        u128::from(value) * u128::from(multiplier) * pow10(formatting.digits_right())
+            / u128::from(divisor)
+    }
+
+    /// Scales a summation attribute (e.g. `CurrentSummationDelivered`) into `UnitOfMeasure`.
+    ///
+    /// Returns `None` for the non-value or invalid BCD values.
+    pub fn summation(&self, raw: Uint48) -> Option<f64> {
+        Some(self.scale(self.decode(raw.value()?)?))
+    }
+
+    /// Scales a demand attribute (e.g. `InstantaneousDemand`) into `UnitOfMeasure` per hour.
+    ///
+    /// Returns `None` for the non-value or invalid BCD values.
+    pub fn demand(&self, raw: Int24) -> Option<f64> {
+        let raw = raw.value()?;
+        let value = self.scale(self.decode(u64::from(raw.unsigned_abs()))?);
+        Some(if raw < 0 { -value } else { value })
+    }
+
+    /// Returns the summation in kWh, or `None` if the meter is not measuring kWh.
+    pub fn kilowatt_hours(&self, raw: Uint48) -> Option<f64> {
+        if self.unit_of_measure.is_kilowatt_hours() {
+            self.summation(raw)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the summation in m³, or `None` if the meter is not measuring m³.
+    pub fn cubic_meters(&self, raw: Uint48) -> Option<f64> {
+        if self.unit_of_measure.is_cubic_meters() {
+            self.summation(raw)
+        } else {
+            None
+        }
+    }
+
+    /// Formats a summation attribute according to `SummationFormatting` into `buf`.
+    ///
+    /// Returns `None` for invalid values or if `buf` is too small.
+    pub fn format_summation<'b>(&self, raw: Uint48, buf: &'b mut [u8]) -> Option<&'b str> {
+        let value = self.decode(raw.value()?)?;
+        let formatting = self.summation_formatting;
+        formatting.write(false, self.fixed(value, formatting), buf)
+    }
+
+    /// Formats a demand attribute according to `DemandFormatting` into `buf`.
+    ///
+    /// Returns `None` for invalid values or if `buf` is too small.
+    pub fn format_demand<'b>(&self, raw: Int24, buf: &'b mut [u8]) -> Option<&'b str> {
+        let raw = raw.value()?;
+        let value = self.decode(u64::from(raw.unsigned_abs()))?;
+        let formatting = self.demand_formatting;
+        formatting.write(raw < 0, self.fixed(value, formatting), buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::TryRead;
+
+    use super::*;
+
+    fn meter() -> Metering {
+        Metering {
+            current_summation_delivered: Uint48::new(1_234_567).expect("Invalid Uint48 in test"),
+            instantaneous_demand: Int24::new(-1_500).expect("Invalid Int24 in test"),
+            multiplier: Uint24::new(1).expect("Invalid Uint24 in test"),
+            divisor: Uint24::new(1000).expect("Invalid Uint24 in test"),
+            summation_formatting: Formatting::new(7, 2, true),
+            demand_formatting: Formatting::new(4, 3, false),
+            ..Metering::default()
+        }
+    }
+
+    #[test]
+    fn unpack_formatting() {
+        // given
+        let input = [0xfb];
+
+        // when
+        let (formatting, _) =
+            Formatting::try_read(&input, ()).expect("Could not read Formatting in test");
+
+        // then
+        assert_eq!(formatting.digits_right(), 3);
+        assert_eq!(formatting.digits_left(), 15);
+        assert!(formatting.suppress_leading_zeros());
+        assert_eq!(formatting, Formatting::new(15, 3, true));
+    }
+
+    #[test]
+    fn convert_summation() {
+        // given
+        let meter = meter();
+
+        // then
+        let kwh = meter
+            .kilowatt_hours(meter.current_summation_delivered)
+            .expect("Expected kWh");
+        assert!((kwh - 1_234.567).abs() < 0.000_1);
+        assert_eq!(meter.cubic_meters(meter.current_summation_delivered), None);
+        assert_eq!(meter.summation(Uint48::NON_VALUE), None);
+        let demand = meter
+            .demand(meter.instantaneous_demand)
+            .expect("Expected demand");
+        assert!((demand + 1.5).abs() < 0.000_1);
+    }
+
+    #[test]
+    fn format_summation_and_demand() {
+        // given
+        let meter = meter();
+        let mut buf = [0u8; 32];
+
+        // then
+        assert_eq!(
+            meter.format_summation(meter.current_summation_delivered, &mut buf),
+            Some("1234.56")
+        );
+        assert_eq!(
+            meter.format_demand(meter.instantaneous_demand, &mut buf),
+            Some("-0001.500")
+        );
+        assert_eq!(
+            meter.format_summation(meter.current_summation_delivered, &mut buf[..4]),
+            None
+        );
+    }
+
+    #[test]
+    fn decode_bcd_values() {
+        // given
+        let meter = Metering {
+            unit_of_measure: UnitOfMeasure::CubicMetersBcd,
+            ..Metering::default()
+        };
+
+        // then
+        let value = meter
+            .cubic_meters(Uint48::new(0x0012_3456).expect("Invalid Uint48 in test"))
+            .expect("Expected m³");
+        assert!((value - 123_456.0).abs() < f64::EPSILON);
+        assert_eq!(
+            meter.summation(Uint48::new(0x0000_00ab).expect("Invalid Uint48 in test")),
+            None
+        );
+    }
+}
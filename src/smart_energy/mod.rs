@@ -0,0 +1,6 @@
+//!
+//! Smart Energy Clusters
+
+/// Provides an interface to metering devices (electricity, gas, water, heat, ...), including
+/// the current summation, demand and their formatting.
+pub mod metering;
@@ -0,0 +1,117 @@
+//! ZCL Status
+use crate::impl_byte;
+
+impl_byte! {
+    /// ZCL status enumeration
+    ///
+    /// See Section 2.6.3 (Values can be found in Table 2-12)
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ZclStatus {
+        /// Operation was successful.
+        Success = 0x00,
+        /// Operation was not successful.
+        Failure = 0x01,
+        /// The sender of the command does not have authorization to carry out this command.
+        NotAuthorized = 0x7e,
+        /// A reserved field/subfield/bit contains a non-zero value.
+        ReservedFieldNotZero = 0x7f,
+        /// The command appears to contain the wrong fields.
+        MalformedCommand = 0x80,
+        /// The specified cluster command is not supported on the device.
+        UnsupClusterCommand = 0x81,
+        /// The specified general ZCL command is not supported on the device.
+        UnsupGeneralCommand = 0x82,
+        /// A manufacturer specific unicast, cluster specific command was received with an unknown
+        /// manufacturer code.
+        UnsupManufClusterCommand = 0x83,
+        /// A manufacturer specific unicast, ZCL specific command was received with an unknown
+        /// manufacturer code.
+        UnsupManufGeneralCommand = 0x84,
+        /// At least one field of the command contains an incorrect value.
+        InvalidField = 0x85,
+        /// The specified attribute does not exist on the device.
+        UnsupportedAttribute = 0x86,
+        /// Out of range error, or set to a reserved value.
+        InvalidValue = 0x87,
+        /// Attempt to write a read only attribute.
+        ReadOnly = 0x88,
+        /// An operation failed due to an insufficient amount of free space available.
+        InsufficientSpace = 0x89,
+        /// An attempt to create an entry in a table failed due to a duplicate entry already being
+        /// present in the table.
+        DuplicateExists = 0x8a,
+        /// The requested information could not be found.
+        NotFound = 0x8b,
+        /// Periodic reports cannot be issued for this attribute.
+        UnreportableAttribute = 0x8c,
+        /// The data type given for an attribute is incorrect.
+        InvalidDataType = 0x8d,
+        /// The selector for an attribute is incorrect.
+        InvalidSelector = 0x8e,
+        /// A request has been made to read an attribute that the requestor is not authorized to
+        /// read.
+        WriteOnly = 0x8f,
+        /// Setting the requested values would put the device in an inconsistent state on startup.
+        InconsistentStartupState = 0x90,
+        /// An attempt has been made to write an attribute that is present but is defined using an
+        /// out-of-band method and not over the air.
+        DefinedOutOfBand = 0x91,
+        /// The supplied values are inconsistent.
+        Inconsistent = 0x92,
+        /// The credentials presented by the device sending the command are not sufficient to
+        /// perform this action.
+        ActionDenied = 0x93,
+        /// The exchange was aborted due to excessive response time.
+        Timeout = 0x94,
+        /// Failed case when a client or a server decides to abort the upgrade process.
+        Abort = 0x95,
+        /// Invalid OTA upgrade image.
+        InvalidImage = 0x96,
+        /// Server does not have data block available yet.
+        WaitForData = 0x97,
+        /// No OTA upgrade image available for the client.
+        NoImageAvailable = 0x98,
+        /// The client still requires more OTA upgrade image files to successfully upgrade.
+        RequireMoreImage = 0x99,
+        /// The command has been received and is being processed.
+        NotificationPending = 0x9a,
+        /// An operation was unsuccessful due to a hardware failure.
+        HardwareFailure = 0xc0,
+        /// An operation was unsuccessful due to a software failure.
+        SoftwareFailure = 0xc1,
+        /// An error occurred during calibration.
+        CalibrationError = 0xc2,
+        /// The cluster is not supported.
+        UnsupportedCluster = 0xc3,
+    }
+}
+
+impl ZclStatus {
+    /// Returns `true` for [`ZclStatus::Success`].
+    pub fn is_success(self) -> bool {
+        self == Self::Success
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byte::TryRead;
+
+    use super::*;
+
+    #[test]
+    fn unpack_status() {
+        // given
+        let input = [0x8b];
+
+        // when
+        let (status, _) =
+            ZclStatus::try_read(&input, ()).expect("Could not read ZclStatus in test");
+
+        // then
+        assert_eq!(status, ZclStatus::NotFound);
+        assert!(!status.is_success());
+        assert!(ZclStatus::try_read(&[0x02], ()).is_err());
+    }
+}